@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /// Component that marks an entity as controlled by AI
 #[derive(Component, Debug)]
@@ -6,8 +7,21 @@ pub struct AIControlled {
     pub difficulty: AIDifficulty,
 }
 
+/// Difficulty levels
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Difficulty {
+    #[allow(dead_code)]
+    Easy,
+    #[allow(dead_code)]
+    Normal,
+    #[allow(dead_code)]
+    Hard,
+    #[allow(dead_code)]
+    Veteran,
+}
+
 /// Different AI difficulty levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AIDifficulty {
     Easy,
     Medium,
@@ -22,38 +36,113 @@ impl Default for AIControlled {
     }
 }
 
+impl AIDifficulty {
+    /// Seconds between strategic decisions
+    pub fn think_interval(&self) -> f32 {
+        match self {
+            AIDifficulty::Easy => 3.0,
+            AIDifficulty::Medium => 1.5,
+            AIDifficulty::Hard => 0.5,
+        }
+    }
+
+    /// How much stronger the army must be than the defenders before heading out
+    /// (Easy doesn't wait, it heads straight for the objective)
+    pub fn commit_ratio(&self) -> Option<f32> {
+        match self {
+            AIDifficulty::Easy => None,
+            AIDifficulty::Medium => Some(1.0),
+            AIDifficulty::Hard => Some(1.5),
+        }
+    }
+
+    /// Strength ratio below which the base turns around and goes home
+    /// (Easy never retreats)
+    pub fn retreat_ratio(&self) -> Option<f32> {
+        match self {
+            AIDifficulty::Easy => None,
+            AIDifficulty::Medium => Some(0.5),
+            AIDifficulty::Hard => Some(0.8),
+        }
+    }
+
+    /// Minimum number of combat units before the base leaves home
+    pub fn minimum_army(&self) -> usize {
+        match self {
+            AIDifficulty::Easy => 0,
+            AIDifficulty::Medium => 2,
+            AIDifficulty::Hard => 4,
+        }
+    }
+
+    /// Number of engineers kept busy gathering
+    pub fn gatherer_count(&self) -> usize {
+        match self {
+            AIDifficulty::Easy => 1,
+            AIDifficulty::Medium => 2,
+            AIDifficulty::Hard => 4,
+        }
+    }
+
+    /// Whether the whole enemy army is counted, or only the units already defending the objective
+    pub fn counts_reinforcements(&self) -> bool {
+        matches!(self, AIDifficulty::Hard)
+    }
+
+    /// Whether units switch to the weakest enemy in range instead of the closest
+    pub fn focus_fire(&self) -> bool {
+        matches!(self, AIDifficulty::Hard)
+    }
+
+    /// Whether engineers are spread across resource nodes instead of piling onto the nearest one
+    pub fn spreads_gatherers(&self) -> bool {
+        matches!(self, AIDifficulty::Hard)
+    }
+}
+
+impl From<Difficulty> for AIDifficulty {
+    fn from(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => AIDifficulty::Easy,
+            Difficulty::Normal => AIDifficulty::Medium,
+            Difficulty::Hard | Difficulty::Veteran => AIDifficulty::Hard,
+        }
+    }
+}
+
 /// Component for the AI player's base
 #[derive(Component, Debug)]
 pub struct AIBase;
 
-/// Plugin for AI behavior
-pub struct AIPlugin;
+/// What the AI is currently trying to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AIPhase {
+    Gather,   // Build up an economy and army at home
+    Advance,  // Drive the base toward the strategic location
+    Hold,     // Sit on the strategic location until it is captured
+    Retreat,  // Fall back home to regroup
+}
 
-impl Plugin for AIPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_systems(Update, 
-            ai_controller
-        );
-        
-        info!("AI Plugin initialized");
-    }
+/// Decision-making state for an AI-controlled base
+#[derive(Component, Debug)]
+pub struct AIBrain {
+    pub phase: AIPhase,
+    pub think_timer: Timer,
+    pub objective: Option<Entity>,
+    pub objective_position: Option<Vec2>,
+    pub home: Vec2,
+    pub strength_ratio: f32,
 }
 
-/// System that controls AI behavior
-fn ai_controller(
-    time: Res<Time>,
-    ai_bases: Query<(Entity, &Transform), With<AIBase>>,
-) {
-    // For now, just log that the AI is thinking
-    if (time.elapsed_seconds() * 10.0) as u64 % 50 == 0 {
-        for (entity, _transform) in ai_bases.iter() {
-            debug!("AI is thinking for entity {:?}", entity);
+impl AIBrain {
+    pub fn new(difficulty: AIDifficulty, home: Vec2) -> Self {
+        Self {
+            phase: AIPhase::Gather,
+            think_timer: Timer::from_seconds(difficulty.think_interval(), TimerMode::Repeating),
+            objective: None,
+            objective_position: None,
+            home,
+            strength_ratio: 0.0,
         }
     }
-    
-    // Later we'll implement more complex AI behaviors here:
-    // - Resource gathering
-    // - Base movement toward strategic locations
-    // - Unit production and management
-    // - Combat tactics
 }
@@ -1,10 +1,24 @@
 use bevy::prelude::*;
 use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
+use crate::components::economy::ResourceType;
 use crate::components::unit::Team;
-use crate::components::player::MechanicalBase;
+use crate::components::player::{MechanicalBase, PlayerResources};
+use crate::states::game_state::GameState;
+use crate::systems::module_catalog::ModuleDefinitions;
+use crate::systems::module_effects::Cooldown;
+
+/// Share of a finished module's cost given back when it is detached
+pub const MODULE_REFUND_FRACTION: f32 = 0.5;
+
+/// Share of its max health below which a damaged module goes offline until repaired
+pub const MODULE_DISABLE_THRESHOLD: f32 = 0.25;
+
+/// Tint of a module while it is still being built
+pub const UNDER_CONSTRUCTION_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.4);
 
 /// Represents a module that can be attached to the mechanical base
-#[derive(Component, Debug, Clone, Reflect)]
+#[derive(Component, Debug, Clone, Reflect, Serialize, Deserialize)]
 #[reflect(Component)]
 pub struct BaseModule {
     pub module_type: ModuleType,
@@ -16,7 +30,7 @@ pub struct BaseModule {
 }
 
 /// Types of base modules with their specific properties
-#[derive(Debug, Clone, PartialEq, Reflect)]
+#[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
 pub enum ModuleType {
     // Movement modules - enhance base mobility
     Movement {
@@ -83,6 +97,35 @@ pub enum ModuleType {
     },
 }
 
+/// Module families without their stats, used to group and rank modules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum ModuleCategory {
+    Movement,
+    Storage,
+    Defense,
+    Production,
+    Sensor,
+    Energy,
+    Weapon,
+    Utility,
+}
+
+impl ModuleType {
+    /// Which family this module belongs to
+    pub fn category(&self) -> ModuleCategory {
+        match self {
+            ModuleType::Movement { .. } => ModuleCategory::Movement,
+            ModuleType::Storage { .. } => ModuleCategory::Storage,
+            ModuleType::Defense { .. } => ModuleCategory::Defense,
+            ModuleType::Production { .. } => ModuleCategory::Production,
+            ModuleType::Sensor { .. } => ModuleCategory::Sensor,
+            ModuleType::Energy { .. } => ModuleCategory::Energy,
+            ModuleType::Weapon { .. } => ModuleCategory::Weapon,
+            ModuleType::Utility { .. } => ModuleCategory::Utility,
+        }
+    }
+}
+
 /// Component marking an attachment point on the base
 #[derive(Component, Debug)]
 pub struct AttachmentPoint {
@@ -103,24 +146,31 @@ pub struct AttachableModule {
     pub build_progress: f32,     // Current build progress (0.0-1.0)
 }
 
-/// Types of resources that can be stored or generated
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
-pub enum ResourceType {
-    Basic,
-    Wood,
-    Stone,
-    Iron,
-    Copper,
-    Alloy,
-    Energy,
-    Fuel,
-    Ammunition,
-    Research,
-    Population,
+/// Catalog module placed on a base, remembered so it can be refunded and its point freed
+#[derive(Component, Debug, Clone)]
+pub struct InstalledModule {
+    pub name: String,  // Module catalog name
+    pub point: Entity, // Attachment point it occupies
+}
+
+/// Request to build a catalog module on one of a base's attachment points
+///
+/// Sent by the build menu for the player and by the AI for its own bases.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct AttachModuleRequest {
+    pub base: Entity,
+    pub point: Entity,
+    pub module: String,
+}
+
+/// Request to take a module off its base, or to cancel it while it is still being built
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct DetachModuleRequest {
+    pub module: Entity,
 }
 
 /// Types of damage that can be dealt by weapons
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
 pub enum DamageType {
     Kinetic,    // Standard physical damage
     Energy,     // Laser, plasma, etc.
@@ -128,10 +178,11 @@ pub enum DamageType {
     Chemical,   // Acid, fire
     Sonic,      // Sound-based
     EMP,        // Anti-electronic
+    Flak,       // Airburst shells, made for bringing down aircraft
 }
 
 /// Types of utility effects
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
 pub enum UtilityEffect {
     Repair,          // Repairs nearby friendly units/structures
     Cloak,          // Provides stealth to nearby units
@@ -147,6 +198,28 @@ pub enum UtilityEffect {
     ResourceBoost,  // Increases resource gathering rate
 }
 
+/// How effects of the same kind from different sources combine on one target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackRule {
+    Strongest, // Only the strongest source counts
+    Sum,       // Every source adds up
+}
+
+impl UtilityEffect {
+    /// Whether the effect is aimed at enemies rather than allies
+    pub fn targets_enemies(&self) -> bool {
+        matches!(self, UtilityEffect::Jammer | UtilityEffect::Stun | UtilityEffect::Slow | UtilityEffect::Reveal)
+    }
+
+    /// How several sources of this effect combine; only healing adds up
+    pub fn stack_rule(&self) -> StackRule {
+        match self {
+            UtilityEffect::Repair | UtilityEffect::Heal => StackRule::Sum,
+            _ => StackRule::Strongest,
+        }
+    }
+}
+
 // Default implementations for module creation
 impl Default for BaseModule {
     fn default() -> Self {
@@ -155,6 +228,11 @@ impl Default for BaseModule {
 }
 
 impl BaseModule {
+    /// Whether the module is too damaged to work
+    pub fn is_disabled(&self) -> bool {
+        self.health < self.max_health * MODULE_DISABLE_THRESHOLD
+    }
+
     /// Create a new movement module
     pub fn new_movement_module(speed_mod: f32, efficiency: f32, terrain_penalty_reduction: f32) -> Self {
         Self {
@@ -310,44 +388,182 @@ impl Plugin for BaseModulePlugin {
         // Register reflection types
         app.register_type::<BaseModule>()
             .register_type::<ModuleType>()
+            .init_resource::<ModuleDefinitions>()
+            .add_event::<AttachModuleRequest>()
+            .add_event::<DetachModuleRequest>()
             // Add systems
-            .add_systems(Update, (update_module_effects, handle_module_attachment));
+            .add_systems(
+                Update,
+                (
+                    handle_module_attachment,
+                    handle_module_detachment,
+                    update_module_construction,
+                    remove_destroyed_modules,
+                ).chain().run_if(in_state(GameState::Gameplay))
+            );
     }
 }
 
-/// System to update active module effects
-fn update_module_effects(
-    mut bases: Query<(&Children, &mut MechanicalBase)>,
-    modules: Query<&BaseModule>,
+/// System to start building requested modules on free attachment points
+/// The player pays from their stockpile, other teams from their base's own resources
+pub fn handle_module_attachment(
+    mut commands: Commands,
+    mut requests: EventReader<AttachModuleRequest>,
+    definitions: Res<ModuleDefinitions>,
+    mut bases: Query<&mut MechanicalBase>,
+    mut points: Query<&mut AttachmentPoint>,
+    mut player_resources: Option<ResMut<PlayerResources>>,
 ) {
-    for (children, mut base) in bases.iter_mut() {
-        // Reset base stats
-        base.base_movement_speed = base.base_movement_speed; // Base speed
-        
-        // Apply module effects
-        for &child in children.iter() {
-            if let Ok(module) = modules.get(child) {
-                if module.active {
-                    match &module.module_type {
-                        ModuleType::Movement { speed_modifier, .. } => {
-                            base.effective_movement_speed = base.base_movement_speed * *speed_modifier;
-                        }
-                        _ => {}
-                    }
-                }
-            }
+    for request in requests.read() {
+        let Some(definition) = definitions.catalog.get(&request.module) else {
+            warn!("Unknown module \"{}\", ignoring attach request", request.module);
+            continue;
+        };
+        let Ok(mut base) = bases.get_mut(request.base) else { continue };
+        if !base.attachment_points.contains(&request.point) {
+            warn!("Attachment point {:?} isn't on base {:?}", request.point, request.base);
+            continue;
+        }
+        let Ok(mut point) = points.get_mut(request.point) else { continue };
+
+        let attachable = definition.to_attachable();
+        if !point.can_attach(&attachable) {
+            info!("{} can't go on that attachment point", request.module);
+            continue;
         }
+
+        let paid = match (base.team, player_resources.as_mut()) {
+            (Team::Player, Some(resources)) => resources.resources.spend(&definition.cost),
+            _ => base.resources.spend(&definition.cost),
+        };
+        if let Err(err) = paid {
+            info!("Not enough resources to build {}: {}", request.module, err);
+            continue;
+        }
+
+        let module = commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: UNDER_CONSTRUCTION_COLOR,
+                    custom_size: Some(attachable.size),
+                    ..default()
+                },
+                transform: Transform::from_xyz(point.position.x, point.position.y, 0.1)
+                    .with_rotation(Quat::from_rotation_z(point.rotation)),
+                ..default()
+            },
+            attachable,
+            InstalledModule {
+                name: request.module.clone(),
+                point: request.point,
+            },
+            Name::new(request.module.clone()),
+        )).id();
+        commands.entity(request.base).add_child(module);
+
+        point.occupied = true;
+        point.attached_module = Some(module);
+        info!("{:?} base started building {}", base.team, request.module);
     }
 }
 
-/// System to handle module attachment/detachment
-pub fn handle_module_attachment(
-    _commands: Commands,
-    _attachment_points: Query<(&mut AttachmentPoint, &GlobalTransform)>,
-    _modules: Query<(&mut AttachableModule, &Transform, Entity)>, 
-    _mouse_buttons: Res<ButtonInput<MouseButton>>,
-    _windows: Query<&Window>,
-    _camera_q: Query<(&Camera, &GlobalTransform)>,
+/// System to take modules off their base, refunding part of the cost
+/// Modules still under construction are cancelled and refunded in full
+pub fn handle_module_detachment(
+    mut commands: Commands,
+    mut requests: EventReader<DetachModuleRequest>,
+    definitions: Res<ModuleDefinitions>,
+    modules: Query<(&InstalledModule, &Parent, Option<&AttachableModule>)>,
+    mut bases: Query<&mut MechanicalBase>,
+    mut points: Query<&mut AttachmentPoint>,
+    mut player_resources: Option<ResMut<PlayerResources>>,
+) {
+    for request in requests.read() {
+        let Ok((installed, parent, under_construction)) = modules.get(request.module) else { continue };
+        let Ok(mut base) = bases.get_mut(parent.get()) else { continue };
+
+        let refund_fraction = if under_construction.is_some() { 1.0 } else { MODULE_REFUND_FRACTION };
+        let refund = definitions
+            .catalog
+            .get(&installed.name)
+            .map(|definition| definition.cost.scaled(refund_fraction))
+            .unwrap_or_default();
+        match (base.team, player_resources.as_mut()) {
+            (Team::Player, Some(resources)) => resources.resources.refund(&refund),
+            _ => base.resources.refund(&refund),
+        }
+
+        if let Ok(mut point) = points.get_mut(installed.point) {
+            point.occupied = false;
+            point.attached_module = None;
+        }
+        base.modules.retain(|module| *module != request.module);
+        commands.entity(request.module).despawn_recursive();
+        info!("{:?} base detached {}", base.team, installed.name);
+    }
+}
+
+/// System to advance module construction and switch modules on once they are built
+fn update_module_construction(
+    mut commands: Commands,
+    time: Res<Time>,
+    definitions: Res<ModuleDefinitions>,
+    mut building: Query<(Entity, &mut AttachableModule, &InstalledModule, &Parent, &mut Sprite)>,
+    mut bases: Query<&mut MechanicalBase>,
 ) {
-    // Implementation will be added later
+    for (entity, mut attachable, installed, parent, mut sprite) in building.iter_mut() {
+        attachable.build_progress = (attachable.build_progress + time.delta_seconds() / attachable.build_time.max(f32::EPSILON)).min(1.0);
+        if attachable.build_progress < 1.0 {
+            continue;
+        }
+
+        let Ok(mut base) = bases.get_mut(parent.get()) else { continue };
+        let module = match definitions.catalog.get(&installed.name) {
+            Some(definition) => definition.to_base_module(base.team),
+            None => BaseModule {
+                module_type: attachable.module_type.clone(),
+                team: base.team,
+                ..default()
+            },
+        };
+
+        if let ModuleType::Weapon { attack_speed, .. } = module.module_type {
+            commands.entity(entity).insert(Cooldown {
+                timer: Timer::from_seconds(1.0 / attack_speed.max(0.01), TimerMode::Once),
+            });
+        }
+        commands.entity(entity).remove::<AttachableModule>().insert(module);
+        sprite.color = Color::WHITE;
+        base.add_module(entity);
+        info!("{:?} base finished building {}", base.team, installed.name);
+    }
+}
+
+/// System to knock modules that have run out of health off their base
+/// Their attachment point is freed for a new module, with nothing refunded
+pub fn remove_destroyed_modules(
+    mut commands: Commands,
+    modules: Query<(Entity, &BaseModule, &Parent, Option<&InstalledModule>)>,
+    mut bases: Query<&mut MechanicalBase>,
+    mut points: Query<&mut AttachmentPoint>,
+) {
+    for (entity, module, parent, installed) in modules.iter() {
+        if module.health > 0.0 {
+            continue;
+        }
+        let Ok(mut base) = bases.get_mut(parent.get()) else { continue };
+
+        let mut free_points = points.iter_many_mut(&base.attachment_points);
+        while let Some(mut point) = free_points.fetch_next() {
+            if point.attached_module == Some(entity) {
+                point.occupied = false;
+                point.attached_module = None;
+            }
+        }
+        base.modules.retain(|&other| other != entity);
+        commands.entity(entity).despawn_recursive();
+
+        let name = installed.map_or_else(|| format!("{:?}", module.module_type.category()), |installed| installed.name.clone());
+        info!("{:?} base lost its {} module", base.team, name);
+    }
 }
@@ -1,4 +1,6 @@
 use bevy::prelude::*;
+use crate::components::economy::{ResourceType, ResourceWallet};
+use crate::components::unit_types::UnitType;
 
 #[derive(Component)]
 pub struct Building {
@@ -10,9 +12,24 @@ pub struct Building {
 
 #[derive(Component)]
 pub struct BuildingSpawner {
-    pub unit_type: String,
+    pub unit_type: Option<UnitType>, // Unit produced whenever the queue is empty, if any
+    pub queue: Vec<UnitType>,        // Units ordered ahead of the standing production
+    pub produces: Vec<UnitType>,     // Units this building is able to produce
     pub spawn_time: f32,
     pub spawn_timer: Timer,
+    pub in_production: Option<UnitType>, // Unit the spawn timer is counting down for
+}
+
+impl BuildingSpawner {
+    /// Whether this building is able to produce the given unit
+    pub fn can_produce(&self, unit_type: UnitType) -> bool {
+        self.produces.contains(&unit_type)
+    }
+
+    /// Unit that comes out next: the first queued order, or else the standing production
+    pub fn next_unit(&self) -> Option<UnitType> {
+        self.queue.first().copied().or(self.unit_type)
+    }
 }
 
 #[derive(Component)]
@@ -25,12 +42,5 @@ pub struct ResourceGenerator {
 #[derive(Component)]
 pub struct Constructable {
     pub construction_time: f32,
-    pub resource_cost: Vec<(ResourceType, i32)>,
-}
-
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
-pub enum ResourceType {
-    Wood,
-    Stone,
-    Iron,
+    pub resource_cost: ResourceWallet,
 }
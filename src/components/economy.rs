@@ -0,0 +1,146 @@
+use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Types of resources that can be gathered, stored, generated or spent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum ResourceType {
+    Basic,
+    Wood,
+    Stone,
+    Iron,
+    Copper,
+    Crystal,
+    Alloy,
+    Energy,
+    Fuel,
+    Ammunition,
+    Research,
+    Population,
+}
+
+/// Amounts of each resource type, used both for stockpiles and for costs
+///
+/// Serializes as a plain list of `(ResourceType, amount)` pairs, so data files can
+/// write costs as `[(Wood, 20), (Stone, 10)]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ResourceWallet {
+    amounts: Vec<(ResourceType, i32)>,
+}
+
+/// Returned by `ResourceWallet::spend` when the wallet can't cover a cost
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InsufficientResources {
+    pub missing: ResourceWallet, // How much of each resource is lacking
+}
+
+impl fmt::Display for InsufficientResources {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not enough resources, missing")?;
+        for (resource_type, amount) in self.missing.iter() {
+            write!(f, " {} {:?}", amount, resource_type)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for InsufficientResources {}
+
+impl ResourceWallet {
+    /// Create an empty wallet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Amount of a resource held (zero if the wallet has none)
+    pub fn get(&self, resource_type: ResourceType) -> i32 {
+        self.amounts
+            .iter()
+            .find(|(held_type, _)| *held_type == resource_type)
+            .map_or(0, |(_, amount)| *amount)
+    }
+
+    /// Set the amount of a resource, adding an entry for it if needed
+    pub fn set(&mut self, resource_type: ResourceType, amount: i32) {
+        match self.amounts.iter_mut().find(|(held_type, _)| *held_type == resource_type) {
+            Some((_, held)) => *held = amount,
+            None => self.amounts.push((resource_type, amount)),
+        }
+    }
+
+    /// Add an amount of a single resource
+    pub fn add(&mut self, resource_type: ResourceType, amount: i32) {
+        let held = self.get(resource_type);
+        self.set(resource_type, held.saturating_add(amount));
+    }
+
+    /// Iterate over every resource entry in the wallet
+    pub fn iter(&self) -> impl Iterator<Item = (ResourceType, i32)> + '_ {
+        self.amounts.iter().copied()
+    }
+
+    /// Whether the wallet holds no entries at all
+    pub fn is_empty(&self) -> bool {
+        self.amounts.is_empty()
+    }
+
+    /// Resources still needed to pay the given cost (empty if it's affordable)
+    pub fn shortfall(&self, cost: &ResourceWallet) -> ResourceWallet {
+        cost.iter()
+            .filter_map(|(resource_type, amount)| {
+                let missing = amount - self.get(resource_type);
+                (missing > 0).then_some((resource_type, missing))
+            })
+            .collect()
+    }
+
+    /// Whether the wallet covers the given cost
+    pub fn can_afford(&self, cost: &ResourceWallet) -> bool {
+        self.shortfall(cost).is_empty()
+    }
+
+    /// Deduct a cost, leaving the wallet untouched if any resource falls short
+    pub fn spend(&mut self, cost: &ResourceWallet) -> Result<(), InsufficientResources> {
+        let missing = self.shortfall(cost);
+        if !missing.is_empty() {
+            return Err(InsufficientResources { missing });
+        }
+
+        for (resource_type, amount) in cost.iter() {
+            self.add(resource_type, -amount);
+        }
+        Ok(())
+    }
+
+    /// Give back a previously spent cost
+    pub fn refund(&mut self, cost: &ResourceWallet) {
+        for (resource_type, amount) in cost.iter() {
+            self.add(resource_type, amount);
+        }
+    }
+
+    /// A share of every amount in the wallet, rounded down
+    pub fn scaled(&self, factor: f32) -> ResourceWallet {
+        self.iter()
+            .map(|(resource_type, amount)| (resource_type, (amount as f32 * factor).floor() as i32))
+            .filter(|(_, amount)| *amount != 0)
+            .collect()
+    }
+}
+
+impl FromIterator<(ResourceType, i32)> for ResourceWallet {
+    fn from_iter<I: IntoIterator<Item = (ResourceType, i32)>>(iter: I) -> Self {
+        let mut wallet = ResourceWallet::new();
+        for (resource_type, amount) in iter {
+            wallet.add(resource_type, amount);
+        }
+        wallet
+    }
+}
+
+impl<const N: usize> From<[(ResourceType, i32); N]> for ResourceWallet {
+    fn from(amounts: [(ResourceType, i32); N]) -> Self {
+        amounts.into_iter().collect()
+    }
+}
@@ -2,6 +2,7 @@ pub mod unit;
 pub mod unit_label;
 pub mod unit_types;
 pub mod building;
+pub mod economy;
 pub mod resource;
 pub mod terrain;
 pub mod player;
@@ -13,5 +14,4 @@ pub mod unit_sprite;
 // Export plugins
 // Temporarily commented out for debugging
 // pub use unit_label::UnitLabelPlugin;
-pub use ai::AIPlugin;
 pub use unit_sprite::IsometricSpritePlugin;
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use bevy::reflect::Reflect;
 use crate::components::unit::Team;
-use crate::components::base_modules::ResourceType;
+use crate::components::economy::{ResourceType, ResourceWallet};
 
 /// The main mobile base that players control
 #[derive(Component, Debug, Reflect)]
@@ -12,7 +12,7 @@ pub struct MechanicalBase {
     pub base_movement_speed: f32,  // Base speed without modules
     pub effective_movement_speed: f32,  // Speed after module modifiers
     pub team: Team,
-    pub resources: Vec<(ResourceType, i32)>,
+    pub resources: ResourceWallet,
     pub power_output: f32,  // Total power generated
     pub power_consumed: f32, // Power currently in use
     pub max_power: f32,      // Maximum power capacity
@@ -28,11 +28,11 @@ impl Default for MechanicalBase {
             base_movement_speed: 30.0,  // Base speed without modules
             effective_movement_speed: 30.0,  // Will be updated by module system
             team: Team::Player,
-            resources: vec![
+            resources: ResourceWallet::from([
                 (ResourceType::Wood, 100),
                 (ResourceType::Stone, 50),
                 (ResourceType::Iron, 25),
-            ],
+            ]),
             power_output: 100.0,  // Base power generation
             power_consumed: 0.0,  // Starts with no power consumption
             max_power: 150.0,     // Base power capacity
@@ -65,9 +65,9 @@ impl MechanicalBase {
 }
 
 /// Player resources and stats
-#[derive(Resource)]
+#[derive(Resource, Clone, Debug)]
 pub struct PlayerResources {
-    pub resources: Vec<(ResourceType, i32)>,
+    pub resources: ResourceWallet,
     pub score: i32,
     pub strategic_points_controlled: i32,
 }
@@ -75,11 +75,11 @@ pub struct PlayerResources {
 impl Default for PlayerResources {
     fn default() -> Self {
         Self {
-            resources: vec![
+            resources: ResourceWallet::from([
                 (ResourceType::Wood, 100),
                 (ResourceType::Stone, 50),
                 (ResourceType::Iron, 25),
-            ],
+            ]),
             score: 0,
             strategic_points_controlled: 0,
         }
@@ -90,10 +90,13 @@ impl Default for PlayerResources {
 #[derive(Component)]
 pub struct PlayerControlled;
 
-/// Target location that the base needs to reach
-#[derive(Component)]
+/// Target location that the base needs to reach and hold
+///
+/// Kept on every strategic location so the victory rules know how long it has been held.
+#[derive(Component, Debug, Clone, Default)]
 pub struct StrategicTarget {
     pub position: Vec2,
     pub is_reached: bool,
-    pub time_held: f32,
+    pub time_held: f32,          // Seconds `held_by` has held it without a break
+    pub held_by: Option<Team>,
 }
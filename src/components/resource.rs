@@ -1,19 +1,47 @@
 use bevy::prelude::*;
-use crate::components::building::ResourceType;
+use crate::components::economy::ResourceType;
 
 #[derive(Component)]
 pub struct ResourceNode {
     pub resource_type: ResourceType,
     pub amount_remaining: i32,
-    pub max_gatherers: i32,
-    pub current_gatherers: i32,
+    pub max_amount: i32,
+    pub max_gatherers: i32,     // How many units can harvest at once
+    pub current_gatherers: i32, // Units currently harvesting, kept up to date by the economy systems
+}
+
+impl ResourceNode {
+    /// Create a full node holding the given amount
+    pub fn new(resource_type: ResourceType, amount: i32, max_gatherers: i32) -> Self {
+        Self {
+            resource_type,
+            amount_remaining: amount,
+            max_amount: amount,
+            max_gatherers,
+            current_gatherers: 0,
+        }
+    }
+
+    /// Whether another gatherer can start harvesting this node
+    pub fn has_free_slot(&self) -> bool {
+        self.amount_remaining > 0 && self.current_gatherers < self.max_gatherers
+    }
 }
 
 #[derive(Component)]
 pub struct Gatherer {
-    pub gather_rate: f32,
+    pub gather_rate: f32,                    // Amount harvested each time the gather timer finishes
     pub gather_timer: Timer,
     pub carry_capacity: i32,
     pub current_load: i32,
-    pub target_resource: Option<Entity>,
+    pub target_resource: Option<Entity>,     // Node this unit holds a harvesting slot on
+    pub carried_type: Option<ResourceType>,  // What the current load is made of
+    pub returning: bool,                     // Heading back to a base to drop off the load
+}
+
+impl Gatherer {
+    /// Whether the unit can't carry any more
+    pub fn is_full(&self) -> bool {
+        self.current_load >= self.carry_capacity
+    }
 }
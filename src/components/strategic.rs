@@ -1,10 +1,37 @@
 use bevy::prelude::*;
-use crate::components::unit::Team;
-use crate::components::player::MechanicalBase;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::components::unit::{Team, Unit};
+use crate::components::unit_types::UnitType;
+use crate::components::player::{MechanicalBase, PlayerResources};
 use crate::resources::map_data::GameMap;
 use crate::resources::map::plugin::MapInitialized;
-use crate::states::game_state::GameState;
-use rand::Rng;
+use crate::states::game_state::{starting_new_match, GameState};
+use crate::systems::unit_catalog::{UnitDefinition, UnitDefinitions, UnitTag};
+
+/// Default distance from a strategic location within which units and bases count toward capturing it
+pub const CAPTURE_RADIUS: f32 = 100.0;
+
+/// Default control points gained per second for each point of capture strength
+pub const CAPTURE_RATE: f32 = 2.0;
+
+/// Default control points lost per second while nobody is nearby
+pub const DECAY_RATE: f32 = 2.0;
+
+/// Default control points lost per second while evenly matched teams contest a location
+pub const CONTESTED_DECAY_RATE: f32 = 5.0;
+
+/// Attack power worth one point of capture strength
+pub const CAPTURE_STRENGTH_PER_ATTACK: f32 = 10.0;
+
+/// Capture strength of each mechanical base in range
+pub const BASE_CAPTURE_STRENGTH: f32 = 5.0;
+
+/// Seconds between score payouts for held locations
+pub const SCORE_INTERVAL: f32 = 1.0;
+
+/// Score a team earns each payout for every point of capture value it holds
+pub const SCORE_PER_CAPTURE_VALUE: i32 = 1;
 
 /// Component representing a strategic location target
 #[derive(Component)]
@@ -14,6 +41,13 @@ pub struct StrategicLocation {
     pub total_required: f32,
     pub controlling_team: Option<Team>,
     pub position: Vec2,
+    pub capture_value: i32, // How much holding this location is worth
+    pub capture_radius: f32,
+    pub capture_rate: f32,          // Points per second for each point of capture strength
+    pub decay_rate: f32,            // Points lost per second while nobody is nearby
+    pub contested_decay_rate: f32,  // Points lost per second while rivals are evenly matched
+    pub capturing_team: Option<Team>, // Team the control points belong to while nobody holds it
+    pub state: CaptureState,
 }
 
 impl Default for StrategicLocation {
@@ -24,24 +58,113 @@ impl Default for StrategicLocation {
             total_required: 100.0, // Points needed to capture
             controlling_team: None,
             position: Vec2::ZERO,
+            capture_value: 1,
+            capture_radius: CAPTURE_RADIUS,
+            capture_rate: CAPTURE_RATE,
+            decay_rate: DECAY_RATE,
+            contested_decay_rate: CONTESTED_DECAY_RATE,
+            capturing_team: None,
+            state: CaptureState::Idle,
         }
     }
 }
 
+impl StrategicLocation {
+    /// Team the control points currently count toward, whether it holds the location yet or not
+    pub fn progress_team(&self) -> Option<Team> {
+        self.controlling_team.or(self.capturing_team)
+    }
+}
+
+/// What is happening at a strategic location right now
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CaptureState {
+    #[default]
+    Idle,            // Nobody nearby, control slowly decays
+    Capturing(Team), // Only one team is nearby
+    Contested,       // Rival teams are nearby
+}
+
+/// Event sent when a team finishes capturing a strategic location
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct LocationCaptured {
+    pub location: Entity,
+    pub team: Team,
+}
+
+/// Event sent when a team loses control of a strategic location
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct LocationLost {
+    pub location: Entity,
+    pub team: Team,
+}
+
 /// Marker component for the visual indicator of a strategic location
 #[derive(Component)]
 pub struct StrategicLocationMarker;
 
+/// Score and held locations of one team
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TeamScore {
+    pub score: i32,
+    pub locations_held: i32,
+    pub value_held: i32, // Total capture value of the held locations
+}
+
+/// Scores earned by every team from holding strategic locations
+///
+/// The player's share is also added to `PlayerResources`.
+#[derive(Resource, Debug, Clone)]
+pub struct StrategicScores {
+    pub teams: HashMap<Team, TeamScore>,
+    payout_timer: Timer,
+}
+
+impl Default for StrategicScores {
+    fn default() -> Self {
+        Self {
+            teams: HashMap::new(),
+            payout_timer: Timer::from_seconds(SCORE_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+/// How hard a unit pushes control of a location toward its team
+///
+/// Units count in proportion to their attack power. Units the catalog doesn't tag as `Combat`,
+/// such as gatherers and engineers, can't take or hold ground at all.
+pub fn unit_capture_strength(unit: &Unit, definition: Option<&UnitDefinition>) -> f32 {
+    if definition.is_some_and(|definition| !definition.has_tag(UnitTag::Combat)) {
+        return 0.0;
+    }
+    unit.attack_power.max(0.0) / CAPTURE_STRENGTH_PER_ATTACK
+}
+
+impl StrategicScores {
+    /// Score and held locations of a team, zero if it holds nothing yet
+    pub fn team(&self, team: Team) -> TeamScore {
+        self.teams.get(&team).copied().unwrap_or_default()
+    }
+}
+
 /// Plugin to manage strategic locations
 pub struct StrategicLocationPlugin;
 
 impl Plugin for StrategicLocationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Gameplay), spawn_strategic_locations.after(crate::resources::map::plugin::setup_map))
+        app.init_resource::<StrategicScores>()
+           .init_resource::<UnitDefinitions>()
+           .add_event::<LocationCaptured>()
+           .add_event::<LocationLost>()
+           .add_systems(
+                OnEnter(GameState::Gameplay),
+                (reset_strategic_scores, spawn_strategic_locations.after(crate::resources::map::plugin::setup_map))
+                    .run_if(starting_new_match)
+            )
            .add_systems(
                 Update, 
                 (
-                    update_strategic_location_control,
+                    (update_strategic_location_control, score_strategic_locations).chain(),
                     update_strategic_location_visuals,
                 ).run_if(in_state(GameState::Gameplay))
             );
@@ -50,6 +173,11 @@ impl Plugin for StrategicLocationPlugin {
     }
 }
 
+// Every match starts from zero
+fn reset_strategic_scores(mut scores: ResMut<StrategicScores>) {
+    *scores = StrategicScores::default();
+}
+
 /// System to spawn strategic locations on the map
 fn spawn_strategic_locations(
     mut commands: Commands,
@@ -64,128 +192,189 @@ fn spawn_strategic_locations(
         info!("Map not yet initialized, skipping strategic location spawning");
         return;
     }
-    // Define the number of strategic locations based on the number of players
-    // For now we hardcode 2 players, so 1 strategic location (2/2 = 1)
-    let num_locations = 1;
-    
-    let mut rng = rand::thread_rng();
-    
-    // Create strategic locations in the center area of the map
-    for i in 0..num_locations {
-        // Choose a position in the central area of the map
-        let center_x = game_map.width as f32 / 2.0;
-        let center_y = game_map.height as f32 / 2.0;
-        
-        // Add some randomness to the position, but keep it near the center
-        let offset_x = rng.gen_range(-5.0..5.0);
-        let offset_y = rng.gen_range(-5.0..5.0);
-        
-        let grid_x = (center_x + offset_x).round() as i32;
-        let grid_y = (center_y + offset_y).round() as i32;
+    // Strategic locations come from the map layout
+    for (i, spawn) in game_map.strategic_locations.iter().enumerate() {
+        let world_pos = game_map.tile_center(spawn.position.0, spawn.position.1);
         
-        // Convert grid position to world position
-        let world_pos = game_map.grid_to_world(grid_x, grid_y);
+        let defaults = StrategicLocation::default();
+        spawn_strategic_location(&mut commands, StrategicLocation {
+            name: spawn.name.clone().unwrap_or_else(|| format!("Strategic Point {}", i + 1)),
+            position: world_pos,
+            capture_value: spawn.capture_value,
+            capture_radius: spawn.capture_radius.map_or(defaults.capture_radius, |tiles| tiles * game_map.tile_size),
+            capture_rate: spawn.capture_rate.unwrap_or(defaults.capture_rate),
+            ..defaults
+        }, game_map.tile_size);
         
-        // Create the strategic location
-        let _location = commands.spawn((
-            SpriteBundle {
-                sprite: Sprite {
-                    color: Color::srgba(0.9, 0.7, 0.1, 1.0), // Gold color
-                    custom_size: Some(Vec2::new(game_map.tile_size * 3.0, game_map.tile_size * 3.0)),
-                    ..default()
-                },
-                transform: Transform::from_xyz(world_pos.x, world_pos.y, 5.0), // Above terrain, below units
-                ..default()
-            },
-            StrategicLocation {
-                name: format!("Strategic Point {}", i + 1),
-                position: Vec2::new(world_pos.x, world_pos.y),
+        info!("Spawned Strategic Location {} at position: {:?}", i + 1, world_pos);
+    }
+}
+
+/// Spawn a strategic location along with its visual marker
+pub fn spawn_strategic_location(commands: &mut Commands, location: StrategicLocation, tile_size: f32) -> Entity {
+    let position = location.position;
+    let name = location.name.clone();
+    
+    // Create the strategic location
+    let entity = commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgba(0.9, 0.7, 0.1, 1.0), // Gold color
+                custom_size: Some(Vec2::new(tile_size * 3.0, tile_size * 3.0)),
                 ..default()
             },
-            Name::new(format!("Strategic Location {}", i + 1)),
-        )).id();
-        
-        // Add a visual marker to make it more visible
-        commands.spawn((
-            SpriteBundle {
-                sprite: Sprite {
-                    color: Color::srgba(1.0, 0.9, 0.3, 1.0), // Brighter gold for the marker
-                    custom_size: Some(Vec2::new(game_map.tile_size * 1.5, game_map.tile_size * 1.5)),
-                    ..default()
-                },
-                transform: Transform::from_xyz(world_pos.x, world_pos.y, 6.0), // Above the base sprite
+            transform: Transform::from_xyz(position.x, position.y, 5.0), // Above terrain, below units
+            ..default()
+        },
+        location,
+        Name::new(name.clone()),
+    )).id();
+    
+    // Add a visual marker to make it more visible
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgba(1.0, 0.9, 0.3, 1.0), // Brighter gold for the marker
+                custom_size: Some(Vec2::new(tile_size * 1.5, tile_size * 1.5)),
                 ..default()
             },
-            StrategicLocationMarker,
-            Name::new(format!("Strategic Location Marker {}", i + 1)),
-        ));
-        
-        info!("Spawned Strategic Location {} at position: {:?}", i + 1, world_pos);
-    }
+            transform: Transform::from_xyz(position.x, position.y, 6.0), // Above the base sprite
+            ..default()
+        },
+        StrategicLocationMarker,
+        Name::new(format!("{} Marker", name)),
+    ));
+    
+    entity
 }
 
 /// System to update the control status of strategic locations
+/// Each team pushes control toward itself in proportion to how much stronger it is than its rivals nearby
 fn update_strategic_location_control(
     time: Res<Time>,
-    mut locations: Query<&mut StrategicLocation>,
+    mut locations: Query<(Entity, &mut StrategicLocation)>,
     bases: Query<(&Transform, &MechanicalBase)>,
+    units: Query<(&Transform, &Unit, Option<&UnitType>)>,
+    definitions: Res<UnitDefinitions>,
+    mut captured_events: EventWriter<LocationCaptured>,
+    mut lost_events: EventWriter<LocationLost>,
 ) {
-    // Define the capture radius
-    let capture_radius = 100.0;
-    
-    for mut location in locations.iter_mut() {
-        // Check which teams have bases within the capture radius
-        let mut controlling_teams: Vec<Team> = Vec::new();
-        
-        for (transform, base) in bases.iter() {
-            let base_pos = transform.translation.truncate();
-            let distance = location.position.distance(base_pos);
-            
-            if distance < capture_radius {
-                controlling_teams.push(base.team);
+    let presence: Vec<(Vec2, Team, f32)> = bases
+        .iter()
+        .map(|(transform, base)| (transform.translation.truncate(), base.team, BASE_CAPTURE_STRENGTH))
+        .chain(units.iter().map(|(transform, unit, unit_type)| {
+            let definition = unit_type.and_then(|unit_type| definitions.catalog.get(*unit_type));
+            (transform.translation.truncate(), unit.team, unit_capture_strength(unit, definition))
+        }))
+        .filter(|(_, team, weight)| *team != Team::Neutral && *weight > 0.0)
+        .collect();
+
+    for (entity, mut location) in locations.iter_mut() {
+        // Add up the strength of every team in range
+        let mut strength: HashMap<Team, f32> = HashMap::new();
+        for (position, team, weight) in &presence {
+            if location.position.distance(*position) < location.capture_radius {
+                *strength.entry(*team).or_default() += weight;
             }
         }
-        
-        // Update control points based on present teams
-        if controlling_teams.len() == 1 {
-            // Only one team present, they gain control
-            let team = controlling_teams[0];
-            
-            // If this is a different team than the current controlling team, reset points
-            if location.controlling_team.is_some() && location.controlling_team != Some(team) {
-                location.control_points = 0.0;
-                location.controlling_team = None;
+
+        let mut ranked: Vec<(Team, f32)> = strength.into_iter().collect();
+        ranked.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+
+        location.state = match ranked.as_slice() {
+            [] => CaptureState::Idle,
+            [(team, _)] => CaptureState::Capturing(*team),
+            _ => CaptureState::Contested,
+        };
+
+        let delta = time.delta_seconds();
+        let (pushing_team, advantage) = match ranked.as_slice() {
+            [] => (None, 0.0),
+            [(team, strength)] => (Some(*team), *strength),
+            [(team, strongest), (_, runner_up), ..] => (Some(*team), strongest - runner_up),
+        };
+
+        match pushing_team {
+            // One side outnumbers the rest and pushes control its way
+            Some(team) if advantage > 0.0 => {
+                let amount = location.capture_rate * advantage * delta;
+                if location.progress_team().is_none_or(|owner| owner == team) {
+                    location.capturing_team = Some(team);
+                    location.control_points = (location.control_points + amount).min(location.total_required);
+
+                    if location.control_points >= location.total_required && location.controlling_team != Some(team) {
+                        location.controlling_team = Some(team);
+                        info!("Team {:?} has captured location: {}", team, location.name);
+                        captured_events.send(LocationCaptured { location: entity, team });
+                    }
+                } else {
+                    // Someone else's control has to be worn down first
+                    drain_control(entity, &mut location, amount, &mut lost_events);
+                }
             }
-            
-            // Increase control points for this team
-            location.control_points += time.delta_seconds() * 10.0; // 10 points per second
-            
-            // Cap at max points
-            if location.control_points >= location.total_required {
-                location.control_points = location.total_required;
-                location.controlling_team = Some(team);
-                
-                info!("Team {:?} has captured location: {}", team, location.name);
+            // Evenly matched rivals wear control down faster than an empty location
+            Some(_) => {
+                let amount = location.contested_decay_rate * delta;
+                drain_control(entity, &mut location, amount, &mut lost_events);
             }
-        } else if controlling_teams.len() > 1 {
-            // Multiple teams present, control is contested
-            // Slowly decrease control points when contested
-            location.control_points = (location.control_points - time.delta_seconds() * 5.0).max(0.0);
-            
-            if location.control_points <= 0.0 {
-                location.controlling_team = None;
-            }
-        } else {
-            // No teams present, slowly decay control
-            location.control_points = (location.control_points - time.delta_seconds() * 2.0).max(0.0);
-            
-            if location.control_points <= 0.0 {
-                location.controlling_team = None;
+            None => {
+                let amount = location.decay_rate * delta;
+                drain_control(entity, &mut location, amount, &mut lost_events);
             }
         }
     }
 }
 
+// Take control points away from a location, making it neutral when they run out
+fn drain_control(entity: Entity, location: &mut StrategicLocation, amount: f32, lost_events: &mut EventWriter<LocationLost>) {
+    location.control_points = (location.control_points - amount).max(0.0);
+    if location.control_points > 0.0 {
+        return;
+    }
+
+    location.capturing_team = None;
+    if let Some(team) = location.controlling_team.take() {
+        info!("Team {:?} has lost location: {}", team, location.name);
+        lost_events.send(LocationLost { location: entity, team });
+    }
+}
+
+/// System to pay out score for held locations, weighted by how valuable each one is
+pub fn score_strategic_locations(
+    time: Res<Time>,
+    mut scores: ResMut<StrategicScores>,
+    locations: Query<&StrategicLocation>,
+    player_resources: Option<ResMut<PlayerResources>>,
+) {
+    // Recount what everyone holds right now
+    for team_score in scores.teams.values_mut() {
+        team_score.locations_held = 0;
+        team_score.value_held = 0;
+    }
+    for location in locations.iter() {
+        if let Some(team) = location.controlling_team {
+            let team_score = scores.teams.entry(team).or_default();
+            team_score.locations_held += 1;
+            team_score.value_held += location.capture_value;
+        }
+    }
+
+    let paid_out = scores.payout_timer.tick(time.delta()).times_finished_this_tick() as i32;
+    let mut player_payout = 0;
+    for (team, team_score) in scores.teams.iter_mut() {
+        let payout = team_score.value_held * SCORE_PER_CAPTURE_VALUE * paid_out;
+        team_score.score += payout;
+        if *team == Team::Player {
+            player_payout = payout;
+        }
+    }
+
+    if let Some(mut player_resources) = player_resources {
+        player_resources.score += player_payout;
+        player_resources.strategic_points_controlled = scores.team(Team::Player).locations_held;
+    }
+}
+
 /// System to update the visual appearance of strategic locations
 fn update_strategic_location_visuals(
     mut param_set: ParamSet<(
@@ -1,17 +1,10 @@
 use bevy::prelude::*;
 
-#[derive(Component, Copy, Clone, Debug, PartialEq, Eq, Hash)]
-pub enum TerrainType {
-    Plains,      // Normal movement speed
-    Forest,      // Slows movement, provides wood resources
-    Mountain,    // Very slow movement, provides stone resources
-    Water,       // Impassable for land units
-    MetalDeposit, // Provides iron resources
-    RoughTerrain, // Slows movement
-    StrategicLocation, // Target locations to capture and hold
-}
+// Terrain types live with the map data; re-exported so terrain code has one place to look
+pub use crate::resources::map_data::TerrainType;
 
-#[derive(Component)]
+/// Movement, vision and defense stats of a terrain type
+#[derive(Component, Copy, Clone, Debug, PartialEq)]
 pub struct Terrain {
     pub terrain_type: TerrainType,
     pub movement_modifier: f32, // Multiplier for movement speed (1.0 = normal, <1.0 = slower)
@@ -27,14 +20,6 @@ pub struct MapTile {
     pub is_visible: bool,
 }
 
-#[derive(Component)]
-pub struct StrategicPoint {
-    pub capture_progress: f32, // 0.0 to 1.0
-    pub controlling_team: Option<crate::components::unit::Team>,
-    pub capture_value: i32, // Points value or strategic importance
-    pub position: Vec2, // World position of the strategic point
-}
-
 impl Terrain {
     pub fn new(terrain_type: TerrainType) -> Self {
         match terrain_type {
@@ -50,7 +35,13 @@ impl Terrain {
                 visibility_modifier: 0.6,
                 defense_modifier: 1.3,
             },
-            TerrainType::Mountain => Self {
+            TerrainType::Hills => Self {
+                terrain_type,
+                movement_modifier: 0.6,
+                visibility_modifier: 1.2, // Some view over the lowlands
+                defense_modifier: 1.2,
+            },
+            TerrainType::Mountains => Self {
                 terrain_type,
                 movement_modifier: 0.4,
                 visibility_modifier: 1.5, // Better visibility from high ground
@@ -60,7 +51,7 @@ impl Terrain {
                 terrain_type,
                 movement_modifier: 0.0, // Impassable
                 visibility_modifier: 1.0,
-                defense_modifier: 0.0, // No defense bonus (can't stop on water)
+                defense_modifier: 1.0, // No defense bonus (only aircraft are ever over water)
             },
             TerrainType::MetalDeposit => Self {
                 terrain_type,
@@ -68,18 +59,6 @@ impl Terrain {
                 visibility_modifier: 1.0,
                 defense_modifier: 1.1,
             },
-            TerrainType::RoughTerrain => Self {
-                terrain_type,
-                movement_modifier: 0.6,
-                visibility_modifier: 0.8,
-                defense_modifier: 1.2,
-            },
-            TerrainType::StrategicLocation => Self {
-                terrain_type,
-                movement_modifier: 1.0,
-                visibility_modifier: 1.2,
-                defense_modifier: 1.3,
-            },
         }
     }
 }
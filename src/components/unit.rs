@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use bevy::ecs::entity::Entity;
+use serde::{Deserialize, Serialize};
 
 // Unit components for our RTS game
 
@@ -23,7 +24,7 @@ pub struct Selectable;
 #[derive(Component)]
 pub struct Selected;
 
-#[derive(Component)]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum UnitState {
     Idle,
     Moving,
@@ -34,10 +35,17 @@ pub enum UnitState {
 
 use bevy::reflect::Reflect;
 
-#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect, Serialize, Deserialize)]
 #[reflect(Component)]
 pub enum Team {
     Player,
     Enemy,
     Neutral,
 }
+
+/// Starting experience handed to a unit by the base that produced it
+/// Nothing reads it yet, it has no effect on the unit's stats
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Experience {
+    pub points: f32,
+}
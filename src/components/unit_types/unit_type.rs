@@ -1,8 +1,14 @@
 use bevy::prelude::*;
 use crate::components::unit::{Unit, Team, UnitState};
+use crate::components::base_modules::DamageType;
+use crate::components::resource::Gatherer;
+use crate::systems::damage::ArmorClass;
+use crate::systems::unit_catalog::{UnitCatalog, UnitTag};
+use crate::units::engineer::Engineer;
+use serde::{Deserialize, Serialize};
 
 /// Defines the different types of units available in the game
-#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum UnitType {
     // Gatherer units
     Engineer,
@@ -25,90 +31,128 @@ pub enum UnitType {
 }
 
 impl UnitType {
-    pub fn spawn_unit(&self, commands: &mut Commands, position: Vec2, team: Team) -> Entity {
+    /// Spawn a unit of this type with the stats from the unit catalog
+    pub fn spawn_unit(&self, commands: &mut Commands, catalog: &UnitCatalog, position: Vec2, team: Team) -> Entity {
         // Note: We cannot access GameSprites directly from here because Commands doesn't have access to the world
-        // The calling system will need to pass sprite handles when needed
-        let (health, attack_power, attack_range, movement_speed, _color) = match self {
-            // Gatherer units
-            UnitType::Engineer => (50.0, 5.0, 1.0, 60.0, Color::srgba(0.2, 0.8, 0.2, 1.0)),
-            UnitType::Gatherer => (40.0, 2.0, 1.0, 70.0, Color::srgba(0.2, 0.7, 0.3, 1.0)),
-            
-            // Combat units - Land
-            UnitType::LandToLandTank => (100.0, 15.0, 5.0, 40.0, Color::srgba(0.5, 0.5, 0.5, 1.0)),
-            UnitType::LandToAirTank => (90.0, 12.0, 8.0, 45.0, Color::srgba(0.6, 0.6, 0.6, 1.0)),
-            UnitType::Artillery => (70.0, 25.0, 12.0, 25.0, Color::srgba(0.7, 0.7, 0.7, 1.0)),
-            
-            // Combat units - Air
-            UnitType::AirToAirFighter => (60.0, 18.0, 6.0, 90.0, Color::srgba(0.3, 0.3, 0.8, 1.0)),
-            UnitType::AirToLandBomber => (80.0, 30.0, 5.0, 70.0, Color::srgba(0.4, 0.4, 0.8, 1.0)),
-            
-            // Special large units
-            UnitType::LargeTank => (200.0, 30.0, 7.0, 25.0, Color::srgba(0.8, 0.8, 0.2, 1.0)),
-            UnitType::LargeHoveringAircraft => (150.0, 25.0, 10.0, 50.0, Color::srgba(0.2, 0.8, 0.8, 1.0)),
-            UnitType::LargeBomber => (120.0, 40.0, 8.0, 60.0, Color::srgba(0.8, 0.2, 0.8, 1.0)),
-            UnitType::LargeArtillery => (150.0, 50.0, 15.0, 20.0, Color::srgba(0.9, 0.6, 0.1, 1.0)),
-        };
-        
-        let _team_color = match team {
-            Team::Player => Color::srgba(0.2, 0.6, 0.8, 1.0),
-            Team::Enemy => Color::srgba(0.8, 0.2, 0.2, 1.0),
-            Team::Neutral => Color::srgba(0.7, 0.7, 0.7, 1.0),
-        };
+        // The unit catalog plugin attaches the sprite named by the definition once the unit exists
+        let definition = catalog.definition(*self);
         
-        // Combine unit type color with team color
-        let final_color = if team == Team::Player {
-            Color::srgba(0.2, 0.6, 0.8, 1.0) // Blue for player units
-        } else if team == Team::Enemy {
-            Color::srgba(0.8, 0.2, 0.2, 1.0) // Red for enemy units
-        } else {
-            Color::srgba(0.7, 0.7, 0.7, 1.0) // Gray for neutral units
+        let team_color = match team {
+            Team::Player => Color::srgba(0.2, 0.6, 0.8, 1.0), // Blue for player units
+            Team::Enemy => Color::srgba(0.8, 0.2, 0.2, 1.0),  // Red for enemy units
+            Team::Neutral => Color::srgba(0.7, 0.7, 0.7, 1.0), // Gray for neutral units
         };
         
-        // Create the unit entity
-        let entity = commands.spawn_empty().id();
-        
-        // We don't try to get sprites here anymore - the spawning system will handle that
-        // We only set up the entity properties here
-        let sprite_opt = None;
-        
-        // Add components one by one
-        commands.entity(entity)
-            .insert(if let Some(sprite_handle) = sprite_opt {
-                // Use the sprite texture
-                SpriteBundle {
-                    texture: sprite_handle,
-                    sprite: Sprite {
-                        color: final_color, // Apply team color tint
-                        ..default()
-                    },
-                    transform: Transform::from_xyz(position.x, position.y, 1.0), // Lower z-value to ensure visibility
-                    ..default()
-                }
-            } else {
-                // Fallback to colored square if sprite not available
-                SpriteBundle {
-                    sprite: Sprite {
-                        color: final_color,
-                        custom_size: Some(Vec2::new(16.0, 16.0)),
-                        ..default()
-                    },
-                    transform: Transform::from_xyz(position.x, position.y, 1.0), // Lower z-value to match sprite version
+        // Fallback to colored square until a sprite is attached
+        let entity = commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: team_color,
+                    custom_size: Some(Vec2::splat(definition.size)),
                     ..default()
-                }
-            })
-            .insert(Unit {
-                health,
-                max_health: health,
-                attack_power,
-                attack_range,
-                movement_speed,
+                },
+                transform: Transform::from_xyz(position.x, position.y, 1.0),
+                ..default()
+            },
+            Unit {
+                health: definition.health,
+                max_health: definition.health,
+                attack_power: definition.attack_power,
+                attack_range: definition.attack_range,
+                movement_speed: definition.movement_speed,
                 team,
                 state: UnitState::Idle,
-                attack_cooldown: Timer::from_seconds(1.0, TimerMode::Once),
+                attack_cooldown: Timer::from_seconds(definition.attack_cooldown, TimerMode::Once),
                 attack_target: None,
                 movement_target: None,
+            },
+            *self,
+            self.armor_class(),
+            UnitState::Idle,
+            Name::new(format!("{:?} {}", team, self.name())),
+        )).id();
+        
+        // Tags grant engineering and gathering abilities
+        if definition.has_tag(UnitTag::Builder) {
+            commands.entity(entity).insert(Engineer {
+                build_speed: definition.build_speed,
+                build_timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+                target_building: None,
             });
+        }
+        
+        if definition.has_tag(UnitTag::Gatherer) {
+            commands.entity(entity).insert(Gatherer {
+                gather_rate: definition.gather_rate,
+                gather_timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+                carry_capacity: definition.carry_capacity,
+                current_load: 0,
+                target_resource: None,
+                carried_type: None,
+                returning: false,
+            });
+        }
             
         entity
     }
+    
+    /// Name used to refer to this unit type in production queues
+    pub fn name(&self) -> &'static str {
+        match self {
+            UnitType::Engineer => "Engineer",
+            UnitType::Gatherer => "Gatherer",
+            UnitType::LandToLandTank => "LandToLandTank",
+            UnitType::LandToAirTank => "LandToAirTank",
+            UnitType::Artillery => "Artillery",
+            UnitType::AirToAirFighter => "AirToAirFighter",
+            UnitType::AirToLandBomber => "AirToLandBomber",
+            UnitType::LargeTank => "LargeTank",
+            UnitType::LargeHoveringAircraft => "LargeHoveringAircraft",
+            UnitType::LargeBomber => "LargeBomber",
+            UnitType::LargeArtillery => "LargeArtillery",
+        }
+    }
+    
+    /// Look up a unit type by the name used in production queues
+    pub fn from_name(name: &str) -> Option<Self> {
+        const ALL: [UnitType; 11] = [
+            UnitType::Engineer,
+            UnitType::Gatherer,
+            UnitType::LandToLandTank,
+            UnitType::LandToAirTank,
+            UnitType::Artillery,
+            UnitType::AirToAirFighter,
+            UnitType::AirToLandBomber,
+            UnitType::LargeTank,
+            UnitType::LargeHoveringAircraft,
+            UnitType::LargeBomber,
+            UnitType::LargeArtillery,
+        ];
+        
+        ALL.into_iter().find(|unit_type| unit_type.name() == name)
+    }
+    
+    /// Type of damage this unit deals with its basic attack
+    pub fn damage_type(&self) -> DamageType {
+        match self {
+            UnitType::LandToAirTank => DamageType::Flak,
+            UnitType::Artillery | UnitType::LargeArtillery => DamageType::Explosive,
+            UnitType::AirToLandBomber | UnitType::LargeBomber => DamageType::Explosive,
+            UnitType::LargeHoveringAircraft => DamageType::Energy,
+            _ => DamageType::Kinetic,
+        }
+    }
+    
+    /// Armor class used when this unit is hit
+    pub fn armor_class(&self) -> ArmorClass {
+        match self {
+            UnitType::Engineer | UnitType::Gatherer => ArmorClass::Light,
+            UnitType::LandToAirTank | UnitType::Artillery => ArmorClass::Medium,
+            UnitType::LandToLandTank | UnitType::LargeTank | UnitType::LargeArtillery => ArmorClass::Heavy,
+            UnitType::AirToAirFighter
+            | UnitType::AirToLandBomber
+            | UnitType::LargeHoveringAircraft
+            | UnitType::LargeBomber => ArmorClass::Air,
+        }
+    }
 }
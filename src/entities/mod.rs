@@ -1,4 +1,3 @@
-pub mod unit_types;
 pub mod building_types;
 pub mod resource_types;
 pub mod mobile_base;
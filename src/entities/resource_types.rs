@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 use crate::components::resource::ResourceNode;
-use crate::components::building::ResourceType;
+use crate::components::economy::ResourceType;
 
 /// Defines the different types of resource nodes in the game
 #[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
@@ -50,12 +50,7 @@ impl ResourceNodeType {
                 transform: Transform::from_xyz(position.x, position.y, 1.0),
                 ..default()
             })
-            .insert(ResourceNode {
-                resource_type,
-                amount_remaining: amount,
-                max_gatherers,
-                current_gatherers: 0,
-            })
+            .insert(ResourceNode::new(resource_type, amount, max_gatherers))
             .insert(*self);
             
         entity
@@ -65,7 +60,7 @@ impl ResourceNodeType {
     pub fn from_terrain(terrain_type: &crate::components::terrain::TerrainType) -> Option<Self> {
         match terrain_type {
             crate::components::terrain::TerrainType::Forest => Some(ResourceNodeType::WoodSource),
-            crate::components::terrain::TerrainType::Mountain => Some(ResourceNodeType::StoneDeposit),
+            crate::components::terrain::TerrainType::Mountains => Some(ResourceNodeType::StoneDeposit),
             crate::components::terrain::TerrainType::MetalDeposit => Some(ResourceNodeType::IronDeposit),
             _ => None,
         }
@@ -8,9 +8,11 @@ use bevy::prelude::*;
 pub mod components;
 pub mod entities;
 pub mod resources;
+pub mod simulation;
 pub mod sprites;
 pub mod states;
 pub mod systems;
+pub mod tech;
 pub mod ui;
 pub mod units;
 pub mod utils;
@@ -23,7 +25,7 @@ pub mod prelude {
         building::Building,
         resource::ResourceNode,
         strategic::StrategicLocation,
-        base_modules::ResourceType,
+        economy::{ResourceType, ResourceWallet},
         player::PlayerResources,
     };
     
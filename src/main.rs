@@ -8,6 +8,8 @@ mod ui;
 mod debug;
 mod units;
 mod sprites;
+mod simulation;
+mod tech;
 
 use bevy::prelude::*;
 
@@ -28,11 +30,22 @@ use crate::systems::{
     BaseInitializationPlugin,
     CameraPlugin,
     CameraManagerPlugin,
+    CombatPlugin,
+    DamagePlugin,
+    EconomyPlugin,
+    FogOfWarPlugin,
+    AIPlugin,
+    SaveLoadPlugin,
+    StatusEffectsPlugin,
+    SensorPlugin,
+    UnitCatalogPlugin,
+    BuildingCatalogPlugin,
+    ModuleCatalogPlugin,
+    VictoryPlugin,
 };
 
 // Component plugins
 use crate::components::{
-    AIPlugin,
     base_modules::BaseModulePlugin,
     strategic::StrategicLocationPlugin,
     IsometricSpritePlugin,
@@ -43,12 +56,17 @@ use crate::ui::{
     BaseActionUIPlugin,
     BuildingProductionUIPlugin,
     BuildingSelectionUIPlugin,
+    ModuleBuildUIPlugin,
+    PowerUIPlugin,
+    SensorUIPlugin,
+    StrategicHudPlugin,
     menu::MenuPlugin,
 };
 
 // Resource plugins
 use crate::resources::{
     map::plugin::MapPlugin,
+    map_generator::MapGenerationSettings,
     ResourceNodePlugin,
 };
 
@@ -56,11 +74,14 @@ use crate::resources::{
 use crate::debug::DebugPlugin;
 use crate::entities::MobileBasePlugin;
 use crate::units::EngineerPlugin;
+use crate::tech::TechPlugin;
 use crate::sprites::SpriteLoaderPlugin;
+use crate::utils::config::load_config;
 
 // Re-export commonly used types for registration
 use crate::components::{
-    base_modules::{BaseModule, ModuleType, ResourceType, DamageType, UtilityEffect},
+    base_modules::{BaseModule, ModuleType, DamageType, UtilityEffect},
+    economy::{ResourceType, ResourceWallet},
     player::MechanicalBase,
 };
 
@@ -69,6 +90,14 @@ use crate::components::{
 // use crate::components::UnitLabelPlugin;
 
 fn main() {
+    // Run an AI vs AI match without a window, e.g. `strategy_forge --headless --seconds 300`
+    // Windowed games take the map seed the same way, e.g. `strategy_forge --seed 42`
+    if std::env::args().any(|arg| arg == "--headless") {
+        let config = simulation::SimulationConfig::from_args(std::env::args());
+        simulation::run_simulation(config).print_summary();
+        return;
+    }
+    
     App::new()
         // Add default Bevy plugins
         .add_plugins(DefaultPlugins.set(
@@ -94,18 +123,31 @@ fn main() {
         // Core systems - Add CameraManagerPlugin before CameraPlugin
         .add_plugins(CameraManagerPlugin) // Add this first to manage cameras
         .add_plugins(CameraPlugin)
+        .insert_resource(MapGenerationSettings::from_config(&load_config()).with_args(std::env::args()))
         .add_plugins(MapPlugin)
         .add_plugins(MovementPlugin)
         .add_plugins(AIPlugin)
         .add_plugins(ResourceNodePlugin)
         .add_plugins(ProductionPlugin)
+        .add_plugins(EconomyPlugin)
+        .add_plugins(FogOfWarPlugin)
+        .add_plugins(DamagePlugin)
+        .add_plugins(UnitCatalogPlugin)
+        .add_plugins(BuildingCatalogPlugin)
+        .add_plugins(ModuleCatalogPlugin)
+        .add_plugins(CombatPlugin)
         .add_plugins(StrategicLocationPlugin)
+        .add_plugins(VictoryPlugin)
+        .add_plugins(TechPlugin)
         
         // Base systems
         .add_plugins(BaseModulePlugin)
         .add_plugins(BaseInitializationPlugin)
         .add_plugins(BaseMovePlugin)
         .add_plugins(ModuleEffectsPlugin)
+        .add_plugins(StatusEffectsPlugin)
+        .add_plugins(SensorPlugin)
+        .add_plugins(SaveLoadPlugin)
         
         // Unit systems
         .add_plugins(EngineerPlugin)
@@ -116,6 +158,10 @@ fn main() {
         .add_plugins(BaseActionUIPlugin)
         .add_plugins(BuildingProductionUIPlugin)
         .add_plugins(BuildingSelectionUIPlugin)
+        .add_plugins(ModuleBuildUIPlugin)
+        .add_plugins(PowerUIPlugin)
+        .add_plugins(SensorUIPlugin)
+        .add_plugins(StrategicHudPlugin)
         .add_plugins(MenuPlugin)
         
         // Debug and utility
@@ -128,6 +174,7 @@ fn main() {
         .register_type::<BaseModule>()
         .register_type::<ModuleType>()
         .register_type::<ResourceType>()
+        .register_type::<ResourceWallet>()
         .register_type::<DamageType>()
         .register_type::<UtilityEffect>()
         
@@ -1,2 +1,3 @@
 // Map module for Strategy Forge
 pub mod plugin;
+pub mod tiles;
@@ -1,25 +1,73 @@
+use bevy::asset::AssetLoadFailedEvent;
 use bevy::prelude::*;
-use crate::resources::map_data::generate_map;
+use crate::resources::map::tiles::{respawn_map_tiles, spawn_map_tiles};
+use crate::resources::map_data::GameMap;
+use crate::resources::map_file::{MapFile, MAP_FILE_EXTENSION};
+use crate::resources::map_generator::{MapGenerationSettings, MapGenerator};
+use crate::utils::ron_asset::RonAssetLoader;
 
 // A marker resource to indicate the map has been initialized
 #[derive(Resource)]
 pub struct MapInitialized(pub bool);
 
+/// Map file to play on, as an asset path like `maps/crossroads.map.ron`
+///
+/// `None` plays on a generated map.
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct SelectedMap {
+    pub path: Option<String>,
+}
+
+/// Handle of the map file currently being loaded or played on
+#[derive(Resource, Default)]
+pub struct LoadedMapFile {
+    pub handle: Option<Handle<MapFile>>,
+}
+
+/// Event sent when `GameMap` is replaced after startup, so map contents can be rebuilt
+#[derive(Event)]
+pub struct MapChanged;
+
 // Basic map plugin for Strategy Forge
 pub struct MapPlugin;
 
 impl Plugin for MapPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup_map);
+        app.init_resource::<SelectedMap>()
+           .add_event::<MapChanged>()
+           .add_systems(Startup, (setup_map, spawn_map_tiles).chain());
+
+        // Map files need the asset server, which headless simulations run without
+        if app.world().contains_resource::<AssetServer>() {
+            app.init_resource::<LoadedMapFile>()
+               .init_asset::<MapFile>()
+               .register_asset_loader(RonAssetLoader::<MapFile>::new(&[MAP_FILE_EXTENSION]))
+               .add_systems(
+                    Update,
+                    (
+                        load_selected_map.run_if(resource_changed::<SelectedMap>),
+                        regenerate_map.run_if(resource_exists_and_changed::<MapGenerationSettings>),
+                        apply_loaded_map,
+                        report_map_load_failures,
+                        respawn_map_tiles.run_if(on_event::<MapChanged>()),
+                    ).chain()
+                );
+        }
     }
 }
 
 // Setup map system that's referenced elsewhere in the codebase
-pub fn setup_map(mut commands: Commands) {
+// Generates from `MapGenerationSettings` if the app provides them, otherwise from a random seed
+pub fn setup_map(
+    mut commands: Commands,
+    settings: Option<Res<MapGenerationSettings>>,
+) {
     info!("Setting up game map...");
     
     // Create the map
-    let game_map = generate_map();
+    let settings = settings.map(|settings| settings.clone()).unwrap_or_default();
+    info!("Generating {}x{} map for {} players with seed {}", settings.width, settings.height, settings.players, settings.seed);
+    let game_map = MapGenerator::new(settings).generate();
     
     // Insert map resources
     commands.insert_resource(game_map);
@@ -27,3 +75,94 @@ pub fn setup_map(mut commands: Commands) {
     
     info!("Map setup complete");
 }
+
+// Start loading the selected map file, or go back to a generated map when the selection is cleared
+fn load_selected_map(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    selected: Res<SelectedMap>,
+    mut loaded: ResMut<LoadedMapFile>,
+    map_files: Res<Assets<MapFile>>,
+    settings: Option<Res<MapGenerationSettings>>,
+    mut map_changed: EventWriter<MapChanged>,
+) {
+    match &selected.path {
+        Some(path) => {
+            info!("Loading map {}", path);
+            let handle = asset_server.load(path.clone());
+
+            // Picking a map that is still in memory won't send another load event
+            if let Some(map_file) = map_files.get(&handle) {
+                commands.insert_resource(map_file.to_game_map());
+                map_changed.send(MapChanged);
+            }
+            loaded.handle = Some(handle);
+        }
+        None => {
+            // Nothing to undo if the map was never swapped out
+            if loaded.handle.take().is_some() {
+                let settings = settings.map(|settings| settings.clone()).unwrap_or_default();
+                info!("Generating map with seed {}", settings.seed);
+                commands.insert_resource(MapGenerator::new(settings).generate());
+                map_changed.send(MapChanged);
+            }
+        }
+    }
+}
+
+// Generate a new map when the generation settings pick another seed, unless a map file is selected
+fn regenerate_map(
+    mut commands: Commands,
+    settings: Res<MapGenerationSettings>,
+    selected: Res<SelectedMap>,
+    game_map: Option<Res<GameMap>>,
+    mut map_changed: EventWriter<MapChanged>,
+) {
+    if selected.path.is_some() || game_map.is_some_and(|game_map| game_map.seed == Some(settings.seed)) {
+        return;
+    }
+
+    info!("Generating map with seed {}", settings.seed);
+    commands.insert_resource(MapGenerator::new(settings.clone()).generate());
+    commands.insert_resource(MapInitialized(true));
+    map_changed.send(MapChanged);
+}
+
+/// System to replace the game map once the selected map file is loaded or edited on disk
+pub fn apply_loaded_map(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<MapFile>>,
+    map_files: Res<Assets<MapFile>>,
+    loaded: Res<LoadedMapFile>,
+    mut map_changed: EventWriter<MapChanged>,
+) {
+    for event in events.read() {
+        let id = match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => *id,
+            _ => continue,
+        };
+
+        if loaded.handle.as_ref().map(|handle| handle.id()) != Some(id) {
+            continue;
+        }
+
+        if let Some(map_file) = map_files.get(id) {
+            info!("Map \"{}\" loaded ({}x{}, {} players)", map_file.name, map_file.width, map_file.height, map_file.players());
+            if !map_file.description.is_empty() {
+                info!("{}", map_file.description);
+            }
+            commands.insert_resource(map_file.to_game_map());
+            commands.insert_resource(MapInitialized(true));
+            map_changed.send(MapChanged);
+        }
+    }
+}
+
+// Explain why the selected map couldn't be used; the current map stays in place
+fn report_map_load_failures(
+    mut failures: EventReader<AssetLoadFailedEvent<MapFile>>,
+) {
+    for failure in failures.read() {
+        error!("Could not load map {}, keeping the current map: {}", failure.path, failure.error);
+    }
+}
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use crate::components::terrain::MapTile;
+use crate::resources::map_data::GameMap;
+
+/// Draw order of terrain tiles, below everything else on the map
+const TILE_Z: f32 = -1.0;
+
+/// System to spawn a tile entity for every grid cell of the map
+pub fn spawn_map_tiles(
+    mut commands: Commands,
+    game_map: Option<ResMut<GameMap>>,
+) {
+    let Some(mut game_map) = game_map else { return };
+    spawn_tiles(&mut commands, &mut game_map);
+}
+
+/// System to replace every tile with the new map's when the map changes
+pub fn respawn_map_tiles(
+    mut commands: Commands,
+    mut game_map: ResMut<GameMap>,
+    tiles: Query<Entity, With<MapTile>>,
+) {
+    for entity in tiles.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_tiles(&mut commands, &mut game_map);
+}
+
+// Spawn one sprite per grid cell, coloured by terrain, and record it in the map
+// There is no terrain art yet, so each tile is a flat placeholder colour
+fn spawn_tiles(commands: &mut Commands, game_map: &mut GameMap) {
+    game_map.tile_entities = vec![vec![None; game_map.width]; game_map.height];
+
+    for y in 0..game_map.height as i32 {
+        for x in 0..game_map.width as i32 {
+            let Some(terrain) = game_map.terrain_stats(x, y) else { continue };
+            let position = game_map.tile_center(x, y);
+
+            let entity = commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: terrain.terrain_type.color(),
+                        custom_size: Some(Vec2::splat(game_map.tile_size)),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(position.x, position.y, TILE_Z),
+                    ..default()
+                },
+                MapTile { grid_x: x, grid_y: y, is_explored: false, is_visible: false },
+                terrain,
+            )).id();
+            game_map.set_tile_entity(x, y, entity);
+        }
+    }
+
+    info!("Spawned {} map tiles", game_map.width * game_map.height);
+}
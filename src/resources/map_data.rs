@@ -1,4 +1,7 @@
 use bevy::prelude::*;
+use serde::Deserialize;
+use crate::components::economy::ResourceType;
+use crate::components::terrain::Terrain;
 
 // Basic map data structures for Strategy Forge
 #[derive(Resource)]
@@ -10,15 +13,118 @@ pub struct GameMap {
     pub tile_size: f32,
     // Map of grid positions to tile entities
     pub tile_entities: Vec<Vec<Option<Entity>>>,
+    // Seed the map was generated from, if it was generated
+    pub seed: Option<u64>,
+    // Grid positions where each player starts, in player order
+    pub start_positions: Vec<(i32, i32)>,
+    // Strategic locations to fight over
+    pub strategic_locations: Vec<StrategicSpawn>,
+    // Resource nodes to place when the match starts
+    pub resource_spawns: Vec<ResourceSpawn>,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum TerrainType {
     Plains,
     Forest,
     Hills,
     Mountains,
     Water,
+    MetalDeposit,
+}
+
+/// A resource node placed on the map before the match starts
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub struct ResourceSpawn {
+    pub resource_type: ResourceType,
+    pub position: (i32, i32), // Grid position
+}
+
+/// A strategic location placed on the map before the match starts
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct StrategicSpawn {
+    pub position: (i32, i32), // Grid position
+    #[serde(default = "default_capture_value")]
+    pub capture_value: i32,   // How much holding this location is worth
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub capture_radius: Option<f32>, // In tiles, the default radius if not set
+    #[serde(default)]
+    pub capture_rate: Option<f32>,   // Points per second per point of strength, the default rate if not set
+}
+
+impl StrategicSpawn {
+    /// A strategic location with the default value and a generated name
+    pub fn at(position: (i32, i32)) -> Self {
+        Self { position, capture_value: default_capture_value(), name: None, capture_radius: None, capture_rate: None }
+    }
+}
+
+fn default_capture_value() -> i32 {
+    1
+}
+
+impl TerrainType {
+    /// Every terrain type, in the order they're listed in map files
+    pub const ALL: [TerrainType; 6] = [
+        TerrainType::Plains,
+        TerrainType::Forest,
+        TerrainType::Hills,
+        TerrainType::Mountains,
+        TerrainType::Water,
+        TerrainType::MetalDeposit,
+    ];
+    
+    /// Character standing for this terrain in a map file's terrain grid
+    pub fn symbol(&self) -> char {
+        match self {
+            TerrainType::Plains => '.',
+            TerrainType::Forest => 'T',
+            TerrainType::Hills => 'h',
+            TerrainType::Mountains => '^',
+            TerrainType::Water => '~',
+            TerrainType::MetalDeposit => '*',
+        }
+    }
+    
+    /// Terrain a map file character stands for
+    pub fn from_symbol(symbol: char) -> Option<TerrainType> {
+        TerrainType::ALL.into_iter().find(|terrain| terrain.symbol() == symbol)
+    }
+    
+    /// Movement, vision and defense stats of this terrain
+    pub fn stats(&self) -> Terrain {
+        Terrain::new(*self)
+    }
+    
+    /// Multiplier for movement speed on this terrain (0.0 = impassable)
+    pub fn movement_modifier(&self) -> f32 {
+        self.stats().movement_modifier
+    }
+    
+    /// Multiplier for the sight range of anything standing on this terrain
+    pub fn visibility_modifier(&self) -> f32 {
+        self.stats().visibility_modifier
+    }
+    
+    /// Divisor for damage taken by ground units standing on this terrain
+    pub fn defense_modifier(&self) -> f32 {
+        self.stats().defense_modifier
+    }
+    
+    /// Colour of the tile sprite drawn for this terrain
+    /// A placeholder until terrain textures are added to the sprite sheets
+    pub fn color(&self) -> Color {
+        match self {
+            TerrainType::Plains => Color::srgb(0.45, 0.6, 0.3),
+            TerrainType::Forest => Color::srgb(0.2, 0.4, 0.18),
+            TerrainType::Hills => Color::srgb(0.55, 0.5, 0.32),
+            TerrainType::Mountains => Color::srgb(0.45, 0.42, 0.4),
+            TerrainType::Water => Color::srgb(0.2, 0.35, 0.6),
+            TerrainType::MetalDeposit => Color::srgb(0.5, 0.52, 0.58),
+        }
+    }
 }
 
 impl Default for GameMap {
@@ -30,6 +136,10 @@ impl Default for GameMap {
             initialized: false,
             tile_size: 32.0,
             tile_entities: vec![vec![None; 64]; 64],
+            seed: None,
+            start_positions: Vec::new(),
+            strategic_locations: Vec::new(),
+            resource_spawns: Vec::new(),
         }
     }
 }
@@ -56,6 +166,64 @@ impl GameMap {
         )
     }
     
+    // Get the centre of a tile in world coordinates
+    pub fn tile_center(&self, x: i32, y: i32) -> Vec2 {
+        self.grid_to_world(x, y) + Vec2::splat(self.tile_size / 2.0)
+    }
+    
+    // Get the terrain at a grid position
+    pub fn terrain_at(&self, x: i32, y: i32) -> Option<TerrainType> {
+        if self.is_in_bounds(x, y) {
+            Some(self.terrain[y as usize][x as usize])
+        } else {
+            None
+        }
+    }
+    
+    // Set the terrain at a grid position
+    pub fn set_terrain(&mut self, x: i32, y: i32, terrain_type: TerrainType) {
+        if self.is_in_bounds(x, y) {
+            self.terrain[y as usize][x as usize] = terrain_type;
+        }
+    }
+    
+    // Get the terrain stats at a grid position
+    pub fn terrain_stats(&self, x: i32, y: i32) -> Option<Terrain> {
+        self.terrain_at(x, y).map(Terrain::new)
+    }
+    
+    // Get the terrain stats under a world position
+    pub fn terrain_stats_at(&self, world_pos: Vec2) -> Option<Terrain> {
+        let (x, y) = self.world_to_grid(world_pos);
+        self.terrain_stats(x, y)
+    }
+    
+    // Movement speed multiplier at a grid position (0.0 outside the map)
+    pub fn movement_modifier_at(&self, x: i32, y: i32) -> f32 {
+        self.terrain_stats(x, y).map_or(0.0, |terrain| terrain.movement_modifier)
+    }
+    
+    // Check if ground units can enter a grid position
+    pub fn is_passable(&self, x: i32, y: i32) -> bool {
+        self.movement_modifier_at(x, y) > 0.0
+    }
+    
+    // Furthest point along a straight line that can be reached without crossing impassable ground
+    pub fn last_passable_point(&self, from: Vec2, to: Vec2) -> Vec2 {
+        // Sample every quarter tile so the line can't skip over a single blocked tile
+        let steps = (from.distance(to) / (self.tile_size / 4.0)).ceil().max(1.0) as u32;
+        let mut reached = from;
+        for step in 1..=steps {
+            let point = from.lerp(to, step as f32 / steps as f32);
+            let (x, y) = self.world_to_grid(point);
+            if !self.is_passable(x, y) {
+                break;
+            }
+            reached = point;
+        }
+        reached
+    }
+    
     // Get the entity at a grid position
     pub fn get_tile_entity(&self, x: i32, y: i32) -> Option<Entity> {
         if self.is_in_bounds(x, y) {
@@ -72,15 +240,3 @@ impl GameMap {
         }
     }
 }
-
-// Generate a simple map (placeholder implementation)
-pub fn generate_map() -> GameMap {
-    let mut map = GameMap::default();
-    map.initialized = true;
-    map
-}
-
-// Update map visibility (placeholder implementation)
-pub fn update_map_visibility(_map: &mut GameMap) {
-    // Placeholder for map visibility logic
-}
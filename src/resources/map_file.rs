@@ -0,0 +1,214 @@
+use bevy::asset::io::file::FileAssetReader;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::path::Path;
+use crate::resources::map_data::{GameMap, ResourceSpawn, StrategicSpawn, TerrainType};
+use crate::utils::ron_asset::{RonAsset, RonAssetError};
+
+/// Folder holding hand-authored maps, relative to the assets folder
+pub const MAPS_DIRECTORY: &str = "maps";
+
+/// Extension every map file ends in
+pub const MAP_FILE_EXTENSION: &str = "map.ron";
+
+/// Newest map file version this build understands
+pub const MAP_FILE_VERSION: u32 = 1;
+
+/// Fewest start positions a map can have and still host a match
+const MIN_PLAYERS: usize = 2;
+
+fn default_tile_size() -> f32 {
+    32.0
+}
+
+/// Hand-authored map, loaded from `assets/maps/*.map.ron`
+///
+/// Terrain is drawn as one string per row using `TerrainType::symbol`, with the top row of
+/// the map first so the file reads like the map looks on screen. Positions are grid
+/// coordinates counted from the bottom left, the same as `GameMap`.
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+pub struct MapFile {
+    pub version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub width: usize,
+    pub height: usize,
+    #[serde(default = "default_tile_size")]
+    pub tile_size: f32,
+    pub terrain: Vec<String>,
+    pub start_positions: Vec<(i32, i32)>,  // In player order
+    #[serde(default)]
+    pub strategic_locations: Vec<StrategicSpawn>,
+    #[serde(default)]
+    pub resources: Vec<ResourceSpawn>,
+}
+
+impl RonAsset for MapFile {
+    fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.version > MAP_FILE_VERSION {
+            problems.push(format!("version {} is newer than supported version {}", self.version, MAP_FILE_VERSION));
+        }
+        if self.name.trim().is_empty() {
+            problems.push("name can't be empty".to_string());
+        }
+        if self.width == 0 || self.height == 0 {
+            problems.push(format!("size must be at least 1x1 (got {}x{})", self.width, self.height));
+        }
+        if self.tile_size <= 0.0 {
+            problems.push(format!("tile_size must be positive (got {})", self.tile_size));
+        }
+
+        // Check the grid row by row; positions can only be checked against a grid that's complete
+        let mut grid_complete = self.terrain.len() == self.height;
+        if !grid_complete {
+            problems.push(format!("terrain has {} rows but the map is {} tall", self.terrain.len(), self.height));
+        }
+        for (row, line) in self.terrain.iter().enumerate() {
+            let tiles = line.chars().count();
+            if tiles != self.width {
+                problems.push(format!("terrain row {} is {} tiles wide, expected {}", row + 1, tiles, self.width));
+                grid_complete = false;
+            }
+            if let Some((column, symbol)) = line.chars().enumerate().find(|(_, symbol)| TerrainType::from_symbol(*symbol).is_none()) {
+                problems.push(format!("terrain row {}, column {}: unknown terrain '{}'", row + 1, column + 1, symbol));
+                grid_complete = false;
+            }
+        }
+
+        if self.start_positions.len() < MIN_PLAYERS {
+            problems.push(format!("needs at least {} start positions (got {})", MIN_PLAYERS, self.start_positions.len()));
+        }
+        if !grid_complete {
+            return Err(problems);
+        }
+
+        let map = self.to_game_map();
+        for (index, (x, y)) in self.start_positions.iter().enumerate() {
+            if !map.is_in_bounds(*x, *y) {
+                problems.push(format!("start position {} at ({}, {}) is off the map", index + 1, x, y));
+            } else if !map.is_passable(*x, *y) {
+                problems.push(format!("start position {} at ({}, {}) is on impassable terrain", index + 1, x, y));
+            }
+        }
+        for location in &self.strategic_locations {
+            let (x, y) = location.position;
+            if !map.is_in_bounds(x, y) {
+                problems.push(format!("strategic location at ({}, {}) is off the map", x, y));
+            }
+            if location.capture_value <= 0 {
+                problems.push(format!(
+                    "strategic location at ({}, {}): capture_value must be positive (got {})",
+                    x, y, location.capture_value
+                ));
+            }
+            if location.capture_radius.is_some_and(|radius| radius <= 0.0) {
+                problems.push(format!("strategic location at ({}, {}): capture_radius must be positive", x, y));
+            }
+            if location.capture_rate.is_some_and(|rate| rate <= 0.0) {
+                problems.push(format!("strategic location at ({}, {}): capture_rate must be positive", x, y));
+            }
+        }
+        for resource in &self.resources {
+            let (x, y) = resource.position;
+            if !map.is_in_bounds(x, y) {
+                problems.push(format!("{:?} node at ({}, {}) is off the map", resource.resource_type, x, y));
+            } else if !map.is_passable(x, y) {
+                problems.push(format!("{:?} node at ({}, {}) is on impassable terrain", resource.resource_type, x, y));
+            }
+        }
+
+        if problems.is_empty() { Ok(()) } else { Err(problems) }
+    }
+}
+
+impl MapFile {
+    /// Read and check a map file straight from disk
+    pub fn load(path: &Path) -> Result<MapFile, RonAssetError> {
+        MapFile::from_ron_bytes(&std::fs::read(path)?)
+    }
+
+    /// Number of players the map is laid out for
+    pub fn players(&self) -> usize {
+        self.start_positions.len()
+    }
+
+    /// Build the game map this file describes
+    ///
+    /// Symbols the file doesn't define become plains; `validate` reports them.
+    pub fn to_game_map(&self) -> GameMap {
+        let mut terrain = vec![vec![TerrainType::Plains; self.width]; self.height];
+        for (row, line) in self.terrain.iter().take(self.height).enumerate() {
+            // File rows run top to bottom while grid rows run bottom to top
+            let y = self.height - 1 - row;
+            for (x, symbol) in line.chars().take(self.width).enumerate() {
+                terrain[y][x] = TerrainType::from_symbol(symbol).unwrap_or(TerrainType::Plains);
+            }
+        }
+
+        GameMap {
+            width: self.width,
+            height: self.height,
+            terrain,
+            initialized: true,
+            tile_size: self.tile_size,
+            tile_entities: vec![vec![None; self.width]; self.height],
+            seed: None,
+            start_positions: self.start_positions.clone(),
+            strategic_locations: self.strategic_locations.clone(),
+            resource_spawns: self.resources.clone(),
+        }
+    }
+}
+
+/// A map file found on disk, as shown in the skirmish menu
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapListing {
+    pub asset_path: String, // Path to hand to the asset server
+    pub name: String,
+    pub width: usize,
+    pub height: usize,
+    pub players: usize,
+}
+
+/// Folder the game's hand-authored maps are read from
+pub fn maps_directory() -> std::path::PathBuf {
+    FileAssetReader::get_base_path().join("assets").join(MAPS_DIRECTORY)
+}
+
+/// Every valid map file in a folder, sorted by file name
+///
+/// Files that fail to load are skipped with a warning so one broken map doesn't hide the rest.
+pub fn list_map_files(directory: &Path) -> Vec<MapListing> {
+    let Ok(entries) = std::fs::read_dir(directory) else {
+        warn!("No maps folder at {}", directory.display());
+        return Vec::new();
+    };
+
+    let suffix = format!(".{}", MAP_FILE_EXTENSION);
+    let mut file_names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|file_name| file_name.ends_with(&suffix))
+        .collect();
+    file_names.sort();
+
+    file_names
+        .into_iter()
+        .filter_map(|file_name| match MapFile::load(&directory.join(&file_name)) {
+            Ok(map_file) => Some(MapListing {
+                asset_path: format!("{}/{}", MAPS_DIRECTORY, file_name),
+                name: map_file.name.clone(),
+                width: map_file.width,
+                height: map_file.height,
+                players: map_file.players(),
+            }),
+            Err(err) => {
+                warn!("Skipping map {}: {}", file_name, err);
+                None
+            }
+        })
+        .collect()
+}
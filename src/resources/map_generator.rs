@@ -0,0 +1,372 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{FRAC_PI_4, PI, TAU};
+use crate::components::economy::ResourceType;
+use crate::resources::map_data::{GameMap, ResourceSpawn, StrategicSpawn, TerrainType};
+use crate::utils::config::GameConfig;
+use crate::utils::math::value_noise_2d;
+
+/// Radius around each start position that is kept clear for the base, in tiles
+const START_CLEARING: f32 = 4.0;
+
+/// Radius around the strategic location that is kept clear, in tiles
+const CENTER_CLEARING: f32 = 3.0;
+
+/// Fraction of the map's half-size at which players start from the centre
+const START_DISTANCE: f32 = 0.8;
+
+/// Half-width of the land bridges carved over water between key points, in tiles
+const CORRIDOR_RADIUS: f32 = 1.0;
+
+/// Fraction of the start distance at which flank locations sit from the centre
+const FLANK_DISTANCE: f32 = 0.75;
+
+/// Angle between neighbouring flank locations on the same side, in radians
+const FLANK_SPREAD: f32 = 0.5;
+
+/// Radius kept clear around each flank location, in tiles
+const FLANK_CLEARING: f32 = 2.0;
+
+/// Capture value of the location in the middle of the map
+const CENTRAL_CAPTURE_VALUE: i32 = 3;
+
+/// Capture value of the locations out on the flanks
+const FLANK_CAPTURE_VALUE: i32 = 1;
+
+/// Settings for generating a map
+///
+/// The same settings always produce the same map, so the seed is all that's needed to
+/// reproduce one from a bug report.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapGenerationSettings {
+    pub seed: u64,
+    pub width: usize,
+    pub height: usize,
+    pub tile_size: f32,
+    pub players: usize,
+    pub strategic_points: usize, // The centre, then the rest shared out evenly between players
+}
+
+impl Default for MapGenerationSettings {
+    fn default() -> Self {
+        Self {
+            seed: rand::random(),
+            width: 64,
+            height: 64,
+            tile_size: 32.0,
+            players: 2,
+            strategic_points: 3,
+        }
+    }
+}
+
+impl MapGenerationSettings {
+    /// Default settings with a fixed seed
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed, ..default() }
+    }
+
+    /// Default settings with a random seed and as many strategic locations as the game configuration asks for
+    pub fn from_config(config: &GameConfig) -> Self {
+        Self { strategic_points: config.strategic_points.max(0) as usize, ..default() }
+    }
+
+    /// Override settings from command line arguments (`--seed` and `--points`)
+    pub fn with_args(mut self, args: impl Iterator<Item = String>) -> Self {
+        let args: Vec<String> = args.collect();
+
+        for pair in args.windows(2) {
+            let value = pair[1].as_str();
+            match pair[0].as_str() {
+                "--seed" => match value.parse() {
+                    Ok(seed) => self.seed = seed,
+                    Err(_) => warn!("Ignoring --seed {}: expected a whole number, using seed {}", value, self.seed),
+                },
+                "--points" => match value.parse() {
+                    Ok(points) => self.strategic_points = points,
+                    Err(_) => warn!(
+                        "Ignoring --points {}: expected a whole number, placing {} strategic points",
+                        value, self.strategic_points
+                    ),
+                },
+                _ => {}
+            }
+        }
+
+        self
+    }
+}
+
+/// A resource patch laid out for the first player, then copied to every other player
+struct Feature {
+    center: Vec2,                // Position in tile units
+    radius: f32,                 // Radius of the terrain patch, in tiles
+    terrain: TerrainType,
+    resource_type: ResourceType,
+}
+
+/// Seeded procedural generator for `GameMap`
+///
+/// Terrain and resources have rotational symmetry around the map centre, so every player gets
+/// an identical share of the map no matter where they start.
+pub struct MapGenerator {
+    settings: MapGenerationSettings,
+    rng: StdRng,
+    center: Vec2,
+}
+
+impl MapGenerator {
+    pub fn new(mut settings: MapGenerationSettings) -> Self {
+        // Only half turns keep a rectangle in place, so more players need a square map
+        if settings.players > 2 && settings.width != settings.height {
+            let side = settings.width.min(settings.height);
+            warn!(
+                "A {}x{} map can't be shared evenly between {} players, generating a {}x{} map instead",
+                settings.width, settings.height, settings.players, side, side
+            );
+            settings.width = side;
+            settings.height = side;
+        }
+
+        let rng = StdRng::seed_from_u64(settings.seed);
+        let center = Vec2::new(settings.width as f32, settings.height as f32) / 2.0;
+        Self { settings, rng, center }
+    }
+
+    /// Generate the map
+    pub fn generate(mut self) -> GameMap {
+        let (width, height) = (self.settings.width, self.settings.height);
+        let players = self.settings.players.max(1);
+
+        let mut map = GameMap {
+            width,
+            height,
+            terrain: vec![vec![TerrainType::Plains; width]; height],
+            initialized: true,
+            tile_size: self.settings.tile_size,
+            tile_entities: vec![vec![None; width]; height],
+            seed: Some(self.settings.seed),
+            ..default()
+        };
+
+        self.generate_terrain(&mut map, players);
+
+        // Lay out the first player's corner, then rotate it onto every other player.
+        // Starting in the middle of a tile keeps the rotated starts on tile centres too
+        let (start_x, start_y) = grid_position(self.center + Vec2::from_angle(self.start_angle()) * self.start_radius());
+        let start = Vec2::new(start_x as f32 + 0.5, start_y as f32 + 0.5);
+        let features = self.place_features(start, players);
+
+        for player in 0..players {
+            let player_start = self.rotate(start, player, players);
+            for feature in &features {
+                let center = self.rotate(feature.center, player, players);
+                stamp(&mut map, center, feature.radius, |_| Some(feature.terrain));
+                map.resource_spawns.push(ResourceSpawn {
+                    resource_type: feature.resource_type,
+                    position: grid_position(center),
+                });
+            }
+            map.start_positions.push(grid_position(player_start));
+        }
+
+        // Keep starts and the objective clear, and make sure water never cuts anyone off
+        for player in 0..players {
+            let player_start = self.rotate(start, player, players);
+            stamp(&mut map, player_start, START_CLEARING, |_| Some(TerrainType::Plains));
+            carve_corridor(&mut map, player_start, self.center);
+            for feature in &features {
+                let center = self.rotate(feature.center, player, players);
+                let origin = if feature.center.distance(self.center) < feature.center.distance(start) { self.center } else { player_start };
+                carve_corridor(&mut map, origin, center);
+            }
+        }
+        stamp(&mut map, self.center, CENTER_CLEARING, |_| Some(TerrainType::Plains));
+        self.place_strategic_locations(&mut map, players);
+
+        map
+    }
+
+    // Angle from the centre to the first player's start (bottom left, like the old fixed layout)
+    fn start_angle(&self) -> f32 {
+        PI + FRAC_PI_4
+    }
+
+    // Distance from the centre to every start, in tiles
+    fn start_radius(&self) -> f32 {
+        self.center.min_element() * START_DISTANCE
+    }
+
+    // Fill the map with forests, hills, mountains and lakes from two seeded noise fields
+    fn generate_terrain(&mut self, map: &mut GameMap, players: usize) {
+        let elevation_seed: u64 = self.rng.gen();
+        let moisture_seed: u64 = self.rng.gen();
+
+        for y in 0..map.height {
+            for x in 0..map.width {
+                let point = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let elevation = self.symmetric_noise(point, players, 9.0, elevation_seed);
+                let moisture = self.symmetric_noise(point, players, 7.0, moisture_seed);
+
+                map.terrain[y][x] = if elevation < 0.3 {
+                    TerrainType::Water
+                } else if elevation > 0.72 {
+                    TerrainType::Mountains
+                } else if elevation > 0.65 {
+                    TerrainType::Hills
+                } else if moisture > 0.62 {
+                    TerrainType::Forest
+                } else {
+                    TerrainType::Plains
+                };
+            }
+        }
+    }
+
+    // Two-octave noise averaged over every rotation of the point, so the field is symmetric
+    fn symmetric_noise(&self, point: Vec2, players: usize, scale: f32, seed: u64) -> f32 {
+        let mut samples: Vec<f32> = (0..players)
+            .map(|turn| {
+                let p = self.rotate(point, turn, players) / scale;
+                value_noise_2d(p.x, p.y, seed) * 0.7 + value_noise_2d(p.x * 2.0, p.y * 2.0, seed ^ 1) * 0.3
+            })
+            .collect();
+
+        // Sum in a fixed order so every rotation of the point gets exactly the same value
+        samples.sort_by(|a, b| a.total_cmp(b));
+        let mean = samples.iter().sum::<f32>() / players as f32;
+
+        // Averaging flattens the field, so stretch it back out
+        0.5 + (mean - 0.5) * (players as f32).sqrt()
+    }
+
+    // Pick resource patches around the first player's start, plus a contested one toward the centre
+    fn place_features(&mut self, start: Vec2, players: usize) -> Vec<Feature> {
+        let toward_center = (self.center - start).to_angle();
+
+        let mut kinds = vec![
+            (TerrainType::Forest, 2.2, ResourceType::Wood),
+            (TerrainType::Forest, 2.2, ResourceType::Wood),
+            (TerrainType::Mountains, 1.5, ResourceType::Stone),
+            (TerrainType::MetalDeposit, 1.5, ResourceType::Iron),
+        ];
+        kinds.shuffle(&mut self.rng);
+
+        // Spread the patches in a fan on the side of the start facing the map
+        let spread = [-100.0f32, -35.0, 35.0, 100.0];
+        let mut features: Vec<Feature> = kinds
+            .into_iter()
+            .zip(spread)
+            .map(|((terrain, radius, resource_type), angle)| {
+                let angle = toward_center + (angle + self.rng.gen_range(-10.0..10.0)).to_radians();
+                let distance = self.rng.gen_range(7.0..10.0);
+                Feature { center: start + Vec2::from_angle(angle) * distance, radius, terrain, resource_type }
+            })
+            .collect();
+
+        // Iron halfway around to the next player, the same distance from both of them
+        let contested_angle = self.start_angle() + PI / players as f32;
+        let contested_distance = (CENTER_CLEARING + 3.0).min(self.start_radius() / 2.0);
+        features.push(Feature {
+            center: self.center + Vec2::from_angle(contested_angle) * contested_distance,
+            radius: 1.2,
+            terrain: TerrainType::MetalDeposit,
+            resource_type: ResourceType::Iron,
+        });
+
+        features
+    }
+
+    // Put the most valuable location in the centre and share the rest out between the flanks
+    fn place_strategic_locations(&self, map: &mut GameMap, players: usize) {
+        let count = self.settings.strategic_points;
+        if count == 0 {
+            return;
+        }
+        map.strategic_locations.push(StrategicSpawn {
+            capture_value: CENTRAL_CAPTURE_VALUE,
+            ..StrategicSpawn::at(grid_position(self.center))
+        });
+
+        // Flank locations come one per player so nobody starts closer to more of them
+        let groups = (count - 1) / players;
+        if groups * players != count - 1 {
+            warn!(
+                "{} strategic points can't be shared evenly between {} players, placing {}",
+                count, players, 1 + groups * players
+            );
+        }
+
+        for group in 0..groups {
+            let offset = (group as f32 - (groups - 1) as f32 / 2.0) * FLANK_SPREAD;
+            let angle = self.start_angle() + PI / players as f32 + offset;
+            let (x, y) = grid_position(self.center + Vec2::from_angle(angle) * self.start_radius() * FLANK_DISTANCE);
+            let flank = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+
+            for player in 0..players {
+                let position = self.rotate(flank, player, players);
+                stamp(map, position, FLANK_CLEARING, |_| Some(TerrainType::Plains));
+                carve_corridor(map, self.center, position);
+                map.strategic_locations.push(StrategicSpawn {
+                    capture_value: FLANK_CAPTURE_VALUE,
+                    ..StrategicSpawn::at(grid_position(position))
+                });
+            }
+        }
+    }
+
+    // Rotate a point around the map centre by `turn` out of `turns` equal steps
+    fn rotate(&self, point: Vec2, turn: usize, turns: usize) -> Vec2 {
+        let offset = point - self.center;
+
+        // Quarter turns are done exactly so 2 and 4 player maps line up tile for tile
+        let quarters = turn * 4 / turns;
+        let rotated = if quarters * turns == turn * 4 {
+            match quarters % 4 {
+                0 => offset,
+                1 => Vec2::new(-offset.y, offset.x),
+                2 => -offset,
+                _ => Vec2::new(offset.y, -offset.x),
+            }
+        } else {
+            Vec2::from_angle(TAU * turn as f32 / turns as f32).rotate(offset)
+        };
+
+        self.center + rotated
+    }
+}
+
+// Grid tile containing a point given in tile units
+fn grid_position(point: Vec2) -> (i32, i32) {
+    (point.x.floor() as i32, point.y.floor() as i32)
+}
+
+// Change the terrain of every tile whose centre lies within `radius` tiles of `center`
+fn stamp(map: &mut GameMap, center: Vec2, radius: f32, terrain: impl Fn(TerrainType) -> Option<TerrainType>) {
+    let reach = radius.ceil() as i32 + 1;
+    let (cx, cy) = grid_position(center);
+
+    for y in (cy - reach)..=(cy + reach) {
+        for x in (cx - reach)..=(cx + reach) {
+            let tile_center = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+            if tile_center.distance(center) > radius {
+                continue;
+            }
+            if let Some(new_terrain) = map.terrain_at(x, y).and_then(&terrain) {
+                map.set_terrain(x, y, new_terrain);
+            }
+        }
+    }
+}
+
+// Turn water along the line between two points into plains, so both ends stay connected
+fn carve_corridor(map: &mut GameMap, from: Vec2, to: Vec2) {
+    let steps = (from.distance(to) * 4.0).ceil() as usize;
+    for step in 0..=steps {
+        let point = from.lerp(to, step as f32 / steps.max(1) as f32);
+        stamp(map, point, CORRIDOR_RADIUS, |terrain| (terrain == TerrainType::Water).then_some(TerrainType::Plains));
+    }
+}
@@ -1,43 +1,31 @@
 use bevy::prelude::*;
-
-// Resource types
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub enum ResourceType {
-    Wood,
-    Stone,
-    Iron,
-}
-
-// Resource node component
-#[derive(Component)]
-pub struct ResourceNode {
-    pub resource_type: ResourceType,
-    pub amount_remaining: i32,
-    pub max_amount: i32,
-}
+use crate::components::economy::ResourceType;
+use crate::components::resource::ResourceNode;
+use crate::resources::map::plugin::{apply_loaded_map, setup_map, MapChanged};
+use crate::resources::map_data::GameMap;
 
 // Resource node factory
 #[derive(Default)]
 pub struct ResourceNodeFactory;
 
 impl ResourceNodeFactory {
-    pub fn spawn_resource_node(&self, commands: &mut Commands, position: Vec2) -> Entity {
-        let resource_type = match rand::random::<u8>() % 3 {
-            0 => ResourceType::Wood,
-            1 => ResourceType::Stone,
-            _ => ResourceType::Iron,
-        };
-        
+    /// A full node of the given type with its default size and gatherer slots
+    pub fn node_for(resource_type: ResourceType) -> ResourceNode {
+        match resource_type {
+            ResourceType::Wood => ResourceNode::new(resource_type, 1000, 3),
+            ResourceType::Stone => ResourceNode::new(resource_type, 800, 2),
+            ResourceType::Iron => ResourceNode::new(resource_type, 500, 2),
+            _ => ResourceNode::new(resource_type, 500, 2),
+        }
+    }
+    
+    /// Spawn a full resource node of a specific type
+    pub fn spawn_resource_node_of_type(&self, commands: &mut Commands, position: Vec2, resource_type: ResourceType) -> Entity {
         let color = match resource_type {
             ResourceType::Wood => Color::srgb(0.6, 0.4, 0.2),
             ResourceType::Stone => Color::srgb(0.5, 0.5, 0.5),
             ResourceType::Iron => Color::srgb(0.6, 0.6, 0.7),
-        };
-        
-        let max_amount = match resource_type {
-            ResourceType::Wood => 1000,
-            ResourceType::Stone => 800,
-            ResourceType::Iron => 500,
+            _ => Color::srgb(0.8, 0.8, 0.8),
         };
         
         commands.spawn((
@@ -50,11 +38,7 @@ impl ResourceNodeFactory {
                 transform: Transform::from_xyz(position.x, position.y, 1.0),
                 ..default()
             },
-            ResourceNode {
-                resource_type,
-                amount_remaining: max_amount,
-                max_amount,
-            },
+            Self::node_for(resource_type),
         )).id()
     }
 }
@@ -64,22 +48,44 @@ pub struct ResourceNodePlugin;
 
 impl Plugin for ResourceNodePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup_resource_nodes);
+        app.add_systems(Startup, setup_resource_nodes.after(setup_map))
+           .add_systems(Update, respawn_resource_nodes.after(apply_loaded_map).run_if(on_event::<MapChanged>()));
     }
 }
 
-// System to set up initial resource nodes
-fn setup_resource_nodes(mut commands: Commands) {
+// System to set up the resource nodes laid out by the map
+fn setup_resource_nodes(mut commands: Commands, game_map: Option<Res<GameMap>>) {
     info!("Setting up resource nodes...");
     
-    let factory = ResourceNodeFactory::default();
+    let Some(game_map) = game_map else {
+        warn!("No map to place resource nodes on");
+        return;
+    };
     
-    // Create some initial resource nodes at fixed positions
-    // In a real game, this would be based on the map and possibly randomized
-    factory.spawn_resource_node(&mut commands, Vec2::new(100.0, 100.0));
-    factory.spawn_resource_node(&mut commands, Vec2::new(-100.0, 100.0));
-    factory.spawn_resource_node(&mut commands, Vec2::new(100.0, -100.0));
-    factory.spawn_resource_node(&mut commands, Vec2::new(-100.0, -100.0));
+    spawn_map_resource_nodes(&mut commands, &game_map);
     
-    info!("Resource nodes setup complete");
+    info!("Resource nodes setup complete ({} nodes)", game_map.resource_spawns.len());
+}
+
+// System to replace every resource node with the new map's when the map changes
+fn respawn_resource_nodes(
+    mut commands: Commands,
+    game_map: Res<GameMap>,
+    nodes: Query<Entity, With<ResourceNode>>,
+) {
+    for entity in nodes.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_map_resource_nodes(&mut commands, &game_map);
+    
+    info!("Resource nodes respawned for new map ({} nodes)", game_map.resource_spawns.len());
+}
+
+// Spawn a full node at every resource spawn the map lays out
+fn spawn_map_resource_nodes(commands: &mut Commands, game_map: &GameMap) {
+    let factory = ResourceNodeFactory;
+    for spawn in game_map.resource_spawns.iter() {
+        let position = game_map.tile_center(spawn.position.0, spawn.position.1);
+        factory.spawn_resource_node_of_type(commands, position, spawn.resource_type);
+    }
 }
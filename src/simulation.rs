@@ -0,0 +1,348 @@
+//! Headless match simulation
+//!
+//! Runs a full AI vs AI match without a window or renderer, using a fixed
+//! timestep so matches can be played in CI and from integration tests.
+
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::collections::HashMap;
+use std::time::Duration;
+use crate::components::ai::{AIBase, AIControlled, AIDifficulty};
+use crate::components::building::{Building, BuildingSpawner};
+use crate::components::economy::ResourceWallet;
+use crate::components::player::{MechanicalBase, PlayerResources};
+use crate::components::strategic::{StrategicLocation, StrategicLocationPlugin};
+use crate::components::unit::Team;
+use crate::components::unit_types::UnitType;
+use crate::resources::map::plugin::MapPlugin;
+use crate::resources::map_data::GameMap;
+use crate::resources::map_generator::MapGenerationSettings;
+use crate::resources::ResourceNodePlugin;
+use crate::states::game_state::GameState;
+use crate::systems::{
+    AIPlugin,
+    BaseInitializationPlugin,
+    BaseMovePlugin,
+    CombatPlugin,
+    EconomyPlugin,
+    MovementPlugin,
+    ProductionPlugin,
+    VictoryPlugin,
+};
+use crate::systems::damage::DamageRules;
+use crate::systems::unit_catalog::{UnitCatalog, UnitDefinitions};
+use crate::systems::victory::{MatchResult, VictoryReason};
+use crate::units::EngineerPlugin;
+
+/// Settings for a headless match
+#[derive(Resource, Debug, Clone)]
+pub struct SimulationConfig {
+    pub duration: f32,                 // Simulated seconds to run for
+    pub timestep: f32,                 // Seconds advanced per update
+    pub sample_interval: f32,          // Seconds between control history samples
+    pub end_on_victory: bool,          // Stop as soon as the victory rules end the match
+    pub player_difficulty: AIDifficulty,
+    pub enemy_difficulty: AIDifficulty,
+    pub map_seed: u64,                 // Seed for the generated map
+    pub strategic_points: usize,       // Strategic locations on the generated map
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            duration: 600.0,
+            timestep: 0.1,
+            sample_interval: 1.0,
+            end_on_victory: true,
+            player_difficulty: AIDifficulty::Medium,
+            enemy_difficulty: AIDifficulty::Medium,
+            map_seed: 1,
+            strategic_points: 1,
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Build a config from command line arguments
+    /// (`--seconds`, `--timestep`, `--player-ai`, `--enemy-ai`, `--seed` and `--points`)
+    pub fn from_args(args: impl Iterator<Item = String>) -> Self {
+        let mut config = Self::default();
+        let args: Vec<String> = args.collect();
+
+        for pair in args.windows(2) {
+            let value = pair[1].as_str();
+            match pair[0].as_str() {
+                "--seconds" => config.duration = value.parse().unwrap_or(config.duration),
+                "--timestep" => config.timestep = value.parse().unwrap_or(config.timestep),
+                "--player-ai" => config.player_difficulty = parse_difficulty(value).unwrap_or(config.player_difficulty),
+                "--enemy-ai" => config.enemy_difficulty = parse_difficulty(value).unwrap_or(config.enemy_difficulty),
+                "--seed" => config.map_seed = value.parse().unwrap_or(config.map_seed),
+                "--points" => config.strategic_points = value.parse().unwrap_or(config.strategic_points),
+                _ => {}
+            }
+        }
+
+        config
+    }
+}
+
+// Parse a difficulty name from the command line
+fn parse_difficulty(name: &str) -> Option<AIDifficulty> {
+    match name.to_lowercase().as_str() {
+        "easy" => Some(AIDifficulty::Easy),
+        "medium" | "normal" => Some(AIDifficulty::Medium),
+        "hard" => Some(AIDifficulty::Hard),
+        _ => None,
+    }
+}
+
+/// Control status of one strategic location at a point in time
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControlSample {
+    pub time: f32,
+    pub location: String,
+    pub controlling_team: Option<Team>,
+    pub control_points: f32,
+}
+
+/// Outcome of a headless match
+#[derive(Debug, Clone)]
+pub struct SimulationReport {
+    pub winner: Option<Team>,          // None if time ran out before a victory rule was met
+    pub reason: Option<VictoryReason>,
+    pub map_seed: u64,
+    pub elapsed: f32,
+    pub control_history: Vec<ControlSample>,
+    pub final_resources: HashMap<Team, ResourceWallet>, // Held in each team's bases
+}
+
+impl SimulationReport {
+    /// Print a short summary of the match to stdout
+    pub fn print_summary(&self) {
+        println!("Map seed: {}", self.map_seed);
+        match (self.winner, &self.reason) {
+            (Some(team), Some(reason)) => println!("Winner: {:?} after {:.1}s ({:?})", team, self.elapsed, reason),
+            _ => println!("No winner after {:.1}s", self.elapsed),
+        }
+
+        let mut last_owner: Vec<(&str, Option<Team>)> = Vec::new();
+        for sample in &self.control_history {
+            let previous = last_owner.iter_mut().find(|(name, _)| *name == sample.location);
+            match previous {
+                Some((_, owner)) if *owner == sample.controlling_team => {}
+                Some((_, owner)) => {
+                    *owner = sample.controlling_team;
+                    println!("  {:>7.1}s  {} -> {:?}", sample.time, sample.location, sample.controlling_team);
+                }
+                None => last_owner.push((sample.location.as_str(), sample.controlling_team)),
+            }
+        }
+
+        for (team, resources) in &self.final_resources {
+            println!("Final resources of {:?}: {:?}", team, resources);
+        }
+    }
+}
+
+/// Control history collected while the match runs
+#[derive(Resource, Default)]
+struct SimulationRecorder {
+    sample_timer: Timer,
+    samples: Vec<ControlSample>,
+}
+
+/// Plugin that sets up a headless AI vs AI match with all the gameplay plugins
+pub struct SimulationPlugin {
+    pub config: SimulationConfig,
+}
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+           .insert_state(GameState::Loading)
+           .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(self.config.timestep)))
+           .insert_resource(self.config.clone())
+           .insert_resource(MapGenerationSettings {
+                strategic_points: self.config.strategic_points,
+                ..MapGenerationSettings::with_seed(self.config.map_seed)
+            })
+           .insert_resource(SimulationRecorder {
+                sample_timer: Timer::from_seconds(self.config.sample_interval, TimerMode::Repeating),
+                ..default()
+            })
+           // Input resources are normally provided by the window plugins
+           .init_resource::<ButtonInput<MouseButton>>()
+           .init_resource::<ButtonInput<KeyCode>>()
+           // The damage table asset needs the asset server, so headless matches use the built-in table
+           .init_resource::<DamageRules>()
+           .insert_resource(PlayerResources::default());
+
+        // Gameplay plugins that don't need a window or renderer
+        app.add_plugins((
+            MapPlugin,
+            ResourceNodePlugin,
+            StrategicLocationPlugin,
+            BaseInitializationPlugin,
+            BaseMovePlugin,
+            MovementPlugin,
+            ProductionPlugin,
+            CombatPlugin,
+            EngineerPlugin,
+            EconomyPlugin,
+            AIPlugin,
+            VictoryPlugin,
+        ));
+
+        app.add_systems(Startup, setup_match.after(crate::resources::map::plugin::setup_map))
+           .add_systems(Update, record_control_history.run_if(in_state(GameState::Gameplay)));
+    }
+}
+
+/// Build an app ready to run a headless match
+pub fn build_headless_app(config: SimulationConfig) -> App {
+    let mut app = App::new();
+    app.add_plugins(SimulationPlugin { config });
+    app
+}
+
+/// Run a headless match to completion and report the outcome
+pub fn run_simulation(config: SimulationConfig) -> SimulationReport {
+    let mut app = build_headless_app(config.clone());
+    let steps = (config.duration / config.timestep).ceil() as usize;
+
+    let mut elapsed = 0.0;
+    for _ in 0..steps {
+        app.update();
+        elapsed += config.timestep;
+
+        if config.end_on_victory && app.world().contains_resource::<MatchResult>() {
+            break;
+        }
+    }
+
+    report(&mut app, elapsed)
+}
+
+// Collect the outcome of the match from the world
+fn report(app: &mut App, elapsed: f32) -> SimulationReport {
+    let world = app.world_mut();
+
+    let mut locations = world.query::<&StrategicLocation>();
+    let final_samples: Vec<ControlSample> = locations
+        .iter(world)
+        .map(|location| ControlSample {
+            time: elapsed,
+            location: location.name.clone(),
+            controlling_team: location.controlling_team,
+            control_points: location.control_points,
+        })
+        .collect();
+
+    // Teams keep their own stockpiles in their bases
+    let mut final_resources: HashMap<Team, ResourceWallet> = HashMap::new();
+    for base in world.query::<&MechanicalBase>().iter(world) {
+        let wallet = final_resources.entry(base.team).or_default();
+        for (resource_type, amount) in base.resources.iter() {
+            wallet.add(resource_type, amount);
+        }
+    }
+
+    let result = world.get_resource::<MatchResult>().cloned();
+
+    // Always finish the history with the state at the end of the match
+    let mut control_history = world.resource::<SimulationRecorder>().samples.clone();
+    control_history.extend(final_samples);
+
+    SimulationReport {
+        winner: result.as_ref().map(|result| result.winner),
+        reason: result.map(|result| result.reason),
+        map_seed: world.resource::<SimulationConfig>().map_seed,
+        elapsed,
+        control_history,
+        final_resources,
+    }
+}
+
+// Spawn both AI players on opposite corners of the map and start the match
+fn setup_match(
+    mut commands: Commands,
+    config: Res<SimulationConfig>,
+    game_map: Res<GameMap>,
+    unit_definitions: Res<UnitDefinitions>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let start = |index: usize, fallback: (i32, i32)| {
+        let (x, y) = game_map.start_positions.get(index).copied().unwrap_or(fallback);
+        game_map.tile_center(x, y)
+    };
+    let near = start(0, (8, 8));
+    let far = start(1, (game_map.width as i32 - 8, game_map.height as i32 - 8));
+
+    spawn_ai_player(&mut commands, &unit_definitions.catalog, near, Team::Player, config.player_difficulty);
+    spawn_ai_player(&mut commands, &unit_definitions.catalog, far, Team::Enemy, config.enemy_difficulty);
+
+    next_state.set(GameState::Gameplay);
+    info!("Headless match set up: {:?} vs {:?}", config.player_difficulty, config.enemy_difficulty);
+}
+
+// Spawn a base, a factory and a starting army for one AI player
+fn spawn_ai_player(commands: &mut Commands, catalog: &UnitCatalog, position: Vec2, team: Team, difficulty: AIDifficulty) {
+    commands.spawn((
+        TransformBundle::from_transform(Transform::from_xyz(position.x, position.y, 1.0)),
+        MechanicalBase {
+            base_movement_speed: 50.0,
+            effective_movement_speed: 50.0,
+            team,
+            ..default()
+        },
+        AIControlled { difficulty },
+        AIBase,
+        Name::new(format!("{:?} Base", team)),
+    ));
+
+    commands.spawn((
+        TransformBundle::from_transform(Transform::from_xyz(position.x, position.y - 60.0, 2.0)),
+        Building {
+            health: 300.0,
+            max_health: 300.0,
+            construction_progress: 1.0,
+            is_completed: true,
+        },
+        BuildingSpawner {
+            unit_type: Some(UnitType::Engineer),
+            queue: Vec::new(),
+            produces: vec![UnitType::Engineer, UnitType::LandToLandTank, UnitType::LandToAirTank, UnitType::Artillery],
+            spawn_time: 10.0,
+            spawn_timer: Timer::from_seconds(10.0, TimerMode::Repeating),
+            in_production: Some(UnitType::Engineer),
+        },
+        team,
+        Name::new(format!("{:?} Factory", team)),
+    ));
+
+    for (i, unit_type) in [UnitType::LandToLandTank, UnitType::LandToLandTank, UnitType::Artillery].iter().enumerate() {
+        let offset = Vec2::new(40.0 + i as f32 * 30.0, 40.0);
+        unit_type.spawn_unit(commands, catalog, position + offset, team);
+    }
+}
+
+// Sample the control status of every strategic location
+fn record_control_history(
+    time: Res<Time>,
+    mut recorder: ResMut<SimulationRecorder>,
+    locations: Query<&StrategicLocation>,
+) {
+    recorder.sample_timer.tick(time.delta());
+    if recorder.sample_timer.just_finished() {
+        let now = time.elapsed_seconds();
+        for location in locations.iter() {
+            recorder.samples.push(ControlSample {
+                time: now,
+                location: location.name.clone(),
+                controlling_team: location.controlling_team,
+                control_points: location.control_points,
+            });
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
+use crate::components::unit::Team;
 
 /// Resource to manage game sprites
-#[derive(Resource, Default)]
+#[derive(Resource, Default, Clone)]
 pub struct GameSprites {
     // Map unit type to its sprite handles (for each direction)
     pub unit_sprites: HashMap<String, Vec<Handle<Image>>>,
@@ -19,6 +20,40 @@ impl GameSprites {
     pub fn get_base_sprite(&self, team: &str, direction: usize) -> Option<&Handle<Image>> {
         self.base_sprites.get(team).and_then(|sprites| sprites.get(direction % 8))
     }
+    
+    /// Mechanical base sprite tinted for its team, or a colored square until the sprites are loaded
+    pub fn base_sprite_bundle(&self, team: Team, position: Vec2) -> SpriteBundle {
+        // Player bases face right and enemy bases face left
+        let (key, direction, tint, fallback) = match team {
+            Team::Player => ("base_player", 0, Color::srgba(0.9, 0.9, 1.0, 1.0), Color::srgba(0.2, 0.6, 0.8, 1.0)),
+            Team::Enemy => ("base_enemy", 4, Color::srgba(1.0, 0.8, 0.8, 1.0), Color::srgba(0.8, 0.2, 0.2, 1.0)),
+            Team::Neutral => ("", 0, Color::WHITE, Color::srgba(0.7, 0.7, 0.7, 1.0)),
+        };
+        
+        let texture = if self.is_loaded { self.get_base_sprite(key, direction).cloned() } else { None };
+        match texture {
+            Some(texture) => SpriteBundle {
+                texture,
+                sprite: Sprite {
+                    color: tint,
+                    // Size will be determined by the sprite
+                    ..default()
+                },
+                transform: Transform::from_xyz(position.x, position.y, 1.0) // Low z value to ensure visibility
+                    .with_scale(Vec3::new(0.25, 0.25, 1.0)), // Scale down to 25% size
+                ..default()
+            },
+            None => SpriteBundle {
+                sprite: Sprite {
+                    color: fallback,
+                    custom_size: Some(Vec2::new(40.0, 40.0)), // Larger size for the base
+                    ..default()
+                },
+                transform: Transform::from_xyz(position.x, position.y, 1.0),
+                ..default()
+            },
+        }
+    }
 }
 
 pub struct SpriteLoaderPlugin;
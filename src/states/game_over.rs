@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use crate::states::game_state::GameState;
 use crate::systems::camera_manager::spawn_camera_for_state;
+use crate::systems::victory::MatchResult;
 use crate::utils::font_loader::get_font_handle;
 
 pub struct GameOverPlugin;
@@ -8,8 +9,13 @@ pub struct GameOverPlugin;
 impl Plugin for GameOverPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GameState::GameOver), setup_game_over)
-           .add_systems(Update, handle_game_over_input.run_if(in_state(GameState::GameOver)))
-           .add_systems(OnExit(GameState::GameOver), cleanup_game_over);
+           .add_systems(OnEnter(GameState::Victory), setup_victory)
+           .add_systems(
+                Update,
+                handle_game_over_input.run_if(in_state(GameState::GameOver).or_else(in_state(GameState::Victory)))
+            )
+           .add_systems(OnExit(GameState::GameOver), cleanup_game_over)
+           .add_systems(OnExit(GameState::Victory), cleanup_game_over);
     }
 }
 
@@ -17,14 +23,64 @@ impl Plugin for GameOverPlugin {
 #[derive(Component)]
 struct GameOverUI;
 
+// Colours of one result screen
+#[derive(Component, Clone, Copy)]
+struct ResultPalette {
+    background: Color,
+    title: Color,
+    message: Color,
+    button: Color,
+    button_hovered: Color,
+}
+
+const DEFEAT_PALETTE: ResultPalette = ResultPalette {
+    background: Color::srgb(0.1, 0.0, 0.0),
+    title: Color::srgba(0.9, 0.1, 0.1, 1.0),
+    message: Color::srgba(0.9, 0.6, 0.6, 1.0),
+    button: Color::srgba(0.3, 0.1, 0.1, 1.0),
+    button_hovered: Color::srgba(0.4, 0.2, 0.2, 1.0),
+};
+
+const VICTORY_PALETTE: ResultPalette = ResultPalette {
+    background: Color::srgb(0.0, 0.05, 0.12),
+    title: Color::srgba(0.95, 0.8, 0.2, 1.0),
+    message: Color::srgba(0.6, 0.8, 0.95, 1.0),
+    button: Color::srgba(0.1, 0.2, 0.35, 1.0),
+    button_hovered: Color::srgba(0.2, 0.3, 0.45, 1.0),
+};
+
 fn setup_game_over(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    result: Option<Res<MatchResult>>,
 ) {
     // Set up camera with state management
     spawn_camera_for_state(&mut commands, GameState::GameOver);
-    
-    // Game over screen
+
+    let message = result.map_or("Your base has been destroyed!".to_string(), |result| result.describe());
+    spawn_result_screen(&mut commands, &asset_server, "DEFEAT", &message, DEFEAT_PALETTE);
+}
+
+fn setup_victory(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    result: Option<Res<MatchResult>>,
+) {
+    // Set up camera with state management
+    spawn_camera_for_state(&mut commands, GameState::Victory);
+
+    let message = result.map_or("The enemy has been defeated!".to_string(), |result| result.describe());
+    spawn_result_screen(&mut commands, &asset_server, "VICTORY", &message, VICTORY_PALETTE);
+}
+
+// Full screen panel with the result, the reason for it and a way back to the main menu
+fn spawn_result_screen(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    title: &str,
+    message: &str,
+    palette: ResultPalette,
+) {
     commands
         .spawn((
             NodeBundle {
@@ -36,51 +92,54 @@ fn setup_game_over(
                     justify_content: JustifyContent::Center,
                     ..default()
                 },
-                background_color: BackgroundColor(Color::srgb(0.1, 0.0, 0.0)),
+                background_color: BackgroundColor(palette.background),
                 ..default()
             },
             GameOverUI,
         ))
         .with_children(|parent| {
-            // Game Over text
+            // Result title
             parent.spawn(TextBundle::from_section(
-                "GAME OVER",
+                title,
                 TextStyle {
-                    font: get_font_handle(&asset_server),
+                    font: get_font_handle(asset_server),
                     font_size: 100.0,
-                    color: Color::srgba(0.9, 0.1, 0.1, 1.0),
+                    color: palette.title,
                 },
             ));
             
-            // Message
+            // Why the match ended
             parent.spawn(TextBundle::from_section(
-                "Your base has been destroyed!",
+                message,
                 TextStyle {
-                    font: get_font_handle(&asset_server),
+                    font: get_font_handle(asset_server),
                     font_size: 36.0,
-                    color: Color::srgba(0.9, 0.6, 0.6, 1.0),
+                    color: palette.message,
                 },
             ));
             
             // Return to main menu button
             parent
-                .spawn(ButtonBundle {
-                    style: Style {
-                        width: Val::Px(250.0),
-                        height: Val::Px(65.0),
-                        margin: UiRect::all(Val::Px(30.0)),
-                        align_items: AlignItems::Center,
-                        justify_content: JustifyContent::Center,
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(250.0),
+                            height: Val::Px(65.0),
+                            margin: UiRect::all(Val::Px(30.0)),
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        background_color: BackgroundColor(palette.button),
                         ..default()
                     },
-                    background_color: BackgroundColor(Color::srgb(0.3, 0.1, 0.1)),
-                    ..default()
-                })
+                    palette,
+                ))
                 .with_children(|parent| {
                     parent.spawn(TextBundle::from_section(
                         "Return to Main Menu",
                         TextStyle {
-                            font: get_font_handle(&asset_server),
+                            font: get_font_handle(asset_server),
                             font_size: 24.0,
                             color: Color::srgba(0.9, 0.9, 0.9, 1.0),
                         },
@@ -91,23 +150,23 @@ fn setup_game_over(
 
 fn handle_game_over_input(
     mut button_query: Query<
-        (&Interaction, &mut BackgroundColor),
+        (&Interaction, &mut BackgroundColor, &ResultPalette),
         (Changed<Interaction>, With<Button>),
     >,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
     // Return to main menu on button click
-    for (interaction, mut background_color) in button_query.iter_mut() {
+    for (interaction, mut background_color, palette) in button_query.iter_mut() {
         match *interaction {
             Interaction::Pressed => {
                 next_state.set(GameState::MainMenu);
             }
             Interaction::Hovered => {
-                *background_color = BackgroundColor(Color::srgba(0.4, 0.2, 0.2, 1.0));
+                *background_color = BackgroundColor(palette.button_hovered);
             }
             Interaction::None => {
-                *background_color = BackgroundColor(Color::srgba(0.3, 0.1, 0.1, 1.0));
+                *background_color = BackgroundColor(palette.button);
             }
         }
     }
@@ -26,3 +26,14 @@ impl Plugin for GameStatePlugin {
         app.init_state::<GameState>();
     }
 }
+
+/// Run condition for systems that set up a fresh match when gameplay is entered
+///
+/// True unless gameplay is only being resumed from the pause menu, so per-match
+/// progress survives Resume, Save, Load and Settings.
+pub fn starting_new_match(mut transitions: EventReader<StateTransitionEvent<GameState>>) -> bool {
+    transitions
+        .read()
+        .last()
+        .is_none_or(|transition| transition.exited != Some(GameState::Paused))
+}
@@ -1,12 +1,16 @@
 use bevy::prelude::*;
 use crate::states::game_state::GameState;
 use crate::utils::font_loader::get_font_handle;
-use crate::components::unit::{Unit, Team};
+use crate::components::unit::{Unit, Team, Selected};
 use crate::components::unit_types::UnitType;
-use crate::components::base_modules::ResourceType;
+use crate::components::economy::{ResourceType, ResourceWallet};
 use crate::components::player::{MechanicalBase, PlayerResources};
+use crate::components::ai::{AIBase, AIControlled, AIDifficulty};
+use crate::ui::menu::GameSettings;
 use crate::sprites::GameSprites;
+use crate::resources::map_data::GameMap;
 use crate::systems::camera_manager::spawn_camera_for_state;
+use crate::systems::unit_catalog::{UnitCatalog, UnitDefinitions};
 
 // TODO: Move UnitType to components/unit.rs or create a proper unit_types module
 
@@ -22,7 +26,6 @@ impl Plugin for GameplayPlugin {
                     unit_selection,
                     update_selection_visuals,
                     update_selection_box_visual, // Add system for selection box visualization
-                    handle_game_over_condition,
                 ).run_if(in_state(GameState::Gameplay))
            )
            .add_systems(OnExit(GameState::Gameplay), cleanup_gameplay);
@@ -36,9 +39,6 @@ struct GameplayUI;
 #[derive(Component)]
 struct SelectionBoxVisual;
 
-#[derive(Component)]
-struct Selected;
-
 // Using Team from unit.rs instead of defining it here
 
 // Resources
@@ -57,13 +57,25 @@ struct SelectionBox {
 
 fn setup_gameplay(
     mut commands: Commands,
-    mut _meshes: ResMut<Assets<Mesh>>,
-    mut _materials: ResMut<Assets<ColorMaterial>>,
     asset_server: Res<AssetServer>,
     game_sprites: Res<GameSprites>,
+    game_settings: Option<Res<GameSettings>>,
+    unit_definitions: Res<UnitDefinitions>,
+    game_map: Option<Res<GameMap>>,
 ) {
-    // Set up camera with state management
-    spawn_camera_for_state(&mut commands, GameState::Gameplay);
+    // Players start where the map puts them, falling back to the old fixed corners
+    let start = |index: usize, fallback: Vec2| {
+        game_map
+            .as_ref()
+            .and_then(|map| map.start_positions.get(index).map(|(x, y)| map.tile_center(*x, *y)))
+            .unwrap_or(fallback)
+    };
+    let player_start = start(0, Vec2::new(-250.0, -250.0));
+    let enemy_start = start(1, Vec2::new(250.0, 250.0));
+    
+    // Set up camera with state management, looking at the player's base
+    let camera = spawn_camera_for_state(&mut commands, GameState::Gameplay);
+    commands.entity(camera).insert(Transform::from_xyz(player_start.x, player_start.y, 1000.0));
     
     // Initialize game resources
     commands.insert_resource(GameResources {
@@ -158,10 +170,15 @@ fn setup_gameplay(
         });
     
     // Spawn example units with proper sprites
-    spawn_example_units(&mut commands, &game_sprites);
+    spawn_example_units(&mut commands, &unit_definitions.catalog, player_start, enemy_start);
+    
+    // The enemy base is driven by the AI at the difficulty chosen in the settings
+    let ai_difficulty = game_settings
+        .map(|settings| AIDifficulty::from(settings.difficulty))
+        .unwrap_or(AIDifficulty::Medium);
     
     // Spawn mechanical bases with proper sprites
-    spawn_mechanical_bases(&mut commands, &game_sprites);
+    spawn_mechanical_bases(&mut commands, &game_sprites, ai_difficulty, player_start, enemy_start);
 }
 
 fn handle_input(
@@ -174,155 +191,42 @@ fn handle_input(
     }
 }
 
-fn spawn_example_units(commands: &mut Commands, game_sprites: &Res<GameSprites>) {
+fn spawn_example_units(commands: &mut Commands, catalog: &UnitCatalog, player_start: Vec2, enemy_start: Vec2) {
     // Spawn specific unit types that will use our sprites
-    // 2 tanks and 2 artillery units for each team
-    
-    // Spawn player's units
-    // Player tanks
-    for i in 0..2 {
-        let unit_type = UnitType::LandToLandTank;
-        let position = Vec2::new(-200.0 + i as f32 * 50.0, -150.0);
-        
-        // Get the tank sprite from the GameSprites resource
-        let sprite_handle = if game_sprites.is_loaded {
-            game_sprites.get_unit_sprite("tank_player", 0).cloned()
-        } else {
-            None
-        };
-        
-        // Spawn the tank
-        let tank_entity = unit_type.spawn_unit(commands, position, Team::Player);
-        
-        // If we have a sprite, attach it to the entity
-        if let Some(texture) = sprite_handle {
-            commands.entity(tank_entity).insert(texture);
-        }
-        
-        commands.entity(tank_entity).insert(Name::new(format!("Player Tank {}", i)));
-    }
-    
-    // Player artillery
-    for i in 0..2 {
-        let unit_type = UnitType::Artillery;
-        let position = Vec2::new(-200.0 + i as f32 * 50.0, -200.0);
-        
-        // Get the artillery sprite from the GameSprites resource
-        let sprite_handle = if game_sprites.is_loaded {
-            game_sprites.get_unit_sprite("artillery_player", 0).cloned()
-        } else {
-            None
-        };
-        
-        // Spawn the artillery
-        let artillery_entity = unit_type.spawn_unit(commands, position, Team::Player);
-        
-        // If we have a sprite, attach it to the entity
-        if let Some(texture) = sprite_handle {
-            commands.entity(artillery_entity).insert(texture);
-        }
-        
-        commands.entity(artillery_entity).insert(Name::new(format!("Player Artillery {}", i)));
-    }
-    
-    // Spawn enemy units
-    // Enemy tanks
+    // 2 tanks and 2 artillery units for each team, in front of their base
     for i in 0..2 {
-        let unit_type = UnitType::LandToLandTank;
-        let position = Vec2::new(200.0 - i as f32 * 50.0, 150.0);
-        
-        // Get the tank sprite from the GameSprites resource - use direction 4 (facing left)
-        let sprite_handle = if game_sprites.is_loaded {
-            game_sprites.get_unit_sprite("tank_enemy", 4).cloned()
-        } else {
-            None
-        };
+        let offset = i as f32 * 50.0;
         
-        // Spawn the tank
-        let tank_entity = unit_type.spawn_unit(commands, position, Team::Enemy);
+        let entity = UnitType::LandToLandTank.spawn_unit(commands, catalog, player_start + Vec2::new(50.0 + offset, 100.0), Team::Player);
+        commands.entity(entity).insert(Name::new(format!("Player Tank {}", i)));
+        let entity = UnitType::Artillery.spawn_unit(commands, catalog, player_start + Vec2::new(50.0 + offset, 50.0), Team::Player);
+        commands.entity(entity).insert(Name::new(format!("Player Artillery {}", i)));
         
-        // If we have a sprite, attach it to the entity
-        if let Some(texture) = sprite_handle {
-            commands.entity(tank_entity).insert(texture);
-        }
-        
-        commands.entity(tank_entity).insert(Name::new(format!("Enemy Tank {}", i)));
-    }
-    
-    // Enemy artillery
-    for i in 0..2 {
-        let unit_type = UnitType::Artillery;
-        let position = Vec2::new(200.0 - i as f32 * 50.0, 200.0);
-        
-        // Get the artillery sprite from the GameSprites resource - use direction 4 (facing left)
-        let sprite_handle = if game_sprites.is_loaded {
-            game_sprites.get_unit_sprite("artillery_enemy", 4).cloned()
-        } else {
-            None
-        };
-        
-        // Spawn the artillery
-        let artillery_entity = unit_type.spawn_unit(commands, position, Team::Enemy);
-        
-        // If we have a sprite, attach it to the entity
-        if let Some(texture) = sprite_handle {
-            commands.entity(artillery_entity).insert(texture);
-        }
-        
-        commands.entity(artillery_entity).insert(Name::new(format!("Enemy Artillery {}", i)));
+        let entity = UnitType::LandToLandTank.spawn_unit(commands, catalog, enemy_start - Vec2::new(50.0 + offset, 100.0), Team::Enemy);
+        commands.entity(entity).insert(Name::new(format!("Enemy Tank {}", i)));
+        let entity = UnitType::Artillery.spawn_unit(commands, catalog, enemy_start - Vec2::new(50.0 + offset, 50.0), Team::Enemy);
+        commands.entity(entity).insert(Name::new(format!("Enemy Artillery {}", i)));
     }
     
     info!("Spawned tanks and artillery units");
 }
 
 // Function to spawn mechanical bases
-fn spawn_mechanical_bases(commands: &mut Commands, game_sprites: &Res<GameSprites>) {
+fn spawn_mechanical_bases(commands: &mut Commands, game_sprites: &Res<GameSprites>, ai_difficulty: AIDifficulty, player_start: Vec2, enemy_start: Vec2) {
     // Spawn player's mechanical base with sprite
-    let player_base_sprite = if game_sprites.is_loaded {
-        // Use direction 0 (facing right)
-        game_sprites.get_base_sprite("base_player", 0).cloned()
-    } else {
-        None
-    };
-    
     commands.spawn((
-        if let Some(texture) = player_base_sprite {
-            // Use the base sprite
-            SpriteBundle {
-                texture,
-                sprite: Sprite {
-                    // Still apply a slight blue tint to indicate player team
-                    color: Color::srgba(0.9, 0.9, 1.0, 1.0),
-                    // Size will be determined by the sprite
-                    ..default()
-                },
-                transform: Transform::from_xyz(-250.0, -250.0, 1.0) // Low z value to ensure visibility
-                    .with_scale(Vec3::new(0.25, 0.25, 1.0)), // Scale down to 25% size
-                ..default()
-            }
-        } else {
-            // Fallback to colored square if sprite not loaded
-            SpriteBundle {
-                sprite: Sprite {
-                    color: Color::srgba(0.2, 0.6, 0.8, 1.0), // Blue for player
-                    custom_size: Some(Vec2::new(40.0, 40.0)), // Larger size for the base
-                    ..default()
-                },
-                transform: Transform::from_xyz(-250.0, -250.0, 1.0),
-                ..default()
-            }
-        },
+        game_sprites.base_sprite_bundle(Team::Player, player_start),
         MechanicalBase {
             health: 1000.0,
             max_health: 1000.0,
             base_movement_speed: 50.0,
             effective_movement_speed: 50.0,
             team: Team::Player,
-            resources: vec![
+            resources: ResourceWallet::from([
                 (ResourceType::Wood, 100),
                 (ResourceType::Stone, 50),
                 (ResourceType::Iron, 25),
-            ],
+            ]),
             power_output: 100.0,
             power_consumed: 0.0,
             max_power: 150.0,
@@ -333,57 +237,29 @@ fn spawn_mechanical_bases(commands: &mut Commands, game_sprites: &Res<GameSprite
     ));
     
     // Spawn enemy's mechanical base with sprite
-    let enemy_base_sprite = if game_sprites.is_loaded {
-        // Use direction 4 (facing left) for enemy base
-        game_sprites.get_base_sprite("base_enemy", 4).cloned()
-    } else {
-        None
-    };
-    
     commands.spawn((
-        if let Some(texture) = enemy_base_sprite {
-            // Use the base sprite
-            SpriteBundle {
-                texture,
-                sprite: Sprite {
-                    // Apply a red tint to indicate enemy team
-                    color: Color::srgba(1.0, 0.8, 0.8, 1.0),
-                    // Size will be determined by the sprite
-                    ..default()
-                },
-                transform: Transform::from_xyz(250.0, 250.0, 1.0) // Low z value to ensure visibility
-                    .with_scale(Vec3::new(0.25, 0.25, 1.0)), // Scale down to 25% size
-                ..default()
-            }
-        } else {
-            // Fallback to colored square if sprite not loaded
-            SpriteBundle {
-                sprite: Sprite {
-                    color: Color::srgba(0.8, 0.2, 0.2, 1.0), // Red for enemy
-                    custom_size: Some(Vec2::new(40.0, 40.0)), // Larger size for the base
-                    ..default()
-                },
-                transform: Transform::from_xyz(250.0, 250.0, 1.0),
-                ..default()
-            }
-        },
+        game_sprites.base_sprite_bundle(Team::Enemy, enemy_start),
         MechanicalBase {
             health: 1000.0,
             max_health: 1000.0,
             base_movement_speed: 50.0,
             effective_movement_speed: 50.0,
             team: Team::Enemy,
-            resources: vec![
+            resources: ResourceWallet::from([
                 (ResourceType::Wood, 100),
                 (ResourceType::Stone, 50),
                 (ResourceType::Iron, 25),
-            ],
+            ]),
             power_output: 100.0,
             power_consumed: 0.0,
             max_power: 150.0,
             attachment_points: Vec::new(),
             modules: Vec::new(),
         },
+        AIControlled {
+            difficulty: ai_difficulty,
+        },
+        AIBase,
         Name::new("Enemy Base"),
     ));
     
@@ -680,32 +556,6 @@ fn update_selection_visuals(
     }
 }
 
-fn handle_game_over_condition(
-    units: Query<&Unit>,
-    mut next_state: ResMut<NextState<GameState>>,
-) {
-    // Count player and enemy units
-    let mut player_count = 0;
-    let mut enemy_count = 0;
-
-    for unit in units.iter() {
-        match unit.team {
-            Team::Player => player_count += 1,
-            Team::Enemy => enemy_count += 1,
-            _ => {}
-        }
-    }
-
-    // Check win/loss conditions
-    if player_count == 0 {
-        // Player lost all units
-        next_state.set(GameState::GameOver);
-    } else if enemy_count == 0 {
-        // Player defeated all enemies
-        next_state.set(GameState::GameOver);
-    }
-}
-
 fn cleanup_gameplay(
     mut commands: Commands,
     ui_query: Query<Entity, With<GameplayUI>>,
@@ -1,34 +1,509 @@
 use bevy::prelude::*;
-// These will be used when implementing AI systems
-// use crate::components::unit::{Unit, UnitState, Team};
+use crate::components::ai::{AIBrain, AIControlled, AIDifficulty, AIPhase};
+use crate::components::base_modules::{AttachModuleRequest, AttachableModule, AttachmentPoint, BaseModule, ModuleType};
+use crate::components::building::BuildingSpawner;
+use crate::components::player::MechanicalBase;
+use crate::components::strategic::{LocationCaptured, LocationLost, StrategicLocation, CAPTURE_RADIUS};
+use crate::components::unit::{Team, Unit, UnitState};
+use crate::components::unit_types::UnitType;
+use crate::resources::map_data::GameMap;
+use crate::components::resource::ResourceNode;
+use crate::states::game_state::GameState;
+use crate::systems::base_movement::MoveTarget as BaseMoveTarget;
+use crate::systems::combat::{attack_range_world, is_hostile};
+use crate::systems::damage::ArmorClass;
+use crate::systems::module_catalog::ModuleDefinitions;
+use crate::systems::module_effects::Cooldown;
+use crate::systems::movement::MoveTarget;
+use crate::systems::sensors::ScanRequest;
+use crate::units::engineer::{Engineer, SelectedResource};
+
+/// Enemy units this close to the objective count as its defenders
+const DEFENDER_RADIUS: f32 = 300.0;
+
+/// How close the base has to get to home before a retreat is over
+const HOME_RADIUS: f32 = 50.0;
+
+/// Units within this distance of their rally point are left alone
+const RALLY_RADIUS: f32 = 60.0;
+
+/// Spacing between units gathered around the same rally point
+const FORMATION_SPACING: f32 = 30.0;
+
+// Modules the AI builds onto its base, most wanted first
+const MODULE_PRIORITIES: [&str; 8] = [
+    "Autocannon",
+    "Armor Plating",
+    "Workshop",
+    "Reactor",
+    "Radar",
+    "Tracks",
+    "Cargo Hold",
+    "Repair Drone",
+];
 
 // AI systems plugin
 pub struct AIPlugin;
 
 impl Plugin for AIPlugin {
-    fn build(&self, _app: &mut App) {
-        // We'll add systems later when implemented
-        // app.add_systems(Update, enemy_unit_ai);
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ModuleDefinitions>()
+           .add_event::<LocationCaptured>()
+           .add_event::<LocationLost>()
+           .add_event::<AttachModuleRequest>()
+           .add_event::<ScanRequest>()
+           .add_systems(
+                Update,
+                (
+                    attach_ai_brains,
+                    ai_controller,
+                    enemy_base_movement,
+                    enemy_resource_gathering,
+                    enemy_production,
+                    enemy_module_construction,
+                    enemy_sensor_scans,
+                    enemy_unit_ai,
+                ).chain().run_if(in_state(GameState::Gameplay))
+            );
+
+        info!("AI Plugin initialized");
     }
 }
 
-// System to control enemy units
-pub fn enemy_unit_ai(
-    // Will implement later
+/// Snapshot of one AI player's decisions, shared by the systems that carry them out
+#[derive(Debug, Clone, Copy)]
+struct AIPlayer {
+    team: Team,
+    difficulty: AIDifficulty,
+    phase: AIPhase,
+    base_position: Vec2,
+    home: Vec2,
+    objective: Option<Vec2>,
+    thinking: bool,    // The think timer finished this frame
+}
+
+// Collect every AI player that has made at least one decision
+fn ai_players(ai_bases: &Query<(&Transform, &MechanicalBase, &AIControlled, &AIBrain)>) -> Vec<AIPlayer> {
+    ai_bases
+        .iter()
+        .map(|(transform, base, ai, brain)| AIPlayer {
+            team: base.team,
+            difficulty: ai.difficulty,
+            phase: brain.phase,
+            base_position: transform.translation.truncate(),
+            home: brain.home,
+            objective: brain.objective_position,
+            thinking: brain.think_timer.just_finished(),
+        })
+        .collect()
+}
+
+/// Engineers and gatherers don't count toward army strength
+pub fn is_combat_unit(unit: &Unit, unit_type: Option<&UnitType>) -> bool {
+    unit.attack_power > 0.0 && !matches!(unit_type, Some(UnitType::Engineer) | Some(UnitType::Gatherer))
+}
+
+/// Rough measure of how much a unit contributes to a fight
+pub fn unit_strength(unit: &Unit) -> f32 {
+    unit.attack_power * unit.health
+}
+
+// Give every newly AI-controlled base somewhere to store its decisions
+fn attach_ai_brains(
+    mut commands: Commands,
+    bases: Query<(Entity, &Transform, &AIControlled), Without<AIBrain>>,
 ) {
-    // Enemy AI logic
+    for (entity, transform, ai) in bases.iter() {
+        commands.entity(entity).insert(AIBrain::new(ai.difficulty, transform.translation.truncate()));
+        info!("AI ({:?}) took control of base {:?}", ai.difficulty, entity);
+    }
 }
 
-// System for enemy resource gathering
-pub fn enemy_resource_gathering(
-    // Will implement later
+// System that makes the strategic decisions for each AI base
+// Picks an objective, weighs up both armies and decides whether to gather, advance, hold or retreat
+// A location changing hands makes every AI think again straight away
+fn ai_controller(
+    time: Res<Time>,
+    mut bases: Query<(&Transform, &MechanicalBase, &AIControlled, &mut AIBrain)>,
+    locations: Query<(Entity, &StrategicLocation)>,
+    units: Query<(&Transform, &Unit, Option<&UnitType>)>,
+    mut captured_events: EventReader<LocationCaptured>,
+    mut lost_events: EventReader<LocationLost>,
 ) {
-    // Enemy resource gathering logic
+    let control_changed = captured_events.read().count() + lost_events.read().count() > 0;
+
+    for (transform, base, ai, mut brain) in bases.iter_mut() {
+        brain.think_timer.tick(time.delta());
+        if !brain.think_timer.just_finished() && !control_changed {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let team = base.team;
+        let difficulty = ai.difficulty;
+
+        // Head for the best location we don't already own, or hold the best one we do
+        // Valuable locations are worth travelling further for
+        let objective = locations
+            .iter()
+            .min_by(|(_, a), (_, b)| {
+                let a_key = (a.controlling_team == Some(team), position.distance(a.position) / a.capture_value.max(1) as f32);
+                let b_key = (b.controlling_team == Some(team), position.distance(b.position) / b.capture_value.max(1) as f32);
+                a_key.partial_cmp(&b_key).unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|(entity, location)| (entity, location.position));
+
+        brain.objective = objective.map(|(entity, _)| entity);
+        brain.objective_position = objective.map(|(_, position)| position);
+
+        let Some(objective_position) = brain.objective_position else {
+            brain.phase = AIPhase::Gather;
+            continue;
+        };
+        let objective_radius = brain
+            .objective
+            .and_then(|entity| locations.get(entity).ok())
+            .map_or(CAPTURE_RADIUS, |(_, location)| location.capture_radius);
+
+        // Weigh up our army against whoever is in the way
+        let mut own_strength = 0.0;
+        let mut army_size = 0;
+        let mut enemy_strength = 0.0;
+
+        for (unit_transform, unit, unit_type) in units.iter() {
+            if !is_combat_unit(unit, unit_type) {
+                continue;
+            }
+
+            if unit.team == team {
+                own_strength += unit_strength(unit);
+                army_size += 1;
+            } else if is_hostile(team, unit.team) {
+                let defending = unit_transform.translation.truncate().distance(objective_position) <= DEFENDER_RADIUS;
+                if defending || difficulty.counts_reinforcements() {
+                    enemy_strength += unit_strength(unit);
+                }
+            }
+        }
+
+        brain.strength_ratio = own_strength / enemy_strength.max(1.0);
+
+        let ready = army_size >= difficulty.minimum_army()
+            && difficulty.commit_ratio().map_or(true, |ratio| brain.strength_ratio >= ratio);
+        let losing = difficulty.retreat_ratio().is_some_and(|ratio| brain.strength_ratio < ratio);
+
+        let next_phase = match brain.phase {
+            AIPhase::Gather if ready => AIPhase::Advance,
+            AIPhase::Gather => AIPhase::Gather,
+            AIPhase::Advance | AIPhase::Hold if losing => AIPhase::Retreat,
+            AIPhase::Advance | AIPhase::Hold if position.distance(objective_position) < objective_radius => AIPhase::Hold,
+            AIPhase::Advance | AIPhase::Hold => AIPhase::Advance,
+            AIPhase::Retreat if position.distance(brain.home) < HOME_RADIUS => AIPhase::Gather,
+            AIPhase::Retreat => AIPhase::Retreat,
+        };
+
+        if next_phase != brain.phase {
+            info!(
+                "{:?} AI ({:?}) switching from {:?} to {:?} (strength ratio {:.2})",
+                team, difficulty, brain.phase, next_phase, brain.strength_ratio
+            );
+            brain.phase = next_phase;
+        }
+    }
 }
 
 // System for enemy base movement
+// Drives the base toward the objective while advancing or holding, and home while retreating
 pub fn enemy_base_movement(
-    // Will implement later
+    mut commands: Commands,
+    bases: Query<(Entity, &Transform, &AIBrain, Option<&BaseMoveTarget>), With<MechanicalBase>>,
+) {
+    for (entity, transform, brain, move_target) in bases.iter() {
+        let destination = match brain.phase {
+            AIPhase::Gather => None,
+            AIPhase::Advance | AIPhase::Hold => brain.objective_position,
+            AIPhase::Retreat => Some(brain.home),
+        };
+
+        match destination {
+            Some(destination) => {
+                let already_heading = move_target.is_some_and(|target| target.target_position.distance(destination) < 1.0);
+                if !already_heading && transform.translation.truncate().distance(destination) > 5.0 {
+                    commands.entity(entity).insert((
+                        BaseMoveTarget { target_position: destination },
+                        UnitState::Moving,
+                    ));
+                }
+            }
+            None => {
+                if move_target.is_some() {
+                    commands.entity(entity).remove::<BaseMoveTarget>().insert(UnitState::Idle);
+                }
+            }
+        }
+    }
+}
+
+// System for enemy resource gathering
+// Keeps the difficulty's number of engineers assigned to resource nodes that still have something left
+pub fn enemy_resource_gathering(
+    mut commands: Commands,
+    ai_bases: Query<(&Transform, &MechanicalBase, &AIControlled, &AIBrain)>,
+    engineers: Query<(Entity, &Unit, Option<&SelectedResource>, Option<&MoveTarget>), With<Engineer>>,
+    nodes: Query<(Entity, &Transform, &ResourceNode)>,
 ) {
-    // Enemy base movement strategies
+    for player in ai_players(&ai_bases) {
+        let mut assigned = Vec::new();
+        let mut idle = Vec::new();
+
+        for (entity, unit, selected, move_target) in engineers.iter() {
+            if unit.team != player.team {
+                continue;
+            }
+
+            match selected {
+                Some(selected) if nodes.get(selected.resource_entity).is_ok_and(|(_, _, node)| node.amount_remaining > 0) => {
+                    assigned.push(selected.resource_entity);
+                }
+                Some(_) => {
+                    // The node ran dry or disappeared, find a new one
+                    commands.entity(entity).remove::<SelectedResource>().insert(UnitState::Idle);
+                    idle.push(entity);
+                }
+                None if move_target.is_none() => idle.push(entity),
+                None => {}
+            }
+        }
+
+        let wanted = player.difficulty.gatherer_count().saturating_sub(assigned.len());
+
+        for engineer in idle.into_iter().take(wanted) {
+            let busy = |node: Entity| assigned.iter().filter(|other| **other == node).count();
+            let choice = nodes
+                .iter()
+                .filter(|(_, _, node)| node.amount_remaining > 0)
+                .map(|(entity, transform, _)| {
+                    let position = transform.translation.truncate();
+                    // Hard spreads engineers out, everyone else piles onto the closest node
+                    let crowding = if player.difficulty.spreads_gatherers() { busy(entity) } else { 0 };
+                    (entity, position, crowding, player.base_position.distance(position))
+                })
+                .min_by(|a, b| (a.2, a.3).partial_cmp(&(b.2, b.3)).unwrap_or(std::cmp::Ordering::Equal));
+
+            let Some((node, node_position, _, _)) = choice else { break };
+
+            commands.entity(engineer).insert((
+                SelectedResource { resource_entity: node },
+                MoveTarget { position: node_position },
+                UnitState::Gathering,
+            ));
+            assigned.push(node);
+            debug!("{:?} AI sent engineer {:?} to resource node {:?}", player.team, engineer, node);
+        }
+    }
+}
+
+// System for enemy unit production
+// Each time the AI thinks it chooses what its spawners build next based on the current army
+pub fn enemy_production(
+    ai_bases: Query<(&Transform, &MechanicalBase, &AIControlled, &AIBrain)>,
+    mut buildings: Query<(&mut BuildingSpawner, &Team)>,
+    units: Query<(&Unit, Option<&UnitType>)>,
+) {
+    for player in ai_players(&ai_bases).into_iter().filter(|player| player.thinking) {
+        let mut engineers = 0;
+        let mut tanks = 0;
+        let mut artillery = 0;
+        let mut anti_air = 0;
+        let mut hostile_air = 0;
+        let mut hostile_heavy = 0;
+
+        for (unit, unit_type) in units.iter() {
+            if unit.team == player.team {
+                match unit_type {
+                    Some(UnitType::Engineer) => engineers += 1,
+                    Some(UnitType::Artillery) | Some(UnitType::LargeArtillery) => artillery += 1,
+                    Some(UnitType::LandToAirTank) => anti_air += 1,
+                    Some(_) => tanks += 1,
+                    None => {}
+                }
+            } else if is_hostile(player.team, unit.team) {
+                match unit_type.map(UnitType::armor_class) {
+                    Some(ArmorClass::Air) => hostile_air += 1,
+                    Some(ArmorClass::Heavy) => hostile_heavy += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        let next = if engineers < player.difficulty.gatherer_count() {
+            UnitType::Engineer
+        } else {
+            match player.difficulty {
+                // Easy only ever builds tanks
+                AIDifficulty::Easy => UnitType::LandToLandTank,
+                // Medium keeps a fixed mix of two tanks per artillery piece
+                AIDifficulty::Medium if artillery * 2 < tanks => UnitType::Artillery,
+                AIDifficulty::Medium => UnitType::LandToLandTank,
+                // Hard counters whatever the enemy is fielding
+                AIDifficulty::Hard if hostile_air > anti_air => UnitType::LandToAirTank,
+                AIDifficulty::Hard if hostile_heavy > artillery => UnitType::Artillery,
+                AIDifficulty::Hard => UnitType::LandToLandTank,
+            }
+        };
+
+        for (mut spawner, team) in buildings.iter_mut() {
+            if *team == player.team && spawner.unit_type != Some(next) && spawner.can_produce(next) {
+                spawner.unit_type = Some(next);
+                debug!("{:?} AI queued {} production", player.team, next.name());
+            }
+        }
+    }
+}
+
+// System for enemy base upgrades
+// Each time the AI thinks it orders the most wanted module it can afford and has room for,
+// one module at a time
+pub fn enemy_module_construction(
+    definitions: Res<ModuleDefinitions>,
+    bases: Query<(Entity, &MechanicalBase, &AIBrain), With<AIControlled>>,
+    points: Query<&AttachmentPoint>,
+    under_construction: Query<&Parent, With<AttachableModule>>,
+    mut attach_requests: EventWriter<AttachModuleRequest>,
+) {
+    for (entity, base, brain) in bases.iter() {
+        if !brain.think_timer.just_finished() {
+            continue;
+        }
+        if under_construction.iter().any(|parent| parent.get() == entity) {
+            continue;
+        }
+
+        let order = MODULE_PRIORITIES.iter().find_map(|&name| {
+            let definition = definitions.catalog.get(name)?;
+            if !base.resources.can_afford(&definition.cost) {
+                return None;
+            }
+            let attachable = definition.to_attachable();
+            let point = base
+                .attachment_points
+                .iter()
+                .copied()
+                .find(|&point| points.get(point).is_ok_and(|point| point.can_attach(&attachable)))?;
+            Some((name, point))
+        });
+
+        if let Some((name, point)) = order {
+            debug!("{:?} AI ordered {}", base.team, name);
+            attach_requests.send(AttachModuleRequest { base: entity, point, module: name.to_string() });
+        }
+    }
+}
+
+// System for enemy scouting
+// Whenever a sensor is charged the AI scans toward the nearest hostile base
+pub fn enemy_sensor_scans(
+    bases: Query<(Entity, &Transform, &MechanicalBase, &AIBrain, &Children), With<AIControlled>>,
+    hostile_bases: Query<(&Transform, &MechanicalBase)>,
+    sensors: Query<(&BaseModule, &Cooldown)>,
+    mut scan_requests: EventWriter<ScanRequest>,
+) {
+    for (entity, transform, base, brain, children) in bases.iter() {
+        if !brain.think_timer.just_finished() {
+            continue;
+        }
+        let scan_ready = children.iter().any(|&child| {
+            sensors.get(child).is_ok_and(|(module, cooldown)| {
+                matches!(module.module_type, ModuleType::Sensor { .. }) && module.active && cooldown.timer.finished()
+            })
+        });
+        if !scan_ready {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let target = hostile_bases
+            .iter()
+            .filter(|(_, other)| is_hostile(base.team, other.team))
+            .map(|(other_transform, _)| other_transform.translation.truncate())
+            .min_by(|a, b| a.distance(position).total_cmp(&b.distance(position)));
+
+        if let Some(target) = target {
+            debug!("{:?} AI scanned toward {:?}", base.team, target);
+            scan_requests.send(ScanRequest { base: entity, target });
+        }
+    }
+}
+
+// System to control enemy units
+// Rallies the army according to the current phase and, on Hard, focuses fire on the weakest target
+pub fn enemy_unit_ai(
+    mut commands: Commands,
+    game_map: Option<Res<GameMap>>,
+    ai_bases: Query<(&Transform, &MechanicalBase, &AIControlled, &AIBrain)>,
+    mut units: Query<(Entity, &Transform, &mut Unit, Option<&UnitType>, Option<&MoveTarget>)>,
+) {
+    let players = ai_players(&ai_bases);
+    if players.is_empty() {
+        return;
+    }
+
+    // Snapshot positions and health so we can pick targets without aliasing the query
+    let snapshot: Vec<(Entity, Vec2, Team, f32)> = units
+        .iter()
+        .map(|(entity, transform, unit, _, _)| (entity, transform.translation.truncate(), unit.team, unit.health))
+        .collect();
+
+    for (entity, transform, mut unit, unit_type, move_target) in units.iter_mut() {
+        let Some(player) = players.iter().find(|player| player.team == unit.team) else { continue };
+        if !is_combat_unit(&unit, unit_type) {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+
+        if unit.attack_target.is_some() {
+            if player.difficulty.focus_fire() {
+                let range = attack_range_world(&unit, game_map.as_deref());
+                let weakest = snapshot
+                    .iter()
+                    .filter(|(_, other_pos, team, health)| {
+                        is_hostile(unit.team, *team) && *health > 0.0 && position.distance(*other_pos) <= range
+                    })
+                    .min_by(|a, b| a.3.partial_cmp(&b.3).unwrap_or(std::cmp::Ordering::Equal))
+                    .map(|(other, _, _, _)| *other);
+
+                if weakest.is_some() {
+                    unit.attack_target = weakest;
+                }
+            }
+            continue;
+        }
+
+        if move_target.is_some() {
+            continue;
+        }
+
+        let rally_point = match (player.phase, player.difficulty) {
+            // Easy leaves its units wherever they were built until it starts moving
+            (AIPhase::Gather, AIDifficulty::Easy) => None,
+            (AIPhase::Gather, _) => Some(player.base_position),
+            // Medium escorts the base, Easy and Hard send the army straight at the objective
+            (AIPhase::Advance, AIDifficulty::Medium) => Some(player.base_position),
+            (AIPhase::Advance, _) | (AIPhase::Hold, _) => player.objective,
+            (AIPhase::Retreat, _) => Some(player.home),
+        };
+
+        let Some(rally_point) = rally_point else { continue };
+        if position.distance(rally_point) <= RALLY_RADIUS {
+            continue;
+        }
+
+        // Spread units around the rally point so they don't stack on one spot
+        let offset = Vec2::from_angle(entity.index() as f32 * 2.4) * FORMATION_SPACING;
+        commands.entity(entity).insert((
+            MoveTarget { position: rally_point + offset },
+            UnitState::Moving,
+        ));
+    }
 }
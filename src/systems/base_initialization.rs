@@ -2,15 +2,17 @@ use bevy::prelude::*;
 use crate::components::player::MechanicalBase;
 use crate::components::base_modules::{
     AttachmentPoint, 
+    DamageType,
     ModuleType,
-    ResourceType,
+    UtilityEffect,
 };
+use crate::components::economy::ResourceType;
 use crate::states::game_state::GameState;
 
 /// System to initialize attachment points on the mechanical base
 pub fn initialize_base_attachments(
     mut commands: Commands,
-    mut bases: Query<(Entity, &mut MechanicalBase)>, 
+    mut bases: Query<(Entity, &mut MechanicalBase), Added<MechanicalBase>>,
 ) {
     for (base_entity, mut base) in bases.iter_mut() {
         // Only initialize if no attachment points exist yet
@@ -36,6 +38,7 @@ pub fn initialize_base_attachments(
                             terrain_penalty_reduction: 0.5
                         },
                     ),
+                    SpatialBundle::from_transform(Transform::from_translation(offset.extend(0.1))),
                     Name::new("Movement Attachment"),
                 )).id();
                 
@@ -63,6 +66,7 @@ pub fn initialize_base_attachments(
                             passive_generation: 0.1, // Small passive generation of resources
                         },
                     ),
+                    SpatialBundle::from_transform(Transform::from_translation(offset.extend(0.1))),
                     Name::new("Utility Attachment"),
                 )).id();
                 
@@ -70,20 +74,47 @@ pub fn initialize_base_attachments(
                 commands.entity(base_entity).add_child(point_entity);
             }
             
+            // Add one hardpoint for each of the other module kinds in a ring outside the rest
+            for (index, (module_type, name)) in hardpoint_types().into_iter().enumerate() {
+                let angle = std::f32::consts::PI / 6.0 + index as f32 * std::f32::consts::PI / 3.0;
+                let offset = Vec2::from_angle(angle) * 48.0;
+                let point_entity = commands.spawn((
+                    AttachmentPoint::new(offset, angle, Vec2::new(18.0, 18.0), module_type),
+                    SpatialBundle::from_transform(Transform::from_translation(offset.extend(0.1))),
+                    Name::new(name),
+                )).id();
+                
+                base.add_attachment_point(point_entity);
+                commands.entity(base_entity).add_child(point_entity);
+            }
+            
             info!("Initialized {} attachment points for base", 
                  base.attachment_points.len());
         }
     }
 }
 
+// Module kinds that get a single hardpoint each; only the kind matters when attaching
+fn hardpoint_types() -> [(ModuleType, &'static str); 6] {
+    [
+        (ModuleType::Defense { armor_bonus: 0.0, shield_strength: 0.0, shield_recharge_rate: 0.0, damage_resistance: 0.0 }, "Defense Hardpoint"),
+        (ModuleType::Weapon { damage: 0.0, attack_speed: 0.0, range: 0.0, damage_type: DamageType::Kinetic, splash_radius: 0.0, tracking_speed: 0.0 }, "Weapon Hardpoint"),
+        (ModuleType::Sensor { detection_radius: 0.0, stealth_detection: 0.0, vision_range: 0.0, scan_cooldown: 0.0 }, "Sensor Hardpoint"),
+        (ModuleType::Energy { power_output: 0.0, power_capacity: 0.0, efficiency: 0.0, power_transfer_rate: 0.0 }, "Energy Hardpoint"),
+        (ModuleType::Production { build_speed: 0.0, queue_slots: 0, cost_reduction: 0.0, experience_gain: 0.0 }, "Production Hardpoint"),
+        (ModuleType::Utility { effect_type: UtilityEffect::Repair, effect_strength: 0.0, area_of_effect: 0.0, cooldown: 0.0 }, "Utility Hardpoint"),
+    ]
+}
+
 /// Plugin for base initialization systems
 pub struct BaseInitializationPlugin;
 
 impl Plugin for BaseInitializationPlugin {
     fn build(&self, app: &mut App) {
+        // Bases are spawned on entering gameplay, so give each new base its points once
         app.add_systems(
-            OnEnter(GameState::Gameplay),
-            initialize_base_attachments
+            Update,
+            initialize_base_attachments.run_if(in_state(GameState::Gameplay))
         );
     }
 }
@@ -2,7 +2,9 @@ use bevy::prelude::*;
 use crate::components::player::MechanicalBase;
 use crate::components::unit::{UnitState, Selected};
 use crate::resources::map::plugin::MapInitialized;
+use crate::resources::map_data::GameMap;
 use crate::states::game_state::GameState;
+use crate::utils::pathfinding::{speed_modifier_at, MovePath};
 
 /// Component for a target location the mechanical base should move to
 #[derive(Component)]
@@ -93,36 +95,62 @@ fn set_base_move_target(
 }
 
 /// System to move mechanical bases toward their target positions
+///
+/// Bases follow a terrain-aware path around impassable tiles and slow down on rough ground.
 fn handle_base_movement(
     time: Res<Time>,
     mut commands: Commands,
-    mut query: Query<(Entity, &mut Transform, &MechanicalBase, &MoveTarget, &UnitState)>,
+    game_map: Option<Res<GameMap>>,
+    mut query: Query<(Entity, &mut Transform, &MechanicalBase, &MoveTarget, &UnitState, Option<&mut MovePath>)>,
+    stale_paths: Query<Entity, (With<MechanicalBase>, With<MovePath>, Without<MoveTarget>)>,
 ) {
-    for (entity, mut transform, base, move_target, state) in query.iter_mut() {
+    let game_map = game_map.as_deref();
+
+    // Drop paths left behind by cancelled move orders
+    for entity in stale_paths.iter() {
+        commands.entity(entity).remove::<MovePath>();
+    }
+
+    for (entity, mut transform, base, move_target, state, path) in query.iter_mut() {
         // Only move if the unit state is Moving
         if matches!(state, UnitState::Moving) {
             let current_position = transform.translation.truncate();
             let target_position = move_target.target_position;
             
-            // Calculate direction vector
-            let direction = (target_position - current_position).normalize_or_zero();
+            // Plan a new route if the target changed
+            let mut path = path.map(Mut::into_inner);
+            let mut new_path = None;
+            if !path.as_deref_mut().is_some_and(|path| path.follows(target_position, game_map)) {
+                let Some(planned) = MovePath::plan(game_map, current_position, target_position) else {
+                    commands.entity(entity).remove::<(MoveTarget, MovePath)>();
+                    commands.entity(entity).insert(UnitState::Idle);
+                    warn!("No path for base to {:?}", target_position);
+                    continue;
+                };
+                new_path = Some(planned);
+            }
+            let Some(route) = new_path.as_mut().or(path) else { continue };
             
             // Calculate movement distance this frame
-            let movement_speed = base.effective_movement_speed;
+            let movement_speed = base.effective_movement_speed * speed_modifier_at(game_map, current_position);
             let movement_distance = movement_speed * time.delta_seconds();
             
             // Calculate new position
-            let new_position = current_position + direction * movement_distance;
+            let new_position = route.advance(current_position, movement_distance);
             
             // Update transform
             transform.translation.x = new_position.x;
             transform.translation.y = new_position.y;
             
+            if let Some(planned) = new_path {
+                commands.entity(entity).insert(planned);
+            }
+            
             // Check if we've reached the target (within a small threshold)
-            let distance_to_target = current_position.distance(target_position);
+            let distance_to_target = new_position.distance(target_position);
             if distance_to_target < 5.0 {
                 // We've arrived, remove the move target and set state to Idle
-                commands.entity(entity).remove::<MoveTarget>();
+                commands.entity(entity).remove::<(MoveTarget, MovePath)>();
                 commands.entity(entity).insert(UnitState::Idle);
                 info!("Base reached its destination");
             }
@@ -0,0 +1,231 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use crate::components::economy::{ResourceType, ResourceWallet};
+use crate::components::unit::Team;
+use crate::components::unit_types::UnitType;
+use crate::entities::building_types::BuildingType;
+use crate::utils::ron_asset::{RonAsset, RonAssetLoader};
+
+/// Path of the building catalog shipped with the game, relative to the assets folder
+pub const BUILDING_CATALOG_PATH: &str = "data/catalog.buildings.ron";
+
+/// Built-in copy of the building catalog, used until the asset finishes loading
+const BUILTIN_BUILDING_CATALOG: &str = include_str!("../../assets/data/catalog.buildings.ron");
+
+/// Resource a building produces on a timer
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct GeneratorDefinition {
+    pub resource_type: ResourceType,
+    pub rate: f32,      // Amount produced each interval
+    pub interval: f32,  // Seconds between payouts
+}
+
+/// Sprite colour of a building for each team, as red, green and blue from 0 to 1
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct BuildingColors {
+    pub player: (f32, f32, f32),
+    pub enemy: (f32, f32, f32),
+    pub neutral: (f32, f32, f32),
+}
+
+impl Default for BuildingColors {
+    fn default() -> Self {
+        Self {
+            player: (0.2, 0.6, 0.8),   // Blue
+            enemy: (0.8, 0.2, 0.2),    // Red
+            neutral: (0.7, 0.7, 0.7),  // Gray
+        }
+    }
+}
+
+impl BuildingColors {
+    /// Colour of the building when owned by a team
+    pub fn for_team(&self, team: Team) -> Color {
+        let (red, green, blue) = match team {
+            Team::Player => self.player,
+            Team::Enemy => self.enemy,
+            Team::Neutral => self.neutral,
+        };
+        Color::srgb(red, green, blue)
+    }
+}
+
+/// Stats, cost and production list for a single building type
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BuildingDefinition {
+    pub health: f32,
+    pub construction_time: f32,
+    pub size: f32,
+    #[serde(default)]
+    pub cost: ResourceWallet,
+    #[serde(default)]
+    pub generator: Option<GeneratorDefinition>,
+    #[serde(default)]
+    pub produces: Vec<String>,  // Unit catalog names, checked when the catalog loads
+    #[serde(default = "default_spawn_time")]
+    pub spawn_time: f32,        // Seconds per unit for units missing from the unit catalog
+    #[serde(default)]
+    pub colors: BuildingColors,
+}
+
+fn default_spawn_time() -> f32 {
+    10.0
+}
+
+impl Default for BuildingDefinition {
+    fn default() -> Self {
+        Self {
+            health: 200.0,
+            construction_time: 20.0,
+            size: 32.0,
+            cost: ResourceWallet::new(),
+            generator: None,
+            produces: Vec::new(),
+            spawn_time: default_spawn_time(),
+            colors: BuildingColors::default(),
+        }
+    }
+}
+
+impl BuildingDefinition {
+    /// Units this building can produce, skipping any name the unit catalog doesn't know
+    pub fn production(&self) -> Vec<UnitType> {
+        self.produces
+            .iter()
+            .filter_map(|name| {
+                let unit_type = UnitType::from_name(name);
+                if unit_type.is_none() {
+                    warn!("Ignoring unknown unit \"{}\" in a building's production list", name);
+                }
+                unit_type
+            })
+            .collect()
+    }
+}
+
+/// Data-driven building stats, loaded from `assets/data/*.buildings.ron`
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+pub struct BuildingCatalog {
+    pub version: u32,
+    pub buildings: HashMap<BuildingType, BuildingDefinition>,
+}
+
+impl RonAsset for BuildingCatalog {
+    fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        for (building_type, definition) in &self.buildings {
+            if definition.health <= 0.0 {
+                problems.push(format!("{:?}: health must be positive (got {})", building_type, definition.health));
+            }
+            if definition.construction_time < 0.0 {
+                problems.push(format!(
+                    "{:?}: construction_time can't be negative (got {})",
+                    building_type, definition.construction_time
+                ));
+            }
+            for (resource_type, amount) in definition.cost.iter() {
+                if amount < 0 {
+                    problems.push(format!("{:?}: cost of {:?} is negative ({})", building_type, resource_type, amount));
+                }
+            }
+            if let Some(generator) = &definition.generator {
+                if generator.rate < 0.0 || generator.interval <= 0.0 {
+                    problems.push(format!(
+                        "{:?}: generator needs a non-negative rate and a positive interval (got {} every {}s)",
+                        building_type, generator.rate, generator.interval
+                    ));
+                }
+            }
+            if !definition.produces.is_empty() && definition.spawn_time <= 0.0 {
+                problems.push(format!("{:?}: spawn_time must be positive (got {})", building_type, definition.spawn_time));
+            }
+            for name in &definition.produces {
+                if UnitType::from_name(name).is_none() {
+                    problems.push(format!("{:?}: produces unknown unit \"{}\"", building_type, name));
+                }
+            }
+        }
+
+        if problems.is_empty() { Ok(()) } else { Err(problems) }
+    }
+}
+
+impl Default for BuildingCatalog {
+    fn default() -> Self {
+        ron::from_str(BUILTIN_BUILDING_CATALOG).expect("built-in building catalog should be valid RON")
+    }
+}
+
+impl BuildingCatalog {
+    /// Definition for a building type, if the catalog has one
+    pub fn get(&self, building_type: BuildingType) -> Option<&BuildingDefinition> {
+        self.buildings.get(&building_type)
+    }
+
+    /// Definition for a building type, falling back to generic stats if it is missing
+    pub fn definition(&self, building_type: BuildingType) -> BuildingDefinition {
+        self.get(building_type).cloned().unwrap_or_default()
+    }
+
+    /// Overlay another catalog on top of this one, keeping entries it doesn't mention
+    pub fn merge(&mut self, other: &BuildingCatalog) {
+        self.version = other.version;
+        for (building_type, definition) in &other.buildings {
+            self.buildings.insert(*building_type, definition.clone());
+        }
+    }
+}
+
+/// The building catalog currently in effect
+#[derive(Resource, Default)]
+pub struct BuildingDefinitions {
+    pub catalog: BuildingCatalog,
+    pub handle: Option<Handle<BuildingCatalog>>,
+}
+
+/// Plugin that loads the building catalog asset and keeps `BuildingDefinitions` in sync with it
+pub struct BuildingCatalogPlugin;
+
+impl Plugin for BuildingCatalogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BuildingDefinitions>()
+           .init_asset::<BuildingCatalog>()
+           .register_asset_loader(RonAssetLoader::<BuildingCatalog>::new(&["buildings.ron"]))
+           .add_systems(Startup, load_building_catalog)
+           .add_systems(Update, sync_building_catalog);
+    }
+}
+
+// Start loading the building catalog from disk
+fn load_building_catalog(
+    asset_server: Res<AssetServer>,
+    mut definitions: ResMut<BuildingDefinitions>,
+) {
+    definitions.handle = Some(asset_server.load(BUILDING_CATALOG_PATH));
+}
+
+// Copy the catalog into `BuildingDefinitions` whenever it is loaded or edited on disk
+fn sync_building_catalog(
+    mut events: EventReader<AssetEvent<BuildingCatalog>>,
+    catalogs: Res<Assets<BuildingCatalog>>,
+    mut definitions: ResMut<BuildingDefinitions>,
+) {
+    for event in events.read() {
+        let id = match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => *id,
+            _ => continue,
+        };
+
+        if definitions.handle.as_ref().map(|handle| handle.id()) != Some(id) {
+            continue;
+        }
+
+        if let Some(catalog) = catalogs.get(id) {
+            info!("Building catalog v{} loaded ({} buildings)", catalog.version, catalog.buildings.len());
+            definitions.catalog.merge(catalog);
+        }
+    }
+}
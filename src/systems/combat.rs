@@ -1,27 +1,359 @@
 use bevy::prelude::*;
-// These will be used when implementing the combat systems
-// use crate::components::unit::{Unit, UnitState, Team};
+use std::collections::HashSet;
+use crate::components::base_modules::{BaseModule, DamageType};
+use crate::components::player::MechanicalBase;
+use crate::components::unit::{Unit, UnitState, Team};
+use crate::components::unit_types::UnitType;
+use crate::resources::map_data::GameMap;
+use crate::states::game_state::GameState;
+use crate::systems::damage::{ArmorClass, DamageRules, DefenseProfile};
+use crate::systems::module_effects::BaseDefenses;
+use crate::systems::movement::MoveTarget;
+use crate::systems::sensors::{is_concealed_from, Detected};
+use crate::systems::status_effects::StatusEffects;
+
+/// Tile size used to convert `Unit::attack_range` into world units when no map is loaded
+const DEFAULT_RANGE_SCALE: f32 = 32.0;
+
+/// Extra slack before a unit gives up on a target that drifted out of range
+const TARGET_LEASH: f32 = 1.25;
+
+/// Share of a base hit taken by the module facing the attacker, the hull takes the rest
+const MODULE_HIT_SHARE: f32 = 0.5;
 
 // Combat systems plugin
 pub struct CombatPlugin;
 
 impl Plugin for CombatPlugin {
-    fn build(&self, _app: &mut App) {
-        // We'll add systems later when implementing combat mechanics
-        // app.add_systems(Update, handle_combat);
+    fn build(&self, app: &mut App) {
+        app.add_event::<UnitDestroyedEvent>()
+           .add_event::<BaseDamageEvent>()
+           .add_event::<BaseDestroyedEvent>()
+           .add_systems(
+                Update,
+                (
+                    check_attack_range,
+                    handle_combat,
+                    apply_base_damage,
+                ).chain().run_if(in_state(GameState::Gameplay))
+            );
+
+        info!("Combat Plugin initialized");
+    }
+}
+
+/// Event fired when a unit's health drops to zero and it is removed from the game
+#[derive(Event, Debug, Clone, Copy)]
+pub struct UnitDestroyedEvent {
+    pub entity: Entity,
+    pub team: Team,
+    pub killer: Option<Entity>,
+}
+
+/// Event fired when a base's health drops to zero and it is removed along with its modules
+#[derive(Event, Debug, Clone, Copy)]
+pub struct BaseDestroyedEvent {
+    pub entity: Entity,
+    pub team: Team,
+    pub killer: Option<Entity>,
+}
+
+/// Event fired when something hits a mechanical base
+///
+/// The damage is raw; `apply_base_damage` resolves it against the base's defenses. `origin` is
+/// where the hit came from, which decides the module that takes part of it.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct BaseDamageEvent {
+    pub base: Entity,
+    pub amount: f32,
+    pub damage_type: DamageType,
+    pub attacker: Option<Entity>,
+    pub origin: Vec2,
+}
+
+/// Convert a unit's attack range (measured in map tiles) into world units
+pub fn attack_range_world(unit: &Unit, game_map: Option<&GameMap>) -> f32 {
+    let tile_size = game_map.map_or(DEFAULT_RANGE_SCALE, |map| map.tile_size);
+    unit.attack_range * tile_size
+}
+
+// System to check for units in attack range
+// Drops targets that died, escaped or cloaked and picks the closest enemy for idle units,
+// going for enemy bases only when no enemy unit is in range
+pub fn check_attack_range(
+    mut commands: Commands,
+    game_map: Option<Res<GameMap>>,
+    mut units: Query<(Entity, &Transform, &mut Unit, Option<&MoveTarget>)>,
+    bases: Query<(Entity, &Transform, &MechanicalBase)>,
+    concealment: Query<(Option<&StatusEffects>, Option<&Detected>)>,
+) {
+    // Snapshot every unit's and base's position and team so we can search without aliasing the query
+    let mut snapshot: Vec<(Entity, Vec2, Team, f32, bool)> = units
+        .iter()
+        .map(|(entity, transform, unit, _)| (entity, transform.translation.truncate(), unit.team, unit.health, false))
+        .collect();
+    snapshot.extend(
+        bases
+            .iter()
+            .map(|(entity, transform, base)| (entity, transform.translation.truncate(), base.team, base.health, true)),
+    );
+
+    // Cloaked units can't be targeted by a team that hasn't detected them
+    let concealed = |target: Entity, team: Team| {
+        concealment.get(target).is_ok_and(|(statuses, detected)| is_concealed_from(team, statuses, detected))
+    };
+
+    for (entity, transform, mut unit, move_target) in units.iter_mut() {
+        let position = transform.translation.truncate();
+        let range = attack_range_world(&unit, game_map.as_deref());
+
+        // Validate the current target
+        if let Some(target) = unit.attack_target {
+            let still_valid = snapshot.iter().any(|(other, other_pos, _, health, _)| {
+                *other == target
+                    && *health > 0.0
+                    && !concealed(*other, unit.team)
+                    && position.distance(*other_pos) <= range * TARGET_LEASH
+            });
+
+            if !still_valid {
+                unit.attack_target = None;
+            }
+        }
+
+        // Units under a move order don't stop to pick fights
+        if unit.attack_target.is_some() || move_target.is_some() || unit.attack_power <= 0.0 {
+            continue;
+        }
+
+        // Acquire the closest hostile unit within range, or failing that the closest hostile base
+        let mut closest_target = None;
+        let mut closest_key = (true, f32::MAX);
+
+        for (other, other_pos, other_team, health, is_base) in snapshot.iter() {
+            if *other == entity || !is_hostile(unit.team, *other_team) || *health <= 0.0 || concealed(*other, unit.team) {
+                continue;
+            }
+
+            let distance = position.distance(*other_pos);
+            let key = (*is_base, distance);
+            if distance <= range && (closest_target.is_none() || key < closest_key) {
+                closest_key = key;
+                closest_target = Some(*other);
+            }
+        }
+
+        if let Some(target) = closest_target {
+            unit.attack_target = Some(target);
+            debug!("Unit {:?} acquired target {:?}", entity, target);
+        } else if matches!(unit.state, UnitState::Attacking) {
+            // Nothing left to shoot at, stand down
+            unit.state = UnitState::Idle;
+            commands.entity(entity).insert(UnitState::Idle);
+        }
     }
 }
 
 // System to handle combat between units
+// Ticks attack cooldowns, applies damage and despawns units that die
 pub fn handle_combat(
-    // Will implement later
+    mut commands: Commands,
+    time: Res<Time>,
+    game_map: Option<Res<GameMap>>,
+    damage_rules: Res<DamageRules>,
+    mut units: Query<(Entity, &Transform, &mut Unit)>,
+    bases: Query<(Entity, &Transform), With<MechanicalBase>>,
+    unit_kinds: Query<(Option<&UnitType>, Option<&ArmorClass>)>,
+    mut statuses: Query<&mut StatusEffects>,
+    mut destroyed_events: EventWriter<UnitDestroyedEvent>,
+    mut base_damage: EventWriter<BaseDamageEvent>,
 ) {
-    // Combat logic
+    let mut attacks = Vec::new();
+
+    // First, work out which attacks land this frame
+    {
+        let positions: Vec<(Entity, Vec2)> = units
+            .iter()
+            .map(|(entity, transform, _)| (entity, transform.translation.truncate()))
+            .chain(bases.iter().map(|(entity, transform)| (entity, transform.translation.truncate())))
+            .collect();
+
+        for (entity, transform, mut unit) in units.iter_mut() {
+            // Stunned or slowed units reload more slowly, if at all
+            let attack_rate = statuses.get(entity).map_or(1.0, |statuses| statuses.attack_rate_multiplier());
+            unit.attack_cooldown.tick(time.delta().mul_f32(attack_rate));
+
+            let Some(target) = unit.attack_target else { continue };
+            let Some((_, target_pos)) = positions.iter().find(|(other, _)| *other == target) else {
+                unit.attack_target = None;
+                continue;
+            };
+
+            let range = attack_range_world(&unit, game_map.as_deref());
+            if transform.translation.truncate().distance(*target_pos) > range {
+                continue;
+            }
+
+            if !matches!(unit.state, UnitState::Attacking) {
+                unit.state = UnitState::Attacking;
+                commands.entity(entity).insert(UnitState::Attacking);
+            }
+
+            if unit.attack_cooldown.finished() {
+                let damage_type = unit_kinds
+                    .get(entity)
+                    .ok()
+                    .and_then(|(unit_type, _)| unit_type.map(UnitType::damage_type))
+                    .unwrap_or(DamageType::Kinetic);
+                let damage = unit.attack_power * statuses.get(entity).map_or(1.0, |statuses| statuses.damage_multiplier());
+                attacks.push((entity, transform.translation.truncate(), target, *target_pos, damage, damage_type));
+                unit.attack_cooldown.reset();
+            }
+        }
+    }
+
+    // Then, apply the damage
+    let mut destroyed = HashSet::new();
+    for (attacker, attacker_pos, target, target_pos, damage, damage_type) in attacks {
+        if destroyed.contains(&target) {
+            continue;
+        }
+
+        // Bases resolve hits against their own shields and armor
+        if bases.contains(target) {
+            base_damage.send(BaseDamageEvent {
+                base: target,
+                amount: damage,
+                damage_type,
+                attacker: Some(attacker),
+                origin: attacker_pos,
+            });
+            continue;
+        }
+
+        let armor_class = match unit_kinds.get(target) {
+            Ok((_, Some(armor_class))) => *armor_class,
+            Ok((Some(unit_type), None)) => unit_type.armor_class(),
+            _ => ArmorClass::default(),
+        };
+        let result = damage_rules.table.resolve(damage, damage_type, &DefenseProfile::unarmored(armor_class));
+
+        // Ground units take cover in the terrain they stand on; aircraft get nothing from it
+        let cover = match (armor_class, game_map.as_deref()) {
+            (ArmorClass::Air, _) | (_, None) => 1.0,
+            (_, Some(map)) => map.terrain_stats_at(target_pos).map_or(1.0, |terrain| terrain.defense_modifier),
+        };
+
+        // Boosted shields soak up the hit before the hull does
+        let mut hull_damage = result.hull_damage / cover;
+        if let Ok(mut target_statuses) = statuses.get_mut(target) {
+            hull_damage = target_statuses.absorb(hull_damage);
+        }
+
+        if let Ok((_, _, mut target_unit)) = units.get_mut(target) {
+            target_unit.health = (target_unit.health - hull_damage).max(0.0);
+
+            if target_unit.health <= 0.0 {
+                destroyed.insert(target);
+                destroyed_events.send(UnitDestroyedEvent {
+                    entity: target,
+                    team: target_unit.team,
+                    killer: Some(attacker),
+                });
+                info!("{:?} unit {:?} was destroyed by {:?}", target_unit.team, target, attacker);
+            }
+        }
+    }
+
+    // Finally, clear stale targets and remove the dead
+    if !destroyed.is_empty() {
+        for (_, _, mut unit) in units.iter_mut() {
+            if unit.attack_target.is_some_and(|target| destroyed.contains(&target)) {
+                unit.attack_target = None;
+            }
+        }
+
+        for entity in destroyed {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
 }
 
-// System to check for units in attack range
-pub fn check_attack_range(
-    // Will implement later
+/// System to resolve hits on mechanical bases
+///
+/// Each hit goes through the base's resistance, shields and armor (see `BaseDefenses`), then any
+/// boosted shields from utility modules. The module facing the attacker takes `MODULE_HIT_SHARE`
+/// of what is left and the base's health the rest. A base worn down to nothing is despawned.
+pub fn apply_base_damage(
+    mut commands: Commands,
+    mut events: EventReader<BaseDamageEvent>,
+    mut destroyed_events: EventWriter<BaseDestroyedEvent>,
+    damage_rules: Res<DamageRules>,
+    mut bases: Query<(
+        &Transform,
+        &mut MechanicalBase,
+        Option<&Children>,
+        Option<&mut BaseDefenses>,
+        Option<&ArmorClass>,
+        Option<&mut StatusEffects>,
+    )>,
+    mut modules: Query<(&Transform, &mut BaseModule), Without<MechanicalBase>>,
 ) {
-    // Attack range detection logic
+    for event in events.read() {
+        let Ok((transform, mut base, children, defenses, armor_class, statuses)) = bases.get_mut(event.base) else { continue };
+        if base.health <= 0.0 {
+            continue;
+        }
+
+        let armor_class = armor_class.copied().unwrap_or(ArmorClass::Fortified);
+        let result = match defenses {
+            Some(mut defenses) => defenses.take_hit(&damage_rules.table, event.amount, event.damage_type, armor_class),
+            None => damage_rules.table.resolve(event.amount, event.damage_type, &DefenseProfile::unarmored(armor_class)),
+        };
+
+        let mut hull_damage = result.hull_damage;
+        if let Some(mut statuses) = statuses {
+            hull_damage = statuses.absorb(hull_damage);
+        }
+
+        // The module pointing most directly at the attacker is in the line of fire
+        let facing = (event.origin - transform.translation.truncate()).normalize_or_zero();
+        let exposed = children
+            .into_iter()
+            .flatten()
+            .filter_map(|&child| {
+                let (module_transform, module) = modules.get(child).ok()?;
+                let offset = (transform.rotation * module_transform.translation).truncate().normalize_or_zero();
+                let alignment = offset.dot(facing);
+                (module.health > 0.0 && alignment > 0.0).then_some((child, alignment))
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+        if let Some((module_entity, _)) = exposed {
+            if let Ok((_, mut module)) = modules.get_mut(module_entity) {
+                let module_damage = hull_damage * MODULE_HIT_SHARE;
+                let was_working = !module.is_disabled();
+                module.health = (module.health - module_damage).max(0.0);
+                hull_damage -= module_damage;
+                if was_working && module.is_disabled() {
+                    info!("{:?} base module {:?} was knocked offline", base.team, module_entity);
+                }
+            }
+        }
+
+        base.health = (base.health - hull_damage).max(0.0);
+        if base.health <= 0.0 {
+            destroyed_events.send(BaseDestroyedEvent {
+                entity: event.base,
+                team: base.team,
+                killer: event.attacker,
+            });
+            commands.entity(event.base).despawn_recursive();
+            info!("{:?} base was destroyed by {:?}", base.team, event.attacker);
+        }
+    }
+}
+
+/// Check whether two teams should fight each other
+pub fn is_hostile(team: Team, other: Team) -> bool {
+    team != other && team != Team::Neutral && other != Team::Neutral
 }
@@ -0,0 +1,221 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::components::base_modules::DamageType;
+use crate::systems::module_effects::Health;
+use crate::utils::ron_asset::{RonAsset, RonAssetLoader};
+
+/// Path of the balance table shipped with the game, relative to the assets folder
+pub const DAMAGE_TABLE_PATH: &str = "data/balance.damage.ron";
+
+/// Built-in copy of the balance table, used until the asset finishes loading
+const BUILTIN_DAMAGE_TABLE: &str = include_str!("../../assets/data/balance.damage.ron");
+
+/// Armor classes used to look up damage multipliers
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Component)]
+pub enum ArmorClass {
+    Light,      // Engineers, gatherers
+    #[default]
+    Medium,     // Support vehicles, artillery
+    Heavy,      // Tanks
+    Air,        // Aircraft
+    Structure,  // Buildings
+    Fortified,  // Mechanical bases and their modules
+}
+
+/// Special rules for how a damage type interacts with each defensive layer
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct DamageTypeRules {
+    pub shield_multiplier: f32,      // Damage dealt to shields per point of damage
+    pub shield_bypass: f32,          // Fraction of damage that ignores shields (0.0-1.0)
+    pub armor_penetration: f32,      // Fraction of armor ignored (0.0-1.0)
+    pub resistance_penetration: f32, // Fraction of damage resistance ignored (0.0-1.0)
+}
+
+impl Default for DamageTypeRules {
+    fn default() -> Self {
+        Self {
+            shield_multiplier: 1.0,
+            shield_bypass: 0.0,
+            armor_penetration: 0.0,
+            resistance_penetration: 0.0,
+        }
+    }
+}
+
+/// Data-driven damage resolution table, loaded from `assets/data/*.damage.ron`
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+pub struct DamageTable {
+    pub version: u32,
+    pub max_resistance: f32,
+    pub minimum_damage: f32,
+    pub multipliers: HashMap<DamageType, HashMap<ArmorClass, f32>>,
+    #[serde(default)]
+    pub type_rules: HashMap<DamageType, DamageTypeRules>,
+}
+
+impl RonAsset for DamageTable {}
+
+impl Default for DamageTable {
+    fn default() -> Self {
+        ron::from_str(BUILTIN_DAMAGE_TABLE).expect("built-in damage table should be valid RON")
+    }
+}
+
+/// Defensive stats of whatever is being hit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DefenseProfile {
+    pub armor_class: ArmorClass,
+    pub armor: f32,
+    pub shield: f32,
+    pub resistance: f32,
+}
+
+impl DefenseProfile {
+    /// A target with no armor, shields or resistance
+    pub fn unarmored(armor_class: ArmorClass) -> Self {
+        Self {
+            armor_class,
+            armor: 0.0,
+            shield: 0.0,
+            resistance: 0.0,
+        }
+    }
+
+    /// Build a profile from a `Health` component
+    pub fn from_health(health: &Health, armor_class: ArmorClass) -> Self {
+        Self {
+            armor_class,
+            armor: health.armor,
+            shield: health.shield,
+            resistance: health.damage_resistance,
+        }
+    }
+}
+
+/// How much of a hit went to shields and how much to the hull
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DamageResult {
+    pub shield_damage: f32,
+    pub hull_damage: f32,
+}
+
+impl DamageTable {
+    /// Multiplier for a damage type against an armor class (1.0 if not listed)
+    pub fn multiplier(&self, damage_type: DamageType, armor_class: ArmorClass) -> f32 {
+        self.multipliers
+            .get(&damage_type)
+            .and_then(|row| row.get(&armor_class))
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    /// Special rules for a damage type (defaults if not listed)
+    pub fn rules(&self, damage_type: DamageType) -> DamageTypeRules {
+        self.type_rules.get(&damage_type).copied().unwrap_or_default()
+    }
+
+    /// Resolve a hit against a target's defensive layers
+    pub fn resolve(&self, amount: f32, damage_type: DamageType, defense: &DefenseProfile) -> DamageResult {
+        if amount <= 0.0 {
+            return DamageResult::default();
+        }
+
+        let rules = self.rules(damage_type);
+
+        // 1. Percentage resistance, capped by the table
+        let resistance = defense.resistance.clamp(0.0, self.max_resistance)
+            * (1.0 - rules.resistance_penetration.clamp(0.0, 1.0));
+        let damage = amount * (1.0 - resistance);
+
+        // 2. Shields soak up what doesn't bypass them
+        let bypassing = damage * rules.shield_bypass.clamp(0.0, 1.0);
+        let shielded = damage - bypassing;
+        let mut shield_damage = 0.0;
+        let mut through_shield = shielded;
+
+        if defense.shield > 0.0 && rules.shield_multiplier > 0.0 {
+            let potential = shielded * rules.shield_multiplier;
+            shield_damage = potential.min(defense.shield);
+            through_shield = (potential - shield_damage) / rules.shield_multiplier;
+        }
+
+        // 3. Armor is a flat reduction, with a minimum so every hit counts
+        let hull_raw = bypassing + through_shield;
+        let mut hull_damage = 0.0;
+
+        if hull_raw > 0.0 {
+            let armor = defense.armor.max(0.0) * (1.0 - rules.armor_penetration.clamp(0.0, 1.0));
+            hull_damage = (hull_raw - armor).max(self.minimum_damage.min(hull_raw));
+
+            // 4. Damage type vs armor class
+            hull_damage *= self.multiplier(damage_type, defense.armor_class);
+        }
+
+        DamageResult {
+            shield_damage,
+            hull_damage,
+        }
+    }
+
+    /// Resolve a hit and apply it directly to a `Health` component
+    pub fn apply(&self, health: &mut Health, amount: f32, damage_type: DamageType, armor_class: ArmorClass) -> DamageResult {
+        let result = self.resolve(amount, damage_type, &DefenseProfile::from_health(health, armor_class));
+        health.shield = (health.shield - result.shield_damage).max(0.0);
+        health.current = (health.current - result.hull_damage).max(0.0);
+        result
+    }
+}
+
+/// The damage table currently in effect
+#[derive(Resource, Default)]
+pub struct DamageRules {
+    pub table: DamageTable,
+    pub handle: Option<Handle<DamageTable>>,
+}
+
+/// Plugin that loads the damage table asset and keeps `DamageRules` in sync with it
+pub struct DamagePlugin;
+
+impl Plugin for DamagePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DamageRules>()
+           .init_asset::<DamageTable>()
+           .register_asset_loader(RonAssetLoader::<DamageTable>::new(&["damage.ron"]))
+           .add_systems(Startup, load_damage_table)
+           .add_systems(Update, sync_damage_table);
+    }
+}
+
+// Start loading the balance table from disk
+fn load_damage_table(
+    asset_server: Res<AssetServer>,
+    mut rules: ResMut<DamageRules>,
+) {
+    rules.handle = Some(asset_server.load(DAMAGE_TABLE_PATH));
+}
+
+// Copy the table into `DamageRules` whenever it is loaded or edited on disk
+fn sync_damage_table(
+    mut events: EventReader<AssetEvent<DamageTable>>,
+    tables: Res<Assets<DamageTable>>,
+    mut rules: ResMut<DamageRules>,
+) {
+    for event in events.read() {
+        let id = match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => *id,
+            _ => continue,
+        };
+
+        if rules.handle.as_ref().map(|handle| handle.id()) != Some(id) {
+            continue;
+        }
+
+        if let Some(table) = tables.get(id) {
+            info!("Damage table v{} loaded", table.version);
+            rules.table = table.clone();
+        }
+    }
+}
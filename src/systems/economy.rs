@@ -1,36 +1,488 @@
 use bevy::prelude::*;
-// These will be used when implementing the economy systems
-// use crate::components::resource::{ResourceNode, Gatherer};
-// use crate::components::building::ResourceType;
-// use crate::components::player::PlayerResources;
+use std::collections::{HashMap, HashSet};
+use crate::components::base_modules::{BaseModule, ModuleType};
+use crate::components::economy::{ResourceType, ResourceWallet};
+use crate::components::player::{MechanicalBase, PlayerResources};
+use crate::components::resource::{Gatherer, ResourceNode};
+use crate::components::unit::{Team, Unit, UnitState};
+use crate::resources::map_data::GameMap;
+use crate::states::game_state::GameState;
+use crate::systems::base_movement::MoveTarget as BaseMoveTarget;
+use crate::systems::module_effects::ModulePower;
+use crate::systems::movement::MoveTarget;
+use crate::systems::status_effects::StatusEffects;
+use crate::units::engineer::SelectedResource;
+use crate::utils::pathfinding::MovePath;
+
+/// How close a gatherer has to be to a node to harvest it
+pub const GATHER_RANGE: f32 = 30.0;
+
+/// How close a gatherer has to be to a base to drop off its load
+pub const DELIVERY_RANGE: f32 = 40.0;
+
+/// How much of each resource a base can hold before any storage modules add to it
+pub const BASE_STORAGE_CAPACITY: i32 = 1000;
+
+/// How long a gatherer turned away by a full base waits before trying again, in seconds
+pub const FULL_BASE_RETRY_SECONDS: f32 = 2.0;
+
+/// Gatherer parked at a full base, waiting to try delivering the rest of its load
+#[derive(Component, Debug)]
+pub struct DeliveryBackoff {
+    pub timer: Timer,
+}
+
+/// Sent whenever a gatherer drops off its load at a base
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct ResourcesDelivered {
+    pub base: Entity,
+    pub team: Team,
+    pub resource_type: ResourceType,
+    pub amount: i32,
+}
+
+/// Resource storage of a mechanical base, fed by its storage modules
+///
+/// Every resource is capped at `BASE_STORAGE_CAPACITY` plus whatever the base's storage modules
+/// for it add. Working storage modules also trickle in their `passive_generation` each second.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct ResourceStorage {
+    pub extra_capacity: ResourceWallet,            // Capacity storage modules add per resource
+    pub generation: HashMap<ResourceType, f32>,    // Passive income per second
+    pub accrued: HashMap<ResourceType, f32>,       // Income earned but not yet a whole unit
+}
+
+impl ResourceStorage {
+    /// Most of a resource the base can hold
+    pub fn capacity(&self, resource_type: ResourceType) -> i32 {
+        BASE_STORAGE_CAPACITY.saturating_add(self.extra_capacity.get(resource_type))
+    }
+}
 
 // Economy systems plugin
 pub struct EconomyPlugin;
 
 impl Plugin for EconomyPlugin {
-    fn build(&self, _app: &mut App) {
-        // We'll add systems later when implementing resource gathering mechanics
-        // app.add_systems(Update, update_player_resources);
+    fn build(&self, app: &mut App) {
+        app.add_event::<ResourcesDelivered>()
+           .add_systems(
+               Update,
+               (
+                   update_resource_storage,
+                   gather_resources,
+                   deliver_resources,
+                   generate_passive_income,
+                   remove_depleted_nodes,
+               ).chain().run_if(in_state(GameState::Gameplay))
+           );
+
+        info!("Economy Plugin initialized");
     }
 }
 
-// System to handle resource gathering
+/// Point where a unit moving at `speed` from `from` can meet a base that is travelling toward
+/// `base_destination` at `base_speed`
+///
+/// Aims ahead of a moving base instead of trailing behind it. If the base is too fast to catch,
+/// or will have stopped before the unit gets there, the unit heads for where the base will stop.
+pub fn intercept_point(from: Vec2, speed: f32, base_position: Vec2, base_speed: f32, base_destination: Option<Vec2>) -> Vec2 {
+    let Some(destination) = base_destination else { return base_position };
+    let remaining = destination - base_position;
+    if speed <= 0.0 || base_speed <= 0.0 || remaining.length() < f32::EPSILON {
+        return destination;
+    }
+
+    // Solve |base_position + velocity * t - from| = speed * t for the earliest t > 0
+    let velocity = remaining.normalize() * base_speed;
+    let offset = base_position - from;
+    let a = velocity.length_squared() - speed * speed;
+    let b = 2.0 * offset.dot(velocity);
+    let c = offset.length_squared();
+
+    let time = if a.abs() < f32::EPSILON {
+        (b < 0.0).then(|| -c / b)
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            None
+        } else {
+            let root = discriminant.sqrt();
+            [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+                .into_iter()
+                .filter(|t| *t > 0.0)
+                .min_by(|x, y| x.total_cmp(y))
+        }
+    };
+
+    match time {
+        Some(time) if time * base_speed < remaining.length() => base_position + velocity * time,
+        _ => destination,
+    }
+}
+
+// System to walk gatherers to their assigned node and harvest it until they are full
 pub fn gather_resources(
-    // Will implement later
+    mut commands: Commands,
+    time: Res<Time>,
+    mut gatherers: Query<(Entity, &mut Gatherer, &Transform, Option<&SelectedResource>, Option<&MoveTarget>)>,
+    mut nodes: Query<(Entity, &mut ResourceNode, &Transform)>,
+    statuses: Query<&StatusEffects>,
 ) {
-    // Resource gathering logic
+    // Count who is holding a slot on each node, so despawned or reassigned units never leak one
+    let mut claims: HashMap<Entity, i32> = HashMap::new();
+    for (_, gatherer, _, _, _) in gatherers.iter() {
+        if let Some(node) = gatherer.target_resource {
+            *claims.entry(node).or_default() += 1;
+        }
+    }
+
+    for (entity, mut gatherer, transform, selected, move_target) in gatherers.iter_mut() {
+        if gatherer.returning {
+            continue;
+        }
+
+        let Some(selected) = selected else {
+            release_slot(&mut gatherer, &mut claims);
+            continue;
+        };
+
+        let position = transform.translation.truncate();
+        let node_state = nodes
+            .get(selected.resource_entity)
+            .ok()
+            .map(|(_, node, node_transform)| (node.resource_type, node.amount_remaining, node_transform.translation.truncate()));
+
+        // The node ran dry or disappeared: bring home whatever we have, otherwise stand down
+        let Some((resource_type, amount_remaining, node_position)) = node_state.filter(|(_, amount, _)| *amount > 0) else {
+            release_slot(&mut gatherer, &mut claims);
+            if gatherer.current_load > 0 {
+                gatherer.returning = true;
+            } else {
+                commands.entity(entity).remove::<(SelectedResource, MoveTarget)>().insert(UnitState::Idle);
+            }
+            continue;
+        };
+
+        // A load of something else has to be dropped off before switching resource
+        if gatherer.is_full() || gatherer.carried_type.is_some_and(|carried| carried != resource_type && gatherer.current_load > 0) {
+            release_slot(&mut gatherer, &mut claims);
+            gatherer.returning = true;
+            continue;
+        }
+
+        if position.distance(node_position) > GATHER_RANGE {
+            release_slot(&mut gatherer, &mut claims);
+            if move_target.is_none_or(|target| target.position != node_position) {
+                commands.entity(entity).insert((MoveTarget { position: node_position }, UnitState::Gathering));
+            }
+            continue;
+        }
+
+        if move_target.is_some() {
+            commands.entity(entity).remove::<MoveTarget>();
+        }
+
+        // Take a harvesting slot, or move on to the closest node of the same kind with room
+        if gatherer.target_resource != Some(selected.resource_entity) {
+            release_slot(&mut gatherer, &mut claims);
+            let taken = claims.get(&selected.resource_entity).copied().unwrap_or(0);
+            let max_gatherers = nodes.get(selected.resource_entity).map_or(0, |(_, node, _)| node.max_gatherers);
+
+            if taken >= max_gatherers {
+                let alternative = nodes
+                    .iter()
+                    .filter(|(other, node, _)| {
+                        *other != selected.resource_entity
+                            && node.resource_type == resource_type
+                            && node.amount_remaining > 0
+                            && claims.get(other).copied().unwrap_or(0) < node.max_gatherers
+                    })
+                    .map(|(other, _, node_transform)| (other, position.distance(node_transform.translation.truncate())))
+                    .min_by(|a, b| a.1.total_cmp(&b.1));
+
+                if let Some((other, _)) = alternative {
+                    commands.entity(entity).insert(SelectedResource { resource_entity: other });
+                }
+                continue;
+            }
+
+            gatherer.target_resource = Some(selected.resource_entity);
+            *claims.entry(selected.resource_entity).or_default() += 1;
+            commands.entity(entity).insert(UnitState::Gathering);
+        }
+
+        gatherer.gather_timer.tick(time.delta());
+        if !gatherer.gather_timer.just_finished() {
+            continue;
+        }
+
+        let space = gatherer.carry_capacity - gatherer.current_load;
+        let gather_rate = gatherer.gather_rate * statuses.get(entity).map_or(1.0, |statuses| statuses.gather_multiplier());
+        let amount = (gather_rate.round() as i32).max(1).min(space).min(amount_remaining);
+        if let Ok((_, mut node, _)) = nodes.get_mut(selected.resource_entity) {
+            node.amount_remaining -= amount;
+        }
+        gatherer.current_load += amount;
+        gatherer.carried_type = Some(resource_type);
+
+        if gatherer.is_full() || amount >= amount_remaining {
+            release_slot(&mut gatherer, &mut claims);
+            gatherer.returning = true;
+        }
+    }
+
+    for (entity, mut node, _) in nodes.iter_mut() {
+        let current = claims.get(&entity).copied().unwrap_or(0);
+        if node.current_gatherers != current {
+            node.current_gatherers = current;
+        }
+    }
 }
 
-// System to handle resource delivery to base
+// Give up the harvesting slot a gatherer holds, if any
+fn release_slot(gatherer: &mut Gatherer, claims: &mut HashMap<Entity, i32>) {
+    if let Some(node) = gatherer.target_resource.take() {
+        if let Some(count) = claims.get_mut(&node) {
+            *count -= 1;
+        }
+    }
+}
+
+// System to chase down the nearest friendly base and drop off the load
 pub fn deliver_resources(
-    // Will implement later
+    mut commands: Commands,
+    time: Res<Time>,
+    game_map: Option<Res<GameMap>>,
+    mut gatherers: Query<(
+        Entity,
+        &mut Gatherer,
+        &Transform,
+        &Unit,
+        Option<&SelectedResource>,
+        Option<&MoveTarget>,
+        Option<&mut DeliveryBackoff>,
+    )>,
+    mut bases: Query<(
+        Entity,
+        &Transform,
+        &mut MechanicalBase,
+        Option<&BaseMoveTarget>,
+        Option<&MovePath>,
+        Option<&UnitState>,
+        Option<&ResourceStorage>,
+    )>,
+    mut player_resources: Option<ResMut<PlayerResources>>,
+    mut delivered: EventWriter<ResourcesDelivered>,
 ) {
-    // Resource delivery logic
+    // Meeting points closer than a tile to the current one aren't worth replanning the path for
+    let replan_distance = game_map.map_or(32.0, |game_map| game_map.tile_size);
+
+    for (entity, mut gatherer, transform, unit, selected, move_target, backoff) in gatherers.iter_mut() {
+        if !gatherer.returning {
+            continue;
+        }
+
+        if let Some(mut backoff) = backoff {
+            if !backoff.timer.tick(time.delta()).finished() {
+                continue;
+            }
+            commands.entity(entity).remove::<DeliveryBackoff>();
+        }
+
+        let position = transform.translation.truncate();
+        let nearest = bases
+            .iter()
+            .filter(|(_, _, base, _, _, _, _)| base.team == unit.team)
+            .map(|(base_entity, base_transform, base, move_target, path, state, _)| {
+                let base_position = base_transform.translation.truncate();
+                // Only a base that is actually under way will move before we get there,
+                // and it heads for the next waypoint of its path rather than straight at the target
+                let destination = move_target
+                    .filter(|_| matches!(state, Some(UnitState::Moving)))
+                    .map(|target| {
+                        path.and_then(|path| path.waypoints.front().copied())
+                            .unwrap_or(target.target_position)
+                    });
+                (base_entity, base_position, base.effective_movement_speed, destination)
+            })
+            .min_by(|a, b| position.distance(a.1).total_cmp(&position.distance(b.1)));
+
+        // Nowhere to drop off: hold on to the load until a base turns up
+        let Some((base_entity, base_position, base_speed, destination)) = nearest else {
+            commands.entity(entity).remove::<MoveTarget>().insert(UnitState::Idle);
+            continue;
+        };
+
+        if position.distance(base_position) > DELIVERY_RANGE {
+            let meeting_point = intercept_point(position, unit.movement_speed, base_position, base_speed, destination);
+            if move_target.is_none_or(|target| target.position.distance(meeting_point) > replan_distance) {
+                commands.entity(entity).insert((MoveTarget { position: meeting_point }, UnitState::Gathering));
+            }
+            continue;
+        }
+
+        let player_capacity = gatherer.carried_type.map_or(0, |resource_type| {
+            player_storage_capacity(bases.iter().map(|(_, _, base, _, _, _, storage)| (base, storage)), resource_type)
+        });
+        if let (Some(resource_type), Ok((_, _, mut base, _, _, _, storage))) = (gatherer.carried_type, bases.get_mut(base_entity)) {
+            let capacity = match base.team {
+                Team::Player => player_capacity,
+                _ => storage.map_or(BASE_STORAGE_CAPACITY, |storage| storage.capacity(resource_type)),
+            };
+            let amount = store_resources(&mut base, player_resources.as_deref_mut(), resource_type, gatherer.current_load, capacity);
+            if amount > 0 {
+                delivered.send(ResourcesDelivered { base: base_entity, team: unit.team, resource_type, amount });
+                debug!("{:?} delivered {} {:?} to base {:?}", entity, amount, resource_type, base_entity);
+            }
+
+            // A full base takes what fits; the rest stays with the gatherer, which waits a while before trying again
+            if amount < gatherer.current_load {
+                gatherer.current_load -= amount;
+                debug!("Base {:?} has no room for {} more {:?}", base_entity, gatherer.current_load, resource_type);
+                commands.entity(entity).remove::<MoveTarget>().insert((
+                    UnitState::Idle,
+                    DeliveryBackoff { timer: Timer::from_seconds(FULL_BASE_RETRY_SECONDS, TimerMode::Once) },
+                ));
+                continue;
+            }
+        }
+
+        gatherer.current_load = 0;
+        gatherer.carried_type = None;
+        gatherer.returning = false;
+
+        // Head back out if the unit still has a node assigned
+        if selected.is_none() {
+            commands.entity(entity).remove::<MoveTarget>().insert(UnitState::Idle);
+        }
+    }
 }
 
-// System to update player resources
-pub fn update_player_resources(
-    // Will implement later
+/// Most of a resource the player's stockpile can hold
+///
+/// All player bases pay into the one player stockpile, so it holds as much as they can together.
+pub fn player_storage_capacity<'a>(
+    bases: impl Iterator<Item = (&'a MechanicalBase, Option<&'a ResourceStorage>)>,
+    resource_type: ResourceType,
+) -> i32 {
+    bases
+        .filter(|(base, _)| base.team == Team::Player)
+        .map(|(_, storage)| storage.map_or(BASE_STORAGE_CAPACITY, |storage| storage.capacity(resource_type)))
+        .fold(0, i32::saturating_add)
+}
+
+/// Put resources into a team's stockpile up to `capacity`, returning how much fit
+///
+/// Player bases pay into the player's stockpile, the same one costs are paid from, capped by
+/// `player_storage_capacity`; other teams keep their resources on the base, capped by its own storage.
+pub fn store_resources(
+    base: &mut MechanicalBase,
+    player_resources: Option<&mut PlayerResources>,
+    resource_type: ResourceType,
+    amount: i32,
+    capacity: i32,
+) -> i32 {
+    let wallet = match player_resources.filter(|_| base.team == Team::Player) {
+        Some(resources) => &mut resources.resources,
+        None => &mut base.resources,
+    };
+
+    let stored = amount.min(capacity.saturating_sub(wallet.get(resource_type))).max(0);
+    if stored > 0 {
+        wallet.add(resource_type, stored);
+    }
+    stored
+}
+
+/// System to work out each base's storage caps and passive income from its storage modules
+///
+/// Built storage modules add capacity whether or not they are powered; only working ones
+/// generate income, scaled by their power supply.
+pub fn update_resource_storage(
+    mut commands: Commands,
+    mut bases: Query<(Entity, Option<&Children>, Option<&mut ResourceStorage>), With<MechanicalBase>>,
+    modules: Query<(&BaseModule, Option<&ModulePower>)>,
 ) {
-    // Update resource counts
+    for (entity, children, storage) in bases.iter_mut() {
+        let mut extra_capacity = ResourceWallet::new();
+        let mut generation: HashMap<ResourceType, f32> = HashMap::new();
+        for &child in children.into_iter().flatten() {
+            let Ok((module, power)) = modules.get(child) else { continue };
+            let ModuleType::Storage { capacity, resource_type, passive_generation } = module.module_type else { continue };
+            extra_capacity.add(resource_type, capacity);
+            if module.active && passive_generation > 0.0 {
+                *generation.entry(resource_type).or_default() += passive_generation * power.map_or(1.0, |power| power.supply);
+            }
+        }
+
+        match storage {
+            Some(mut storage) => {
+                if storage.extra_capacity != extra_capacity || storage.generation != generation {
+                    storage.accrued.retain(|resource_type, _| generation.contains_key(resource_type));
+                    storage.extra_capacity = extra_capacity;
+                    storage.generation = generation;
+                }
+            }
+            None => {
+                commands.entity(entity).insert(ResourceStorage { extra_capacity, generation, ..default() });
+            }
+        }
+    }
+}
+
+/// System to pay out each base's passive income from its storage modules
+/// Income that would go over the storage cap is lost
+pub fn generate_passive_income(
+    time: Res<Time>,
+    mut bases: Query<(&mut MechanicalBase, &mut ResourceStorage)>,
+    mut player_resources: Option<ResMut<PlayerResources>>,
+) {
+    let delta = time.delta_seconds();
+    let generated: HashSet<ResourceType> = bases.iter().flat_map(|(_, storage)| storage.generation.keys().copied()).collect();
+    let player_capacity: HashMap<ResourceType, i32> = generated
+        .into_iter()
+        .map(|resource_type| {
+            let capacity = player_storage_capacity(bases.iter().map(|(base, storage)| (base, Some(storage))), resource_type);
+            (resource_type, capacity)
+        })
+        .collect();
+    for (mut base, mut storage) in bases.iter_mut() {
+        if storage.generation.is_empty() {
+            continue;
+        }
+
+        // Build up fractions of a unit until a whole one is ready
+        let storage = &mut *storage;
+        let mut payouts = Vec::new();
+        for (&resource_type, &rate) in &storage.generation {
+            let accrued = storage.accrued.entry(resource_type).or_default();
+            *accrued += rate * delta;
+            let whole = accrued.floor();
+            if whole >= 1.0 {
+                *accrued -= whole;
+                payouts.push((resource_type, whole as i32));
+            }
+        }
+
+        for (resource_type, amount) in payouts {
+            let capacity = match base.team {
+                Team::Player => player_capacity[&resource_type],
+                _ => storage.capacity(resource_type),
+            };
+            store_resources(&mut base, player_resources.as_deref_mut(), resource_type, amount, capacity);
+        }
+    }
+}
+
+// System to remove resource nodes that have been mined out
+fn remove_depleted_nodes(
+    mut commands: Commands,
+    nodes: Query<(Entity, &ResourceNode)>,
+) {
+    for (entity, node) in nodes.iter() {
+        if node.amount_remaining <= 0 {
+            commands.entity(entity).despawn_recursive();
+            info!("Resource node {:?} is depleted", entity);
+        }
+    }
 }
@@ -0,0 +1,398 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::sprite::Anchor;
+use std::collections::HashMap;
+use crate::components::base_modules::{BaseModule, ModuleType};
+use crate::components::building::Building;
+use crate::components::player::MechanicalBase;
+use crate::components::terrain::MapTile;
+use crate::components::unit::{Team, Unit};
+use crate::resources::map::plugin::MapChanged;
+use crate::resources::map_data::GameMap;
+use crate::states::game_state::{starting_new_match, GameState};
+use crate::systems::module_effects::ModulePower;
+use crate::systems::sensors::{is_concealed_from, Detected, SensorScan};
+use crate::systems::status_effects::StatusEffects;
+
+/// Sight radius of a unit, in map tiles
+pub const UNIT_SIGHT_RANGE: f32 = 5.0;
+
+/// Sight radius of a mechanical base before sensor modules, in map tiles
+pub const BASE_SIGHT_RANGE: f32 = 7.0;
+
+/// Sight radius of a building, in map tiles
+pub const BUILDING_SIGHT_RANGE: f32 = 4.0;
+
+/// Fog colour over tiles that have never been seen
+const UNSEEN_FOG: [u8; 4] = [0, 0, 0, 255];
+
+/// Fog colour over tiles that were seen before but are out of sight now
+const EXPLORED_FOG: [u8; 4] = [0, 0, 0, 150];
+
+/// Draw order of the fog overlay, above units and buildings
+const FOG_Z: f32 = 50.0;
+
+/// How much of the map a team can see
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileVisibility {
+    Unseen,   // Never been in sight
+    Explored, // Seen before, but nobody is looking now
+    Visible,  // In sight of a unit, base or building
+}
+
+/// Per-team visibility grid over the game map
+#[derive(Resource, Debug, Clone)]
+pub struct FogOfWar {
+    pub viewer: Team, // Team whose view is drawn and whose enemies get hidden
+    pub width: usize,
+    pub height: usize,
+    visible: HashMap<Team, Vec<bool>>,
+    explored: HashMap<Team, Vec<bool>>,
+    explored_on_new_map: HashMap<Team, Vec<bool>>, // Loaded from a save whose map is still being swapped in
+}
+
+impl Default for FogOfWar {
+    fn default() -> Self {
+        Self {
+            viewer: Team::Player,
+            width: 0,
+            height: 0,
+            visible: HashMap::new(),
+            explored: HashMap::new(),
+            explored_on_new_map: HashMap::new(),
+        }
+    }
+}
+
+impl FogOfWar {
+    /// Visibility of a grid tile for a team
+    pub fn tile_visibility(&self, team: Team, x: i32, y: i32) -> TileVisibility {
+        let Some(index) = self.index(x, y) else { return TileVisibility::Unseen };
+        if self.visible.get(&team).is_some_and(|tiles| tiles[index]) {
+            TileVisibility::Visible
+        } else if self.explored.get(&team).is_some_and(|tiles| tiles[index]) {
+            TileVisibility::Explored
+        } else {
+            TileVisibility::Unseen
+        }
+    }
+
+    /// Whether a team currently sees a grid tile
+    pub fn is_visible(&self, team: Team, x: i32, y: i32) -> bool {
+        self.tile_visibility(team, x, y) == TileVisibility::Visible
+    }
+
+    /// Whether a team has ever seen a grid tile
+    pub fn is_explored(&self, team: Team, x: i32, y: i32) -> bool {
+        self.tile_visibility(team, x, y) != TileVisibility::Unseen
+    }
+
+    /// Whether a team currently sees a world position
+    ///
+    /// Positions off the map are never fogged.
+    pub fn is_visible_at(&self, team: Team, game_map: &GameMap, position: Vec2) -> bool {
+        let (x, y) = game_map.world_to_grid(position);
+        !game_map.is_in_bounds(x, y) || self.is_visible(team, x, y)
+    }
+
+    /// Forget everything every team has seen
+    pub fn reset(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+        self.visible.clear();
+        self.explored.clear();
+        self.explored_on_new_map.clear();
+    }
+
+    /// Forget everything seen on the old map, keeping what a loaded save explored on the new one
+    pub fn reset_for_new_map(&mut self, width: usize, height: usize) {
+        let explored = std::mem::take(&mut self.explored_on_new_map);
+        self.reset(width, height);
+        self.restore_explored(explored);
+    }
+
+    /// Tiles each team has ever seen, row by row
+    pub fn explored(&self) -> &HashMap<Team, Vec<bool>> {
+        &self.explored
+    }
+
+    /// Replace what every team has explored, as when loading a save
+    ///
+    /// Grids that don't match the current map size are dropped.
+    pub fn restore_explored(&mut self, explored: HashMap<Team, Vec<bool>>) {
+        let size = self.width * self.height;
+        self.explored = explored.into_iter().filter(|(_, tiles)| tiles.len() == size).collect();
+    }
+
+    /// Keep explored tiles to restore once the map they were saved on is in place
+    pub fn restore_explored_on_new_map(&mut self, explored: HashMap<Team, Vec<bool>>) {
+        self.explored_on_new_map = explored;
+    }
+
+    // Index of a grid tile in the flat visibility vectors
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        (x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height)
+            .then(|| y as usize * self.width + x as usize)
+    }
+
+    // Start a new frame: nobody sees anything until the viewers are stamped back in
+    fn clear_visible(&mut self) {
+        for tiles in self.visible.values_mut() {
+            tiles.fill(false);
+        }
+    }
+
+    // Mark every tile whose centre lies within `range` world units of `center` as seen by a team
+    fn reveal(&mut self, team: Team, game_map: &GameMap, center: Vec2, range: f32) {
+        let size = self.width * self.height;
+        let (cx, cy) = game_map.world_to_grid(center);
+        let radius = (range / game_map.tile_size).ceil() as i32;
+
+        for y in (cy - radius)..=(cy + radius) {
+            for x in (cx - radius)..=(cx + radius) {
+                let Some(index) = self.index(x, y) else { continue };
+                if game_map.tile_center(x, y).distance(center) > range {
+                    continue;
+                }
+                self.visible.entry(team).or_insert_with(|| vec![false; size])[index] = true;
+                self.explored.entry(team).or_insert_with(|| vec![false; size])[index] = true;
+            }
+        }
+    }
+}
+
+/// Marker for the sprite drawing the fog over the map
+#[derive(Component)]
+pub struct FogOverlay;
+
+// Fog of war systems plugin
+pub struct FogOfWarPlugin;
+
+impl Plugin for FogOfWarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FogOfWar>()
+            .add_event::<MapChanged>()
+            .add_systems(OnEnter(GameState::Gameplay), reset_fog_of_war.run_if(starting_new_match))
+            .add_systems(
+                Update,
+                (
+                    update_fog_of_war,
+                    sync_map_tiles,
+                    hide_unseen_enemies,
+                    draw_fog_overlay,
+                ).chain().run_if(in_state(GameState::Gameplay))
+            );
+
+        info!("Fog of War Plugin initialized");
+    }
+}
+
+/// Sight radius in world units of something standing at `position`
+///
+/// High ground extends it and forest cover shortens it.
+pub fn sight_range(game_map: &GameMap, position: Vec2, range_in_tiles: f32) -> f32 {
+    let modifier = game_map.terrain_stats_at(position).map_or(1.0, |terrain| terrain.visibility_modifier);
+    range_in_tiles * game_map.tile_size * modifier
+}
+
+// System to start every match with the whole map unexplored
+fn reset_fog_of_war(
+    mut fog: ResMut<FogOfWar>,
+    game_map: Option<Res<GameMap>>,
+) {
+    if let Some(game_map) = game_map {
+        fog.reset(game_map.width, game_map.height);
+    }
+}
+
+// System to recompute what each team can see from its units, bases, buildings and sensor scans
+// The fog is only flagged as changed when some team's view actually differs from last frame
+// Nothing seen on a replaced map carries over, even if the new map is the same size
+pub fn update_fog_of_war(
+    mut fog: ResMut<FogOfWar>,
+    game_map: Option<Res<GameMap>>,
+    units: Query<(&Transform, &Unit)>,
+    bases: Query<(&Transform, &MechanicalBase, Option<&Children>)>,
+    buildings: Query<(&Transform, &Building, &Team)>,
+    modules: Query<(&BaseModule, Option<&ModulePower>)>,
+    scans: Query<&SensorScan>,
+    mut map_changed: EventReader<MapChanged>,
+) {
+    let map_replaced = map_changed.read().count() > 0;
+    let Some(game_map) = game_map else { return };
+    if map_replaced || fog.width != game_map.width || fog.height != game_map.height {
+        fog.reset_for_new_map(game_map.width, game_map.height);
+    }
+
+    let previous = fog.visible.clone();
+    let fog_state = fog.bypass_change_detection();
+    fog_state.clear_visible();
+
+    for (transform, unit) in units.iter() {
+        let position = transform.translation.truncate();
+        let range = UNIT_SIGHT_RANGE.max(unit.attack_range + 1.0);
+        fog_state.reveal(unit.team, &game_map, position, sight_range(&game_map, position, range));
+    }
+
+    for (transform, base, children) in bases.iter() {
+        // Active sensor modules push the base's sight out further, less so when short of power
+        let sensor_bonus = children
+            .into_iter()
+            .flatten()
+            .filter_map(|child| modules.get(*child).ok())
+            .filter(|(module, _)| module.active)
+            .filter_map(|(module, power)| match module.module_type {
+                ModuleType::Sensor { vision_range, .. } => Some(vision_range * power.map_or(1.0, |power| power.supply)),
+                _ => None,
+            })
+            .fold(0.0, f32::max);
+
+        let position = transform.translation.truncate();
+        let range = sight_range(&game_map, position, BASE_SIGHT_RANGE + sensor_bonus);
+        fog_state.reveal(base.team, &game_map, position, range);
+    }
+
+    for (transform, building, team) in buildings.iter() {
+        if building.is_completed {
+            let position = transform.translation.truncate();
+            fog_state.reveal(*team, &game_map, position, sight_range(&game_map, position, BUILDING_SIGHT_RANGE));
+        }
+    }
+
+    // Scans see over any terrain
+    for scan in scans.iter() {
+        fog_state.reveal(scan.team, &game_map, scan.position, scan.radius);
+    }
+
+    // Newly explored tiles are always visible too, so comparing what is in sight is enough
+    if fog.visible != previous {
+        fog.set_changed();
+    }
+}
+
+// System to mirror the viewer's visibility onto map tile entities
+fn sync_map_tiles(
+    fog: Res<FogOfWar>,
+    mut tiles: Query<&mut MapTile>,
+) {
+    for mut tile in tiles.iter_mut() {
+        let visibility = fog.tile_visibility(fog.viewer, tile.grid_x, tile.grid_y);
+        let is_visible = visibility == TileVisibility::Visible;
+        let is_explored = visibility != TileVisibility::Unseen;
+        if tile.is_visible != is_visible || tile.is_explored != is_explored {
+            tile.is_visible = is_visible;
+            tile.is_explored = is_explored;
+        }
+    }
+}
+
+// System to hide enemy units, bases and buildings the viewer can't see
+// Cloaked enemies stay hidden even in plain sight until the viewer's sensors pick them up
+pub fn hide_unseen_enemies(
+    fog: Res<FogOfWar>,
+    game_map: Option<Res<GameMap>>,
+    mut entities: Query<
+        (
+            &Transform,
+            &mut Visibility,
+            Option<&Unit>,
+            Option<&MechanicalBase>,
+            Option<&Team>,
+            Option<&StatusEffects>,
+            Option<&Detected>,
+        ),
+        Or<(With<Unit>, With<MechanicalBase>, With<Building>)>,
+    >,
+) {
+    let Some(game_map) = game_map else { return };
+
+    for (transform, mut visibility, unit, base, team, statuses, detected) in entities.iter_mut() {
+        let owner = unit.map(|unit| unit.team).or(base.map(|base| base.team)).or(team.copied());
+        let concealed = is_concealed_from(fog.viewer, statuses, detected);
+        let seen = owner == Some(fog.viewer)
+            || (!concealed && fog.is_visible_at(fog.viewer, &game_map, transform.translation.truncate()));
+
+        let wanted = if seen { Visibility::Inherited } else { Visibility::Hidden };
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+}
+
+// System to paint the viewer's fog into a texture stretched over the map
+// The texture is only repainted when the fog changes, and rebuilt whenever the map is replaced
+fn draw_fog_overlay(
+    mut commands: Commands,
+    fog: Res<FogOfWar>,
+    game_map: Option<Res<GameMap>>,
+    images: Option<ResMut<Assets<Image>>>,
+    overlay: Query<(Entity, &Handle<Image>), With<FogOverlay>>,
+    mut map_changed: EventReader<MapChanged>,
+) {
+    let map_replaced = map_changed.read().count() > 0;
+
+    // Headless apps have nothing to draw on
+    let (Some(game_map), Some(mut images)) = (game_map, images) else { return };
+    if fog.width == 0 || fog.height == 0 {
+        return;
+    }
+
+    if let Ok((entity, handle)) = overlay.get_single() {
+        let resized = images
+            .get(handle)
+            .is_some_and(|image| image.width() as usize != fog.width || image.height() as usize != fog.height);
+        if !map_replaced && !resized {
+            if fog.is_changed() {
+                if let Some(image) = images.get_mut(handle) {
+                    paint_fog(&fog, image);
+                }
+            }
+            return;
+        }
+
+        // The old texture no longer fits the map, start over with a new one
+        images.remove(handle);
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let mut image = Image::new_fill(
+        Extent3d { width: fog.width as u32, height: fog.height as u32, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        &UNSEEN_FOG,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    paint_fog(&fog, &mut image);
+    let map_size = Vec2::new(game_map.width as f32, game_map.height as f32) * game_map.tile_size;
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(map_size),
+                anchor: Anchor::BottomLeft,
+                ..default()
+            },
+            texture: images.add(image),
+            transform: Transform::from_xyz(0.0, 0.0, FOG_Z),
+            ..default()
+        },
+        FogOverlay,
+        Name::new("Fog of War"),
+    ));
+}
+
+// Colour every texel of the overlay after the viewer's visibility of its tile
+fn paint_fog(fog: &FogOfWar, image: &mut Image) {
+    for y in 0..fog.height {
+        for x in 0..fog.width {
+            let color = match fog.tile_visibility(fog.viewer, x as i32, y as i32) {
+                TileVisibility::Unseen => UNSEEN_FOG,
+                TileVisibility::Explored => EXPLORED_FOG,
+                TileVisibility::Visible => [0, 0, 0, 0],
+            };
+            // Texture rows run top to bottom while grid rows run bottom to top
+            let index = ((fog.height - 1 - y) * fog.width + x) * 4;
+            image.data[index..index + 4].copy_from_slice(&color);
+        }
+    }
+}
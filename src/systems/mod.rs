@@ -4,21 +4,42 @@
 pub mod ai;
 pub mod base_initialization;
 pub mod base_movement;
+pub mod building_catalog;
 pub mod camera;
 pub mod camera_manager;
 pub mod combat;
+pub mod damage;
 pub mod economy;
+pub mod fog_of_war;
 pub mod input;
+pub mod module_catalog;
 pub mod module_effects;
 pub mod movement;
 pub mod production;
+pub mod save_load;
+pub mod sensors;
+pub mod status_effects;
 pub mod ui;
+pub mod unit_catalog;
+pub mod victory;
 
 // Re-export commonly used items
+pub use ai::AIPlugin;
 pub use base_initialization::BaseInitializationPlugin;
 pub use base_movement::BaseMovePlugin;
+pub use building_catalog::BuildingCatalogPlugin;
 pub use camera::CameraPlugin;
 pub use camera_manager::CameraManagerPlugin;
+pub use combat::CombatPlugin;
+pub use damage::DamagePlugin;
+pub use economy::EconomyPlugin;
+pub use fog_of_war::FogOfWarPlugin;
+pub use module_catalog::ModuleCatalogPlugin;
 pub use module_effects::ModuleEffectsPlugin;
 pub use movement::MovementPlugin;
 pub use production::ProductionPlugin;
+pub use save_load::SaveLoadPlugin;
+pub use sensors::SensorPlugin;
+pub use status_effects::StatusEffectsPlugin;
+pub use unit_catalog::UnitCatalogPlugin;
+pub use victory::VictoryPlugin;
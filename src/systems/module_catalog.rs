@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use crate::components::base_modules::{AttachableModule, BaseModule, ModuleType};
+use crate::components::economy::ResourceWallet;
+use crate::components::unit::Team;
+use crate::utils::ron_asset::{RonAsset, RonAssetLoader};
+
+/// Path of the module catalog shipped with the game, relative to the assets folder
+pub const MODULE_CATALOG_PATH: &str = "data/catalog.modules.ron";
+
+/// Built-in copy of the module catalog, used until the asset finishes loading
+const BUILTIN_MODULE_CATALOG: &str = include_str!("../../assets/data/catalog.modules.ron");
+
+/// Stats, cost and build time for a single base module
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ModuleDefinition {
+    pub module_type: ModuleType,
+    pub health: f32,
+    #[serde(default)]
+    pub power_consumption: f32,
+    #[serde(default)]
+    pub cost: ResourceWallet,      // Resources charged when construction starts
+    pub build_time: f32,           // Seconds from ordering the module to it working
+    #[serde(default = "default_size")]
+    pub size: f32,
+}
+
+fn default_size() -> f32 {
+    15.0
+}
+
+impl ModuleDefinition {
+    /// The working module for a team, as it is once construction finishes
+    pub fn to_base_module(&self, team: Team) -> BaseModule {
+        BaseModule {
+            module_type: self.module_type.clone(),
+            health: self.health,
+            max_health: self.health,
+            power_consumption: self.power_consumption,
+            active: true,
+            team,
+        }
+    }
+
+    /// The module as it is while being built
+    pub fn to_attachable(&self) -> AttachableModule {
+        AttachableModule {
+            module_type: self.module_type.clone(),
+            size: Vec2::splat(self.size),
+            build_time: self.build_time,
+            build_progress: 0.0,
+        }
+    }
+}
+
+/// Data-driven base module stats, loaded from `assets/data/*.modules.ron`
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+pub struct ModuleCatalog {
+    pub version: u32,
+    pub modules: HashMap<String, ModuleDefinition>,
+}
+
+impl RonAsset for ModuleCatalog {
+    fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        for (name, definition) in &self.modules {
+            if name.trim().is_empty() {
+                problems.push("module names can't be empty".to_string());
+            }
+            if definition.health <= 0.0 {
+                problems.push(format!("{}: health must be positive (got {})", name, definition.health));
+            }
+            if definition.build_time <= 0.0 {
+                problems.push(format!("{}: build_time must be positive (got {})", name, definition.build_time));
+            }
+            if definition.power_consumption < 0.0 {
+                problems.push(format!(
+                    "{}: power_consumption can't be negative (got {})",
+                    name, definition.power_consumption
+                ));
+            }
+            for (resource_type, amount) in definition.cost.iter() {
+                if amount < 0 {
+                    problems.push(format!("{}: cost of {:?} is negative ({})", name, resource_type, amount));
+                }
+            }
+        }
+
+        if problems.is_empty() { Ok(()) } else { Err(problems) }
+    }
+}
+
+impl Default for ModuleCatalog {
+    fn default() -> Self {
+        ron::from_str(BUILTIN_MODULE_CATALOG).expect("built-in module catalog should be valid RON")
+    }
+}
+
+impl ModuleCatalog {
+    /// Definition for a module by name, if the catalog has one
+    pub fn get(&self, name: &str) -> Option<&ModuleDefinition> {
+        self.modules.get(name)
+    }
+
+    /// Every module name, sorted so menus list them in a stable order
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.modules.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+
+    /// Overlay another catalog on top of this one, keeping entries it doesn't mention
+    pub fn merge(&mut self, other: &ModuleCatalog) {
+        self.version = other.version;
+        for (name, definition) in &other.modules {
+            self.modules.insert(name.clone(), definition.clone());
+        }
+    }
+}
+
+/// The module catalog currently in effect
+#[derive(Resource, Default)]
+pub struct ModuleDefinitions {
+    pub catalog: ModuleCatalog,
+    pub handle: Option<Handle<ModuleCatalog>>,
+}
+
+/// Plugin that loads the module catalog asset and keeps `ModuleDefinitions` in sync with it
+pub struct ModuleCatalogPlugin;
+
+impl Plugin for ModuleCatalogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ModuleDefinitions>()
+           .init_asset::<ModuleCatalog>()
+           .register_asset_loader(RonAssetLoader::<ModuleCatalog>::new(&["modules.ron"]))
+           .add_systems(Startup, load_module_catalog)
+           .add_systems(Update, sync_module_catalog);
+    }
+}
+
+// Start loading the module catalog from disk
+fn load_module_catalog(
+    asset_server: Res<AssetServer>,
+    mut definitions: ResMut<ModuleDefinitions>,
+) {
+    definitions.handle = Some(asset_server.load(MODULE_CATALOG_PATH));
+}
+
+// Copy the catalog into `ModuleDefinitions` whenever it is loaded or edited on disk
+fn sync_module_catalog(
+    mut events: EventReader<AssetEvent<ModuleCatalog>>,
+    catalogs: Res<Assets<ModuleCatalog>>,
+    mut definitions: ResMut<ModuleDefinitions>,
+) {
+    for event in events.read() {
+        let id = match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => *id,
+            _ => continue,
+        };
+
+        if definitions.handle.as_ref().map(|handle| handle.id()) != Some(id) {
+            continue;
+        }
+
+        if let Some(catalog) = catalogs.get(id) {
+            info!("Module catalog v{} loaded ({} modules)", catalog.version, catalog.modules.len());
+            definitions.catalog.merge(catalog);
+        }
+    }
+}
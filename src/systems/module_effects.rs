@@ -2,87 +2,131 @@ use bevy::{
     prelude::*,
     reflect::Reflect
 };
+use std::collections::HashMap;
 use std::time::Duration;
+use crate::components::economy::ResourceWallet;
 use crate::components::player::MechanicalBase;
 use crate::components::base_modules::{
-    BaseModule, ModuleType, DamageType, UtilityEffect, ResourceType
+    BaseModule, ModuleCategory, ModuleType, DamageType, UtilityEffect
 };
-use crate::components::unit::Team;
+use crate::components::unit::{Team, Unit};
+use crate::resources::map_data::GameMap;
+use crate::systems::combat::{is_hostile, BaseDamageEvent};
+use crate::systems::damage::{ArmorClass, DamageResult, DamageRules, DamageTable, DefenseProfile};
+use crate::systems::movement::MoveTarget;
+use crate::systems::production::BASE_QUEUE_SLOTS;
+use crate::systems::status_effects::{effect_color, StatusEffects};
+use crate::utils::pathfinding::MovePath;
+use serde::{Deserialize, Serialize};
 
-/// System to manage module activation/deactivation based on power availability
+/// System to share each base's power out between its modules
+///
+/// Energy modules that are switched on add to the base's own output. The rest is handed to
+/// the other module categories in the base's priority order; when a category can't be fully
+/// powered its modules share what is left and run at reduced strength (a brownout). Modules
+/// that would get less than `BROWNOUT_CUTOFF` of their power stall instead, leaving the power
+/// for lower priorities. Modules damaged below `MODULE_DISABLE_THRESHOLD` drop out as if
+/// switched off until they are repaired.
 pub fn manage_module_power(
-    mut bases: Query<(&mut MechanicalBase, &Children)>,
-    mut modules: Query<(&mut BaseModule, &mut Sprite)>,
+    mut commands: Commands,
+    bases: Query<(&Children, Option<&PowerPriority>), With<MechanicalBase>>,
+    mut modules: Query<(Entity, &mut BaseModule, Option<&mut ModulePower>)>,
 ) {
-    for (base, children) in &mut bases {
-        let mut available_power = base.power_output - base.power_consumed;
-        
-        // First pass: Deactivate all modules to start with a clean slate
+    let default_priority = PowerPriority::default();
+
+    for (children, priority) in &bases {
+        let priority = priority.unwrap_or(&default_priority);
+
+        // Add up what the base generates and what each category asks for
+        let mut available_power = BASE_POWER_OUTPUT;
+        let mut demand: HashMap<ModuleCategory, f32> = HashMap::new();
         for &child in children.iter() {
-            if let Ok((mut module, _)) = modules.get_mut(child) {
-                module.active = false;
+            let Ok((_, module, power)) = modules.get(child) else { continue };
+            if !power.is_none_or(|power| power.switched_on) || module.is_disabled() {
+                continue;
+            }
+            match &module.module_type {
+                ModuleType::Energy { power_output, .. } => available_power += *power_output - module.power_consumption,
+                module_type => *demand.entry(module_type.category()).or_default() += module.power_consumption,
             }
         }
-        
-        // Second pass: Activate modules in priority order until we run out of power
-        // Priority order: 1. Defense, 2. Movement, 3. Sensors, 4. Weapons, 5. Production, 6. Storage, 7. Utility
-        let module_order = [
-            ModuleType::Defense { armor_bonus: 0.0, shield_strength: 0.0, shield_recharge_rate: 0.0, damage_resistance: 0.0 },
-            ModuleType::Movement { speed_modifier: 0.0, efficiency: 0.0, terrain_penalty_reduction: 0.0 },
-            ModuleType::Sensor { detection_radius: 0.0, stealth_detection: 0.0, vision_range: 0.0, scan_cooldown: 0.0 },
-            ModuleType::Weapon { damage: 0.0, attack_speed: 0.0, range: 0.0, damage_type: DamageType::Kinetic, splash_radius: 0.0, tracking_speed: 0.0 },
-            ModuleType::Production { build_speed: 0.0, queue_slots: 0, cost_reduction: 0.0, experience_gain: 0.0 },
-            ModuleType::Storage { capacity: 0, resource_type: ResourceType::Basic, passive_generation: 0.0 },
-            ModuleType::Utility { effect_type: UtilityEffect::Repair, effect_strength: 0.0, area_of_effect: 0.0, cooldown: 0.0 },
-        ];
-        
-        for module_type in module_order.iter() {
-            for &child in children.iter() {
-                if available_power <= 0.0 { break; }
-                
-                if let Ok((mut module, mut sprite)) = modules.get_mut(child) {
-                    if !module.active && std::mem::discriminant(&module.module_type) == std::mem::discriminant(module_type) {
-                        if available_power >= module.power_consumption {
-                            module.active = true;
-                            available_power -= module.power_consumption;
-                            // Update visual state - use white for active modules
-                            sprite.color = Color::WHITE;
-                        } else {
-                            // Not enough power, keep inactive - use gray (sRGB 0.5, 0.5, 0.5) for inactive modules
-                            module.active = false;
-                            sprite.color = Color::srgb(0.5, 0.5, 0.5);
-                        }
+
+        // Hand the power out in priority order
+        let mut supply: HashMap<ModuleCategory, f32> = HashMap::new();
+        for &category in &priority.order {
+            let needed = demand.get(&category).copied().unwrap_or(0.0);
+            let share = if needed > 0.0 { (available_power.max(0.0) / needed).min(1.0) } else { 1.0 };
+            let share = if share < BROWNOUT_CUTOFF { 0.0 } else { share };
+            available_power -= needed * share;
+            supply.insert(category, share);
+        }
+
+        for &child in children.iter() {
+            let Ok((entity, mut module, power)) = modules.get_mut(child) else { continue };
+            let switched_on = power.as_ref().is_none_or(|power| power.switched_on);
+            let module_supply = match module.module_type.category() {
+                _ if !switched_on || module.is_disabled() => 0.0,
+                ModuleCategory::Energy => 1.0,
+                category => supply.get(&category).copied().unwrap_or(0.0),
+            };
+
+            let active = module_supply > 0.0;
+            if module.active != active {
+                module.active = active;
+            }
+            match power {
+                Some(mut power) => {
+                    if power.supply != module_supply {
+                        power.supply = module_supply;
                     }
                 }
+                None => {
+                    commands.entity(entity).insert(ModulePower { switched_on, supply: module_supply });
+                }
             }
         }
     }
 }
 
+/// System to shade modules by how much power they get, whenever that changes
+pub fn tint_modules_by_power(
+    mut modules: Query<(&ModulePower, &mut Sprite), Changed<ModulePower>>,
+) {
+    for (power, mut sprite) in modules.iter_mut() {
+        // Fade from gray (no power) to white (fully powered)
+        let brightness = 0.5 + 0.5 * power.supply;
+        sprite.color = Color::srgb(brightness, brightness, brightness);
+    }
+}
+
 /// System to apply module effects to the base each frame
 pub fn apply_module_effects(
+    mut commands: Commands,
     time: Res<Time>,
-    mut bases: Query<(&mut MechanicalBase, &Children)>,
-    modules: Query<(&BaseModule, &Sprite)>,
+    mut bases: Query<(Entity, &mut MechanicalBase, &Children, Option<&mut BaseDefenses>, Option<&mut ProductionBonuses>)>,
+    modules: Query<(&BaseModule, Option<&ModulePower>)>,
 ) {
     let _delta = time.delta_seconds();
     
-    for (mut base, children) in &mut bases {
+    for (entity, mut base, children, defenses, production) in &mut bases {
         // Reset base stats that are modified by modules
         let mut effective_stats = BaseStats::default();
         
         // Apply effects from all child modules
         for &child in children.iter() {
-            if let Ok((module, _)) = modules.get(child) {
+            if let Ok((module, power)) = modules.get(child) {
                 if !module.active { continue; }
                 
+                // Browned-out modules only give part of their effect
+                let supply = power.map_or(1.0, |power| power.supply);
+                
                 match &module.module_type {
                     ModuleType::Movement {
                         speed_modifier,
                         efficiency: _,
                         terrain_penalty_reduction,
                     } => {
-                        effective_stats.speed_multiplier *= speed_modifier;
+                        effective_stats.speed_multiplier *= 1.0 + (speed_modifier - 1.0) * supply;
                         effective_stats.terrain_penalty_reduction = effective_stats
                             .terrain_penalty_reduction
                             .max(*terrain_penalty_reduction);
@@ -93,13 +137,13 @@ pub fn apply_module_effects(
                         shield_recharge_rate,
                         damage_resistance,
                     } => {
-                        effective_stats.armor += *armor_bonus;
+                        effective_stats.armor += *armor_bonus * supply;
                         effective_stats.max_shield = effective_stats
                             .max_shield
-                            .max(*shield_strength);
-                        effective_stats.shield_recharge_rate += *shield_recharge_rate;
+                            .max(*shield_strength * supply);
+                        effective_stats.shield_recharge_rate += *shield_recharge_rate * supply;
                         effective_stats.damage_resistance = (effective_stats.damage_resistance
-                            + *damage_resistance)
+                            + *damage_resistance * supply)
                             .min(0.9); // Cap at 90% damage resistance
                     }
                     ModuleType::Energy {
@@ -117,13 +161,24 @@ pub fn apply_module_effects(
                         effective_stats.has_weapons = true;
                     }
                     ModuleType::Sensor { .. } => {
-                        // Sensor effects are handled in the sensor system
+                        // Sensor vision, scans and stealth detection are handled in the sensor systems
                     }
-                    ModuleType::Production { .. } => {
-                        // Production effects are handled in the production system
+                    ModuleType::Production {
+                        build_speed,
+                        queue_slots,
+                        cost_reduction,
+                        experience_gain,
+                    } => {
+                        // Applied to unit production by the production system
+                        effective_stats.production.build_speed *= 1.0 + (build_speed - 1.0) * supply;
+                        effective_stats.production.queue_slots = effective_stats.production.queue_slots.saturating_add(*queue_slots);
+                        effective_stats.production.cost_reduction = (effective_stats.production.cost_reduction
+                            + *cost_reduction * supply)
+                            .min(MAX_COST_REDUCTION);
+                        effective_stats.production.experience_gain += *experience_gain * supply;
                     }
                     ModuleType::Storage { .. } => {
-                        // Storage effects are handled in the resource system
+                        // Storage caps and passive income are handled in the economy systems
                     }
                     ModuleType::Utility { .. } => {
                         // Utility effects are handled in the utility system
@@ -132,7 +187,7 @@ pub fn apply_module_effects(
                 
                 // Apply module power consumption
                 if module.active {
-                    effective_stats.power_consumed += module.power_consumption;
+                    effective_stats.power_consumed += module.power_consumption * supply;
                 }
             }
         }
@@ -147,6 +202,45 @@ pub fn apply_module_effects(
         
         // Ensure we don't have more power than capacity
         base.power_consumed = base.power_consumed.min(base.max_power);
+
+        // Keep the defensive stats on the base so hits can be resolved against them
+        match defenses {
+            Some(mut defenses) => defenses.set_stats(&effective_stats),
+            None => {
+                let mut defenses = BaseDefenses::default();
+                defenses.set_stats(&effective_stats);
+                defenses.shield = defenses.max_shield; // Shields come online fully charged
+                commands.entity(entity).insert(defenses);
+            }
+        }
+
+        // Likewise the production bonuses, for the base's buildings to read
+        match production {
+            Some(mut production) => {
+                if *production != effective_stats.production {
+                    *production = effective_stats.production;
+                }
+            }
+            None => {
+                commands.entity(entity).insert(effective_stats.production);
+            }
+        }
+    }
+}
+
+/// System to recharge base shields once they haven't been hit for a while
+pub fn recharge_base_shields(
+    time: Res<Time>,
+    mut bases: Query<&mut BaseDefenses>,
+) {
+    let delta = time.delta_seconds();
+
+    for mut defenses in bases.iter_mut() {
+        if defenses.recharge_delay > 0.0 {
+            defenses.recharge_delay = (defenses.recharge_delay - delta).max(0.0);
+        } else if defenses.shield < defenses.max_shield {
+            defenses.shield = (defenses.shield + defenses.shield_recharge_rate * delta).min(defenses.max_shield);
+        }
     }
 }
 
@@ -155,13 +249,13 @@ pub fn handle_weapon_modules(
     time: Res<Time>,
     mut commands: Commands,
     bases: Query<(&Transform, &Team, &Children), With<MechanicalBase>>,
-    mut weapon_modules: Query<(&mut BaseModule, &GlobalTransform, &mut Cooldown)>,
+    mut weapon_modules: Query<(&mut BaseModule, &GlobalTransform, &mut Cooldown, Option<&ModulePower>)>,
     mut targets: Query<(&Transform, &mut Health, &Team), Without<MechanicalBase>>,
     asset_server: Res<AssetServer>,
 ) {
     for (_base_transform, team, children) in &bases {
         for &child in children.iter() {
-            if let Ok((module, module_transform, mut cooldown)) = weapon_modules.get_mut(child) {
+            if let Ok((module, module_transform, mut cooldown, power)) = weapon_modules.get_mut(child) {
                 // Skip if module is not active or not a weapon
                 if !module.active { continue; }
                 if let ModuleType::Weapon {
@@ -172,8 +266,8 @@ pub fn handle_weapon_modules(
                     splash_radius,
                     tracking_speed: _,
                 } = &module.module_type {
-                    // Update cooldown
-                    cooldown.timer.tick(time.delta());
+                    // Update cooldown, more slowly when the weapon is short of power
+                    cooldown.timer.tick(time.delta().mul_f32(power.map_or(1.0, |power| power.supply)));
                     
                     if cooldown.timer.finished() {
                         // Find closest valid target
@@ -240,8 +334,11 @@ pub fn update_projectiles(
             &mut Projectile,
             Option<&mut Lifetime>
         )>,
-        Query<(&Transform, &mut Health, &Team)>
+        Query<(&Transform, &mut Health, &Team, Option<&ArmorClass>)>
     )>,
+    bases: Query<(Entity, &Transform), (With<MechanicalBase>, Without<Projectile>)>,
+    damage_rules: Res<DamageRules>,
+    mut base_damage: EventWriter<BaseDamageEvent>,
 ) {
     // Update projectile positions and lifetimes
     let mut projectiles_to_despawn = Vec::new();
@@ -273,7 +370,7 @@ pub fn update_projectiles(
             
             if distance_moved >= distance_to_target {
                 // We've reached the target, store damage info for later processing
-                damage_events.push((transform.translation, projectile.splash_radius, projectile.damage, projectile.damage_type));
+                damage_events.push((transform.translation, projectile.source, projectile.splash_radius, projectile.damage, projectile.damage_type));
                 projectiles_to_despawn.push(entity);
             }
         }
@@ -282,8 +379,22 @@ pub fn update_projectiles(
     // Then, apply damage to targets
     {
         let mut targets = query_set.p1();
-        for (impact_pos, splash_radius, damage, damage_type) in damage_events {
-            for (target_transform, mut health, _) in targets.iter_mut() {
+        for (impact_pos, source, splash_radius, damage, damage_type) in damage_events {
+            // Bases caught in the blast resolve the hit against their own defenses
+            for (base, base_transform) in bases.iter() {
+                let distance = impact_pos.distance(base_transform.translation);
+                if distance <= splash_radius {
+                    base_damage.send(BaseDamageEvent {
+                        base,
+                        amount: damage * (1.0 - (distance / splash_radius).min(1.0)),
+                        damage_type,
+                        attacker: None,
+                        origin: source.truncate(),
+                    });
+                }
+            }
+
+            for (target_transform, mut health, _, armor_class) in targets.iter_mut() {
                 let distance = impact_pos.distance(target_transform.translation);
                 
                 // Apply splash damage if in range
@@ -292,16 +403,13 @@ pub fn update_projectiles(
                     let damage_multiplier = 1.0 - (distance / splash_radius).min(1.0);
                     let actual_damage = damage * damage_multiplier;
                     
-                    // Apply damage based on damage type
-                    match damage_type {
-                        DamageType::Kinetic => {
-                            health.current -= actual_damage;
-                        }
-                        // Add other damage type effects here
-                        _ => {
-                            health.current -= actual_damage;
-                        }
-                    }
+                    // Resolve damage through shields, armor and the damage type matrix
+                    damage_rules.table.apply(
+                        &mut health,
+                        actual_damage,
+                        damage_type,
+                        armor_class.copied().unwrap_or_default(),
+                    );
                     
                     // Visual feedback for hit
                     commands.spawn((
@@ -329,13 +437,116 @@ pub fn update_projectiles(
     }
 }
 
-/// System to handle utility module effects
+/// System to fire utility modules, applying their effect to everything in range
+///
+/// Each module pulses once per `cooldown`, reaching `area_of_effect` around itself. Buffs land
+/// on allies and debuffs on enemies, and last until just after the next pulse so they hold while
+/// a target stays in range. Stun lasts `effect_strength` seconds instead, and Teleport jumps
+/// allies with a move order up to `effect_strength` toward their destination, stopping short of
+/// impassable terrain.
 pub fn handle_utility_modules(
-    _time: Res<Time>,
-    _commands: Commands,
-    _bases: Query<(&Transform, &Team, &Children), With<MechanicalBase>>,
-    _modules: Query<(&BaseModule, &GlobalTransform, &mut Cooldown)>,
+    time: Res<Time>,
+    mut commands: Commands,
+    game_map: Option<Res<GameMap>>,
+    bases: Query<(&MechanicalBase, &Children)>,
+    mut modules: Query<(&BaseModule, &GlobalTransform, Option<&mut Cooldown>, Option<&ModulePower>)>,
+    mut targets: Query<
+        (Entity, &mut Transform, Option<&Unit>, Option<&MechanicalBase>, Option<&MoveTarget>, Option<&mut StatusEffects>),
+        Without<BaseModule>,
+    >,
 ) {
+    // Targets without any status effects yet, gathered so two pulses in a frame don't overwrite each other
+    let mut new_statuses: HashMap<Entity, StatusEffects> = HashMap::new();
+
+    for (base, children) in &bases {
+        for &child in children.iter() {
+            let Ok((module, module_transform, cooldown, power)) = modules.get_mut(child) else { continue };
+            let ModuleType::Utility { effect_type, effect_strength, area_of_effect, cooldown: pulse_interval } = module.module_type else {
+                continue;
+            };
+            if !module.active {
+                continue;
+            }
+
+            let pulse_interval = pulse_interval.max(MIN_UTILITY_COOLDOWN);
+            let Some(mut cooldown) = cooldown else {
+                commands.entity(child).insert(Cooldown { timer: Timer::from_seconds(pulse_interval, TimerMode::Once) });
+                continue;
+            };
+
+            // Short of power, the module takes longer to recharge
+            cooldown.timer.tick(time.delta().mul_f32(power.map_or(1.0, |power| power.supply)));
+            if !cooldown.timer.finished() {
+                continue;
+            }
+            cooldown.timer.set_duration(Duration::from_secs_f32(pulse_interval));
+            cooldown.timer.reset();
+
+            let center = module_transform.translation().truncate();
+            let duration = match effect_type {
+                UtilityEffect::Stun => effect_strength,
+                _ => pulse_interval + STATUS_GRACE,
+            };
+
+            for (entity, mut transform, unit, target_base, move_target, statuses) in targets.iter_mut() {
+                let Some(team) = unit.map(|unit| unit.team).or(target_base.map(|base| base.team)) else { continue };
+                let position = transform.translation.truncate();
+                if position.distance(center) > area_of_effect {
+                    continue;
+                }
+
+                let in_scope = if effect_type.targets_enemies() { is_hostile(base.team, team) } else { team == base.team };
+                let fits = match effect_type {
+                    UtilityEffect::Repair => target_base.is_some(),
+                    _ => unit.is_some(),
+                };
+                if !in_scope || !fits {
+                    continue;
+                }
+
+                if effect_type == UtilityEffect::Teleport {
+                    // Jump toward the destination and plan a fresh path from there
+                    let Some(target) = move_target else { continue };
+                    let offset = target.position - position;
+                    let mut landing = position + offset.clamp_length_max(effect_strength);
+                    if let Some(game_map) = game_map.as_deref() {
+                        landing = game_map.last_passable_point(position, landing);
+                    }
+                    transform.translation = landing.extend(transform.translation.z);
+                    commands.entity(entity).remove::<MovePath>();
+                    continue;
+                }
+
+                match statuses {
+                    Some(mut statuses) => statuses.apply(effect_type, effect_strength, duration, child),
+                    None => new_statuses.entry(entity).or_default().apply(effect_type, effect_strength, duration, child),
+                }
+            }
+
+            // Leave a fading pulse on the map showing the area covered
+            commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: effect_color(effect_type).with_alpha(0.3),
+                        custom_size: Some(Vec2::splat(area_of_effect * 2.0)),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(center.extend(0.05)),
+                    ..default()
+                },
+                Effect {
+                    effect_type,
+                    position: center.extend(0.05),
+                    duration: PULSE_DURATION,
+                    strength: effect_strength,
+                },
+            ));
+        }
+    }
+
+    for (entity, statuses) in new_statuses {
+        commands.entity(entity).insert(statuses);
+    }
 }
 
 // Helper components and structs
@@ -377,7 +588,7 @@ pub struct Effect {
 }
 
 /// Health component for damageable entities
-#[derive(Component, Reflect)]
+#[derive(Component, Clone, Debug, Reflect, Serialize, Deserialize)]
 #[reflect(Component)]
 pub struct Health {
     pub current: f32,
@@ -385,10 +596,192 @@ pub struct Health {
     pub shield: f32,
     pub max_shield: f32,
     pub armor: f32,
+    pub damage_resistance: f32,
+}
+
+/// Power a base generates on its own, before energy modules
+pub const BASE_POWER_OUTPUT: f32 = 100.0;
+
+/// Share of its power a module needs to keep working at all during a brownout
+pub const BROWNOUT_CUTOFF: f32 = 0.25;
+
+/// Shortest time between two pulses of a utility module
+const MIN_UTILITY_COOLDOWN: f32 = 0.1;
+
+/// Extra time a utility effect lasts past the next pulse, so it doesn't flicker between pulses
+const STATUS_GRACE: f32 = 0.5;
+
+/// How long the pulse a utility module leaves on the map takes to fade
+const PULSE_DURATION: f32 = 0.6;
+
+/// Named power priority presets a base can switch between
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum PowerProfile {
+    #[default]
+    Balanced, // Defenses and movement first
+    Sprint,   // Everything into the engines
+    Siege,    // Weapons first
+    Custom,   // Edited by hand
+}
+
+impl PowerProfile {
+    /// Presets the player can cycle through
+    pub const PRESETS: [PowerProfile; 3] = [PowerProfile::Balanced, PowerProfile::Sprint, PowerProfile::Siege];
+
+    /// Order the preset powers module categories in, most important first
+    pub fn order(&self) -> Vec<ModuleCategory> {
+        use ModuleCategory::*;
+        match self {
+            PowerProfile::Balanced | PowerProfile::Custom => vec![Defense, Movement, Sensor, Weapon, Production, Storage, Utility],
+            PowerProfile::Sprint => vec![Movement, Sensor, Defense, Utility, Weapon, Production, Storage],
+            PowerProfile::Siege => vec![Weapon, Defense, Sensor, Production, Utility, Movement, Storage],
+        }
+    }
+
+    /// The preset after this one, wrapping around; custom orders go back to the first preset
+    pub fn next(&self) -> PowerProfile {
+        let index = Self::PRESETS.iter().position(|preset| preset == self);
+        index.map_or(Self::PRESETS[0], |index| Self::PRESETS[(index + 1) % Self::PRESETS.len()])
+    }
+}
+
+/// Order a base powers its module categories in when there isn't enough to go round
+#[derive(Component, Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Component)]
+pub struct PowerPriority {
+    pub profile: PowerProfile,
+    pub order: Vec<ModuleCategory>, // Most important first; energy modules are always powered
+}
+
+impl Default for PowerPriority {
+    fn default() -> Self {
+        Self::from_profile(PowerProfile::default())
+    }
+}
+
+impl PowerPriority {
+    /// Priorities for one of the presets
+    pub fn from_profile(profile: PowerProfile) -> Self {
+        Self { profile, order: profile.order() }
+    }
+
+    /// Move a category one place up the order, turning this into a custom profile
+    pub fn raise(&mut self, category: ModuleCategory) {
+        let Some(index) = self.order.iter().position(|&entry| entry == category) else { return };
+        if index > 0 {
+            self.order.swap(index, index - 1);
+            self.profile = PowerProfile::Custom;
+        }
+    }
+}
+
+/// Power state of a single module
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Component)]
+pub struct ModulePower {
+    pub switched_on: bool, // Set by the player; switched-off modules never draw power
+    pub supply: f32,       // Share of the module's power it is getting (0.0-1.0)
+}
+
+impl Default for ModulePower {
+    fn default() -> Self {
+        Self { switched_on: true, supply: 1.0 }
+    }
+}
+
+/// Seconds a base's shields wait after a hit before they start recharging
+pub const SHIELD_RECHARGE_DELAY: f32 = 4.0;
+
+/// Defensive layers of a mechanical base, fed by its defense modules
+///
+/// Hits are resolved against these before the base's hull: resistance takes a share off,
+/// shields soak up what is left, and armor removes a flat amount from what gets through.
+#[derive(Component, Debug, Clone, Default, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Component)]
+pub struct BaseDefenses {
+    pub armor: f32,
+    pub shield: f32,
+    pub max_shield: f32,
+    pub shield_recharge_rate: f32, // Shield points per second
+    pub damage_resistance: f32,    // Share of incoming damage ignored (0.0-0.9)
+    pub recharge_delay: f32,       // Seconds until the shields start recharging again
+}
+
+impl BaseDefenses {
+    /// Defensive profile to resolve a hit against
+    pub fn profile(&self, armor_class: ArmorClass) -> DefenseProfile {
+        DefenseProfile {
+            armor_class,
+            armor: self.armor,
+            shield: self.shield,
+            resistance: self.damage_resistance,
+        }
+    }
+
+    /// Resolve a hit, draining the shields and holding off their recharge
+    ///
+    /// Returns how the hit was split; the caller applies the hull damage.
+    pub fn take_hit(&mut self, table: &DamageTable, amount: f32, damage_type: DamageType, armor_class: ArmorClass) -> DamageResult {
+        let result = table.resolve(amount, damage_type, &self.profile(armor_class));
+        self.shield = (self.shield - result.shield_damage).max(0.0);
+        if amount > 0.0 {
+            self.recharge_delay = SHIELD_RECHARGE_DELAY;
+        }
+        result
+    }
+
+    // Take the stats the base's modules add up to, keeping the current shield within its new maximum
+    fn set_stats(&mut self, stats: &BaseStats) {
+        self.armor = stats.armor;
+        self.max_shield = stats.max_shield;
+        self.shield_recharge_rate = stats.shield_recharge_rate;
+        self.damage_resistance = stats.damage_resistance;
+        self.shield = self.shield.min(self.max_shield);
+    }
+}
+
+/// Largest share of a unit's cost production modules can knock off
+pub const MAX_COST_REDUCTION: f32 = 0.5;
+
+/// Production bonuses a base's production modules add up to
+///
+/// They apply to units produced by the base's buildings, see `handle_unit_production`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct ProductionBonuses {
+    pub build_speed: f32,     // Production speed multiplier
+    pub queue_slots: u8,      // Production queue slots on top of `BASE_QUEUE_SLOTS`
+    pub cost_reduction: f32,  // Share knocked off unit costs (0.0-MAX_COST_REDUCTION)
+    pub experience_gain: f32, // Experience produced units start with
+}
+
+// A base without production modules produces at the normal speed and cost
+impl Default for ProductionBonuses {
+    fn default() -> Self {
+        Self {
+            build_speed: 1.0,
+            queue_slots: 0,
+            cost_reduction: 0.0,
+            experience_gain: 0.0,
+        }
+    }
+}
+
+impl ProductionBonuses {
+    /// How many units a building can have queued
+    pub fn queue_capacity(&self) -> usize {
+        BASE_QUEUE_SLOTS + self.queue_slots as usize
+    }
+
+    /// What a unit costs after the cost reduction, rounded to the nearest whole amount
+    pub fn discounted(&self, cost: &ResourceWallet) -> ResourceWallet {
+        cost.iter()
+            .map(|(resource_type, amount)| (resource_type, (amount as f32 * (1.0 - self.cost_reduction)).round() as i32))
+            .collect()
+    }
 }
 
 /// Tracks effective stats after applying all module effects
-#[derive(Default)]
 struct BaseStats {
     speed_multiplier: f32,
     armor: f32,
@@ -400,6 +793,26 @@ struct BaseStats {
     power_capacity: f32,  // Added missing field
     has_weapons: bool,
     terrain_penalty_reduction: f32,
+    production: ProductionBonuses,
+}
+
+// A base with no modules keeps its own speed and power
+impl Default for BaseStats {
+    fn default() -> Self {
+        Self {
+            speed_multiplier: 1.0,
+            armor: 0.0,
+            max_shield: 0.0,
+            shield_recharge_rate: 0.0,
+            damage_resistance: 0.0,
+            power_generated: BASE_POWER_OUTPUT,
+            power_consumed: 0.0,
+            power_capacity: 0.0,
+            has_weapons: false,
+            terrain_penalty_reduction: 0.0,
+            production: ProductionBonuses::default(),
+        }
+    }
 }
 
 /// Plugin for module systems
@@ -414,11 +827,16 @@ impl Plugin for ModuleEffectsPlugin {
             .register_type::<Projectile>()
             .register_type::<Effect>()
             .register_type::<Health>()
+            .register_type::<ArmorClass>()
+            .register_type::<PowerPriority>()
+            .register_type::<ModulePower>()
+            .register_type::<BaseDefenses>()
+            .register_type::<ProductionBonuses>()
+            .add_event::<BaseDamageEvent>()
             
             // Add systems
             .add_systems(Update, (
-                manage_module_power,
-                apply_module_effects,
+                (manage_module_power, tint_modules_by_power, apply_module_effects, recharge_base_shields).chain(),
                 handle_weapon_modules,
                 update_projectiles,
                 handle_utility_modules,
@@ -1,6 +1,11 @@
 use bevy::prelude::*;
 use crate::states::game_state::GameState;
+use crate::components::player::MechanicalBase;
 use crate::components::unit::{Unit, UnitState, Selected};
+use crate::resources::map_data::GameMap;
+use crate::systems::status_effects::StatusEffects;
+use crate::units::engineer::{repair_target_at, Engineer};
+use crate::utils::pathfinding::{speed_modifier_at, MovePath};
 
 // Simple component to mark a unit's destination
 #[derive(Component, Debug)]
@@ -27,12 +32,14 @@ impl Plugin for MovementPlugin {
 }
 
 // System to handle right-click for movement commands
+// Engineers clicked onto a friendly base are left to the repair order instead
 pub fn handle_right_click(
     mut commands: Commands,
     mouse_input: Res<ButtonInput<MouseButton>>,
     windows: Query<&Window>,
     camera_q: Query<(&Camera, &GlobalTransform)>,
-    selected_units: Query<Entity, With<Selected>>,
+    selected_units: Query<(Entity, Option<&Unit>, Has<Engineer>), With<Selected>>,
+    bases: Query<(Entity, &Transform, &MechanicalBase)>,
 ) {
     // Check if right mouse button was just pressed
     if mouse_input.just_pressed(MouseButton::Right) {
@@ -50,7 +57,13 @@ pub fn handle_right_click(
                         info!("Right-clicked at world position: {:?}", target_pos);
                         
                         // Set move target for all selected units
-                        for entity in selected_units.iter() {
+                        for (entity, unit, is_engineer) in selected_units.iter() {
+                            let repairing = is_engineer
+                                && unit.is_some_and(|unit| repair_target_at(bases.iter(), unit.team, target_pos).is_some());
+                            if repairing {
+                                continue;
+                            }
+
                             commands.entity(entity).insert(MoveTarget {
                                 position: target_pos,
                             });
@@ -66,45 +79,68 @@ pub fn handle_right_click(
 }
 
 // System to move units toward their targets
+// Units follow a terrain-aware path, replanning whenever their target moves to another tile
 pub fn move_units(
     mut commands: Commands,
     time: Res<Time>,
-    mut units: Query<(Entity, &mut Transform, &Unit, &MoveTarget)>,
+    game_map: Option<Res<GameMap>>,
+    mut units: Query<(Entity, &mut Transform, &Unit, &MoveTarget, Option<&mut MovePath>, Option<&StatusEffects>)>,
+    stale_paths: Query<Entity, (With<Unit>, With<MovePath>, Without<MoveTarget>)>,
 ) {
-    for (entity, mut transform, unit, target) in units.iter_mut() {
-        // Get current position and target position
-        let current_pos = Vec2::new(transform.translation.x, transform.translation.y);
+    let game_map = game_map.as_deref();
+
+    // Drop paths left behind by orders that were cancelled elsewhere
+    for entity in stale_paths.iter() {
+        commands.entity(entity).remove::<MovePath>();
+    }
+
+    for (entity, mut transform, unit, target, path, statuses) in units.iter_mut() {
+        // Stunned units stay put until it wears off
+        if statuses.is_some_and(StatusEffects::is_stunned) {
+            continue;
+        }
+
+        let current_pos = transform.translation.truncate();
         let target_pos = target.position;
-        
-        // Calculate direction to target
-        let direction = (target_pos - current_pos).normalize_or_zero();
-        if direction == Vec2::ZERO {
-            continue; // Skip if we're already at the target or can't determine direction
+
+        let mut path = path.map(Mut::into_inner);
+        let mut new_path = None;
+        if !path.as_deref_mut().is_some_and(|path| path.follows(target_pos, game_map)) {
+            match MovePath::plan(game_map, current_pos, target_pos) {
+                Some(planned) => new_path = Some(planned),
+                None => {
+                    // Nowhere to go: the destination is blocked or cut off
+                    commands.entity(entity).remove::<(MoveTarget, MovePath)>();
+                    commands.entity(entity).insert(UnitState::Idle);
+                    warn!("No path for unit {:?} to {:?}", entity, target_pos);
+                    continue;
+                }
+            }
         }
-        
-        // Calculate movement for this frame
-        let move_speed = unit.movement_speed * time.delta_seconds();
-        let move_delta = direction * move_speed;
-        
+
+        let Some(route) = new_path.as_mut().or(path) else { continue };
+
+        // Calculate movement for this frame, slowed by the terrain underfoot and any status effects
+        let status_modifier = statuses.map_or(1.0, StatusEffects::speed_multiplier);
+        let move_speed = unit.movement_speed * speed_modifier_at(game_map, current_pos) * status_modifier * time.delta_seconds();
+        let new_pos = route.advance(current_pos, move_speed);
+
         // Update position
-        transform.translation.x += move_delta.x;
-        transform.translation.y += move_delta.y;
-        
+        transform.translation.x = new_pos.x;
+        transform.translation.y = new_pos.y;
+
+        if let Some(planned) = new_path {
+            commands.entity(entity).insert(planned);
+        }
+
         // Check if we've reached the target (within a small distance)
-        let new_pos = Vec2::new(transform.translation.x, transform.translation.y);
         let distance_to_target = new_pos.distance(target_pos);
-        
+
         if distance_to_target < 10.0 {
             // Target reached, remove movement target and set state to Idle
-            commands.entity(entity).remove::<MoveTarget>();
+            commands.entity(entity).remove::<(MoveTarget, MovePath)>();
             commands.entity(entity).insert(UnitState::Idle);
             info!("Unit reached destination. Distance: {}", distance_to_target);
         }
     }
 }
-
-
-
-
-
-
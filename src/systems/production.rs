@@ -1,32 +1,56 @@
 use bevy::prelude::*;
 use crate::components::building::{Building, BuildingSpawner};
-use crate::components::base_modules::ResourceType;
-use crate::components::player::PlayerResources;
-use crate::components::unit::{Team, Unit, UnitState};
-use crate::components::unit_types::UnitType;
-use crate::components::resource::Gatherer;
+use crate::components::player::{MechanicalBase, PlayerResources};
+use crate::components::unit::{Experience, Team};
 use crate::states::game_state::GameState;
-use crate::units::engineer::Engineer;
-use std::time::Duration;
+use crate::systems::module_effects::ProductionBonuses;
+use crate::systems::unit_catalog::UnitDefinitions;
+
+/// Units a building can have queued before any production modules add to it
+pub const BASE_QUEUE_SLOTS: usize = 3;
 
 pub struct ProductionPlugin;
 
 impl Plugin for ProductionPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (handle_unit_production,).run_if(in_state(GameState::Gameplay))
-        );
+        app.init_resource::<UnitDefinitions>()
+           .add_systems(
+               Update,
+               (handle_unit_production,).run_if(in_state(GameState::Gameplay))
+           );
         
         info!("Production Plugin initialized");
     }
 }
 
+/// Production bonuses for a building, taken from the nearest base of its team
+///
+/// Buildings belong to the closest friendly base; with no base around they get no bonuses.
+pub fn building_bonuses<'a>(
+    team: Team,
+    position: Vec2,
+    bases: impl IntoIterator<Item = (&'a Transform, &'a MechanicalBase, Option<&'a ProductionBonuses>)>,
+) -> ProductionBonuses {
+    bases
+        .into_iter()
+        .filter(|(_, base, _)| base.team == team)
+        .min_by(|(a, _, _), (b, _, _)| {
+            let a = a.translation.truncate().distance_squared(position);
+            let b = b.translation.truncate().distance_squared(position);
+            a.total_cmp(&b)
+        })
+        .and_then(|(_, _, bonuses)| bonuses.copied())
+        .unwrap_or_default()
+}
+
 /// System to handle unit production from buildings
+/// Production modules on the building's base speed it up, cut the cost and train the new units
 fn handle_unit_production(
     time: Res<Time>,
     mut buildings: Query<(Entity, &mut BuildingSpawner, &Building, &Transform, &Team)>,
+    bases: Query<(&Transform, &MechanicalBase, Option<&ProductionBonuses>)>,
     mut player_resources: Option<ResMut<PlayerResources>>,
+    definitions: Res<UnitDefinitions>,
     mut commands: Commands,
 ) {
     for (_entity, mut spawner, building, transform, team) in buildings.iter_mut() {
@@ -35,8 +59,14 @@ fn handle_unit_production(
             continue;
         }
         
-        // Tick the spawn timer
-        spawner.spawn_timer.tick(time.delta());
+        // A different unit at the head of the queue starts over with its own build time
+        if spawner.in_production != spawner.next_unit() {
+            restart_spawn_timer(&mut spawner, &definitions);
+        }
+        
+        // Tick the spawn timer, faster with production modules on the base
+        let bonuses = building_bonuses(*team, transform.translation.truncate(), &bases);
+        spawner.spawn_timer.tick(time.delta().mul_f32(bonuses.build_speed));
         
         // Check if it's time to spawn a unit
         if spawner.spawn_timer.just_finished() {
@@ -46,113 +76,49 @@ fn handle_unit_production(
             // Determine spawn position (slightly offset from building)
             let spawn_pos = building_pos + Vec2::new(40.0, 0.0);
             
-            // Check the unit type to spawn
-            match spawner.unit_type.as_str() {
-                "Engineer" => {
-                    // Check if player has enough resources (if this is a player building)
-                    let can_afford = if *team == Team::Player {
-                        if let Some(ref mut resources) = player_resources {
-                            // Engineer costs 20 Wood and 10 Stone
-                            // Find available resources
-                            let mut wood_available = 0;
-                            let mut stone_available = 0;
-                            
-                            for (res_type, amount) in &resources.resources {
-                                match res_type {
-                                    ResourceType::Wood => wood_available = *amount,
-                                    ResourceType::Stone => stone_available = *amount,
-                                    _ => {},
-                                }
-                            }
-                            
-                            if wood_available >= 20 && stone_available >= 10 {
-                                // Deduct resources
-                                for (res_type, amount) in &mut resources.resources {
-                                    match res_type {
-                                        ResourceType::Wood => *amount -= 20,
-                                        ResourceType::Stone => *amount -= 10,
-                                        _ => {}
-                                    }
-                                }
-                                true
-                            } else {
-                                false
-                            }
-                        } else {
-                            true // No player resources system yet, so always allow
-                        }
-                    } else {
-                        true // AI teams don't check resources for now
-                    };
-                    
-                    if can_afford {
-                        let engineer = spawn_local_engineer(&mut commands, spawn_pos, *team);
-                        commands.entity(engineer).insert(UnitType::Engineer);
-                        info!("Spawned Engineer unit for team {:?}", team);
-                    } else {
-                        info!("Not enough resources to spawn Engineer");
-                    }
-                },
-                // Add other unit types as needed
-                _ => {
-                    // Default to engineer for now
-                    let _engineer_entity = spawn_local_engineer(&mut commands, spawn_pos, *team);
-                    info!("Spawned default Engineer unit for team {:?}", team);
+            // Idle buildings and units the building can't make produce nothing
+            let Some(unit_type) = spawner.next_unit() else { continue };
+            let queued = !spawner.queue.is_empty();
+            if !spawner.can_produce(unit_type) {
+                warn!("Building cannot produce {}, skipping", unit_type.name());
+                if queued {
+                    spawner.queue.remove(0);
+                }
+                continue;
+            }
+            let definition = definitions.catalog.definition(unit_type);
+            
+            // Player buildings pay the discounted catalog cost; AI teams don't check resources for now
+            let can_afford = match (team, player_resources.as_mut()) {
+                (Team::Player, Some(resources)) => resources.resources.spend(&bonuses.discounted(&definition.cost)).is_ok(),
+                _ => true,
+            };
+            
+            if can_afford {
+                let unit = unit_type.spawn_unit(&mut commands, &definitions.catalog, spawn_pos, *team);
+                if bonuses.experience_gain > 0.0 {
+                    commands.entity(unit).insert(Experience { points: bonuses.experience_gain });
+                }
+                if queued {
+                    spawner.queue.remove(0);
                 }
+                info!("Spawned {} unit for team {:?}", unit_type.name(), team);
+            } else {
+                info!("Not enough resources to spawn {}", unit_type.name());
             }
             
-            // Reset the timer for next spawn
-            spawner.spawn_timer = Timer::from_seconds(spawner.spawn_time, TimerMode::Repeating);
+            // Reset the timer using the build time of whatever is queued next
+            restart_spawn_timer(&mut spawner, &definitions);
         }
     }
 }
 
-// Local version of spawn_engineer to avoid module export issues
-fn spawn_local_engineer(commands: &mut Commands, position: Vec2, team: Team) -> Entity {
-    let color = match team {
-        Team::Player => Color::srgb(0.2, 0.6, 0.8), // Blue for player
-        Team::Enemy => Color::srgb(0.8, 0.2, 0.2),  // Red for enemy
-        Team::Neutral => Color::srgb(0.5, 0.5, 0.5), // Gray for neutral
-    };
-    
-    let engineer = commands.spawn((
-        SpriteBundle {
-            sprite: Sprite {
-                color,
-                custom_size: Some(Vec2::new(24.0, 24.0)),
-                ..default()
-            },
-            transform: Transform::from_xyz(position.x, position.y, 10.0),
-            ..default()
-        },
-        Unit {
-            health: 50.0,
-            max_health: 50.0,
-            movement_speed: 60.0,
-            team,
-            attack_power: 5.0,
-            attack_range: 1.0,
-            state: UnitState::Idle,
-            attack_cooldown: Timer::from_seconds(1.0, TimerMode::Once),
-            attack_target: None,
-            movement_target: None,
-        },
-        UnitType::Engineer,
-        Engineer {
-            build_speed: 10.0,
-            build_timer: Timer::new(Duration::from_secs_f32(1.0), TimerMode::Repeating),
-            target_building: None,
-        },
-        Gatherer {
-            gather_rate: 5.0,
-            gather_timer: Timer::new(Duration::from_secs_f32(1.0), TimerMode::Repeating),
-            carry_capacity: 20,
-            current_load: 0,
-            target_resource: None,
-        },
-        UnitState::Idle,
-        Name::new(format!("{:?} Engineer", team)),
-    )).id();
-    
-    engineer
+// Start timing the unit that comes out next, using its catalog build time
+fn restart_spawn_timer(spawner: &mut BuildingSpawner, definitions: &UnitDefinitions) {
+    let next_unit = spawner.next_unit();
+    let build_time = next_unit
+        .and_then(|next| definitions.catalog.get(next))
+        .map_or(spawner.spawn_time, |next| next.build_time);
+    spawner.spawn_timer = Timer::from_seconds(build_time, TimerMode::Repeating);
+    spawner.in_production = next_unit;
 }
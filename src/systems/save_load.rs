@@ -0,0 +1,1291 @@
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::components::ai::{AIBase, AIBrain, AIControlled, AIDifficulty, AIPhase};
+use crate::components::base_modules::{AttachableModule, AttachmentPoint, BaseModule, InstalledModule, ModuleType, UtilityEffect, UNDER_CONSTRUCTION_COLOR};
+use crate::components::economy::{ResourceType, ResourceWallet};
+use crate::components::building::{Building, BuildingSpawner};
+use crate::components::player::{MechanicalBase, PlayerResources, StrategicTarget};
+use crate::components::resource::{Gatherer, ResourceNode};
+use crate::components::strategic::{
+    spawn_strategic_location, StrategicLocation, StrategicLocationMarker, StrategicScores, TeamScore, CAPTURE_RADIUS, CAPTURE_RATE,
+};
+use crate::components::unit::{Experience, Team, Unit, UnitState};
+use crate::components::unit_types::UnitType;
+use crate::entities::building_types::BuildingType;
+use crate::resources::map::plugin::{LoadedMapFile, MapChanged, MapInitialized, SelectedMap};
+use crate::resources::map_data::GameMap;
+use crate::resources::map_generator::{MapGenerationSettings, MapGenerator};
+use crate::resources::resource_nodes::ResourceNodeFactory;
+use crate::sprites::GameSprites;
+use crate::states::game_state::GameState;
+use crate::systems::base_movement::MoveTarget as BaseMoveTarget;
+use crate::systems::building_catalog::{BuildingCatalog, BuildingDefinitions};
+use crate::systems::fog_of_war::FogOfWar;
+use crate::systems::module_effects::{BaseDefenses, Cooldown, Health, ModulePower, PowerPriority, Projectile};
+use crate::systems::movement::MoveTarget;
+use crate::systems::sensors::EnemyIntel;
+use crate::systems::status_effects::{StatusEffect, StatusEffects};
+use crate::systems::unit_catalog::{UnitCatalog, UnitDefinitions};
+use crate::systems::victory::{MatchResult, VictoryProgress};
+use crate::tech::{FactionTechTrees, TechStatus};
+use crate::ui::menu::GameSettings;
+use crate::units::engineer::SelectedResource;
+
+/// Version written to new save files; bump when the format changes
+pub const SAVE_VERSION: u32 = 1;
+
+/// Save file used by the quick save and quick load keys
+pub const QUICK_SAVE_PATH: &str = "saves/quicksave.ron";
+
+/// Save file used by the pause menu
+pub const SAVE_SLOT_PATH: &str = "saves/savegame.ron";
+
+/// Event requesting that the current match be written to disk
+#[derive(Event, Debug, Clone)]
+pub struct SaveGameEvent {
+    pub path: PathBuf,
+}
+
+/// Event requesting that the current match be replaced by a saved one
+#[derive(Event, Debug, Clone)]
+pub struct LoadGameEvent {
+    pub path: PathBuf,
+}
+
+/// Errors that can occur while saving or loading a match
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Serialize(ron::Error),
+    Parse(ron::error::SpannedError),
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "could not access save file: {}", err),
+            SaveError::Serialize(err) => write!(f, "could not write save data: {}", err),
+            SaveError::Parse(err) => write!(f, "malformed save file: {}", err),
+            SaveError::UnsupportedVersion(version) => {
+                write!(f, "save file version {} is newer than supported version {}", version, SAVE_VERSION)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<std::io::Error> for SaveError {
+    fn from(err: std::io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
+
+impl From<ron::Error> for SaveError {
+    fn from(err: ron::Error) -> Self {
+        SaveError::Serialize(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for SaveError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        SaveError::Parse(err)
+    }
+}
+
+/// Complete snapshot of a match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    pub player_resources: Option<SavedPlayerResources>,
+    pub bases: Vec<SavedBase>,
+    pub units: Vec<SavedUnit>,
+    pub buildings: Vec<SavedBuilding>,
+    pub resource_nodes: Vec<SavedResourceNode>,
+    pub strategic_locations: Vec<SavedStrategicLocation>,
+    #[serde(default)]
+    pub tech_trees: Vec<SavedTechTree>,
+    #[serde(default)]
+    pub map: Option<SavedMap>, // Older saves load onto whatever map is in play
+    #[serde(default)]
+    pub strategic_scores: HashMap<Team, TeamScore>,
+    #[serde(default)]
+    pub victory_progress: VictoryProgress,
+    #[serde(default)]
+    pub explored_tiles: HashMap<Team, String>, // Row by row, '#' for explored tiles and '.' for the rest
+}
+
+/// Map a match was played on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SavedMap {
+    /// A generated map, rebuilt from the settings it was generated with
+    Generated(MapGenerationSettings),
+    /// A map file, by asset path
+    File(String),
+}
+
+/// Saved research progress of one faction's tech tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedTechTree {
+    pub faction: String,
+    pub current_research: Option<String>,
+    pub research_points: f32,
+    pub technologies: Vec<SavedTechnology>,
+}
+
+/// Saved research status of a single technology
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedTechnology {
+    pub id: String,
+    pub status: TechStatus,
+    pub research_progress: f32,
+}
+
+/// Saved copy of `PlayerResources`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedPlayerResources {
+    pub resources: ResourceWallet,
+    pub score: i32,
+    pub strategic_points_controlled: i32,
+}
+
+/// Saved timer progress
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SavedTimer {
+    pub duration: f32,
+    pub elapsed: f32,
+    pub repeating: bool,
+}
+
+impl SavedTimer {
+    pub fn from_timer(timer: &Timer) -> Self {
+        Self {
+            duration: timer.duration().as_secs_f32(),
+            elapsed: timer.elapsed_secs(),
+            repeating: timer.mode() == TimerMode::Repeating,
+        }
+    }
+
+    pub fn to_timer(&self) -> Timer {
+        let mode = if self.repeating { TimerMode::Repeating } else { TimerMode::Once };
+        let mut timer = Timer::from_seconds(self.duration, mode);
+        timer.set_elapsed(Duration::from_secs_f32(self.elapsed));
+        timer
+    }
+}
+
+/// Saved mechanical base, including its modules and attachment points
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedBase {
+    pub id: u64,
+    pub name: Option<String>,
+    pub position: [f32; 2],
+    pub team: Team,
+    pub health: f32,
+    pub max_health: f32,
+    pub base_movement_speed: f32,
+    pub effective_movement_speed: f32,
+    pub resources: ResourceWallet,
+    pub power_output: f32,
+    pub power_consumed: f32,
+    pub max_power: f32,
+    pub hull: Option<Health>,
+    pub state: Option<UnitState>,
+    pub move_target: Option<[f32; 2]>,
+    pub modules: Vec<SavedModule>,
+    pub attachment_points: Vec<SavedAttachmentPoint>,
+    pub ai: Option<SavedAI>,
+    #[serde(default)]
+    pub power_priority: Option<PowerPriority>,
+    #[serde(default)]
+    pub defenses: Option<BaseDefenses>,
+    #[serde(default)]
+    pub constructions: Vec<SavedConstruction>,
+    #[serde(default)]
+    pub status_effects: Vec<SavedStatusEffect>,
+}
+
+/// Saved catalog module that is still being built, already paid for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedConstruction {
+    pub id: u64,
+    pub catalog_name: String,
+    pub module_type: ModuleType,
+    pub size: [f32; 2],
+    pub build_time: f32,
+    pub build_progress: f32,
+    pub offset: [f32; 2],
+    pub rotation: f32,
+}
+
+/// Saved module attached to a base
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedModule {
+    pub id: u64,
+    pub module: BaseModule,
+    pub offset: [f32; 2],
+    pub cooldown: Option<SavedTimer>,
+    #[serde(default)]
+    pub catalog_name: Option<String>, // Module catalog entry, used to refund it when detached
+    #[serde(default)]
+    pub power: Option<ModulePower>,
+}
+
+/// Saved attachment point on a base
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedAttachmentPoint {
+    pub position: [f32; 2],
+    pub rotation: f32,
+    pub size: [f32; 2],
+    pub module_type: ModuleType,
+    pub occupied: bool,
+    pub attached_module: Option<u64>,
+}
+
+/// Saved AI decision-making state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedAI {
+    pub difficulty: AIDifficulty,
+    pub phase: AIPhase,
+    pub home: [f32; 2],
+    pub objective: Option<[f32; 2]>,
+    pub strength_ratio: f32,
+}
+
+/// Saved unit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedUnit {
+    pub id: u64,
+    pub name: Option<String>,
+    pub unit_type: Option<UnitType>,
+    pub position: [f32; 2],
+    pub team: Team,
+    pub health: f32,
+    pub max_health: f32,
+    pub attack_power: f32,
+    pub attack_range: f32,
+    pub movement_speed: f32,
+    pub state: UnitState,
+    pub attack_cooldown: SavedTimer,
+    pub attack_target: Option<u64>,
+    pub move_target: Option<[f32; 2]>,
+    pub hull: Option<Health>,
+    #[serde(default)]
+    pub experience: Option<Experience>,
+    pub gatherer: Option<SavedGatherer>,
+    pub selected_resource: Option<u64>,
+    #[serde(default)]
+    pub status_effects: Vec<SavedStatusEffect>,
+}
+
+/// Saved status effect on a unit or base
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedStatusEffect {
+    pub effect: UtilityEffect,
+    pub strength: f32,
+    pub remaining: f32,
+    pub source: u64,
+}
+
+/// Saved gathering progress of a unit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedGatherer {
+    pub gather_rate: f32,
+    pub gather_timer: SavedTimer,
+    pub carry_capacity: i32,
+    pub current_load: i32,
+    pub target_resource: Option<u64>,
+    #[serde(default)]
+    pub carried_type: Option<ResourceType>,
+    #[serde(default)]
+    pub returning: bool,
+}
+
+/// Saved building
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedBuilding {
+    pub id: u64,
+    pub name: Option<String>,
+    pub building_type: Option<BuildingType>,
+    pub position: [f32; 2],
+    pub team: Option<Team>,
+    pub health: f32,
+    pub max_health: f32,
+    pub construction_progress: f32,
+    pub is_completed: bool,
+    pub spawner: Option<SavedSpawner>,
+}
+
+/// Saved production state of a building
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSpawner {
+    pub unit_type: Option<UnitType>,
+    #[serde(default)]
+    pub queue: Vec<UnitType>,
+    pub produces: Vec<UnitType>,
+    pub spawn_time: f32,
+    pub spawn_timer: SavedTimer,
+    #[serde(default)]
+    pub in_production: Option<UnitType>,
+}
+
+/// Saved resource node and how much has been mined from it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedResourceNode {
+    pub id: u64,
+    pub position: [f32; 2],
+    pub resource_type: ResourceType,
+    pub amount_remaining: i32,
+    pub max_amount: i32,
+}
+
+/// Saved strategic location and who controls it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedStrategicLocation {
+    pub name: String,
+    pub position: [f32; 2],
+    pub control_points: f32,
+    pub total_required: f32,
+    pub controlling_team: Option<Team>,
+    #[serde(default = "default_capture_value")]
+    pub capture_value: i32,
+    #[serde(default = "default_capture_radius")]
+    pub capture_radius: f32,
+    #[serde(default = "default_capture_rate")]
+    pub capture_rate: f32,
+    #[serde(default)]
+    pub capturing_team: Option<Team>,
+    #[serde(default)]
+    pub held_by: Option<Team>,
+    #[serde(default)]
+    pub time_held: f32, // Seconds `held_by` has held it, toward the victory rules
+}
+
+// Saves from before locations had values count every location once
+fn default_capture_value() -> i32 {
+    1
+}
+
+// Saves from before locations had their own capture settings use the defaults
+fn default_capture_radius() -> f32 {
+    CAPTURE_RADIUS
+}
+
+fn default_capture_rate() -> f32 {
+    CAPTURE_RATE
+}
+
+/// Plugin that saves and loads matches from the pause menu and the quick save keys
+pub struct SaveLoadPlugin;
+
+impl Plugin for SaveLoadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SaveGameEvent>()
+           .add_event::<LoadGameEvent>()
+           .add_systems(Update, handle_quick_save_keys.run_if(in_state(GameState::Gameplay)))
+           .add_systems(Update, (handle_save_requests, handle_load_requests).chain());
+
+        info!("Save/Load Plugin initialized");
+    }
+}
+
+/// Write a save game to disk as RON, creating the save folder if needed
+pub fn write_save_file(path: &Path, save: &SaveGame) -> Result<(), SaveError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let text = ron::ser::to_string_pretty(save, ron::ser::PrettyConfig::default())?;
+    std::fs::write(path, text)?;
+    Ok(())
+}
+
+/// Read a save game from disk, rejecting files from newer versions of the game
+pub fn read_save_file(path: &Path) -> Result<SaveGame, SaveError> {
+    let text = std::fs::read_to_string(path)?;
+    let save: SaveGame = ron::from_str(&text)?;
+
+    if save.version > SAVE_VERSION {
+        return Err(SaveError::UnsupportedVersion(save.version));
+    }
+
+    Ok(save)
+}
+
+// Helpers for converting positions to and from the save format
+fn to_array(position: Vec2) -> [f32; 2] {
+    [position.x, position.y]
+}
+
+fn to_vec2(position: [f32; 2]) -> Vec2 {
+    Vec2::new(position[0], position[1])
+}
+
+/// Capture everything needed to restore the current match
+pub fn capture_save(world: &mut World) -> SaveGame {
+    let player_resources = world.get_resource::<PlayerResources>().map(|resources| SavedPlayerResources {
+        resources: resources.resources.clone(),
+        score: resources.score,
+        strategic_points_controlled: resources.strategic_points_controlled,
+    });
+
+    // Bases, with their modules and attachment points
+    let mut bases = Vec::new();
+    let mut base_query = world.query::<(
+        Entity,
+        &Transform,
+        &MechanicalBase,
+        Option<&Name>,
+        Option<&Health>,
+        Option<&UnitState>,
+        Option<&BaseMoveTarget>,
+        Option<&AIControlled>,
+        Option<&AIBrain>,
+    )>();
+
+    for (entity, transform, base, name, hull, state, move_target, ai, brain) in base_query.iter(world) {
+        let modules = base.modules.iter().filter_map(|&module_entity| {
+            let module = world.get::<BaseModule>(module_entity)?;
+            let offset = world.get::<Transform>(module_entity).map_or(Vec2::ZERO, |t| t.translation.truncate());
+            Some(SavedModule {
+                id: module_entity.to_bits(),
+                module: module.clone(),
+                offset: to_array(offset),
+                cooldown: world.get::<Cooldown>(module_entity).map(|cooldown| SavedTimer::from_timer(&cooldown.timer)),
+                catalog_name: world.get::<InstalledModule>(module_entity).map(|installed| installed.name.clone()),
+                power: world.get::<ModulePower>(module_entity).copied(),
+            })
+        }).collect();
+
+        // Modules still being built keep their progress, since they have been paid for
+        let constructions: Vec<SavedConstruction> = base.attachment_points.iter().filter_map(|&point_entity| {
+            let point = world.get::<AttachmentPoint>(point_entity)?;
+            let module_entity = point.attached_module?;
+            let attachable = world.get::<AttachableModule>(module_entity)?;
+            let installed = world.get::<InstalledModule>(module_entity)?;
+            Some(SavedConstruction {
+                id: module_entity.to_bits(),
+                catalog_name: installed.name.clone(),
+                module_type: attachable.module_type.clone(),
+                size: to_array(attachable.size),
+                build_time: attachable.build_time,
+                build_progress: attachable.build_progress,
+                offset: to_array(point.position),
+                rotation: point.rotation,
+            })
+        }).collect();
+
+        let attachment_points = base.attachment_points.iter().filter_map(|&point_entity| {
+            let point = world.get::<AttachmentPoint>(point_entity)?;
+            // Anything else on the point can't be restored, so the point is saved free
+            let saved = point.attached_module.is_some_and(|module| {
+                world.get::<BaseModule>(module).is_some() || constructions.iter().any(|construction| construction.id == module.to_bits())
+            });
+            Some(SavedAttachmentPoint {
+                position: to_array(point.position),
+                rotation: point.rotation,
+                size: to_array(point.size),
+                module_type: point.module_type.clone(),
+                occupied: point.occupied && saved,
+                attached_module: point.attached_module.filter(|_| saved).map(Entity::to_bits),
+            })
+        }).collect();
+
+        let ai = match (ai, brain) {
+            (Some(ai), brain) => Some(SavedAI {
+                difficulty: ai.difficulty,
+                phase: brain.map_or(AIPhase::Gather, |brain| brain.phase),
+                home: to_array(brain.map_or(transform.translation.truncate(), |brain| brain.home)),
+                objective: brain.and_then(|brain| brain.objective_position).map(to_array),
+                strength_ratio: brain.map_or(0.0, |brain| brain.strength_ratio),
+            }),
+            (None, _) => None,
+        };
+
+        bases.push(SavedBase {
+            id: entity.to_bits(),
+            name: name.map(|name| name.to_string()),
+            position: to_array(transform.translation.truncate()),
+            team: base.team,
+            health: base.health,
+            max_health: base.max_health,
+            base_movement_speed: base.base_movement_speed,
+            effective_movement_speed: base.effective_movement_speed,
+            resources: base.resources.clone(),
+            power_output: base.power_output,
+            power_consumed: base.power_consumed,
+            max_power: base.max_power,
+            hull: hull.cloned(),
+            state: state.copied(),
+            move_target: move_target.map(|target| to_array(target.target_position)),
+            modules,
+            attachment_points,
+            ai,
+            power_priority: world.get::<PowerPriority>(entity).cloned(),
+            defenses: world.get::<BaseDefenses>(entity).cloned(),
+            constructions,
+            status_effects: save_status_effects(world.get::<StatusEffects>(entity)),
+        });
+    }
+
+    // Units
+    let mut unit_query = world.query::<(
+        Entity,
+        &Transform,
+        &Unit,
+        Option<&Name>,
+        Option<&UnitType>,
+        Option<&UnitState>,
+        Option<&MoveTarget>,
+        Option<&Health>,
+        Option<&Experience>,
+        Option<&Gatherer>,
+        Option<&SelectedResource>,
+        Option<&StatusEffects>,
+    )>();
+
+    let units = unit_query
+        .iter(world)
+        .map(|(entity, transform, unit, name, unit_type, state, move_target, hull, experience, gatherer, selected, effects)| SavedUnit {
+            id: entity.to_bits(),
+            name: name.map(|name| name.to_string()),
+            unit_type: unit_type.copied(),
+            position: to_array(transform.translation.truncate()),
+            team: unit.team,
+            health: unit.health,
+            max_health: unit.max_health,
+            attack_power: unit.attack_power,
+            attack_range: unit.attack_range,
+            movement_speed: unit.movement_speed,
+            state: state.copied().unwrap_or(unit.state),
+            attack_cooldown: SavedTimer::from_timer(&unit.attack_cooldown),
+            attack_target: unit.attack_target.map(Entity::to_bits),
+            move_target: move_target.map(|target| to_array(target.position)),
+            hull: hull.cloned(),
+            experience: experience.copied(),
+            gatherer: gatherer.map(|gatherer| SavedGatherer {
+                gather_rate: gatherer.gather_rate,
+                gather_timer: SavedTimer::from_timer(&gatherer.gather_timer),
+                carry_capacity: gatherer.carry_capacity,
+                current_load: gatherer.current_load,
+                target_resource: gatherer.target_resource.map(Entity::to_bits),
+                carried_type: gatherer.carried_type,
+                returning: gatherer.returning,
+            }),
+            selected_resource: selected.map(|selected| selected.resource_entity.to_bits()),
+            status_effects: save_status_effects(effects),
+        })
+        .collect();
+
+    // Buildings
+    let mut building_query = world.query::<(
+        Entity,
+        &Transform,
+        &Building,
+        Option<&Name>,
+        Option<&BuildingType>,
+        Option<&Team>,
+        Option<&BuildingSpawner>,
+    )>();
+
+    let buildings = building_query
+        .iter(world)
+        .map(|(entity, transform, building, name, building_type, team, spawner)| SavedBuilding {
+            id: entity.to_bits(),
+            name: name.map(|name| name.to_string()),
+            building_type: building_type.copied(),
+            position: to_array(transform.translation.truncate()),
+            team: team.copied(),
+            health: building.health,
+            max_health: building.max_health,
+            construction_progress: building.construction_progress,
+            is_completed: building.is_completed,
+            spawner: spawner.map(|spawner| SavedSpawner {
+                unit_type: spawner.unit_type,
+                queue: spawner.queue.clone(),
+                produces: spawner.produces.clone(),
+                spawn_time: spawner.spawn_time,
+                spawn_timer: SavedTimer::from_timer(&spawner.spawn_timer),
+                in_production: spawner.in_production,
+            }),
+        })
+        .collect();
+
+    // Resource nodes
+    let mut node_query = world.query::<(Entity, &Transform, &ResourceNode)>();
+    let resource_nodes = node_query
+        .iter(world)
+        .map(|(entity, transform, node)| SavedResourceNode {
+            id: entity.to_bits(),
+            position: to_array(transform.translation.truncate()),
+            resource_type: node.resource_type,
+            amount_remaining: node.amount_remaining,
+            max_amount: node.max_amount,
+        })
+        .collect();
+
+    // Strategic locations
+    let mut location_query = world.query::<(&StrategicLocation, Option<&StrategicTarget>)>();
+    let strategic_locations = location_query
+        .iter(world)
+        .map(|(location, target)| SavedStrategicLocation {
+            name: location.name.clone(),
+            position: to_array(location.position),
+            control_points: location.control_points,
+            total_required: location.total_required,
+            controlling_team: location.controlling_team,
+            capture_value: location.capture_value,
+            capture_radius: location.capture_radius,
+            capture_rate: location.capture_rate,
+            capturing_team: location.capturing_team,
+            held_by: target.and_then(|target| target.held_by),
+            time_held: target.map_or(0.0, |target| target.time_held),
+        })
+        .collect();
+
+    // Research, sorted so the same match always saves the same way
+    let mut tech_trees: Vec<SavedTechTree> = world
+        .get_resource::<FactionTechTrees>()
+        .map(|trees| {
+            trees.trees.iter().map(|(faction, tree)| {
+                let mut technologies: Vec<SavedTechnology> = tree.technologies.values().map(|tech| SavedTechnology {
+                    id: tech.id.clone(),
+                    status: tech.status,
+                    research_progress: tech.research_progress,
+                }).collect();
+                technologies.sort_by(|a, b| a.id.cmp(&b.id));
+                SavedTechTree {
+                    faction: faction.clone(),
+                    current_research: tree.current_research.clone(),
+                    research_points: tree.research_points,
+                    technologies,
+                }
+            }).collect()
+        })
+        .unwrap_or_default();
+    tech_trees.sort_by(|a, b| a.faction.cmp(&b.faction));
+
+    SaveGame {
+        version: SAVE_VERSION,
+        player_resources,
+        bases,
+        units,
+        buildings,
+        resource_nodes,
+        strategic_locations,
+        tech_trees,
+        map: capture_map(world),
+        strategic_scores: world.get_resource::<StrategicScores>().map(|scores| scores.teams.clone()).unwrap_or_default(),
+        victory_progress: world.get_resource::<VictoryProgress>().cloned().unwrap_or_default(),
+        explored_tiles: world
+            .get_resource::<FogOfWar>()
+            .map(|fog| {
+                fog.explored().iter().map(|(team, tiles)| {
+                    (*team, tiles.iter().map(|&explored| if explored { '#' } else { '.' }).collect())
+                }).collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+// Status effects on an entity, with their sources saved by id
+fn save_status_effects(effects: Option<&StatusEffects>) -> Vec<SavedStatusEffect> {
+    effects
+        .map(|effects| {
+            effects.effects.iter().map(|status| SavedStatusEffect {
+                effect: status.effect,
+                strength: status.strength,
+                remaining: status.remaining,
+                source: status.source.to_bits(),
+            }).collect()
+        })
+        .unwrap_or_default()
+}
+
+// Remember the map file being played on, or how the generated map was made
+fn capture_map(world: &World) -> Option<SavedMap> {
+    if let Some(path) = world.get_resource::<SelectedMap>().and_then(|selected| selected.path.clone()) {
+        return Some(SavedMap::File(path));
+    }
+
+    let game_map = world.get_resource::<GameMap>()?;
+    let seed = game_map.seed?;
+    let settings = world
+        .get_resource::<MapGenerationSettings>()
+        .filter(|settings| settings.seed == seed)
+        .cloned()
+        .unwrap_or_else(|| MapGenerationSettings {
+            seed,
+            width: game_map.width,
+            height: game_map.height,
+            tile_size: game_map.tile_size,
+            players: game_map.start_positions.len().max(1),
+            strategic_points: game_map.strategic_locations.len(),
+        });
+    Some(SavedMap::Generated(settings))
+}
+
+// Switch to the map a save was played on, unless it is already in play
+// Returns whether the map is being swapped out
+fn restore_map(world: &mut World, map: &SavedMap) -> bool {
+    let selected_path = world.get_resource::<SelectedMap>().and_then(|selected| selected.path.clone());
+
+    match map {
+        SavedMap::File(path) => {
+            if selected_path.as_ref() == Some(path) {
+                return false;
+            }
+            info!("Save was played on {}, loading it", path);
+            world.insert_resource(SelectedMap { path: Some(path.clone()) });
+            true
+        }
+        SavedMap::Generated(settings) => {
+            let current_seed = world.get_resource::<GameMap>().and_then(|game_map| game_map.seed);
+            if selected_path.is_none() && current_seed == Some(settings.seed) {
+                return false;
+            }
+            info!("Save was played on a generated map, regenerating it with seed {}", settings.seed);
+            world.insert_resource(settings.clone());
+            world.insert_resource(MapGenerator::new(settings.clone()).generate());
+            world.insert_resource(MapInitialized(true));
+
+            // Drop the map file so it doesn't replace the generated map once it finishes loading
+            if let Some(mut loaded) = world.get_resource_mut::<LoadedMapFile>() {
+                loaded.handle = None;
+            }
+            if selected_path.is_some() {
+                world.insert_resource(SelectedMap::default());
+            }
+            if world.contains_resource::<Events<MapChanged>>() {
+                world.send_event(MapChanged);
+            }
+            true
+        }
+    }
+}
+
+// Put every faction's research back where it was
+fn restore_tech_trees(world: &mut World, saved_trees: &[SavedTechTree]) {
+    let Some(mut trees) = world.get_resource_mut::<FactionTechTrees>() else { return };
+
+    for saved in saved_trees {
+        let Some(tree) = trees.trees.get_mut(&saved.faction) else {
+            warn!("Save has research for unknown faction {}", saved.faction);
+            continue;
+        };
+
+        tree.current_research = saved.current_research.clone();
+        tree.research_points = saved.research_points;
+        for technology in &saved.technologies {
+            if let Some(tech) = tree.get_technology_mut(&technology.id) {
+                tech.status = technology.status;
+                tech.research_progress = technology.research_progress;
+            }
+        }
+    }
+}
+
+/// Replace the current match with a saved one
+pub fn restore_save(world: &mut World, save: &SaveGame) {
+    // Clear out everything the save file will replace
+    let mut existing = world.query_filtered::<Entity, Or<(
+        With<MechanicalBase>,
+        With<Unit>,
+        With<Building>,
+        With<ResourceNode>,
+        With<StrategicLocation>,
+        With<StrategicLocationMarker>,
+        With<Projectile>,
+    )>>();
+    let stale: Vec<Entity> = existing.iter(world).collect();
+    for entity in stale {
+        if let Some(entity) = world.get_entity_mut(entity) {
+            entity.despawn_recursive();
+        }
+    }
+
+    let map_swapped = save.map.as_ref().is_some_and(|map| restore_map(world, map));
+    restore_tech_trees(world, &save.tech_trees);
+
+    if let Some(saved) = &save.player_resources {
+        world.insert_resource(PlayerResources {
+            resources: saved.resources.clone(),
+            score: saved.score,
+            strategic_points_controlled: saved.strategic_points_controlled,
+        });
+    }
+
+    let tile_size = world.get_resource::<GameMap>().map_or(32.0, |map| map.tile_size);
+    let catalog = world
+        .get_resource::<UnitDefinitions>()
+        .map(|definitions| definitions.catalog.clone())
+        .unwrap_or_default();
+    let building_catalog = world
+        .get_resource::<BuildingDefinitions>()
+        .map(|definitions| definitions.catalog.clone())
+        .unwrap_or_default();
+    let sprites = world.get_resource::<GameSprites>().cloned().unwrap_or_default();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, world);
+    let mut entity_map: HashMap<u64, Entity> = HashMap::new();
+
+    // Resource nodes first, so gatherers have something to point at
+    let factory = ResourceNodeFactory;
+    for saved in &save.resource_nodes {
+        let entity = factory.spawn_resource_node_of_type(&mut commands, to_vec2(saved.position), saved.resource_type);
+        commands.entity(entity).insert(ResourceNode {
+            amount_remaining: saved.amount_remaining,
+            max_amount: saved.max_amount,
+            ..ResourceNodeFactory::node_for(saved.resource_type)
+        });
+        entity_map.insert(saved.id, entity);
+    }
+
+    for saved in &save.strategic_locations {
+        let entity = spawn_strategic_location(&mut commands, StrategicLocation {
+            name: saved.name.clone(),
+            control_points: saved.control_points,
+            total_required: saved.total_required,
+            controlling_team: saved.controlling_team,
+            position: to_vec2(saved.position),
+            capture_value: saved.capture_value,
+            capture_radius: saved.capture_radius,
+            capture_rate: saved.capture_rate,
+            capturing_team: saved.capturing_team,
+            ..default()
+        }, tile_size);
+        commands.entity(entity).insert(StrategicTarget {
+            position: to_vec2(saved.position),
+            is_reached: saved.held_by.is_some(),
+            time_held: saved.time_held,
+            held_by: saved.held_by,
+        });
+    }
+
+    for saved in &save.bases {
+        let entity = restore_base(&mut commands, &sprites, saved, &mut entity_map);
+        entity_map.insert(saved.id, entity);
+    }
+
+    for saved in &save.buildings {
+        let entity = restore_building(&mut commands, &building_catalog, saved);
+        entity_map.insert(saved.id, entity);
+    }
+
+    for saved in &save.units {
+        let entity = restore_unit(&mut commands, &catalog, saved);
+        entity_map.insert(saved.id, entity);
+    }
+
+    // Now that everything exists again, reconnect references between entities
+    for saved in &save.units {
+        let entity = entity_map[&saved.id];
+        let lookup = |id: Option<u64>| id.and_then(|id| entity_map.get(&id).copied());
+
+        commands.entity(entity).insert(Unit {
+            health: saved.health,
+            max_health: saved.max_health,
+            attack_power: saved.attack_power,
+            attack_range: saved.attack_range,
+            movement_speed: saved.movement_speed,
+            team: saved.team,
+            state: saved.state,
+            attack_cooldown: saved.attack_cooldown.to_timer(),
+            attack_target: lookup(saved.attack_target),
+            movement_target: saved.move_target.map(to_vec2),
+        });
+
+        if let Some(gatherer) = &saved.gatherer {
+            commands.entity(entity).insert(Gatherer {
+                gather_rate: gatherer.gather_rate,
+                gather_timer: gatherer.gather_timer.to_timer(),
+                carry_capacity: gatherer.carry_capacity,
+                current_load: gatherer.current_load,
+                target_resource: lookup(gatherer.target_resource),
+                carried_type: gatherer.carried_type,
+                returning: gatherer.returning,
+            });
+        }
+
+        if let Some(resource_entity) = lookup(saved.selected_resource) {
+            commands.entity(entity).insert(SelectedResource { resource_entity });
+        }
+
+        if !saved.status_effects.is_empty() {
+            commands.entity(entity).insert(restore_status_effects(&saved.status_effects, &entity_map));
+        }
+    }
+
+    for saved in &save.bases {
+        if !saved.status_effects.is_empty() {
+            commands.entity(entity_map[&saved.id]).insert(restore_status_effects(&saved.status_effects, &entity_map));
+        }
+
+        for point in &saved.attachment_points {
+            let attached = point.attached_module.and_then(|id| entity_map.get(&id).copied());
+            let point_entity = commands.spawn((
+                AttachmentPoint {
+                    position: to_vec2(point.position),
+                    rotation: point.rotation,
+                    size: to_vec2(point.size),
+                    module_type: point.module_type.clone(),
+                    occupied: point.occupied,
+                    attached_module: attached,
+                },
+                SpatialBundle::from_transform(Transform::from_xyz(point.position[0], point.position[1], 0.1)),
+                Name::new("Attachment Point"),
+            )).id();
+            commands.entity(entity_map[&saved.id]).add_child(point_entity);
+
+            // Catalog modules need to know their point to be detached again
+            let catalog_name = saved
+                .modules
+                .iter()
+                .find(|module| Some(module.id) == point.attached_module)
+                .and_then(|module| module.catalog_name.clone())
+                .or_else(|| {
+                    saved
+                        .constructions
+                        .iter()
+                        .find(|construction| Some(construction.id) == point.attached_module)
+                        .map(|construction| construction.catalog_name.clone())
+                });
+            if let (Some(module_entity), Some(name)) = (attached, catalog_name) {
+                commands.entity(module_entity).insert(InstalledModule { name, point: point_entity });
+            }
+        }
+    }
+
+    queue.apply(world);
+
+    // Bases track their children by entity, so fill those lists in last
+    for saved in &save.bases {
+        let base_entity = entity_map[&saved.id];
+        let modules: Vec<Entity> = saved.modules.iter().filter_map(|module| entity_map.get(&module.id).copied()).collect();
+        let points: Vec<Entity> = world
+            .get::<Children>(base_entity)
+            .map(|children| children.iter().copied().filter(|child| world.get::<AttachmentPoint>(*child).is_some()).collect())
+            .unwrap_or_default();
+
+        if let Some(mut base) = world.get_mut::<MechanicalBase>(base_entity) {
+            base.modules = modules;
+            base.attachment_points = points;
+        }
+    }
+
+    restore_match_progress(world, save, map_swapped);
+
+    info!(
+        "Restored save: {} bases, {} units, {} buildings, {} resource nodes",
+        save.bases.len(), save.units.len(), save.buildings.len(), save.resource_nodes.len()
+    );
+}
+
+// Put scores, victory timers and explored tiles back, and drop what was known about the old match
+fn restore_match_progress(world: &mut World, save: &SaveGame, map_swapped: bool) {
+    if let Some(mut scores) = world.get_resource_mut::<StrategicScores>() {
+        scores.teams = save.strategic_scores.clone();
+    }
+
+    if world.contains_resource::<VictoryProgress>() {
+        world.insert_resource(save.victory_progress.clone());
+    }
+    world.remove_resource::<MatchResult>();
+
+    // Intel is keyed by the old match's entities
+    if let Some(mut intel) = world.get_resource_mut::<EnemyIntel>() {
+        intel.reports.clear();
+    }
+
+    if let Some(mut fog) = world.get_resource_mut::<FogOfWar>() {
+        let explored: HashMap<Team, Vec<bool>> = save
+            .explored_tiles
+            .iter()
+            .map(|(team, tiles)| (*team, tiles.chars().map(|tile| tile == '#').collect()))
+            .collect();
+
+        // A new map resets the fog once it is in place, so the tiles wait for that
+        if map_swapped {
+            fog.restore_explored_on_new_map(explored);
+        } else {
+            fog.restore_explored(explored);
+        }
+    }
+}
+
+// Status effects with their sources pointed at the restored entities
+// Effects from sources that weren't saved keep running until they wear off
+fn restore_status_effects(saved: &[SavedStatusEffect], entity_map: &HashMap<u64, Entity>) -> StatusEffects {
+    StatusEffects {
+        effects: saved.iter().map(|status| StatusEffect {
+            effect: status.effect,
+            strength: status.strength,
+            remaining: status.remaining,
+            source: entity_map.get(&status.source).copied().unwrap_or(Entity::PLACEHOLDER),
+        }).collect(),
+    }
+}
+
+// Team colors used for restored entities
+fn team_color(team: Team) -> Color {
+    match team {
+        Team::Player => Color::srgba(0.2, 0.6, 0.8, 1.0),
+        Team::Enemy => Color::srgba(0.8, 0.2, 0.2, 1.0),
+        Team::Neutral => Color::srgba(0.7, 0.7, 0.7, 1.0),
+    }
+}
+
+// Spawn a saved base along with its modules
+fn restore_base(commands: &mut Commands, sprites: &GameSprites, saved: &SavedBase, entity_map: &mut HashMap<u64, Entity>) -> Entity {
+    let entity = commands.spawn((
+        sprites.base_sprite_bundle(saved.team, to_vec2(saved.position)),
+        MechanicalBase {
+            health: saved.health,
+            max_health: saved.max_health,
+            base_movement_speed: saved.base_movement_speed,
+            effective_movement_speed: saved.effective_movement_speed,
+            team: saved.team,
+            resources: saved.resources.clone(),
+            power_output: saved.power_output,
+            power_consumed: saved.power_consumed,
+            max_power: saved.max_power,
+            attachment_points: Vec::new(),
+            modules: Vec::new(),
+        },
+        saved.state.unwrap_or(UnitState::Idle),
+    )).id();
+
+    if let Some(name) = &saved.name {
+        commands.entity(entity).insert(Name::new(name.clone()));
+    }
+
+    if let Some(hull) = &saved.hull {
+        commands.entity(entity).insert(hull.clone());
+    }
+
+    if let Some(target) = saved.move_target {
+        commands.entity(entity).insert(BaseMoveTarget { target_position: to_vec2(target) });
+    }
+
+    if let Some(priority) = &saved.power_priority {
+        commands.entity(entity).insert(priority.clone());
+    }
+
+    if let Some(defenses) = &saved.defenses {
+        commands.entity(entity).insert(defenses.clone());
+    }
+
+    if let Some(ai) = &saved.ai {
+        let mut brain = AIBrain::new(ai.difficulty, to_vec2(ai.home));
+        brain.phase = ai.phase;
+        brain.objective_position = ai.objective.map(to_vec2);
+        brain.strength_ratio = ai.strength_ratio;
+        commands.entity(entity).insert((AIControlled { difficulty: ai.difficulty }, AIBase, brain));
+    }
+
+    for module in &saved.modules {
+        let offset = to_vec2(module.offset);
+        let module_entity = commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: if module.module.active { Color::WHITE } else { Color::srgb(0.5, 0.5, 0.5) },
+                    custom_size: Some(Vec2::new(15.0, 15.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(offset.x, offset.y, 0.1),
+                ..default()
+            },
+            module.module.clone(),
+        )).id();
+
+        if let Some(cooldown) = &module.cooldown {
+            commands.entity(module_entity).insert(Cooldown { timer: cooldown.to_timer() });
+        }
+
+        if let Some(power) = module.power {
+            commands.entity(module_entity).insert(power);
+        }
+
+        commands.entity(entity).add_child(module_entity);
+        entity_map.insert(module.id, module_entity);
+    }
+
+    for construction in &saved.constructions {
+        let offset = to_vec2(construction.offset);
+        let module_entity = commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: UNDER_CONSTRUCTION_COLOR,
+                    custom_size: Some(to_vec2(construction.size)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(offset.x, offset.y, 0.1)
+                    .with_rotation(Quat::from_rotation_z(construction.rotation)),
+                ..default()
+            },
+            AttachableModule {
+                module_type: construction.module_type.clone(),
+                size: to_vec2(construction.size),
+                build_time: construction.build_time,
+                build_progress: construction.build_progress,
+            },
+            Name::new(construction.catalog_name.clone()),
+        )).id();
+
+        commands.entity(entity).add_child(module_entity);
+        entity_map.insert(construction.id, module_entity);
+    }
+
+    entity
+}
+
+// Spawn a saved building
+fn restore_building(commands: &mut Commands, catalog: &BuildingCatalog, saved: &SavedBuilding) -> Entity {
+    let position = to_vec2(saved.position);
+    let team = saved.team.unwrap_or(Team::Neutral);
+
+    let entity = match saved.building_type {
+        Some(building_type) => building_type.spawn_building(commands, catalog, position, team),
+        None => commands.spawn(SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgba(0.7, 0.5, 0.3, 1.0),
+                custom_size: Some(Vec2::new(64.0, 64.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(position.x, position.y, 2.0),
+            ..default()
+        }).id(),
+    };
+
+    commands.entity(entity).insert(Building {
+        health: saved.health,
+        max_health: saved.max_health,
+        construction_progress: saved.construction_progress,
+        is_completed: saved.is_completed,
+    });
+
+    if let Some(team) = saved.team {
+        commands.entity(entity).insert(team);
+    }
+
+    if let Some(name) = &saved.name {
+        commands.entity(entity).insert(Name::new(name.clone()));
+    }
+
+    match &saved.spawner {
+        Some(spawner) => {
+            commands.entity(entity).insert(BuildingSpawner {
+                unit_type: spawner.unit_type,
+                queue: spawner.queue.clone(),
+                produces: spawner.produces.clone(),
+                spawn_time: spawner.spawn_time,
+                spawn_timer: spawner.spawn_timer.to_timer(),
+                in_production: spawner.in_production,
+            });
+        }
+        None => {
+            commands.entity(entity).remove::<BuildingSpawner>();
+        }
+    }
+
+    entity
+}
+
+// Spawn a saved unit; its stats and references are filled in once every entity exists
+fn restore_unit(commands: &mut Commands, catalog: &UnitCatalog, saved: &SavedUnit) -> Entity {
+    let position = to_vec2(saved.position);
+
+    let entity = match saved.unit_type {
+        Some(unit_type) => unit_type.spawn_unit(commands, catalog, position, saved.team),
+        None => commands.spawn(SpriteBundle {
+            sprite: Sprite {
+                color: team_color(saved.team),
+                custom_size: Some(Vec2::new(16.0, 16.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(position.x, position.y, 1.0),
+            ..default()
+        }).id(),
+    };
+
+    commands.entity(entity).insert(saved.state);
+
+    if let Some(name) = &saved.name {
+        commands.entity(entity).insert(Name::new(name.clone()));
+    }
+
+    if let Some(target) = saved.move_target {
+        commands.entity(entity).insert(MoveTarget { position: to_vec2(target) });
+    }
+
+    if let Some(hull) = &saved.hull {
+        commands.entity(entity).insert(hull.clone());
+    }
+
+    if let Some(experience) = saved.experience {
+        commands.entity(entity).insert(experience);
+    }
+
+    entity
+}
+
+// Translate the quick save and quick load keys into save/load requests
+fn handle_quick_save_keys(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    settings: Option<Res<GameSettings>>,
+    mut save_events: EventWriter<SaveGameEvent>,
+    mut load_events: EventWriter<LoadGameEvent>,
+) {
+    let bindings = settings.map(|settings| settings.key_bindings.clone()).unwrap_or_default();
+
+    if keyboard_input.just_pressed(bindings.quick_save) {
+        save_events.send(SaveGameEvent { path: PathBuf::from(QUICK_SAVE_PATH) });
+    }
+
+    if keyboard_input.just_pressed(bindings.quick_load) {
+        load_events.send(LoadGameEvent { path: PathBuf::from(QUICK_SAVE_PATH) });
+    }
+}
+
+// Write the match to disk for every pending save request
+fn handle_save_requests(world: &mut World) {
+    let requests: Vec<PathBuf> = world
+        .resource_mut::<Events<SaveGameEvent>>()
+        .drain()
+        .map(|event| event.path)
+        .collect();
+
+    for path in requests {
+        let save = capture_save(world);
+        match write_save_file(&path, &save) {
+            Ok(()) => info!("Game saved to {}", path.display()),
+            Err(err) => error!("Failed to save game to {}: {}", path.display(), err),
+        }
+    }
+}
+
+// Replace the match with the most recent pending load request
+fn handle_load_requests(world: &mut World) {
+    let request = world
+        .resource_mut::<Events<LoadGameEvent>>()
+        .drain()
+        .map(|event| event.path)
+        .last();
+
+    let Some(path) = request else { return };
+
+    match read_save_file(&path) {
+        Ok(save) => {
+            restore_save(world, &save);
+            info!("Game loaded from {}", path.display());
+        }
+        Err(err) => error!("Failed to load game from {}: {}", path.display(), err),
+    }
+}
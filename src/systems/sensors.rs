@@ -0,0 +1,297 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::components::base_modules::{BaseModule, InstalledModule, ModuleType};
+use crate::components::player::MechanicalBase;
+use crate::components::unit::{Team, Unit};
+use crate::resources::map_data::GameMap;
+use crate::states::game_state::{starting_new_match, GameState};
+use crate::systems::combat::is_hostile;
+use crate::systems::fog_of_war::FogOfWar;
+use crate::systems::module_effects::{Cooldown, ModulePower};
+use crate::systems::status_effects::StatusEffects;
+
+/// How far from its base a sensor can aim a scan, as a multiple of its detection radius
+pub const SCAN_RANGE_FACTOR: f32 = 3.0;
+
+/// How long a scan keeps its area revealed, in seconds
+pub const SCAN_DURATION: f32 = 8.0;
+
+/// Shortest time a sensor can take to recharge its scan
+const MIN_SCAN_COOLDOWN: f32 = 1.0;
+
+/// Colour of the circle drawn over a scanned area
+const SCAN_COLOR: Color = Color::srgba(0.3, 0.9, 1.0, 0.15);
+
+/// Request for a base's sensors to scan an area
+///
+/// The base's ready sensor with the widest detection radius answers. Targets further away than
+/// it can reach are pulled back to the edge of its range.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ScanRequest {
+    pub base: Entity,
+    pub target: Vec2,
+}
+
+/// An area revealed by a sensor scan until it runs out
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SensorScan {
+    pub team: Team,
+    pub position: Vec2,
+    pub radius: f32,            // Area revealed, in world units
+    pub stealth_detection: f32, // Share of the radius within which cloaked entities are exposed
+    pub remaining: f32,         // Seconds until the scan fades
+}
+
+/// Teams whose sensors have picked up a cloaked entity
+///
+/// A cloaked entity stays hidden and untargetable for every other team.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct Detected {
+    pub teams: Vec<Team>,
+}
+
+impl Detected {
+    /// Whether a team has picked the entity up
+    pub fn by(&self, team: Team) -> bool {
+        self.teams.contains(&team)
+    }
+}
+
+/// Whether an entity is hidden from a team by a cloak that team hasn't detected
+pub fn is_concealed_from(team: Team, statuses: Option<&StatusEffects>, detected: Option<&Detected>) -> bool {
+    statuses.is_some_and(StatusEffects::is_cloaked) && !detected.is_some_and(|detected| detected.by(team))
+}
+
+/// What the viewer last saw of one enemy base
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntelReport {
+    pub team: Team,
+    pub position: Vec2,
+    pub modules: Vec<String>, // Names of the finished modules on the base
+    pub seen_at: f32,         // Seconds since startup when the base was last in sight
+}
+
+/// Module loadouts of the enemy bases the viewer has scouted
+///
+/// A report is refreshed whenever the base is in sight, and kept while it is out of sight.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct EnemyIntel {
+    pub reports: HashMap<Entity, IntelReport>,
+}
+
+// Sensor systems plugin
+pub struct SensorPlugin;
+
+impl Plugin for SensorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemyIntel>()
+           .init_resource::<FogOfWar>()
+           .add_event::<ScanRequest>()
+           .add_systems(OnEnter(GameState::Gameplay), reset_enemy_intel.run_if(starting_new_match))
+           .add_systems(
+                Update,
+                (
+                    charge_sensor_scans,
+                    handle_scan_requests,
+                    expire_sensor_scans,
+                    detect_cloaked_entities,
+                    record_enemy_intel,
+                ).chain().run_if(in_state(GameState::Gameplay))
+            );
+
+        info!("Sensor Plugin initialized");
+    }
+}
+
+/// System to recharge the scan of every working sensor module
+///
+/// Sensors start uncharged, and recharge more slowly when short of power.
+pub fn charge_sensor_scans(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut sensors: Query<(Entity, &BaseModule, Option<&mut Cooldown>, Option<&ModulePower>)>,
+) {
+    for (entity, module, cooldown, power) in sensors.iter_mut() {
+        let ModuleType::Sensor { scan_cooldown, .. } = module.module_type else { continue };
+        if !module.active {
+            continue;
+        }
+
+        match cooldown {
+            Some(mut cooldown) => {
+                cooldown.timer.tick(time.delta().mul_f32(power.map_or(1.0, |power| power.supply)));
+            }
+            None => {
+                let timer = Timer::from_seconds(scan_cooldown.max(MIN_SCAN_COOLDOWN), TimerMode::Once);
+                commands.entity(entity).insert(Cooldown { timer });
+            }
+        }
+    }
+}
+
+/// System to fire scans for bases whose sensors are charged
+pub fn handle_scan_requests(
+    mut commands: Commands,
+    mut requests: EventReader<ScanRequest>,
+    bases: Query<(&Transform, &MechanicalBase, &Children)>,
+    mut sensors: Query<(&BaseModule, &mut Cooldown)>,
+) {
+    for request in requests.read() {
+        let Ok((transform, base, children)) = bases.get(request.base) else { continue };
+
+        // The ready sensor that reaches furthest takes the scan
+        let sensor = children
+            .iter()
+            .filter_map(|&child| {
+                let (module, cooldown) = sensors.get(child).ok()?;
+                match module.module_type {
+                    ModuleType::Sensor { detection_radius, stealth_detection, .. }
+                        if module.active && cooldown.timer.finished() => Some((child, detection_radius, stealth_detection)),
+                    _ => None,
+                }
+            })
+            .max_by(|(_, a, _), (_, b, _)| a.total_cmp(b));
+        let Some((sensor, radius, stealth_detection)) = sensor else {
+            debug!("{:?} base has no sensor ready to scan", base.team);
+            continue;
+        };
+
+        let origin = transform.translation.truncate();
+        let max_range = radius * SCAN_RANGE_FACTOR;
+        let offset = request.target - origin;
+        let position = if offset.length() > max_range { origin + offset.normalize() * max_range } else { request.target };
+
+        if let Ok((_, mut cooldown)) = sensors.get_mut(sensor) {
+            cooldown.timer.reset();
+        }
+
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: SCAN_COLOR,
+                    custom_size: Some(Vec2::splat(radius * 2.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(0.05)),
+                ..default()
+            },
+            SensorScan { team: base.team, position, radius, stealth_detection, remaining: SCAN_DURATION },
+            Name::new("Sensor Scan"),
+        ));
+        info!("{:?} base scanned the area around {:?}", base.team, position);
+    }
+}
+
+// System to fade scans out and remove them once they run out
+fn expire_sensor_scans(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut scans: Query<(Entity, &mut SensorScan, Option<&mut Sprite>)>,
+) {
+    for (entity, mut scan, sprite) in scans.iter_mut() {
+        scan.remaining -= time.delta_seconds();
+        if scan.remaining <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+        } else if let Some(mut sprite) = sprite {
+            sprite.color.set_alpha(SCAN_COLOR.alpha() * (scan.remaining / SCAN_DURATION).min(1.0));
+        }
+    }
+}
+
+/// System to work out which teams have picked up each cloaked unit or base
+///
+/// A working sensor exposes cloaked enemies within `detection_radius * stealth_detection` of its
+/// base, shrinking with its power supply; a scan does the same within its own radius.
+pub fn detect_cloaked_entities(
+    mut commands: Commands,
+    bases: Query<(&Transform, &MechanicalBase, &Children)>,
+    sensors: Query<(&BaseModule, Option<&ModulePower>)>,
+    scans: Query<&SensorScan>,
+    mut cloaked: Query<(Entity, &Transform, Option<&Unit>, Option<&MechanicalBase>, &StatusEffects, Option<&mut Detected>)>,
+) {
+    // Every team's detection circles: (team, centre, radius)
+    let mut detectors: Vec<(Team, Vec2, f32)> = scans
+        .iter()
+        .map(|scan| (scan.team, scan.position, scan.radius * scan.stealth_detection))
+        .collect();
+    for (transform, base, children) in bases.iter() {
+        for &child in children.iter() {
+            let Ok((module, power)) = sensors.get(child) else { continue };
+            let ModuleType::Sensor { detection_radius, stealth_detection, .. } = module.module_type else { continue };
+            if module.active {
+                let supply = power.map_or(1.0, |power| power.supply);
+                detectors.push((base.team, transform.translation.truncate(), detection_radius * stealth_detection * supply));
+            }
+        }
+    }
+
+    for (entity, transform, unit, base, statuses, detected) in cloaked.iter_mut() {
+        if !statuses.is_cloaked() {
+            if detected.is_some() {
+                commands.entity(entity).remove::<Detected>();
+            }
+            continue;
+        }
+        let Some(owner) = unit.map(|unit| unit.team).or(base.map(|base| base.team)) else { continue };
+
+        let position = transform.translation.truncate();
+        let mut teams: Vec<Team> = Vec::new();
+        for &(team, center, radius) in &detectors {
+            if is_hostile(team, owner) && !teams.contains(&team) && position.distance(center) <= radius {
+                teams.push(team);
+            }
+        }
+
+        match detected {
+            Some(mut detected) => {
+                if detected.teams != teams {
+                    detected.teams = teams;
+                }
+            }
+            None => {
+                commands.entity(entity).insert(Detected { teams });
+            }
+        }
+    }
+}
+
+/// System to note down the modules on every enemy base the viewer can currently see
+pub fn record_enemy_intel(
+    time: Res<Time>,
+    fog: Res<FogOfWar>,
+    game_map: Option<Res<GameMap>>,
+    mut intel: ResMut<EnemyIntel>,
+    bases: Query<(Entity, &Transform, &MechanicalBase, Option<&Children>, Option<&StatusEffects>, Option<&Detected>)>,
+    modules: Query<(&BaseModule, Option<&InstalledModule>)>,
+) {
+    let Some(game_map) = game_map else { return };
+
+    // Forget bases that have been destroyed
+    intel.reports.retain(|&entity, _| bases.contains(entity));
+
+    for (entity, transform, base, children, statuses, detected) in bases.iter() {
+        let position = transform.translation.truncate();
+        if !is_hostile(fog.viewer, base.team)
+            || is_concealed_from(fog.viewer, statuses, detected)
+            || !fog.is_visible_at(fog.viewer, &game_map, position)
+        {
+            continue;
+        }
+
+        let loadout = children
+            .into_iter()
+            .flatten()
+            .filter_map(|&child| modules.get(child).ok())
+            .map(|(module, installed)| {
+                installed.map_or_else(|| format!("{:?}", module.module_type.category()), |installed| installed.name.clone())
+            })
+            .collect();
+
+        intel.reports.insert(entity, IntelReport { team: base.team, position, modules: loadout, seen_at: time.elapsed_seconds() });
+    }
+}
+
+// System to start every match without any scouting reports
+fn reset_enemy_intel(mut intel: ResMut<EnemyIntel>) {
+    intel.reports.clear();
+}
@@ -0,0 +1,252 @@
+use bevy::prelude::*;
+use crate::components::base_modules::{BaseModule, StackRule, UtilityEffect};
+use crate::components::player::MechanicalBase;
+use crate::components::unit::Unit;
+use crate::states::game_state::GameState;
+use crate::systems::module_effects::Effect;
+
+/// Most a penalty (Slow, Jammer) can take off a unit's speed, damage or attack rate
+pub const MAX_PENALTY: f32 = 0.9;
+
+/// Size of the marker drawn above an entity under a status effect
+const STATUS_ICON_SIZE: f32 = 6.0;
+
+/// One status effect on an entity, from one source
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct StatusEffect {
+    pub effect: UtilityEffect,
+    pub strength: f32,  // What `strength` means depends on the effect, see `StatusEffects`
+    pub remaining: f32, // Seconds until the effect wears off
+    pub source: Entity, // Module that applied it
+}
+
+/// Status effects currently on a unit or base
+///
+/// Reapplying an effect from the same source refreshes it; effects from different sources
+/// combine by the effect's `StackRule`. Strengths mean:
+/// - Repair, Heal: hit points restored per second (Repair for bases and their modules, Heal for units)
+/// - SpeedBoost, DamageAmp, ResourceBoost: fraction added to speed, damage or gathering
+/// - Slow, Jammer: fraction taken off speed and attack rate, or off damage
+/// - ShieldBoost: hit points of damage absorbed before the unit is hurt
+/// - Cloak, Reveal, Stun: on or off; Reveal cancels Cloak
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    /// Apply an effect, refreshing it if this source already applied one
+    pub fn apply(&mut self, effect: UtilityEffect, strength: f32, duration: f32, source: Entity) {
+        match self.effects.iter_mut().find(|status| status.effect == effect && status.source == source) {
+            Some(status) => {
+                status.strength = strength;
+                status.remaining = status.remaining.max(duration);
+            }
+            None => self.effects.push(StatusEffect { effect, strength, remaining: duration, source }),
+        }
+    }
+
+    /// Combined strength of an effect, or zero if it isn't active
+    pub fn strength(&self, effect: UtilityEffect) -> f32 {
+        let strengths = self.effects.iter().filter(|status| status.effect == effect).map(|status| status.strength);
+        match effect.stack_rule() {
+            StackRule::Strongest => strengths.fold(0.0, f32::max),
+            StackRule::Sum => strengths.sum(),
+        }
+    }
+
+    /// Whether an effect is active at all
+    pub fn has(&self, effect: UtilityEffect) -> bool {
+        self.effects.iter().any(|status| status.effect == effect)
+    }
+
+    /// Stunned entities can't move or attack
+    pub fn is_stunned(&self) -> bool {
+        self.has(UtilityEffect::Stun)
+    }
+
+    /// Cloaked entities can't be seen or targeted by enemies unless they are revealed
+    pub fn is_cloaked(&self) -> bool {
+        self.has(UtilityEffect::Cloak) && !self.has(UtilityEffect::Reveal)
+    }
+
+    /// Multiplier on movement speed
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.is_stunned() {
+            return 0.0;
+        }
+        (1.0 + self.strength(UtilityEffect::SpeedBoost)) * (1.0 - self.strength(UtilityEffect::Slow).min(MAX_PENALTY))
+    }
+
+    /// Multiplier on how quickly attacks come off cooldown
+    pub fn attack_rate_multiplier(&self) -> f32 {
+        if self.is_stunned() {
+            return 0.0;
+        }
+        1.0 - self.strength(UtilityEffect::Slow).min(MAX_PENALTY)
+    }
+
+    /// Multiplier on damage dealt
+    pub fn damage_multiplier(&self) -> f32 {
+        (1.0 + self.strength(UtilityEffect::DamageAmp)) * (1.0 - self.strength(UtilityEffect::Jammer).min(MAX_PENALTY))
+    }
+
+    /// Multiplier on resources gathered
+    pub fn gather_multiplier(&self) -> f32 {
+        1.0 + self.strength(UtilityEffect::ResourceBoost)
+    }
+
+    /// Soak up damage with any boosted shields, returning the damage that gets through
+    pub fn absorb(&mut self, damage: f32) -> f32 {
+        let mut remaining = damage;
+        for status in self.effects.iter_mut().filter(|status| status.effect == UtilityEffect::ShieldBoost) {
+            let absorbed = status.strength.min(remaining);
+            status.strength -= absorbed;
+            remaining -= absorbed;
+        }
+        self.effects.retain(|status| status.effect != UtilityEffect::ShieldBoost || status.strength > 0.0);
+        remaining
+    }
+
+    /// Run effects down and drop the ones that have worn off
+    pub fn tick(&mut self, delta: f32) {
+        for status in self.effects.iter_mut() {
+            status.remaining -= delta;
+        }
+        self.effects.retain(|status| status.remaining > 0.0);
+    }
+}
+
+/// Colour used to show an effect on the map
+pub fn effect_color(effect: UtilityEffect) -> Color {
+    match effect {
+        UtilityEffect::Repair => Color::srgb(0.95, 0.75, 0.2),
+        UtilityEffect::Heal => Color::srgb(0.3, 0.95, 0.4),
+        UtilityEffect::Cloak => Color::srgb(0.55, 0.55, 0.7),
+        UtilityEffect::Jammer => Color::srgb(0.75, 0.3, 0.85),
+        UtilityEffect::ShieldBoost => Color::srgb(0.3, 0.65, 1.0),
+        UtilityEffect::SpeedBoost => Color::srgb(0.3, 0.95, 0.95),
+        UtilityEffect::DamageAmp => Color::srgb(1.0, 0.35, 0.2),
+        UtilityEffect::Stun => Color::srgb(1.0, 1.0, 0.4),
+        UtilityEffect::Slow => Color::srgb(0.35, 0.45, 0.9),
+        UtilityEffect::Reveal => Color::srgb(1.0, 1.0, 1.0),
+        UtilityEffect::Teleport => Color::srgb(0.85, 0.4, 0.95),
+        UtilityEffect::ResourceBoost => Color::srgb(0.6, 0.9, 0.2),
+    }
+}
+
+// Component for the marker drawn above an entity with status effects
+#[derive(Component)]
+pub struct StatusIcon;
+
+// Plugin that runs status effects down and draws them
+pub struct StatusEffectsPlugin;
+
+impl Plugin for StatusEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<StatusEffects>()
+           .add_systems(
+                Update,
+                (
+                    tick_status_effects,
+                    update_status_icons,
+                    fade_effect_pulses,
+                ).chain().run_if(in_state(GameState::Gameplay))
+            );
+
+        info!("Status Effects Plugin initialized");
+    }
+}
+
+/// System to apply healing over time and expire status effects
+pub fn tick_status_effects(
+    time: Res<Time>,
+    mut affected: Query<(&mut StatusEffects, Option<&mut Unit>, Option<&mut MechanicalBase>, Option<&Children>)>,
+    mut modules: Query<&mut BaseModule>,
+) {
+    let delta = time.delta_seconds();
+
+    for (mut statuses, unit, base, children) in affected.iter_mut() {
+        if statuses.effects.is_empty() {
+            continue;
+        }
+
+        let heal = statuses.strength(UtilityEffect::Heal) * delta;
+        if let Some(mut unit) = unit.filter(|_| heal > 0.0) {
+            unit.health = (unit.health + heal).min(unit.max_health);
+        }
+
+        // Repair patches up the base and every module on it
+        let repair = statuses.strength(UtilityEffect::Repair) * delta;
+        if let Some(mut base) = base.filter(|_| repair > 0.0) {
+            base.health = (base.health + repair).min(base.max_health);
+            for &child in children.into_iter().flatten() {
+                if let Ok(mut module) = modules.get_mut(child) {
+                    module.health = (module.health + repair).min(module.max_health);
+                }
+            }
+        }
+
+        statuses.tick(delta);
+    }
+}
+
+// System to show a marker above anything with a status effect, coloured by the longest-lasting one
+fn update_status_icons(
+    mut commands: Commands,
+    affected: Query<(Entity, &StatusEffects, Option<&Children>)>,
+    mut icons: Query<&mut Sprite, With<StatusIcon>>,
+) {
+    for (entity, statuses, children) in affected.iter() {
+        let icon = children.into_iter().flatten().copied().find(|child| icons.contains(*child));
+        let strongest = statuses
+            .effects
+            .iter()
+            .max_by(|a, b| a.remaining.total_cmp(&b.remaining))
+            .map(|status| effect_color(status.effect));
+
+        match (icon, strongest) {
+            (Some(icon), Some(color)) => {
+                if let Ok(mut sprite) = icons.get_mut(icon) {
+                    sprite.color = color;
+                }
+            }
+            (Some(icon), None) => commands.entity(icon).despawn_recursive(),
+            (None, Some(color)) => {
+                let icon = commands
+                    .spawn((
+                        SpriteBundle {
+                            sprite: Sprite {
+                                color,
+                                custom_size: Some(Vec2::splat(STATUS_ICON_SIZE)),
+                                ..default()
+                            },
+                            transform: Transform::from_xyz(0.0, 14.0, 0.5),
+                            ..default()
+                        },
+                        StatusIcon,
+                    ))
+                    .id();
+                commands.entity(entity).add_child(icon);
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+// System to fade out the pulses utility modules leave when they fire
+fn fade_effect_pulses(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pulses: Query<(Entity, &mut Effect, &mut Sprite)>,
+) {
+    for (entity, mut effect, mut sprite) in pulses.iter_mut() {
+        effect.duration -= time.delta_seconds();
+        if effect.duration <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            sprite.color.set_alpha(effect.duration.min(1.0) * 0.3);
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+use crate::components::economy::ResourceWallet;
+use crate::components::resource::Gatherer;
+use crate::components::unit::{Team, Unit};
+use crate::components::unit_types::UnitType;
+use crate::sprites::GameSprites;
+use crate::units::engineer::Engineer;
+use crate::utils::ron_asset::{RonAsset, RonAssetLoader};
+
+/// Path of the unit catalog shipped with the game, relative to the assets folder
+pub const UNIT_CATALOG_PATH: &str = "data/catalog.units.ron";
+
+/// Built-in copy of the unit catalog, used until the asset finishes loading
+const BUILTIN_UNIT_CATALOG: &str = include_str!("../../assets/data/catalog.units.ron");
+
+/// Descriptive tags attached to unit definitions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum UnitTag {
+    Builder,    // Can construct buildings
+    Gatherer,   // Can harvest resource nodes
+    Combat,     // Counts toward army strength
+    Land,
+    Air,
+    AntiAir,    // Effective against aircraft
+    Siege,      // Long range, slow
+    Large,
+}
+
+/// Stats and production data for a single unit type
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct UnitDefinition {
+    pub health: f32,
+    pub attack_power: f32,
+    pub attack_range: f32,
+    pub movement_speed: f32,
+    #[serde(default = "default_attack_cooldown")]
+    pub attack_cooldown: f32,              // Seconds between attacks
+    #[serde(default)]
+    pub cost: ResourceWallet,              // Resources charged when produced
+    #[serde(default = "default_build_time")]
+    pub build_time: f32,                   // Seconds to produce
+    #[serde(default)]
+    pub sprite: Option<String>,            // GameSprites key prefix, e.g. "tank"
+    #[serde(default = "default_size")]
+    pub size: f32,                         // Placeholder square size when there is no sprite
+    #[serde(default)]
+    pub tags: Vec<UnitTag>,
+    #[serde(default = "default_build_speed")]
+    pub build_speed: f32,                  // Health an engineer repairs per second, for Builder units
+    #[serde(default = "default_gather_rate")]
+    pub gather_rate: f32,                  // Amount harvested each second, for Gatherer units
+    #[serde(default = "default_carry_capacity")]
+    pub carry_capacity: i32,               // Most a Gatherer unit carries back to base at once
+}
+
+fn default_attack_cooldown() -> f32 {
+    1.0
+}
+
+fn default_build_time() -> f32 {
+    10.0
+}
+
+fn default_size() -> f32 {
+    16.0
+}
+
+fn default_build_speed() -> f32 {
+    10.0
+}
+
+fn default_gather_rate() -> f32 {
+    5.0
+}
+
+fn default_carry_capacity() -> i32 {
+    20
+}
+
+impl Default for UnitDefinition {
+    fn default() -> Self {
+        Self {
+            health: 100.0,
+            attack_power: 10.0,
+            attack_range: 5.0,
+            movement_speed: 40.0,
+            attack_cooldown: default_attack_cooldown(),
+            cost: ResourceWallet::new(),
+            build_time: default_build_time(),
+            sprite: None,
+            size: default_size(),
+            tags: Vec::new(),
+            build_speed: default_build_speed(),
+            gather_rate: default_gather_rate(),
+            carry_capacity: default_carry_capacity(),
+        }
+    }
+}
+
+impl UnitDefinition {
+    /// Whether this unit has the given tag
+    pub fn has_tag(&self, tag: UnitTag) -> bool {
+        self.tags.contains(&tag)
+    }
+}
+
+/// Data-driven unit stats, loaded from `assets/data/*.units.ron`
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+pub struct UnitCatalog {
+    pub version: u32,
+    pub units: HashMap<UnitType, UnitDefinition>,
+}
+
+impl RonAsset for UnitCatalog {
+    fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        for (unit_type, definition) in &self.units {
+            if definition.health <= 0.0 {
+                problems.push(format!("{}: health must be positive (got {})", unit_type.name(), definition.health));
+            }
+            if definition.carry_capacity <= 0 && definition.has_tag(UnitTag::Gatherer) {
+                problems.push(format!("{}: carry_capacity must be positive (got {})", unit_type.name(), definition.carry_capacity));
+            }
+            if definition.build_time <= 0.0 {
+                problems.push(format!("{}: build_time must be positive (got {})", unit_type.name(), definition.build_time));
+            }
+            for (resource_type, amount) in definition.cost.iter() {
+                if amount < 0 {
+                    problems.push(format!("{}: cost of {:?} is negative ({})", unit_type.name(), resource_type, amount));
+                }
+            }
+        }
+
+        if problems.is_empty() { Ok(()) } else { Err(problems) }
+    }
+}
+
+impl Default for UnitCatalog {
+    fn default() -> Self {
+        ron::from_str(BUILTIN_UNIT_CATALOG).expect("built-in unit catalog should be valid RON")
+    }
+}
+
+impl UnitCatalog {
+    /// Definition for a unit type, if the catalog has one
+    pub fn get(&self, unit_type: UnitType) -> Option<&UnitDefinition> {
+        self.units.get(&unit_type)
+    }
+
+    /// Definition for a unit type, falling back to generic stats if it is missing
+    pub fn definition(&self, unit_type: UnitType) -> UnitDefinition {
+        self.get(unit_type).cloned().unwrap_or_default()
+    }
+
+    /// Overlay another catalog on top of this one, keeping entries it doesn't mention
+    pub fn merge(&mut self, other: &UnitCatalog) {
+        self.version = other.version;
+        for (unit_type, definition) in &other.units {
+            self.units.insert(*unit_type, definition.clone());
+        }
+    }
+}
+
+/// The unit catalog currently in effect
+#[derive(Resource, Default)]
+pub struct UnitDefinitions {
+    pub catalog: UnitCatalog,
+    pub handle: Option<Handle<UnitCatalog>>,
+}
+
+/// Plugin that loads the unit catalog asset and keeps `UnitDefinitions` in sync with it
+pub struct UnitCatalogPlugin;
+
+impl Plugin for UnitCatalogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UnitDefinitions>()
+           .init_asset::<UnitCatalog>()
+           .register_asset_loader(RonAssetLoader::<UnitCatalog>::new(&["units.ron"]))
+           .add_systems(Startup, load_unit_catalog)
+           .add_systems(Update, ((sync_unit_catalog, refresh_unit_stats).chain(), attach_unit_sprites));
+    }
+}
+
+// Start loading the unit catalog from disk
+fn load_unit_catalog(
+    asset_server: Res<AssetServer>,
+    mut definitions: ResMut<UnitDefinitions>,
+) {
+    definitions.handle = Some(asset_server.load(UNIT_CATALOG_PATH));
+}
+
+// Copy the catalog into `UnitDefinitions` whenever it is loaded or edited on disk
+fn sync_unit_catalog(
+    mut events: EventReader<AssetEvent<UnitCatalog>>,
+    catalogs: Res<Assets<UnitCatalog>>,
+    mut definitions: ResMut<UnitDefinitions>,
+) {
+    for event in events.read() {
+        let id = match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => *id,
+            _ => continue,
+        };
+
+        if definitions.handle.as_ref().map(|handle| handle.id()) != Some(id) {
+            continue;
+        }
+
+        if let Some(catalog) = catalogs.get(id) {
+            info!("Unit catalog v{} loaded ({} units)", catalog.version, catalog.units.len());
+            definitions.catalog.merge(catalog);
+        }
+    }
+}
+
+// Apply rebalanced stats to units already on the field, keeping their health percentage
+fn refresh_unit_stats(
+    definitions: Res<UnitDefinitions>,
+    mut units: Query<(&mut Unit, &UnitType, Option<&mut Engineer>, Option<&mut Gatherer>)>,
+) {
+    if !definitions.is_changed() || definitions.is_added() {
+        return;
+    }
+
+    for (mut unit, unit_type, engineer, gatherer) in units.iter_mut() {
+        let Some(definition) = definitions.catalog.get(*unit_type) else { continue };
+
+        let health_fraction = if unit.max_health > 0.0 { unit.health / unit.max_health } else { 1.0 };
+        unit.max_health = definition.health;
+        unit.health = definition.health * health_fraction;
+        unit.attack_power = definition.attack_power;
+        unit.attack_range = definition.attack_range;
+        unit.movement_speed = definition.movement_speed;
+        unit.attack_cooldown.set_duration(Duration::from_secs_f32(definition.attack_cooldown));
+
+        if let Some(mut engineer) = engineer {
+            engineer.build_speed = definition.build_speed;
+        }
+        if let Some(mut gatherer) = gatherer {
+            gatherer.gather_rate = definition.gather_rate;
+            gatherer.carry_capacity = definition.carry_capacity;
+        }
+    }
+}
+
+// Give newly spawned units the sprite named in their catalog entry, whoever spawned them
+// Sprites are keyed by the catalog prefix and team, e.g. "tank_player"
+fn attach_unit_sprites(
+    mut commands: Commands,
+    definitions: Res<UnitDefinitions>,
+    game_sprites: Option<Res<GameSprites>>,
+    units: Query<(Entity, &Unit, &UnitType), Added<UnitType>>,
+) {
+    let Some(game_sprites) = game_sprites.filter(|sprites| sprites.is_loaded) else { return };
+
+    for (entity, unit, unit_type) in units.iter() {
+        let Some(sprite) = definitions.catalog.get(*unit_type).and_then(|definition| definition.sprite.as_ref()) else {
+            continue;
+        };
+
+        // Player units face right and the others face left
+        let (team_suffix, direction) = if unit.team == Team::Player { ("player", 0) } else { ("enemy", 4) };
+        if let Some(texture) = game_sprites.get_unit_sprite(&format!("{}_{}", sprite, team_suffix), direction) {
+            commands.entity(entity).insert(texture.clone());
+        }
+    }
+}
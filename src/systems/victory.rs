@@ -0,0 +1,220 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use crate::components::player::{MechanicalBase, StrategicTarget};
+use crate::components::strategic::{score_strategic_locations, StrategicLocation, StrategicScores};
+use crate::components::unit::Team;
+use crate::states::game_state::{starting_new_match, GameState};
+
+/// Teams that can win or lose a match
+const COMPETING_TEAMS: [Team; 2] = [Team::Player, Team::Enemy];
+
+/// Ways a match can be won
+///
+/// Rules set to `None` or `false` are switched off. The first rule a team meets ends the match.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct VictoryRules {
+    pub hold_location_seconds: Option<f32>, // Hold the most valuable location this long
+    pub hold_majority_seconds: Option<f32>, // Hold more than half the locations this long
+    pub destroy_enemy_bases: bool,          // Win once every enemy base is gone
+    pub score_limit: Option<i32>,           // First team to this strategic score wins
+}
+
+impl Default for VictoryRules {
+    fn default() -> Self {
+        Self {
+            hold_location_seconds: Some(120.0),
+            hold_majority_seconds: Some(60.0),
+            destroy_enemy_bases: true,
+            score_limit: Some(500),
+        }
+    }
+}
+
+/// Why a match ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum VictoryReason {
+    HeldLocation { name: String, seconds: f32 },
+    HeldMajority { held: usize, total: usize, seconds: f32 },
+    BasesDestroyed,
+    ScoreLimit { score: i32 },
+}
+
+/// Outcome of the match that just ended, shown on the victory and game over screens
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct MatchResult {
+    pub winner: Team,
+    pub reason: VictoryReason,
+}
+
+impl MatchResult {
+    /// Whether the local player won
+    pub fn player_won(&self) -> bool {
+        self.winner == Team::Player
+    }
+
+    /// One line explaining the result from the player's point of view
+    pub fn describe(&self) -> String {
+        let winner = if self.player_won() { "You" } else { "The enemy" };
+        match &self.reason {
+            VictoryReason::HeldLocation { name, seconds } => {
+                format!("{} held {} for {:.0} seconds", winner, name, seconds)
+            }
+            VictoryReason::HeldMajority { held, total, seconds } => {
+                format!("{} held {} of {} strategic locations for {:.0} seconds", winner, held, total, seconds)
+            }
+            VictoryReason::BasesDestroyed if self.player_won() => "Every enemy base has been destroyed!".to_string(),
+            VictoryReason::BasesDestroyed => "Your base has been destroyed!".to_string(),
+            VictoryReason::ScoreLimit { score } => format!("{} reached {} points", winner, score),
+        }
+    }
+}
+
+/// Progress toward the rules that take more than one frame to meet
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VictoryProgress {
+    pub majority_held: HashMap<Team, f32>, // Seconds each team has held a majority without a break
+    pub teams_with_bases: HashSet<Team>,   // Teams that have fielded a base this match
+}
+
+// Plugin that checks the victory rules and ends the match
+pub struct VictoryPlugin;
+
+impl Plugin for VictoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VictoryRules>()
+           .init_resource::<VictoryProgress>()
+           .add_systems(OnEnter(GameState::Gameplay), reset_victory_progress.run_if(starting_new_match))
+           .add_systems(
+                Update,
+                (
+                    track_location_holds,
+                    evaluate_victory_rules,
+                ).chain().after(score_strategic_locations).run_if(in_state(GameState::Gameplay))
+            );
+
+        info!("Victory Plugin initialized");
+    }
+}
+
+// Forget the last match when a new one starts
+fn reset_victory_progress(
+    mut commands: Commands,
+    mut progress: ResMut<VictoryProgress>,
+) {
+    *progress = VictoryProgress::default();
+    commands.remove_resource::<MatchResult>();
+}
+
+/// System to time how long each strategic location has been held by its current owner
+fn track_location_holds(
+    mut commands: Commands,
+    time: Res<Time>,
+    untracked: Query<(Entity, &StrategicLocation), Without<StrategicTarget>>,
+    mut targets: Query<(&StrategicLocation, &mut StrategicTarget)>,
+) {
+    for (entity, location) in untracked.iter() {
+        commands.entity(entity).insert(StrategicTarget {
+            position: location.position,
+            ..default()
+        });
+    }
+
+    for (location, mut target) in targets.iter_mut() {
+        if location.controlling_team.is_some() && location.controlling_team == target.held_by {
+            target.time_held += time.delta_seconds();
+        } else {
+            target.held_by = location.controlling_team;
+            target.time_held = 0.0;
+        }
+        target.is_reached = target.held_by.is_some();
+    }
+}
+
+/// System to check every victory rule and move to the victory or game over screen when one is met
+fn evaluate_victory_rules(
+    mut commands: Commands,
+    time: Res<Time>,
+    rules: Res<VictoryRules>,
+    mut progress: ResMut<VictoryProgress>,
+    scores: Res<StrategicScores>,
+    locations: Query<(&StrategicLocation, Option<&StrategicTarget>)>,
+    bases: Query<&MechanicalBase>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // Keep the majority timers running for whoever holds more than half the locations
+    let total = locations.iter().count();
+    for team in COMPETING_TEAMS {
+        let held = locations.iter().filter(|(location, _)| location.controlling_team == Some(team)).count();
+        if held * 2 > total {
+            *progress.majority_held.entry(team).or_default() += time.delta_seconds();
+        } else {
+            progress.majority_held.remove(&team);
+        }
+    }
+
+    let standing: HashSet<Team> = bases.iter().filter(|base| base.health > 0.0).map(|base| base.team).collect();
+    progress.teams_with_bases.extend(standing.iter().copied());
+
+    let Some(result) = check_rules(&rules, &progress, &scores, &locations, &standing) else {
+        return;
+    };
+
+    info!("{:?} wins: {:?}", result.winner, result.reason);
+    next_state.set(if result.player_won() { GameState::Victory } else { GameState::GameOver });
+    commands.insert_resource(result);
+}
+
+// First rule met by any team, checked in the order the rules are listed
+fn check_rules(
+    rules: &VictoryRules,
+    progress: &VictoryProgress,
+    scores: &StrategicScores,
+    locations: &Query<(&StrategicLocation, Option<&StrategicTarget>)>,
+    standing: &HashSet<Team>,
+) -> Option<MatchResult> {
+    if let Some(seconds) = rules.hold_location_seconds {
+        // Only the most valuable locations count, so an undefended flank can't win the match
+        let top_value = locations.iter().map(|(location, _)| location.capture_value).max().unwrap_or(0);
+        let held_long_enough = locations.iter().find_map(|(location, target)| {
+            let target = target?;
+            let winner = target.held_by?;
+            (location.capture_value == top_value && target.time_held >= seconds).then_some((winner, location.name.clone()))
+        });
+        if let Some((winner, name)) = held_long_enough {
+            return Some(MatchResult { winner, reason: VictoryReason::HeldLocation { name, seconds } });
+        }
+    }
+
+    if let Some(seconds) = rules.hold_majority_seconds {
+        let total = locations.iter().count();
+        for team in COMPETING_TEAMS {
+            if progress.majority_held.get(&team).is_some_and(|held_for| *held_for >= seconds) {
+                let held = locations.iter().filter(|(location, _)| location.controlling_team == Some(team)).count();
+                return Some(MatchResult { winner: team, reason: VictoryReason::HeldMajority { held, total, seconds } });
+            }
+        }
+    }
+
+    if rules.destroy_enemy_bases {
+        // A team is beaten once every base it fielded is gone; the player losing theirs comes first
+        let beaten = |team: Team| progress.teams_with_bases.contains(&team) && !standing.contains(&team);
+        if beaten(Team::Player) {
+            return Some(MatchResult { winner: Team::Enemy, reason: VictoryReason::BasesDestroyed });
+        }
+        if beaten(Team::Enemy) {
+            return Some(MatchResult { winner: Team::Player, reason: VictoryReason::BasesDestroyed });
+        }
+    }
+
+    if let Some(limit) = rules.score_limit {
+        for team in COMPETING_TEAMS {
+            let score = scores.team(team).score;
+            if score >= limit {
+                return Some(MatchResult { winner: team, reason: VictoryReason::ScoreLimit { score } });
+            }
+        }
+    }
+
+    None
+}
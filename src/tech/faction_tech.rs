@@ -5,7 +5,7 @@
 
 use bevy::prelude::*;
 use std::collections::HashMap;
-use crate::resources::ResourceType;
+use crate::components::economy::{ResourceType, ResourceWallet};
 use super::tech_tree::{TechTree, TechNode, TechCategory, TechLevel, TechStatus};
 
 /// Plugin for faction-specific technology systems
@@ -24,19 +24,11 @@ impl Plugin for FactionTechPlugin {
 pub struct FactionTech;
 
 /// Resource to store all faction tech trees
-#[derive(Resource)]
+#[derive(Resource, Default)]
 pub struct FactionTechTrees {
     pub trees: HashMap<String, TechTree>,
 }
 
-impl Default for FactionTechTrees {
-    fn default() -> Self {
-        Self {
-            trees: HashMap::new(),
-        }
-    }
-}
-
 /// Initialize all faction tech trees
 fn initialize_faction_tech_trees(mut commands: Commands) {
     let mut tech_trees = FactionTechTrees::default();
@@ -74,11 +66,7 @@ fn create_mechanist_tech_tree() -> TechTree {
         category: TechCategory::Military,
         level: TechLevel::Basic,
         status: TechStatus::Available, // Available from the start
-        research_cost: {
-            let mut cost = HashMap::new();
-            cost.insert("iron".to_string(), 50.0);
-            cost
-        },
+        research_cost: ResourceWallet::from([(ResourceType::Iron, 50)]),
         research_time: 60.0,
         research_progress: 0.0,
         prerequisites: Vec::new(),
@@ -93,12 +81,7 @@ fn create_mechanist_tech_tree() -> TechTree {
         category: TechCategory::Military,
         level: TechLevel::Basic,
         status: TechStatus::Available, // Available from the start
-        research_cost: {
-            let mut cost = HashMap::new();
-            cost.insert("iron".to_string(), 30.0);
-            cost.insert("wood".to_string(), 40.0);
-            cost
-        },
+        research_cost: ResourceWallet::from([(ResourceType::Iron, 30), (ResourceType::Wood, 40)]),
         research_time: 45.0,
         research_progress: 0.0,
         prerequisites: Vec::new(),
@@ -114,12 +97,7 @@ fn create_mechanist_tech_tree() -> TechTree {
         category: TechCategory::Military,
         level: TechLevel::Advanced,
         status: TechStatus::Locked,
-        research_cost: {
-            let mut cost = HashMap::new();
-            cost.insert("iron".to_string(), 100.0);
-            cost.insert("crystal".to_string(), 50.0);
-            cost
-        },
+        research_cost: ResourceWallet::from([(ResourceType::Iron, 100), (ResourceType::Crystal, 50)]),
         research_time: 120.0,
         research_progress: 0.0,
         prerequisites: vec!["basic_ballistics".to_string()],
@@ -134,12 +112,7 @@ fn create_mechanist_tech_tree() -> TechTree {
         category: TechCategory::Military,
         level: TechLevel::Advanced,
         status: TechStatus::Locked,
-        research_cost: {
-            let mut cost = HashMap::new();
-            cost.insert("iron".to_string(), 150.0);
-            cost.insert("stone".to_string(), 75.0);
-            cost
-        },
+        research_cost: ResourceWallet::from([(ResourceType::Iron, 150), (ResourceType::Stone, 75)]),
         research_time: 90.0,
         research_progress: 0.0,
         prerequisites: vec!["basic_ballistics".to_string()],
@@ -155,12 +128,7 @@ fn create_mechanist_tech_tree() -> TechTree {
         category: TechCategory::Economy,
         level: TechLevel::Basic,
         status: TechStatus::Available, // Available from the start
-        research_cost: {
-            let mut cost = HashMap::new();
-            cost.insert("iron".to_string(), 40.0);
-            cost.insert("wood".to_string(), 30.0);
-            cost
-        },
+        research_cost: ResourceWallet::from([(ResourceType::Iron, 40), (ResourceType::Wood, 30)]),
         research_time: 50.0,
         research_progress: 0.0,
         prerequisites: Vec::new(),
@@ -195,12 +163,7 @@ fn create_naturalist_tech_tree() -> TechTree {
         category: TechCategory::Military,
         level: TechLevel::Basic,
         status: TechStatus::Available, // Available from the start
-        research_cost: {
-            let mut cost = HashMap::new();
-            cost.insert("wood".to_string(), 50.0);
-            cost.insert("crystal".to_string(), 20.0);
-            cost
-        },
+        research_cost: ResourceWallet::from([(ResourceType::Wood, 50), (ResourceType::Crystal, 20)]),
         research_time: 60.0,
         research_progress: 0.0,
         prerequisites: Vec::new(),
@@ -215,12 +178,7 @@ fn create_naturalist_tech_tree() -> TechTree {
         category: TechCategory::Military,
         level: TechLevel::Basic,
         status: TechStatus::Available, // Available from the start
-        research_cost: {
-            let mut cost = HashMap::new();
-            cost.insert("wood".to_string(), 40.0);
-            cost.insert("crystal".to_string(), 15.0);
-            cost
-        },
+        research_cost: ResourceWallet::from([(ResourceType::Wood, 40), (ResourceType::Crystal, 15)]),
         research_time: 45.0,
         research_progress: 0.0,
         prerequisites: Vec::new(),
@@ -236,12 +194,7 @@ fn create_naturalist_tech_tree() -> TechTree {
         category: TechCategory::Military,
         level: TechLevel::Advanced,
         status: TechStatus::Locked,
-        research_cost: {
-            let mut cost = HashMap::new();
-            cost.insert("wood".to_string(), 80.0);
-            cost.insert("crystal".to_string(), 60.0);
-            cost
-        },
+        research_cost: ResourceWallet::from([(ResourceType::Wood, 80), (ResourceType::Crystal, 60)]),
         research_time: 120.0,
         research_progress: 0.0,
         prerequisites: vec!["organic_compounds".to_string()],
@@ -257,12 +210,7 @@ fn create_naturalist_tech_tree() -> TechTree {
         category: TechCategory::Economy,
         level: TechLevel::Basic,
         status: TechStatus::Available, // Available from the start
-        research_cost: {
-            let mut cost = HashMap::new();
-            cost.insert("wood".to_string(), 50.0);
-            cost.insert("crystal".to_string(), 20.0);
-            cost
-        },
+        research_cost: ResourceWallet::from([(ResourceType::Wood, 50), (ResourceType::Crystal, 20)]),
         research_time: 50.0,
         research_progress: 0.0,
         prerequisites: Vec::new(),
@@ -297,12 +245,7 @@ fn create_synthetic_tech_tree() -> TechTree {
         category: TechCategory::Military,
         level: TechLevel::Basic,
         status: TechStatus::Available, // Available from the start
-        research_cost: {
-            let mut cost = HashMap::new();
-            cost.insert("crystal".to_string(), 50.0);
-            cost.insert("energy".to_string(), 100.0);
-            cost
-        },
+        research_cost: ResourceWallet::from([(ResourceType::Crystal, 50), (ResourceType::Energy, 100)]),
         research_time: 60.0,
         research_progress: 0.0,
         prerequisites: Vec::new(),
@@ -317,12 +260,7 @@ fn create_synthetic_tech_tree() -> TechTree {
         category: TechCategory::Military,
         level: TechLevel::Basic,
         status: TechStatus::Available, // Available from the start
-        research_cost: {
-            let mut cost = HashMap::new();
-            cost.insert("iron".to_string(), 30.0);
-            cost.insert("energy".to_string(), 80.0);
-            cost
-        },
+        research_cost: ResourceWallet::from([(ResourceType::Iron, 30), (ResourceType::Energy, 80)]),
         research_time: 45.0,
         research_progress: 0.0,
         prerequisites: Vec::new(),
@@ -338,12 +276,7 @@ fn create_synthetic_tech_tree() -> TechTree {
         category: TechCategory::Military,
         level: TechLevel::Advanced,
         status: TechStatus::Locked,
-        research_cost: {
-            let mut cost = HashMap::new();
-            cost.insert("crystal".to_string(), 100.0);
-            cost.insert("energy".to_string(), 200.0);
-            cost
-        },
+        research_cost: ResourceWallet::from([(ResourceType::Crystal, 100), (ResourceType::Energy, 200)]),
         research_time: 120.0,
         research_progress: 0.0,
         prerequisites: vec!["energy_weapons".to_string()],
@@ -359,12 +292,7 @@ fn create_synthetic_tech_tree() -> TechTree {
         category: TechCategory::Economy,
         level: TechLevel::Basic,
         status: TechStatus::Available, // Available from the start
-        research_cost: {
-            let mut cost = HashMap::new();
-            cost.insert("iron".to_string(), 40.0);
-            cost.insert("energy".to_string(), 80.0);
-            cost
-        },
+        research_cost: ResourceWallet::from([(ResourceType::Iron, 40), (ResourceType::Energy, 80)]),
         research_time: 50.0,
         research_progress: 0.0,
         prerequisites: Vec::new(),
@@ -384,11 +312,11 @@ fn create_synthetic_tech_tree() -> TechTree {
 }
 
 /// Get a faction's tech tree by name
-pub fn get_faction_tech_tree(tech_trees: &FactionTechTrees, faction_name: &str) -> Option<&TechTree> {
+pub fn get_faction_tech_tree<'a>(tech_trees: &'a FactionTechTrees, faction_name: &str) -> Option<&'a TechTree> {
     tech_trees.trees.get(faction_name)
 }
 
 /// Get a mutable reference to a faction's tech tree by name
-pub fn get_faction_tech_tree_mut(tech_trees: &mut FactionTechTrees, faction_name: &str) -> Option<&mut TechTree> {
+pub fn get_faction_tech_tree_mut<'a>(tech_trees: &'a mut FactionTechTrees, faction_name: &str) -> Option<&'a mut TechTree> {
     tech_trees.trees.get_mut(faction_name)
 }
@@ -3,16 +3,18 @@
 //! This module implements a technology tree system that allows different factions
 //! to research and unlock new technologies, units, buildings, and abilities.
 
-mod tech_tree;
-mod faction_tech;
-mod tech_effects;
-mod tech_requirements;
-mod tech_ui;
+pub mod tech_tree;
+pub mod faction_tech;
+pub mod tech_effects;
+pub mod tech_requirements;
+pub mod tech_ui;
 
 pub use tech_tree::{TechTree, TechNode, TechCategory, TechLevel, TechStatus};
-pub use faction_tech::{FactionTech, FactionTechPlugin};
+pub use faction_tech::{FactionTech, FactionTechPlugin, FactionTechTrees};
 pub use tech_effects::TechEffectPlugin;
-pub use tech_requirements::TechRequirementPlugin;
+pub use tech_requirements::{
+    TechRequirementPlugin, can_afford_technology, pay_research_cost, refund_research_cost,
+};
 pub use tech_ui::TechUIPlugin;
 
 use bevy::prelude::*;
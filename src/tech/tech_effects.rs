@@ -4,7 +4,7 @@
 //! such as stat bonuses, new abilities, and unlocked units/buildings.
 
 use bevy::prelude::*;
-use super::tech_tree::{TechTree, TechNode, TechStatus};
+use super::tech_tree::TechStatus;
 use super::faction_tech::FactionTechTrees;
 
 /// Plugin for technology effects systems
@@ -19,7 +19,7 @@ impl Plugin for TechEffectPlugin {
 
 /// System to apply technology effects to entities
 fn apply_tech_effects(
-    tech_trees: Res<FactionTechTrees>,
+    _tech_trees: Res<FactionTechTrees>,
     // Add queries for entities that can be affected by technologies
 ) {
     // Implementation will apply effects of researched technologies to appropriate entities
@@ -6,7 +6,9 @@
 use bevy::prelude::*;
 use super::tech_tree::{TechTree, TechNode, TechStatus};
 use super::faction_tech::FactionTechTrees;
-use crate::resources::player_resources::PlayerResources;
+use crate::components::economy::InsufficientResources;
+use crate::components::player::PlayerResources;
+use crate::states::game_state::GameState;
 
 /// Plugin for technology requirements systems
 pub struct TechRequirementPlugin;
@@ -14,14 +16,13 @@ pub struct TechRequirementPlugin;
 impl Plugin for TechRequirementPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Update, check_tech_availability);
+            .add_systems(Update, check_tech_availability.run_if(in_state(GameState::Gameplay)));
     }
 }
 
 /// System to check and update technology availability based on prerequisites
 fn check_tech_availability(
     mut tech_trees: ResMut<FactionTechTrees>,
-    player_resources: Res<PlayerResources>,
 ) {
     for tree in tech_trees.trees.values_mut() {
         update_tech_availability(tree);
@@ -34,28 +35,15 @@ fn update_tech_availability(tech_tree: &mut TechTree) {
     let tech_ids: Vec<String> = tech_tree.technologies.keys().cloned().collect();
     
     for tech_id in tech_ids {
+        let prereqs_met = tech_tree.prerequisites_met(&tech_id);
         if let Some(tech) = tech_tree.get_technology_mut(&tech_id) {
             // Skip technologies that are already researched or researching
             if tech.status == TechStatus::Researched || tech.status == TechStatus::Researching {
                 continue;
             }
             
-            // Check if all prerequisites are met
-            let mut all_prereqs_met = true;
-            for prereq_id in &tech.prerequisites {
-                if let Some(prereq) = tech_tree.get_technology(prereq_id) {
-                    if prereq.status != TechStatus::Researched {
-                        all_prereqs_met = false;
-                        break;
-                    }
-                } else {
-                    all_prereqs_met = false;
-                    break;
-                }
-            }
-            
             // Update status based on prerequisites
-            if all_prereqs_met {
+            if prereqs_met {
                 tech.status = TechStatus::Available;
             } else {
                 tech.status = TechStatus::Locked;
@@ -69,33 +57,21 @@ pub fn can_afford_technology(
     tech: &TechNode,
     player_resources: &PlayerResources,
 ) -> bool {
-    for (resource_name, cost) in &tech.research_cost {
-        // This would need to be adapted to your actual resource system
-        if let Some(current_amount) = player_resources.get_resource(resource_name) {
-            if current_amount < *cost {
-                return false;
-            }
-        } else {
-            return false;
-        }
-    }
-    true
+    player_resources.resources.can_afford(&tech.research_cost)
 }
 
-/// Pay the cost to research a technology
+/// Pay the cost to research a technology, leaving resources untouched if it can't be afforded
 pub fn pay_research_cost(
     tech: &TechNode,
     player_resources: &mut PlayerResources,
-) -> bool {
-    // First check if the player can afford it
-    if !can_afford_technology(tech, player_resources) {
-        return false;
-    }
-    
-    // Deduct the resources
-    for (resource_name, cost) in &tech.research_cost {
-        player_resources.spend_resource(resource_name, *cost);
-    }
-    
-    true
+) -> Result<(), InsufficientResources> {
+    player_resources.resources.spend(&tech.research_cost)
+}
+
+/// Give back the cost of a technology whose research was cancelled
+pub fn refund_research_cost(
+    tech: &TechNode,
+    player_resources: &mut PlayerResources,
+) {
+    player_resources.resources.refund(&tech.research_cost);
 }
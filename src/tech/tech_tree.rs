@@ -5,11 +5,12 @@
 
 use bevy::prelude::*;
 use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::components::economy::ResourceWallet;
 
 /// Technology categories to organize tech trees
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
-#[reflect(Component)]
 pub enum TechCategory {
     /// Military technologies for combat units and weapons
     Military,
@@ -23,7 +24,6 @@ pub enum TechCategory {
 
 /// Technology levels representing progression tiers
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
-#[reflect(Component)]
 pub enum TechLevel {
     Basic,
     Advanced,
@@ -32,8 +32,7 @@ pub enum TechLevel {
 }
 
 /// Current status of a technology
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
-#[reflect(Component)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
 pub enum TechStatus {
     /// Not yet available for research
     Locked,
@@ -62,7 +61,7 @@ pub struct TechNode {
     /// Current research status
     pub status: TechStatus,
     /// Research cost in resources
-    pub research_cost: HashMap<String, f32>,
+    pub research_cost: ResourceWallet,
     /// Time required to research (in seconds)
     pub research_time: f32,
     /// Progress of research (0.0 - 1.0)
@@ -84,7 +83,7 @@ impl Default for TechNode {
             category: TechCategory::Military,
             level: TechLevel::Basic,
             status: TechStatus::Locked,
-            research_cost: HashMap::new(),
+            research_cost: ResourceWallet::new(),
             research_time: 60.0,
             research_progress: 0.0,
             prerequisites: Vec::new(),
@@ -160,42 +159,46 @@ impl TechTree {
 
     /// Update research progress
     pub fn update_research(&mut self, delta_time: f32) {
-        if let Some(tech_id) = &self.current_research {
-            if let Some(tech) = self.get_technology_mut(tech_id) {
-                tech.research_progress += (self.research_rate * delta_time) / tech.research_time;
-                
-                if tech.research_progress >= 1.0 {
-                    tech.research_progress = 1.0;
-                    tech.status = TechStatus::Researched;
-                    self.current_research = None;
-                    
-                    // Unlock technologies that depend on this one
-                    for unlock_id in &tech.unlocks.clone() {
-                        if let Some(unlock_tech) = self.get_technology_mut(unlock_id) {
-                            // Check if all prerequisites are met
-                            let mut all_prereqs_met = true;
-                            for prereq_id in &unlock_tech.prerequisites {
-                                if let Some(prereq) = self.get_technology(prereq_id) {
-                                    if prereq.status != TechStatus::Researched {
-                                        all_prereqs_met = false;
-                                        break;
-                                    }
-                                } else {
-                                    all_prereqs_met = false;
-                                    break;
-                                }
-                            }
-                            
-                            if all_prereqs_met {
-                                unlock_tech.status = TechStatus::Available;
-                            }
-                        }
-                    }
+        let Some(tech_id) = self.current_research.clone() else { return };
+        let research_rate = self.research_rate;
+        let Some(tech) = self.get_technology_mut(&tech_id) else { return };
+
+        tech.research_progress += (research_rate * delta_time) / tech.research_time;
+        if tech.research_progress < 1.0 {
+            return;
+        }
+
+        tech.research_progress = 1.0;
+        tech.status = TechStatus::Researched;
+        let unlocks = tech.unlocks.clone();
+        self.current_research = None;
+
+        // Unlock technologies that depend on this one
+        for unlock_id in &unlocks {
+            if self.prerequisites_met(unlock_id) {
+                if let Some(unlock_tech) = self.get_technology_mut(unlock_id) {
+                    unlock_tech.status = TechStatus::Available;
                 }
             }
         }
     }
 
+    /// Check if every prerequisite of a technology has been researched
+    pub fn prerequisites_met(&self, tech_id: &str) -> bool {
+        self.get_technology(tech_id).is_some_and(|tech| {
+            tech.prerequisites.iter().all(|prereq_id| self.is_researched(prereq_id))
+        })
+    }
+
+    /// Cancel the current research, returning the technology so its cost can be refunded
+    pub fn cancel_research(&mut self) -> Option<&TechNode> {
+        let tech_id = self.current_research.take()?;
+        let tech = self.get_technology_mut(&tech_id)?;
+        tech.status = TechStatus::Available;
+        tech.research_progress = 0.0;
+        Some(tech)
+    }
+
     /// Check if a technology is researched
     pub fn is_researched(&self, tech_id: &str) -> bool {
         if let Some(tech) = self.get_technology(tech_id) {
@@ -7,7 +7,8 @@ use bevy::prelude::*;
 use super::tech_tree::{TechTree, TechNode, TechCategory, TechStatus};
 use super::faction_tech::FactionTechTrees;
 use super::tech_requirements::{can_afford_technology, pay_research_cost};
-use crate::resources::player_resources::PlayerResources;
+use crate::components::player::PlayerResources;
+use crate::states::game_state::GameState;
 
 /// Plugin for technology UI systems
 pub struct TechUIPlugin;
@@ -15,8 +16,10 @@ pub struct TechUIPlugin;
 impl Plugin for TechUIPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Update, update_tech_ui)
-            .add_systems(Update, handle_tech_ui_interaction);
+            .add_systems(Update, (
+                update_tech_ui,
+                handle_tech_ui_interaction,
+            ).run_if(in_state(GameState::Gameplay)));
     }
 }
 
@@ -98,9 +101,12 @@ fn handle_tech_ui_interaction(
                         // Check if player can afford the research
                         if can_afford_technology(&tech_clone, &player_resources) {
                             // Pay the research cost
-                            if pay_research_cost(&tech_clone, &mut player_resources) {
+                            match pay_research_cost(&tech_clone, &mut player_resources) {
                                 // Start researching the technology
-                                tree.start_research(&tech_ui.tech_id);
+                                Ok(()) => {
+                                    tree.start_research(&tech_ui.tech_id);
+                                }
+                                Err(err) => info!("Cannot research {}: {}", tech_clone.name, err),
                             }
                         }
                     }
@@ -216,7 +222,7 @@ fn spawn_category_tab(
                 align_items: AlignItems::Center,
                 ..default()
             },
-            background_color: Color::rgb(0.15, 0.15, 0.15).into(),
+            background_color: Color::srgb(0.15, 0.15, 0.15).into(),
             ..default()
         })
         .insert(TechCategoryTab { category })
@@ -241,10 +247,10 @@ fn spawn_tech_node(
 ) {
     // Node background color based on status
     let bg_color = match tech.status {
-        TechStatus::Locked => Color::rgb(0.2, 0.2, 0.2),
-        TechStatus::Available => Color::rgb(0.0, 0.5, 0.0),
-        TechStatus::Researching => Color::rgb(0.0, 0.0, 0.8),
-        TechStatus::Researched => Color::rgb(0.8, 0.8, 0.0),
+        TechStatus::Locked => Color::srgb(0.2, 0.2, 0.2),
+        TechStatus::Available => Color::srgb(0.0, 0.5, 0.0),
+        TechStatus::Researching => Color::srgb(0.0, 0.0, 0.8),
+        TechStatus::Researched => Color::srgb(0.8, 0.8, 0.0),
     };
     
     parent
@@ -306,7 +312,7 @@ fn spawn_tech_node(
                             margin: UiRect::all(Val::Px(5.0)),
                             ..default()
                         },
-                        background_color: Color::rgb(0.3, 0.3, 0.3).into(),
+                        background_color: Color::srgb(0.3, 0.3, 0.3).into(),
                         ..default()
                     })
                     .with_children(|progress_container| {
@@ -317,7 +323,7 @@ fn spawn_tech_node(
                                 height: Val::Percent(100.0),
                                 ..default()
                             },
-                            background_color: Color::rgb(0.0, 0.8, 0.0).into(),
+                            background_color: Color::srgb(0.0, 0.8, 0.0).into(),
                             ..default()
                         })
                         .insert(TechNodeUI {
@@ -15,6 +15,7 @@ pub enum BaseAction {
     Move,
     Stop,
     Fortify,
+    Scan,
 }
 
 // Plugin for the base action UI
@@ -90,6 +91,7 @@ fn spawn_base_action_ui(commands: &mut Commands, asset_server: &Res<AssetServer>
             create_action_button(parent, asset_server, "Move", BaseAction::Move);
             create_action_button(parent, asset_server, "Stop", BaseAction::Stop);
             create_action_button(parent, asset_server, "Fortify", BaseAction::Fortify);
+            create_action_button(parent, asset_server, "Scan", BaseAction::Scan);
         });
 
     info!("Base action UI spawned");
@@ -161,7 +163,8 @@ fn handle_button_interactions(
                         info!("Build menu would appear here");
                     },
                     BaseAction::Upgrade => {
-                        info!("Upgrade options would appear here");
+                        // The module build menu opens itself for the selected base
+                        info!("Module menu opened");
                     },
                     BaseAction::Move => {
                         // Set selected bases to Moving state
@@ -184,6 +187,10 @@ fn handle_button_interactions(
                     BaseAction::Fortify => {
                         info!("Base fortifying at current position");
                     },
+                    BaseAction::Scan => {
+                        // Scan targeting is handled by the sensor UI
+                        info!("Scan mode activated - left click to scan an area");
+                    },
                 }
             }
             Interaction::Hovered => {
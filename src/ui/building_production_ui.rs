@@ -1,8 +1,14 @@
 use bevy::prelude::*;
 use crate::components::building::{Building, BuildingSpawner};
+use crate::components::economy::ResourceWallet;
+use crate::components::player::MechanicalBase;
 use crate::components::unit::{Selected, Team};
+use crate::components::unit_types::UnitType;
 use crate::entities::building_types::BuildingType;
 use crate::states::game_state::GameState;
+use crate::systems::module_effects::ProductionBonuses;
+use crate::systems::production::building_bonuses;
+use crate::systems::unit_catalog::{UnitCatalog, UnitDefinitions};
 
 // Component to mark UI elements as part of the building production UI
 #[derive(Component)]
@@ -11,20 +17,34 @@ pub struct BuildingProductionUI;
 // Component for the different production options
 #[derive(Component, Clone, Debug, PartialEq, Eq)]
 pub struct ProductionOption {
-    pub unit_type: String,
+    pub unit_type: UnitType,
     pub building_entity: Entity,
 }
 
+// Component for the text showing the building's queue and its base's production bonuses
+#[derive(Component)]
+struct ProductionStatusText {
+    building_entity: Entity,
+}
+
+// Component for the cost shown on a production button
+#[derive(Component)]
+struct ProductionCostText {
+    unit_type: UnitType,
+    building_entity: Entity,
+}
+
 // Plugin for the building production UI
 pub struct BuildingProductionUIPlugin;
 
 impl Plugin for BuildingProductionUIPlugin {
     fn build(&self, app: &mut App) {
-        app
+        app.init_resource::<UnitDefinitions>()
             .add_systems(Update, (
                 update_building_production_ui,
                 handle_production_button_interactions,
-            ).run_if(in_state(GameState::Gameplay)));
+                update_production_status,
+            ).chain().run_if(in_state(GameState::Gameplay)));
             
         info!("Building Production UI Plugin initialized");
     }
@@ -33,29 +53,29 @@ impl Plugin for BuildingProductionUIPlugin {
 // System to show/hide the building production UI when a production building is selected/deselected
 fn update_building_production_ui(
     mut commands: Commands,
-    building_query: Query<(Entity, &BuildingType, &Team, &Building), With<Selected>>,
+    building_query: Query<(Entity, &BuildingType, &Team, &Building, &BuildingSpawner), With<Selected>>,
     ui_query: Query<Entity, With<BuildingProductionUI>>,
     asset_server: Res<AssetServer>,
+    unit_definitions: Res<UnitDefinitions>,
 ) {
     // Check if a player's production building is selected
-    let mut player_production_building = None;
-    
-    for (entity, building_type, team, building) in building_query.iter() {
-        if *team == Team::Player && building.is_completed {
-            match building_type {
-                BuildingType::Barracks | BuildingType::Workshop | BuildingType::Airfield => {
-                    player_production_building = Some((entity, building_type));
-                    break;
-                },
-                _ => {}
-            }
-        }
-    }
+    let player_production_building = building_query
+        .iter()
+        .find(|(_, _, team, building, spawner)| {
+            **team == Team::Player && building.is_completed && !spawner.produces.is_empty()
+        });
     
     // If a player's production building is selected and the UI doesn't exist, create it
-    if let Some((entity, building_type)) = player_production_building {
+    if let Some((entity, building_type, _, _, spawner)) = player_production_building {
         if ui_query.is_empty() {
-            spawn_building_production_ui(&mut commands, &asset_server, entity, *building_type);
+            spawn_building_production_ui(
+                &mut commands,
+                &asset_server,
+                &unit_definitions.catalog,
+                entity,
+                *building_type,
+                &spawner.produces,
+            );
         }
     }
     // If no player's production building is selected but the UI exists, despawn it
@@ -70,8 +90,10 @@ fn update_building_production_ui(
 fn spawn_building_production_ui(
     commands: &mut Commands, 
     asset_server: &Res<AssetServer>,
+    catalog: &UnitCatalog,
     building_entity: Entity,
     building_type: BuildingType,
+    produces: &[UnitType],
 ) {
     // Create the main container - a panel at the bottom right of the screen
     commands
@@ -116,21 +138,22 @@ fn spawn_building_production_ui(
                 }),
             );
             
-            // Add available units based on building type
-            match building_type {
-                BuildingType::Barracks => {
-                    create_production_button(parent, asset_server, "Engineer", building_entity);
-                    // Add more units here as they are implemented
-                },
-                BuildingType::Workshop => {
-                    create_production_button(parent, asset_server, "Engineer", building_entity);
-                    // Add more units here as they are implemented
-                },
-                BuildingType::Airfield => {
-                    create_production_button(parent, asset_server, "Engineer", building_entity);
-                    // Add more units here as they are implemented
-                },
-                _ => {}
+            // Add the queue and production bonuses, filled in by update_production_status
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        font_size: 14.0,
+                        color: Color::srgba(0.8, 0.9, 0.8, 1.0),
+                    },
+                ),
+                ProductionStatusText { building_entity },
+            ));
+            
+            // Add the units this building's catalog entry can produce
+            for unit_type in produces {
+                create_production_button(parent, asset_server, catalog, *unit_type, building_entity);
             }
         });
 
@@ -141,15 +164,12 @@ fn spawn_building_production_ui(
 fn create_production_button(
     parent: &mut ChildBuilder,
     asset_server: &Res<AssetServer>,
-    unit_type: &str,
+    catalog: &UnitCatalog,
+    unit_type: UnitType,
     building_entity: Entity,
 ) {
-    // Get cost information based on unit type
-    let (cost_wood, cost_stone, cost_iron) = match unit_type {
-        "Engineer" => (20, 10, 0),
-        // Add more unit types with their costs
-        _ => (0, 0, 0),
-    };
+    // Get cost information from the unit catalog; base discounts are applied by update_production_status
+    let cost_text = format_cost(&catalog.definition(unit_type).cost);
     
     parent
         .spawn((
@@ -167,16 +187,16 @@ fn create_production_button(
                 ..default()
             },
             ProductionOption {
-                unit_type: unit_type.to_string(),
+                unit_type,
                 building_entity,
             },
-            Name::new(format!("Produce {} Button", unit_type)),
+            Name::new(format!("Produce {} Button", unit_type.name())),
         ))
         .with_children(|parent| {
             // Add unit name
             parent.spawn(
                 TextBundle::from_section(
-                    unit_type,
+                    unit_type.name(),
                     TextStyle {
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                         font_size: 16.0,
@@ -186,26 +206,37 @@ fn create_production_button(
             );
             
             // Add cost information
-            parent.spawn(
+            parent.spawn((
                 TextBundle::from_section(
-                    format!("W:{} S:{} I:{}", cost_wood, cost_stone, cost_iron),
+                    cost_text,
                     TextStyle {
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                         font_size: 14.0,
                         color: Color::srgba(0.8, 0.8, 0.8, 1.0),
                     },
                 ),
-            );
+                ProductionCostText { unit_type, building_entity },
+            ));
         });
 }
 
+// Helper function to list a cost as "Wood:50 Stone:30"
+fn format_cost(cost: &ResourceWallet) -> String {
+    cost.iter()
+        .map(|(resource_type, amount)| format!("{:?}:{}", resource_type, amount))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 // System to handle production button interactions
+// Queues the unit if there is room and makes it the building's standing production
 fn handle_production_button_interactions(
     mut interaction_query: Query<
         (&Interaction, &mut BackgroundColor, &ProductionOption),
         (Changed<Interaction>, With<Button>),
     >,
-    mut building_spawner_query: Query<&mut BuildingSpawner>,
+    mut building_spawner_query: Query<(&mut BuildingSpawner, &Transform, &Team)>,
+    bases: Query<(&Transform, &MechanicalBase, Option<&ProductionBonuses>)>,
 ) {
     for (interaction, mut color, production_option) in interaction_query.iter_mut() {
         match *interaction {
@@ -214,10 +245,18 @@ fn handle_production_button_interactions(
                 *color = BackgroundColor(Color::srgb(0.35, 0.75, 0.35)); // Green for pressed
                 
                 // Handle the production selection
-                if let Ok(mut spawner) = building_spawner_query.get_mut(production_option.building_entity) {
-                    // Set the building to produce the selected unit type
-                    spawner.unit_type = production_option.unit_type.clone();
-                    info!("Set {:?} to produce {}", production_option.building_entity, production_option.unit_type);
+                if let Ok((mut spawner, transform, team)) = building_spawner_query.get_mut(production_option.building_entity) {
+                    // Queue the selected unit type and keep producing it afterwards
+                    if spawner.can_produce(production_option.unit_type) {
+                        let capacity = building_bonuses(*team, transform.translation.truncate(), &bases).queue_capacity();
+                        if spawner.queue.len() < capacity {
+                            spawner.queue.push(production_option.unit_type);
+                            spawner.unit_type = Some(production_option.unit_type);
+                            info!("Queued {} at {:?}", production_option.unit_type.name(), production_option.building_entity);
+                        } else {
+                            info!("Production queue at {:?} is full", production_option.building_entity);
+                        }
+                    }
                 }
             }
             Interaction::Hovered => {
@@ -231,3 +270,42 @@ fn handle_production_button_interactions(
         }
     }
 }
+
+// System to show the selected building's queue, its base's production bonuses and the discounted costs
+fn update_production_status(
+    buildings: Query<(&BuildingSpawner, &Transform, &Team)>,
+    bases: Query<(&Transform, &MechanicalBase, Option<&ProductionBonuses>)>,
+    unit_definitions: Res<UnitDefinitions>,
+    mut status_texts: Query<(&mut Text, &ProductionStatusText), Without<ProductionCostText>>,
+    mut cost_texts: Query<(&mut Text, &ProductionCostText), Without<ProductionStatusText>>,
+) {
+    for (mut text, status) in status_texts.iter_mut() {
+        let Ok((spawner, transform, team)) = buildings.get(status.building_entity) else { continue };
+        let bonuses = building_bonuses(*team, transform.translation.truncate(), &bases);
+
+        let queue = if spawner.queue.is_empty() {
+            "empty".to_string()
+        } else {
+            spawner.queue.iter().map(|unit_type| unit_type.name()).collect::<Vec<_>>().join(", ")
+        };
+        let mut value = format!("Queue ({}/{}): {}", spawner.queue.len(), bonuses.queue_capacity(), queue);
+        value.push_str(&format!(
+            "\nBuild speed x{:.2}  Cost -{:.0}%  Starting XP +{:.0}",
+            bonuses.build_speed,
+            bonuses.cost_reduction * 100.0,
+            bonuses.experience_gain,
+        ));
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+
+    for (mut text, cost) in cost_texts.iter_mut() {
+        let Ok((_, transform, team)) = buildings.get(cost.building_entity) else { continue };
+        let bonuses = building_bonuses(*team, transform.translation.truncate(), &bases);
+        let value = format_cost(&bonuses.discounted(&unit_definitions.catalog.definition(cost.unit_type).cost));
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
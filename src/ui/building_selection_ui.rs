@@ -1,9 +1,10 @@
 use bevy::prelude::*;
-use crate::components::base_modules::ResourceType;
+use crate::components::economy::ResourceType;
 use crate::components::player::PlayerResources;
 use crate::components::unit::Team;
 use crate::entities::building_types::BuildingType;
 use crate::states::game_state::GameState;
+use crate::systems::building_catalog::{BuildingCatalog, BuildingDefinitions};
 
 // Component to mark UI elements as part of the building selection UI
 #[derive(Component)]
@@ -38,6 +39,7 @@ impl Plugin for BuildingSelectionUIPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<BuildingPlacement>()
+            .init_resource::<BuildingDefinitions>()
             .add_systems(
                 Update,
                 (
@@ -57,6 +59,7 @@ fn update_building_selection_ui(
     ui_query: Query<Entity, With<BuildingSelectionUI>>,
     base_action_ui: Query<&crate::ui::base_action_ui::BaseAction, Changed<Interaction>>,
     asset_server: Res<AssetServer>,
+    building_definitions: Res<BuildingDefinitions>,
 ) {
     // Check if the Build button was clicked
     let build_button_clicked = base_action_ui
@@ -65,13 +68,13 @@ fn update_building_selection_ui(
     
     // If Build was clicked and the UI doesn't exist, create it
     if build_button_clicked && ui_query.is_empty() {
-        spawn_building_selection_ui(&mut commands, &asset_server);
+        spawn_building_selection_ui(&mut commands, &asset_server, &building_definitions.catalog);
     }
     // We'll handle closing the UI in the handle_building_option_interactions system
 }
 
 // Function to spawn the building selection UI
-fn spawn_building_selection_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) {
+fn spawn_building_selection_ui(commands: &mut Commands, asset_server: &Res<AssetServer>, catalog: &BuildingCatalog) {
     // Create the main container - a panel on the right side of the screen
     commands
         .spawn((
@@ -131,9 +134,9 @@ fn spawn_building_selection_ui(commands: &mut Commands, asset_server: &Res<Asset
             );
             
             // Production building options
-            create_building_option(parent, asset_server, BuildingType::Barracks);
-            create_building_option(parent, asset_server, BuildingType::Workshop);
-            create_building_option(parent, asset_server, BuildingType::Airfield);
+            create_building_option(parent, asset_server, catalog, BuildingType::Barracks);
+            create_building_option(parent, asset_server, catalog, BuildingType::Workshop);
+            create_building_option(parent, asset_server, catalog, BuildingType::Airfield);
             
             // Resource buildings section
             parent.spawn(
@@ -151,9 +154,9 @@ fn spawn_building_selection_ui(commands: &mut Commands, asset_server: &Res<Asset
             );
             
             // Resource building options
-            create_building_option(parent, asset_server, BuildingType::Sawmill);
-            create_building_option(parent, asset_server, BuildingType::StoneMine);
-            create_building_option(parent, asset_server, BuildingType::IronMine);
+            create_building_option(parent, asset_server, catalog, BuildingType::Sawmill);
+            create_building_option(parent, asset_server, catalog, BuildingType::StoneMine);
+            create_building_option(parent, asset_server, catalog, BuildingType::IronMine);
             
             // Special buildings section
             parent.spawn(
@@ -171,8 +174,8 @@ fn spawn_building_selection_ui(commands: &mut Commands, asset_server: &Res<Asset
             );
             
             // Special building options
-            create_building_option(parent, asset_server, BuildingType::CommandCenter);
-            create_building_option(parent, asset_server, BuildingType::ResearchLab);
+            create_building_option(parent, asset_server, catalog, BuildingType::CommandCenter);
+            create_building_option(parent, asset_server, catalog, BuildingType::ResearchLab);
             
             // Defense buildings section
             parent.spawn(
@@ -190,8 +193,8 @@ fn spawn_building_selection_ui(commands: &mut Commands, asset_server: &Res<Asset
             );
             
             // Defense building options
-            create_building_option(parent, asset_server, BuildingType::Turret);
-            create_building_option(parent, asset_server, BuildingType::AntiAirTurret);
+            create_building_option(parent, asset_server, catalog, BuildingType::Turret);
+            create_building_option(parent, asset_server, catalog, BuildingType::AntiAirTurret);
             
             // Cancel button
             parent.spawn((
@@ -230,22 +233,31 @@ fn spawn_building_selection_ui(commands: &mut Commands, asset_server: &Res<Asset
 fn create_building_option(
     parent: &mut ChildBuilder,
     asset_server: &Res<AssetServer>,
+    catalog: &BuildingCatalog,
     building_type: BuildingType,
 ) {
     // Get building information
-    let (name, wood_cost, stone_cost, iron_cost) = match building_type {
-        BuildingType::Barracks => ("Barracks", 80, 50, 0),
-        BuildingType::Workshop => ("Workshop", 100, 80, 30),
-        BuildingType::Airfield => ("Airfield", 120, 60, 40),
-        BuildingType::Sawmill => ("Sawmill", 50, 30, 0),
-        BuildingType::StoneMine => ("Stone Mine", 60, 20, 0),
-        BuildingType::IronMine => ("Iron Mine", 60, 40, 0),
-        BuildingType::CommandCenter => ("Command Center", 200, 150, 100),
-        BuildingType::ResearchLab => ("Research Lab", 120, 80, 80),
-        BuildingType::Turret => ("Turret", 30, 40, 20),
-        BuildingType::AntiAirTurret => ("Anti-Air Turret", 30, 30, 40),
+    let name = match building_type {
+        BuildingType::Barracks => "Barracks",
+        BuildingType::Workshop => "Workshop",
+        BuildingType::Airfield => "Airfield",
+        BuildingType::Sawmill => "Sawmill",
+        BuildingType::StoneMine => "Stone Mine",
+        BuildingType::IronMine => "Iron Mine",
+        BuildingType::CommandCenter => "Command Center",
+        BuildingType::ResearchLab => "Research Lab",
+        BuildingType::Turret => "Turret",
+        BuildingType::AntiAirTurret => "Anti-Air Turret",
     };
     
+    // Costs come from the building catalog
+    let definition = catalog.definition(building_type);
+    let (wood_cost, stone_cost, iron_cost) = (
+        definition.cost.get(ResourceType::Wood),
+        definition.cost.get(ResourceType::Stone),
+        definition.cost.get(ResourceType::Iron),
+    );
+    
     parent
         .spawn((
             ButtonBundle {
@@ -384,6 +396,7 @@ fn place_building(
     windows: Query<&Window>,
     camera_q: Query<(&Camera, &GlobalTransform)>,
     mut player_resources: Option<ResMut<PlayerResources>>,
+    building_definitions: Res<BuildingDefinitions>,
 ) {
     // Only process if building placement is active
     if !building_placement.active || building_placement.building_type.is_none() {
@@ -409,56 +422,18 @@ fn place_building(
             // When left mouse button is clicked, place the building
             if mouse_buttons.just_pressed(MouseButton::Left) {
                 // Check if player has enough resources
+                let definition = building_definitions.catalog.definition(building_type);
                 if let Some(ref mut resources) = player_resources {
-                    // Get building costs
-                    let (wood_cost, stone_cost, iron_cost) = match building_type {
-                        BuildingType::Barracks => (80, 50, 0),
-                        BuildingType::Workshop => (100, 80, 30),
-                        BuildingType::Airfield => (120, 60, 40),
-                        BuildingType::Sawmill => (50, 30, 0),
-                        BuildingType::StoneMine => (60, 20, 0),
-                        BuildingType::IronMine => (60, 40, 0),
-                        BuildingType::CommandCenter => (200, 150, 100),
-                        BuildingType::ResearchLab => (120, 80, 80),
-                        BuildingType::Turret => (30, 40, 20),
-                        BuildingType::AntiAirTurret => (30, 30, 40),
-                    };
-                    
-                    // Find available resources
-                    let mut wood_available = 0;
-                    let mut stone_available = 0;
-                    let mut iron_available = 0;
-                    
-                    for (res_type, amount) in &resources.resources {
-                        match res_type {
-                            ResourceType::Wood => wood_available = *amount,
-                            ResourceType::Stone => stone_available = *amount,
-                            ResourceType::Iron => iron_available = *amount,
-                            _ => {} // Ignore other resource types for now
-                        }
-                    }
-                    
-                    // Check if player has enough resources
-                    if wood_available >= wood_cost && stone_available >= stone_cost && iron_available >= iron_cost {
-                        // Deduct resources
-                        for (res_type, amount) in &mut resources.resources {
-                            match res_type {
-                                ResourceType::Wood => *amount -= wood_cost,
-                                ResourceType::Stone => *amount -= stone_cost,
-                                ResourceType::Iron => *amount -= iron_cost,
-                                _ => {} // Ignore other resource types for now
-                            }
-                        }
-                        
+                    if resources.resources.spend(&definition.cost).is_ok() {
                         // Place the building
-                        building_type.spawn_building(&mut commands, world_position, Team::Player);
+                        building_type.spawn_building(&mut commands, &building_definitions.catalog, world_position, Team::Player);
                         info!("Placed {:?} at {:?}", building_type, world_position);
                     } else {
                         info!("Not enough resources to build {:?}", building_type);
                     }
                 } else {
                     // No player resources resource, just place the building
-                    building_type.spawn_building(&mut commands, world_position, Team::Player);
+                    building_type.spawn_building(&mut commands, &building_definitions.catalog, world_position, Team::Player);
                     info!("Placed {:?} at {:?} (no resource check)", building_type, world_position);
                 }
                 
@@ -14,7 +14,7 @@ mod pause_menu;
 mod menu_systems;
 
 pub use main_menu::MainMenuPlugin;
-pub use settings_menu::{SettingsMenuPlugin, OpenSettingsEvent, CloseSettingsEvent};
+pub use settings_menu::{SettingsMenuPlugin, OpenSettingsEvent, CloseSettingsEvent, GameSettings};
 pub use campaign_menu::{CampaignMenuPlugin, OpenCampaignMenuEvent, CloseCampaignMenuEvent};
 pub use skirmish_menu::{SkirmishMenuPlugin, OpenSkirmishMenuEvent, CloseSkirmishMenuEvent};
 pub use faction_menu::{FactionMenuPlugin, OpenFactionMenuEvent, CloseFactionMenuEvent};
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
+use std::path::PathBuf;
 use crate::states::game_state::GameState;
+use crate::systems::save_load::{LoadGameEvent, SaveGameEvent, SAVE_SLOT_PATH};
 use super::components::{MenuUI, create_button, create_title};
 
 /// Placeholder for tech tree event until the tech module is fully integrated
@@ -130,6 +132,8 @@ fn handle_pause_menu_buttons(
     mut next_state: ResMut<NextState<GameState>>,
     mut settings_events: EventWriter<super::settings_menu::OpenSettingsEvent>,
     mut tech_events: EventWriter<OpenTechTreeEvent>,
+    mut save_events: EventWriter<SaveGameEvent>,
+    mut load_events: EventWriter<LoadGameEvent>,
 ) {
     for (interaction, button_type, mut background_color) in button_query.iter_mut() {
         match *interaction {
@@ -140,14 +144,12 @@ fn handle_pause_menu_buttons(
                     }
                     PauseMenuButton::SaveGame => {
                         println!("Save Game button pressed!");
-                        // Save game functionality would go here
-                        // For now, just return to gameplay
+                        save_events.send(SaveGameEvent { path: PathBuf::from(SAVE_SLOT_PATH) });
                         next_state.set(GameState::Gameplay);
                     }
                     PauseMenuButton::LoadGame => {
                         println!("Load Game button pressed!");
-                        // Load game functionality would go here
-                        // For now, just return to gameplay
+                        load_events.send(LoadGameEvent { path: PathBuf::from(SAVE_SLOT_PATH) });
                         next_state.set(GameState::Gameplay);
                     }
                     PauseMenuButton::Settings => {
@@ -8,6 +8,7 @@ use bevy::ecs::query::With;
 use bevy::ecs::system::Query;
 use bevy::window::WindowMode as DisplayMode;
 use super::components::{MenuUI, create_button, create_title};
+use crate::components::ai::Difficulty;
 
 /// Component for display mode buttons
 #[derive(Component, Debug, Clone, Copy)]
@@ -84,7 +85,6 @@ pub struct GameSettings {
     
     // Gameplay settings
     pub game_speed: f32,
-    #[allow(dead_code)]
     pub difficulty: Difficulty,
     pub show_tutorials: bool,
     pub auto_save: bool,
@@ -150,19 +150,6 @@ pub enum GraphicsQuality {
     Ultra,
 }
 
-/// Difficulty levels
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum Difficulty {
-    #[allow(dead_code)]
-    Easy,
-    #[allow(dead_code)]
-    Normal,
-    #[allow(dead_code)]
-    Hard,
-    #[allow(dead_code)]
-    Veteran,
-}
-
 /// Key bindings for game actions
 #[derive(Clone, Debug)]
 pub struct KeyBindings {
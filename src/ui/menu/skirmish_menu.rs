@@ -1,5 +1,12 @@
 use bevy::prelude::*;
-use super::components::{MenuUI, create_button, create_title};
+use super::components::{ButtonColors, MenuUI, create_button, create_title};
+use crate::resources::map::plugin::SelectedMap;
+use crate::resources::map_file::{list_map_files, maps_directory};
+use crate::resources::map_generator::MapGenerationSettings;
+use crate::utils::font_loader::get_font_handle;
+
+/// Background of the map button that's currently picked
+const SELECTED_MAP_COLOR: Color = Color::srgb(0.2, 0.45, 0.3);
 
 /// Plugin for the skirmish menu
 pub struct SkirmishMenuPlugin;
@@ -9,7 +16,14 @@ impl Plugin for SkirmishMenuPlugin {
         app.add_event::<OpenSkirmishMenuEvent>()
            .add_event::<CloseSkirmishMenuEvent>()
            .add_systems(Update, handle_open_skirmish_menu_event)
-           .add_systems(Update, handle_skirmish_menu_buttons.run_if(resource_exists::<SkirmishMenuState>))
+           .add_systems(
+                Update,
+                (
+                    handle_skirmish_menu_buttons,
+                    highlight_selected_map,
+                    update_seed_label.run_if(resource_exists_and_changed::<MapGenerationSettings>),
+                ).chain().run_if(resource_exists::<SkirmishMenuState>)
+           )
            .add_systems(Update, handle_close_skirmish_menu_event);
     }
 }
@@ -30,12 +44,18 @@ struct SkirmishMenuState;
 #[derive(Component)]
 struct SkirmishMenuUI;
 
+/// Marker for the text showing the seed of the next generated map
+#[derive(Component)]
+struct SeedLabel;
+
 /// Skirmish menu button types
 #[derive(Component)]
 enum SkirmishMenuButton {
     QuickMatch,
     CustomGame,
     ChallengeMode,
+    Map(Option<String>), // Asset path of a map file, or `None` for a generated map
+    NewSeed,
     Back,
 }
 
@@ -45,6 +65,7 @@ fn handle_open_skirmish_menu_event(
     mut ev_open_skirmish: EventReader<OpenSkirmishMenuEvent>,
     asset_server: Res<AssetServer>,
     skirmish_state: Option<Res<SkirmishMenuState>>,
+    map_settings: Option<Res<MapGenerationSettings>>,
 ) {
     for _ in ev_open_skirmish.read() {
         // Only open skirmish menu if it's not already open
@@ -100,6 +121,28 @@ fn handle_open_skirmish_menu_event(
                             // Challenge Mode button
                             create_button(parent, "Challenge Mode", SkirmishMenuButton::ChallengeMode, &asset_server, 300.0, 50.0);
                             
+                            // Map choice: a generated map or any map file in the maps folder
+                            create_title(parent, "Map", &asset_server, 28.0);
+                            create_button(parent, "Random Map", SkirmishMenuButton::Map(None), &asset_server, 450.0, 40.0);
+                            if let Some(settings) = &map_settings {
+                                parent.spawn((
+                                    TextBundle::from_section(
+                                        seed_label(settings),
+                                        TextStyle {
+                                            font: get_font_handle(&asset_server),
+                                            font_size: 20.0,
+                                            color: Color::srgb(0.8, 0.8, 0.8),
+                                        },
+                                    ),
+                                    SeedLabel,
+                                ));
+                                create_button(parent, "New Seed", SkirmishMenuButton::NewSeed, &asset_server, 300.0, 40.0);
+                            }
+                            for listing in list_map_files(&maps_directory()) {
+                                let label = format!("{} ({} players, {}x{})", listing.name, listing.players, listing.width, listing.height);
+                                create_button(parent, &label, SkirmishMenuButton::Map(Some(listing.asset_path)), &asset_server, 450.0, 40.0);
+                            }
+                            
                             // Back button
                             create_button(parent, "Back", SkirmishMenuButton::Back, &asset_server, 300.0, 50.0);
                         });
@@ -135,6 +178,8 @@ fn handle_skirmish_menu_buttons(
         (Changed<Interaction>, With<Button>),
     >,
     mut ev_close_skirmish: EventWriter<CloseSkirmishMenuEvent>,
+    mut selected_map: ResMut<SelectedMap>,
+    mut map_settings: Option<ResMut<MapGenerationSettings>>,
 ) {
     for (interaction, button_type, mut background_color) in button_query.iter_mut() {
         match *interaction {
@@ -152,6 +197,18 @@ fn handle_skirmish_menu_buttons(
                         println!("Challenge Mode button pressed!");
                         // Challenge mode functionality would go here
                     }
+                    SkirmishMenuButton::Map(path) => {
+                        info!("Selected map: {}", path.as_deref().unwrap_or("random"));
+                        selected_map.path = path.clone();
+                    }
+                    SkirmishMenuButton::NewSeed => {
+                        // A new seed is only used on a generated map
+                        if let Some(settings) = map_settings.as_mut() {
+                            settings.seed = rand::random();
+                            info!("New map seed: {}", settings.seed);
+                        }
+                        selected_map.path = None;
+                    }
                     SkirmishMenuButton::Back => {
                         println!("Back button pressed!");
                         ev_close_skirmish.send(CloseSkirmishMenuEvent);
@@ -167,3 +224,36 @@ fn handle_skirmish_menu_buttons(
         }
     }
 }
+
+/// Keep the picked map's button highlighted
+fn highlight_selected_map(
+    selected_map: Res<SelectedMap>,
+    mut button_query: Query<(&Interaction, &SkirmishMenuButton, &mut BackgroundColor), With<Button>>,
+) {
+    for (interaction, button_type, mut background_color) in button_query.iter_mut() {
+        let SkirmishMenuButton::Map(path) = button_type else { continue };
+        if *interaction != Interaction::None {
+            continue;
+        }
+        
+        let color = if *path == selected_map.path { SELECTED_MAP_COLOR } else { ButtonColors::default().normal };
+        if background_color.0 != color {
+            *background_color = BackgroundColor(color);
+        }
+    }
+}
+
+// Text shown for the seed of the next generated map
+fn seed_label(settings: &MapGenerationSettings) -> String {
+    format!("Seed: {}", settings.seed)
+}
+
+/// Keep the seed label in step with the map generation settings
+fn update_seed_label(
+    map_settings: Res<MapGenerationSettings>,
+    mut labels: Query<&mut Text, With<SeedLabel>>,
+) {
+    for mut text in labels.iter_mut() {
+        text.sections[0].value = seed_label(&map_settings);
+    }
+}
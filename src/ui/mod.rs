@@ -2,9 +2,17 @@ pub mod base_action_ui;
 pub mod building_production_ui;
 pub mod building_selection_ui;
 pub mod menu;
+pub mod module_build_ui;
+pub mod power_ui;
+pub mod sensor_ui;
+pub mod strategic_hud;
 
 // Re-export the plugins for easier imports
 pub use base_action_ui::BaseActionUIPlugin;
 pub use building_production_ui::BuildingProductionUIPlugin;
 pub use building_selection_ui::BuildingSelectionUIPlugin;
 pub use menu::MenuPlugin;
+pub use module_build_ui::ModuleBuildUIPlugin;
+pub use power_ui::PowerUIPlugin;
+pub use sensor_ui::SensorUIPlugin;
+pub use strategic_hud::StrategicHudPlugin;
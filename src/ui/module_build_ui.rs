@@ -0,0 +1,356 @@
+use bevy::prelude::*;
+use crate::components::base_modules::{AttachModuleRequest, AttachmentPoint, DetachModuleRequest, InstalledModule};
+use crate::components::player::MechanicalBase;
+use crate::components::unit::{Selected, Team};
+use crate::states::game_state::GameState;
+use crate::systems::camera::GameCamera;
+use crate::systems::module_catalog::{ModuleCatalog, ModuleDefinitions};
+use crate::ui::base_action_ui::BaseAction;
+use crate::utils::font_loader::get_font_handle;
+
+/// How close a click has to be to an attachment point or module to pick it
+const PICK_RADIUS: f32 = 20.0;
+
+// Highlight colours
+const VALID_POINT_COLOR: Color = Color::srgba(0.3, 0.95, 0.3, 0.6);
+const DETACHABLE_COLOR: Color = Color::srgba(0.95, 0.6, 0.2, 0.6);
+
+// Button colours
+const BUTTON_COLOR: Color = Color::srgb(0.2, 0.2, 0.2);
+const BUTTON_HOVERED_COLOR: Color = Color::srgb(0.33, 0.33, 0.33);
+const CANCEL_COLOR: Color = Color::srgb(0.7, 0.2, 0.2);
+
+/// What the player is doing with their base's modules
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub enum ModulePlacement {
+    #[default]
+    Inactive,
+    Choosing { base: Entity },                 // The module menu is open
+    Placing { base: Entity, module: String },  // Free points for the module are highlighted
+    Detaching { base: Entity },                // Modules that can be removed are highlighted
+}
+
+// Component to mark the module menu
+#[derive(Component)]
+pub struct ModuleBuildUI;
+
+// Component for the buttons in the module menu
+#[derive(Component, Clone, Debug, PartialEq)]
+pub enum ModuleMenuButton {
+    Module(String),
+    Detach,
+    Cancel,
+}
+
+// Component for the highlight drawn over an attachment point or module
+#[derive(Component)]
+pub struct AttachmentHighlight;
+
+// Plugin for choosing, placing and removing base modules
+pub struct ModuleBuildUIPlugin;
+
+impl Plugin for ModuleBuildUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ModulePlacement>()
+           .init_resource::<ModuleDefinitions>()
+           .add_systems(
+                Update,
+                (
+                    open_module_menu,
+                    handle_module_menu_buttons,
+                    handle_module_placement_clicks,
+                    sync_module_menu.run_if(resource_changed::<ModulePlacement>),
+                    update_attachment_highlights,
+                ).chain().run_if(in_state(GameState::Gameplay))
+            )
+           .add_systems(OnExit(GameState::Gameplay), close_module_menu);
+
+        info!("Module Build UI Plugin initialized");
+    }
+}
+
+// System to open the module menu when the Upgrade button is pressed on a player base
+fn open_module_menu(
+    buttons: Query<(&Interaction, &BaseAction), Changed<Interaction>>,
+    bases: Query<(Entity, &MechanicalBase), With<Selected>>,
+    mut placement: ResMut<ModulePlacement>,
+) {
+    let pressed = buttons
+        .iter()
+        .any(|(interaction, action)| *interaction == Interaction::Pressed && *action == BaseAction::Upgrade);
+    if !pressed {
+        return;
+    }
+
+    if let Some((base, _)) = bases.iter().find(|(_, base)| base.team == Team::Player) {
+        *placement = ModulePlacement::Choosing { base };
+    }
+}
+
+// System to rebuild the module menu whenever the placement mode changes
+fn sync_module_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    placement: Res<ModulePlacement>,
+    definitions: Res<ModuleDefinitions>,
+    menus: Query<Entity, With<ModuleBuildUI>>,
+) {
+    for menu in menus.iter() {
+        commands.entity(menu).despawn_recursive();
+    }
+
+    if matches!(*placement, ModulePlacement::Choosing { .. }) {
+        spawn_module_menu(&mut commands, &asset_server, &definitions.catalog);
+    }
+}
+
+// Panel listing every module in the catalog with its cost
+fn spawn_module_menu(commands: &mut Commands, asset_server: &Res<AssetServer>, catalog: &ModuleCatalog) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(100.0),
+                    right: Val::Px(20.0),
+                    width: Val::Px(300.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    row_gap: Val::Px(6.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Stretch,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+                border_color: BorderColor(Color::srgb(0.3, 0.3, 0.3)),
+                ..default()
+            },
+            ModuleBuildUI,
+            Name::new("Module Build UI"),
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Attach Module", text_style(asset_server, 20.0)));
+
+            for name in catalog.names() {
+                let Some(definition) = catalog.get(name) else { continue };
+                let cost = definition
+                    .cost
+                    .iter()
+                    .map(|(resource_type, amount)| format!("{} {:?}", amount, resource_type))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let label = format!("{} - {}s\n{}", name, definition.build_time, cost);
+                spawn_menu_button(parent, asset_server, &label, ModuleMenuButton::Module(name.to_string()), BUTTON_COLOR);
+            }
+
+            spawn_menu_button(parent, asset_server, "Detach Module", ModuleMenuButton::Detach, BUTTON_COLOR);
+            spawn_menu_button(parent, asset_server, "Cancel", ModuleMenuButton::Cancel, CANCEL_COLOR);
+        });
+}
+
+// Helper function to add one button to the module menu
+fn spawn_menu_button(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    label: &str,
+    button: ModuleMenuButton,
+    color: Color,
+) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    padding: UiRect::all(Val::Px(6.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(color),
+                ..default()
+            },
+            button,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(label, text_style(asset_server, 14.0)));
+        });
+}
+
+// System to react to the module menu buttons
+fn handle_module_menu_buttons(
+    mut buttons: Query<(&Interaction, &mut BackgroundColor, &ModuleMenuButton), Changed<Interaction>>,
+    mut placement: ResMut<ModulePlacement>,
+) {
+    for (interaction, mut color, button) in buttons.iter_mut() {
+        let idle_color = if *button == ModuleMenuButton::Cancel { CANCEL_COLOR } else { BUTTON_COLOR };
+        match *interaction {
+            Interaction::Pressed => {
+                let ModulePlacement::Choosing { base } = *placement else { continue };
+                *placement = match button {
+                    ModuleMenuButton::Module(module) => {
+                        info!("Choose where to attach {} (Escape to cancel)", module);
+                        ModulePlacement::Placing { base, module: module.clone() }
+                    }
+                    ModuleMenuButton::Detach => {
+                        info!("Choose a module to detach (Escape to cancel)");
+                        ModulePlacement::Detaching { base }
+                    }
+                    ModuleMenuButton::Cancel => ModulePlacement::Inactive,
+                };
+            }
+            Interaction::Hovered => *color = BackgroundColor(BUTTON_HOVERED_COLOR),
+            Interaction::None => *color = BackgroundColor(idle_color),
+        }
+    }
+}
+
+// System to attach or detach whatever the player clicks on while placing modules
+fn handle_module_placement_clicks(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    mut placement: ResMut<ModulePlacement>,
+    definitions: Res<ModuleDefinitions>,
+    bases: Query<&MechanicalBase>,
+    points: Query<(&AttachmentPoint, &GlobalTransform)>,
+    modules: Query<(Entity, &Parent, &GlobalTransform), With<InstalledModule>>,
+    mut attach_requests: EventWriter<AttachModuleRequest>,
+    mut detach_requests: EventWriter<DetachModuleRequest>,
+) {
+    if matches!(*placement, ModulePlacement::Inactive | ModulePlacement::Choosing { .. }) {
+        return;
+    }
+
+    // Right click stays with the move orders, so only Escape cancels
+    if keys.just_pressed(KeyCode::Escape) {
+        *placement = ModulePlacement::Inactive;
+        info!("Cancelled module placement");
+        return;
+    }
+    if !mouse_buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let Ok(window) = windows.get_single() else { return };
+    let Ok((camera, camera_transform)) = cameras.get_single() else { return };
+    let Some(cursor) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor))
+    else {
+        return;
+    };
+    let distance = |transform: &GlobalTransform| transform.translation().truncate().distance(cursor);
+
+    match placement.clone() {
+        ModulePlacement::Placing { base, module } => {
+            let (Ok(base_data), Some(definition)) = (bases.get(base), definitions.catalog.get(&module)) else {
+                *placement = ModulePlacement::Inactive;
+                return;
+            };
+            let attachable = definition.to_attachable();
+            let picked = base_data
+                .attachment_points
+                .iter()
+                .filter_map(|&point| points.get(point).ok().map(|(data, transform)| (point, data, transform)))
+                .filter(|(_, data, transform)| data.can_attach(&attachable) && distance(transform) < PICK_RADIUS)
+                .min_by(|(_, _, a), (_, _, b)| distance(a).total_cmp(&distance(b)));
+
+            if let Some((point, _, _)) = picked {
+                attach_requests.send(AttachModuleRequest { base, point, module });
+                *placement = ModulePlacement::Inactive;
+            }
+        }
+        ModulePlacement::Detaching { base } => {
+            let picked = modules
+                .iter()
+                .filter(|(_, parent, transform)| parent.get() == base && distance(transform) < PICK_RADIUS)
+                .min_by(|(_, _, a), (_, _, b)| distance(a).total_cmp(&distance(b)));
+
+            if let Some((module, _, _)) = picked {
+                detach_requests.send(DetachModuleRequest { module });
+                *placement = ModulePlacement::Inactive;
+            }
+        }
+        ModulePlacement::Inactive | ModulePlacement::Choosing { .. } => {}
+    }
+}
+
+// System to highlight the attachment points or modules the player can pick
+fn update_attachment_highlights(
+    mut commands: Commands,
+    placement: Res<ModulePlacement>,
+    definitions: Res<ModuleDefinitions>,
+    bases: Query<&MechanicalBase>,
+    points: Query<Ref<AttachmentPoint>>,
+    modules: Query<(Entity, &Parent, &Sprite), With<InstalledModule>>,
+    highlights: Query<Entity, With<AttachmentHighlight>>,
+) {
+    // Only redraw when the mode changes or a point is taken or freed
+    if !placement.is_changed() && !points.iter().any(|point| point.is_changed()) {
+        return;
+    }
+
+    for highlight in highlights.iter() {
+        commands.entity(highlight).despawn_recursive();
+    }
+
+    match &*placement {
+        ModulePlacement::Placing { base, module } => {
+            let (Ok(base), Some(definition)) = (bases.get(*base), definitions.catalog.get(module)) else { return };
+            let attachable = definition.to_attachable();
+            for &point_entity in &base.attachment_points {
+                let Ok(point) = points.get(point_entity) else { continue };
+                if point.can_attach(&attachable) {
+                    spawn_highlight(&mut commands, point_entity, point.size, VALID_POINT_COLOR);
+                }
+            }
+        }
+        ModulePlacement::Detaching { base } => {
+            for (module, parent, sprite) in modules.iter() {
+                if parent.get() == *base {
+                    spawn_highlight(&mut commands, module, sprite.custom_size.unwrap_or(Vec2::splat(15.0)), DETACHABLE_COLOR);
+                }
+            }
+        }
+        ModulePlacement::Inactive | ModulePlacement::Choosing { .. } => {}
+    }
+}
+
+// Helper function to draw a highlight over an attachment point or module
+fn spawn_highlight(commands: &mut Commands, target: Entity, size: Vec2, color: Color) {
+    let highlight = commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(size + Vec2::splat(4.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 0.0, 0.2),
+                ..default()
+            },
+            AttachmentHighlight,
+        ))
+        .id();
+    commands.entity(target).add_child(highlight);
+}
+
+// System to leave placement mode and close the menu when gameplay ends
+fn close_module_menu(
+    mut commands: Commands,
+    mut placement: ResMut<ModulePlacement>,
+    menus: Query<Entity, Or<(With<ModuleBuildUI>, With<AttachmentHighlight>)>>,
+) {
+    *placement = ModulePlacement::Inactive;
+    for entity in menus.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// Text style used throughout the module menu
+fn text_style(asset_server: &Res<AssetServer>, font_size: f32) -> TextStyle {
+    TextStyle {
+        font: get_font_handle(asset_server),
+        font_size,
+        color: Color::srgba(0.95, 0.95, 0.95, 1.0),
+    }
+}
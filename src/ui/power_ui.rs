@@ -0,0 +1,258 @@
+use bevy::prelude::*;
+use crate::components::base_modules::{BaseModule, InstalledModule, ModuleCategory};
+use crate::components::player::MechanicalBase;
+use crate::components::unit::{Selected, Team};
+use crate::states::game_state::GameState;
+use crate::systems::module_effects::{ModulePower, PowerPriority};
+use crate::utils::font_loader::get_font_handle;
+
+// Button colours
+const BUTTON_COLOR: Color = Color::srgb(0.2, 0.2, 0.2);
+const BUTTON_HOVERED_COLOR: Color = Color::srgb(0.33, 0.33, 0.33);
+const SWITCHED_OFF_COLOR: Color = Color::srgb(0.45, 0.15, 0.15);
+
+// Component to mark the power panel
+#[derive(Component)]
+pub struct PowerPanel;
+
+// Component for the buttons in the power panel
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub enum PowerButton {
+    CycleProfile,
+    Raise(ModuleCategory),
+    Toggle(Entity),
+}
+
+// Component for texts that are refreshed every frame
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+enum PowerLabel {
+    Summary,
+    Profile,
+    Module(Entity),
+}
+
+// What the panel was last built for; it is rebuilt when any of this changes
+#[derive(Default, PartialEq)]
+struct PanelLayout {
+    base: Option<Entity>,
+    order: Vec<ModuleCategory>,
+    modules: Vec<Entity>,
+}
+
+// Plugin for the selected base's power panel
+pub struct PowerUIPlugin;
+
+impl Plugin for PowerUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+                Update,
+                (
+                    handle_power_buttons,
+                    sync_power_panel,
+                    update_power_labels,
+                ).chain().run_if(in_state(GameState::Gameplay))
+            )
+           .add_systems(OnExit(GameState::Gameplay), despawn_power_panel);
+
+        info!("Power UI Plugin initialized");
+    }
+}
+
+// System to show the power panel for the selected player base, rebuilding it when its layout changes
+fn sync_power_panel(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut layout: Local<PanelLayout>,
+    bases: Query<(Entity, &MechanicalBase, Option<&PowerPriority>), With<Selected>>,
+    modules: Query<(), With<BaseModule>>,
+    panels: Query<Entity, With<PowerPanel>>,
+) {
+    let selected = bases.iter().find(|(_, base, _)| base.team == Team::Player);
+    let current = match selected {
+        Some((entity, base, priority)) => PanelLayout {
+            base: Some(entity),
+            order: priority.cloned().unwrap_or_default().order,
+            modules: base.modules.iter().copied().filter(|&module| modules.contains(module)).collect(),
+        },
+        None => PanelLayout::default(),
+    };
+
+    if current == *layout && (current.base.is_none() || !panels.is_empty()) {
+        return;
+    }
+
+    for panel in panels.iter() {
+        commands.entity(panel).despawn_recursive();
+    }
+    if current.base.is_some() {
+        spawn_power_panel(&mut commands, &asset_server, &current);
+    }
+    *layout = current;
+}
+
+// Panel listing the category priorities and every module's power switch
+fn spawn_power_panel(commands: &mut Commands, asset_server: &Res<AssetServer>, layout: &PanelLayout) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(160.0),
+                    left: Val::Px(20.0),
+                    width: Val::Px(260.0),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    row_gap: Val::Px(4.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Stretch,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+                border_color: BorderColor(Color::srgb(0.3, 0.3, 0.3)),
+                ..default()
+            },
+            PowerPanel,
+            Name::new("Power Panel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((TextBundle::from_section("", text_style(asset_server, 16.0)), PowerLabel::Summary));
+            spawn_power_button(parent, asset_server, PowerButton::CycleProfile, Some(PowerLabel::Profile), "");
+
+            // Categories in priority order; raising one makes the profile custom
+            for (rank, category) in layout.order.iter().enumerate() {
+                let label = format!("{}. {:?}{}", rank + 1, category, if rank > 0 { "  (raise)" } else { "" });
+                spawn_power_button(parent, asset_server, PowerButton::Raise(*category), None, &label);
+            }
+
+            for &module in &layout.modules {
+                spawn_power_button(parent, asset_server, PowerButton::Toggle(module), Some(PowerLabel::Module(module)), "");
+            }
+        });
+}
+
+// Helper function to add one button to the power panel, optionally with a label refreshed every frame
+fn spawn_power_button(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    button: PowerButton,
+    label: Option<PowerLabel>,
+    text: &str,
+) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    padding: UiRect::axes(Val::Px(6.0), Val::Px(3.0)),
+                    ..default()
+                },
+                background_color: BackgroundColor(BUTTON_COLOR),
+                ..default()
+            },
+            button,
+        ))
+        .with_children(|parent| {
+            let mut text = parent.spawn(TextBundle::from_section(text, text_style(asset_server, 14.0)));
+            if let Some(label) = label {
+                text.insert(label);
+            }
+        });
+}
+
+// System to change profiles, priorities and module switches from the panel
+fn handle_power_buttons(
+    mut commands: Commands,
+    mut buttons: Query<(&Interaction, &mut BackgroundColor, &PowerButton), Changed<Interaction>>,
+    mut bases: Query<(Entity, &MechanicalBase, Option<&mut PowerPriority>), With<Selected>>,
+    mut modules: Query<&mut ModulePower>,
+) {
+    for (interaction, mut color, button) in buttons.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {}
+            Interaction::Hovered => {
+                *color = BackgroundColor(BUTTON_HOVERED_COLOR);
+                continue;
+            }
+            Interaction::None => {
+                *color = BackgroundColor(BUTTON_COLOR);
+                continue;
+            }
+        }
+
+        if let PowerButton::Toggle(module) = *button {
+            if let Ok(mut power) = modules.get_mut(module) {
+                power.switched_on = !power.switched_on;
+            }
+            continue;
+        }
+
+        let Some((entity, _, priority)) = bases.iter_mut().find(|(_, base, _)| base.team == Team::Player) else {
+            continue;
+        };
+        let mut updated = priority.as_deref().cloned().unwrap_or_default();
+        match *button {
+            PowerButton::CycleProfile => updated = PowerPriority::from_profile(updated.profile.next()),
+            PowerButton::Raise(category) => updated.raise(category),
+            PowerButton::Toggle(_) => {}
+        }
+        info!("Power profile set to {:?}: {:?}", updated.profile, updated.order);
+
+        match priority {
+            Some(mut priority) => *priority = updated,
+            None => {
+                commands.entity(entity).insert(updated);
+            }
+        }
+    }
+}
+
+// System to keep the power readouts up to date
+fn update_power_labels(
+    mut labels: Query<(&PowerLabel, &mut Text, &Parent)>,
+    mut button_colors: Query<&mut BackgroundColor, With<PowerButton>>,
+    bases: Query<(&MechanicalBase, Option<&PowerPriority>), With<Selected>>,
+    modules: Query<(&BaseModule, Option<&ModulePower>, Option<&InstalledModule>)>,
+) {
+    let Some((base, priority)) = bases.iter().find(|(base, _)| base.team == Team::Player) else { return };
+
+    for (label, mut text, parent) in labels.iter_mut() {
+        let value = match *label {
+            PowerLabel::Summary => format!("Power {:.0} / {:.0}", base.power_consumed, base.power_output),
+            PowerLabel::Profile => format!("Profile: {:?}", priority.map(|priority| priority.profile).unwrap_or_default()),
+            PowerLabel::Module(entity) => {
+                let Ok((module, power, installed)) = modules.get(entity) else { continue };
+                let power = power.copied().unwrap_or_default();
+                let name = installed.map_or_else(|| format!("{:?}", module.module_type.category()), |installed| installed.name.clone());
+                if let Ok(mut color) = button_colors.get_mut(parent.get()) {
+                    if !power.switched_on {
+                        *color = BackgroundColor(SWITCHED_OFF_COLOR);
+                    }
+                }
+                match (power.switched_on, module.active) {
+                    (false, _) => format!("{}: off", name),
+                    (true, false) => format!("{}: no power", name),
+                    (true, true) => format!("{}: {:.0}%", name, power.supply * 100.0),
+                }
+            }
+        };
+
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
+
+// System to remove the power panel when gameplay ends
+fn despawn_power_panel(mut commands: Commands, panels: Query<Entity, With<PowerPanel>>) {
+    for panel in panels.iter() {
+        commands.entity(panel).despawn_recursive();
+    }
+}
+
+// Text style used throughout the power panel
+fn text_style(asset_server: &Res<AssetServer>, font_size: f32) -> TextStyle {
+    TextStyle {
+        font: get_font_handle(asset_server),
+        font_size,
+        color: Color::srgba(0.95, 0.95, 0.95, 1.0),
+    }
+}
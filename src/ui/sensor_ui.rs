@@ -0,0 +1,182 @@
+use bevy::prelude::*;
+use crate::components::player::MechanicalBase;
+use crate::components::unit::{Selected, Team};
+use crate::states::game_state::GameState;
+use crate::systems::camera::GameCamera;
+use crate::systems::sensors::{EnemyIntel, ScanRequest};
+use crate::ui::base_action_ui::BaseAction;
+use crate::utils::font_loader::get_font_handle;
+
+/// Base waiting for the player to pick where its sensors should scan
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
+pub struct ScanTargeting {
+    pub base: Option<Entity>,
+}
+
+// Component to mark the enemy intel panel
+#[derive(Component)]
+pub struct IntelPanel;
+
+// Component for the text listing the scouted enemy bases
+#[derive(Component)]
+struct IntelText;
+
+// Plugin for aiming sensor scans and showing what they have scouted
+pub struct SensorUIPlugin;
+
+impl Plugin for SensorUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScanTargeting>()
+           .init_resource::<EnemyIntel>()
+           .add_event::<ScanRequest>()
+           .add_systems(
+                Update,
+                (
+                    start_scan_targeting,
+                    handle_scan_clicks,
+                    update_intel_panel,
+                ).chain().run_if(in_state(GameState::Gameplay))
+            )
+           .add_systems(OnExit(GameState::Gameplay), close_sensor_ui);
+
+        info!("Sensor UI Plugin initialized");
+    }
+}
+
+// System to start aiming a scan when the Scan button is pressed on a player base
+fn start_scan_targeting(
+    buttons: Query<(&Interaction, &BaseAction), Changed<Interaction>>,
+    bases: Query<(Entity, &MechanicalBase), With<Selected>>,
+    mut targeting: ResMut<ScanTargeting>,
+) {
+    let pressed = buttons
+        .iter()
+        .any(|(interaction, action)| *interaction == Interaction::Pressed && *action == BaseAction::Scan);
+    if !pressed {
+        return;
+    }
+
+    if let Some((base, _)) = bases.iter().find(|(_, base)| base.team == Team::Player) {
+        targeting.base = Some(base);
+    }
+}
+
+// System to scan wherever the player clicks while aiming
+fn handle_scan_clicks(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    mut targeting: ResMut<ScanTargeting>,
+    mut scan_requests: EventWriter<ScanRequest>,
+) {
+    // The click that pressed the Scan button doesn't count as the target
+    let Some(base) = targeting.base else { return };
+    if targeting.is_changed() {
+        return;
+    }
+
+    if mouse_buttons.just_pressed(MouseButton::Right) || keys.just_pressed(KeyCode::Escape) {
+        targeting.base = None;
+        info!("Cancelled scan");
+        return;
+    }
+    if !mouse_buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let Ok(window) = windows.get_single() else { return };
+    let Ok((camera, camera_transform)) = cameras.get_single() else { return };
+    if let Some(target) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor))
+    {
+        scan_requests.send(ScanRequest { base, target });
+        targeting.base = None;
+    }
+}
+
+// System to list the module loadout last seen on each enemy base
+fn update_intel_panel(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    intel: Res<EnemyIntel>,
+    panels: Query<Entity, With<IntelPanel>>,
+    mut texts: Query<&mut Text, With<IntelText>>,
+) {
+    if intel.reports.is_empty() {
+        for panel in panels.iter() {
+            commands.entity(panel).despawn_recursive();
+        }
+        return;
+    }
+
+    let mut reports: Vec<_> = intel.reports.values().collect();
+    reports.sort_by(|a, b| b.seen_at.total_cmp(&a.seen_at));
+    let mut value = String::from("Enemy Intel");
+    for report in reports {
+        let loadout = if report.modules.is_empty() { "no modules".to_string() } else { report.modules.join(", ") };
+        let age = time.elapsed_seconds() - report.seen_at;
+        let seen = if age < 1.0 { "in sight".to_string() } else { format!("seen {:.0}s ago", age) };
+        value.push_str(&format!("\n{:?} base ({}): {}", report.team, seen, loadout));
+    }
+
+    match texts.get_single_mut() {
+        Ok(mut text) => {
+            if text.sections[0].value != value {
+                text.sections[0].value = value;
+            }
+        }
+        Err(_) if panels.is_empty() => spawn_intel_panel(&mut commands, &asset_server, value),
+        Err(_) => {}
+    }
+}
+
+// Panel in the top left corner listing scouted enemy bases
+fn spawn_intel_panel(commands: &mut Commands, asset_server: &Res<AssetServer>, value: String) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    left: Val::Px(10.0),
+                    max_width: Val::Px(360.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+                border_color: BorderColor(Color::srgb(0.3, 0.3, 0.3)),
+                ..default()
+            },
+            IntelPanel,
+            Name::new("Enemy Intel Panel"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    value,
+                    TextStyle {
+                        font: get_font_handle(asset_server),
+                        font_size: 14.0,
+                        color: Color::srgba(0.95, 0.95, 0.95, 1.0),
+                    },
+                ),
+                IntelText,
+            ));
+        });
+}
+
+// System to stop aiming and remove the intel panel when gameplay ends
+fn close_sensor_ui(
+    mut commands: Commands,
+    mut targeting: ResMut<ScanTargeting>,
+    panels: Query<Entity, With<IntelPanel>>,
+) {
+    targeting.base = None;
+    for panel in panels.iter() {
+        commands.entity(panel).despawn_recursive();
+    }
+}
@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+use crate::components::strategic::{CaptureState, StrategicLocation, StrategicScores};
+use crate::components::unit::Team;
+use crate::states::game_state::GameState;
+use crate::utils::font_loader::get_font_handle;
+
+// Colours for each control state, matching the location sprites on the map
+const NEUTRAL_COLOR: Color = Color::srgb(0.9, 0.7, 0.1);
+const PLAYER_COLOR: Color = Color::srgb(0.3, 0.7, 0.95);
+const ENEMY_COLOR: Color = Color::srgb(0.95, 0.3, 0.3);
+
+// Component to mark the root of the strategic HUD
+#[derive(Component)]
+pub struct StrategicHud;
+
+// Component for the HUD row showing one strategic location
+#[derive(Component)]
+pub struct StrategicHudRow(pub Entity);
+
+// Component for the HUD line showing each team's score
+#[derive(Component)]
+pub struct StrategicScoreText;
+
+// Plugin for the strategic location HUD
+pub struct StrategicHudPlugin;
+
+impl Plugin for StrategicHudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+                Update,
+                (
+                    spawn_strategic_hud,
+                    sync_strategic_hud_rows,
+                    update_strategic_hud,
+                ).chain().run_if(in_state(GameState::Gameplay))
+            )
+           .add_systems(OnExit(GameState::Gameplay), cleanup_strategic_hud);
+
+        info!("Strategic HUD Plugin initialized");
+    }
+}
+
+// System to create the HUD panel in the top right corner
+fn spawn_strategic_hud(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    hud: Query<(), With<StrategicHud>>,
+) {
+    if !hud.is_empty() {
+        return;
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    row_gap: Val::Px(4.0),
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+                ..default()
+            },
+            StrategicHud,
+            Name::new("Strategic HUD"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section("", hud_text_style(&asset_server, Color::WHITE)),
+                StrategicScoreText,
+            ));
+        });
+}
+
+// System to add a row for every new strategic location and drop rows for removed ones
+fn sync_strategic_hud_rows(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    hud: Query<Entity, With<StrategicHud>>,
+    rows: Query<(Entity, &StrategicHudRow)>,
+    locations: Query<Entity, With<StrategicLocation>>,
+) {
+    let Ok(hud) = hud.get_single() else { return };
+
+    for (row, StrategicHudRow(location)) in rows.iter() {
+        if locations.get(*location).is_err() {
+            commands.entity(row).despawn_recursive();
+        }
+    }
+
+    for location in locations.iter() {
+        if rows.iter().any(|(_, row)| row.0 == location) {
+            continue;
+        }
+        let row = commands
+            .spawn((
+                TextBundle::from_section("", hud_text_style(&asset_server, NEUTRAL_COLOR)),
+                StrategicHudRow(location),
+            ))
+            .id();
+        commands.entity(hud).add_child(row);
+    }
+}
+
+// System to show who holds each location, how far along the capture is and each team's score
+fn update_strategic_hud(
+    scores: Res<StrategicScores>,
+    locations: Query<&StrategicLocation>,
+    mut rows: Query<(&StrategicHudRow, &mut Text), Without<StrategicScoreText>>,
+    mut score_text: Query<&mut Text, With<StrategicScoreText>>,
+) {
+    for (StrategicHudRow(location), mut text) in rows.iter_mut() {
+        let Ok(location) = locations.get(*location) else { continue };
+        let progress = (location.control_points / location.total_required * 100.0).round();
+        let (owner, color) = match location.controlling_team {
+            Some(Team::Player) => ("Player".to_string(), PLAYER_COLOR),
+            Some(Team::Enemy) => ("Enemy".to_string(), ENEMY_COLOR),
+            Some(team) => (format!("{:?}", team), NEUTRAL_COLOR),
+            None => ("Neutral".to_string(), NEUTRAL_COLOR),
+        };
+
+        let status = match location.state {
+            CaptureState::Contested => " - contested",
+            CaptureState::Capturing(team) if location.controlling_team != Some(team) => " - capturing",
+            _ => "",
+        };
+
+        let section = &mut text.sections[0];
+        section.value = format!("{} (x{}): {} {}%{}", location.name, location.capture_value, owner, progress, status);
+        section.style.color = color;
+    }
+
+    if let Ok(mut text) = score_text.get_single_mut() {
+        text.sections[0].value = format!(
+            "Score - Player {} | Enemy {}",
+            scores.team(Team::Player).score,
+            scores.team(Team::Enemy).score
+        );
+    }
+}
+
+// System to remove the HUD when leaving gameplay
+fn cleanup_strategic_hud(
+    mut commands: Commands,
+    hud: Query<Entity, With<StrategicHud>>,
+) {
+    for entity in hud.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// Text style shared by every line of the HUD
+fn hud_text_style(asset_server: &Res<AssetServer>, color: Color) -> TextStyle {
+    TextStyle {
+        font: get_font_handle(asset_server),
+        font_size: 18.0,
+        color,
+    }
+}
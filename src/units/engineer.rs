@@ -1,11 +1,18 @@
 use bevy::prelude::*;
-use bevy::ecs::system::ParamSet;
-use crate::components::unit::{Unit, Team, UnitState};
+use crate::components::base_modules::BaseModule;
+use crate::components::player::MechanicalBase;
+use crate::components::unit::{Team, Unit, UnitState};
 use crate::components::unit_types::UnitType;
-use crate::components::resource::{Gatherer, ResourceNode};
-use crate::components::building::{Building, ResourceType, Constructable};
+use crate::components::resource::ResourceNode;
+use crate::components::building::{Building, Constructable};
+use crate::components::economy::{ResourceType, ResourceWallet};
+use crate::systems::movement::MoveTarget;
+use crate::systems::unit_catalog::UnitCatalog;
 use std::time::Duration;
 
+/// How close to a base an engineer has to be to repair its modules
+pub const REPAIR_RANGE: f32 = 60.0;
+
 // Plugin for Engineer unit functionality
 pub struct EngineerPlugin;
 
@@ -18,8 +25,8 @@ impl Plugin for EngineerPlugin {
                 Update,
                 (
                     handle_engineer_selection,
-                    handle_engineer_resource_gathering,
-                ).run_if(in_state(crate::states::game_state::GameState::Gameplay))
+                    repair_base_modules,
+                ).chain().run_if(in_state(crate::states::game_state::GameState::Gameplay))
             );
             
         // Temporarily remove handle_engineer_building from systems until fully fixed
@@ -31,7 +38,6 @@ impl Plugin for EngineerPlugin {
 // Component to mark a unit as an Engineer
 #[derive(Component)]
 pub struct Engineer {
-    #[allow(dead_code)]
     pub build_speed: f32,
     #[allow(dead_code)]
     pub build_timer: Timer,
@@ -52,13 +58,32 @@ pub struct BuildLocation {
     pub building_type: String,
 }
 
+/// Component for the friendly base whose modules an engineer is repairing
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct RepairTarget {
+    pub base: Entity,
+}
+
+/// Friendly base close enough to a clicked position for an engineer on `team` to repair it
+pub fn repair_target_at<'a>(
+    bases: impl IntoIterator<Item = (Entity, &'a Transform, &'a MechanicalBase)>,
+    team: Team,
+    position: Vec2,
+) -> Option<Entity> {
+    bases
+        .into_iter()
+        .find(|(_, transform, base)| base.team == team && position.distance(transform.translation.truncate()) < REPAIR_RANGE)
+        .map(|(entity, _, _)| entity)
+}
+
 // System to handle engineer selection and right-click commands
 pub fn handle_engineer_selection(
     mouse_buttons: Res<ButtonInput<MouseButton>>,
     windows: Query<&Window>,
     camera_q: Query<(&Camera, &GlobalTransform)>,
     resource_nodes: Query<(Entity, &Transform, &ResourceNode)>,
-    engineers: Query<(Entity, &Transform), (With<Engineer>, With<crate::components::unit::Selected>)>,
+    bases: Query<(Entity, &Transform, &MechanicalBase)>,
+    engineers: Query<(Entity, &Transform, &Unit), (With<Engineer>, With<crate::components::unit::Selected>)>,
     mut commands: Commands,
 ) {
     // Only process when right mouse button is just pressed
@@ -76,6 +101,20 @@ pub fn handle_engineer_selection(
                 let selected_engineers: Vec<_> = engineers.iter().collect();
                 
                 if !selected_engineers.is_empty() {
+                    // Check if clicked on a friendly base to repair its modules
+                    let mut repairing = false;
+                    for (engineer_entity, _, unit) in selected_engineers.iter() {
+                        if let Some(base_entity) = repair_target_at(bases.iter(), unit.team, world_position) {
+                            commands.entity(*engineer_entity).remove::<(BuildLocation, SelectedResource)>();
+                            commands.entity(*engineer_entity).insert((RepairTarget { base: base_entity }, UnitState::Building));
+                            repairing = true;
+                        }
+                    }
+                    if repairing {
+                        info!("Engineer assigned to repair base modules");
+                        return;
+                    }
+
                     // Check if clicked on a resource node
                     for (resource_entity, resource_transform, _) in resource_nodes.iter() {
                         let resource_pos = resource_transform.translation.truncate();
@@ -86,9 +125,9 @@ pub fn handle_engineer_selection(
                             info!("Engineer assigned to gather resource");
                             
                             // Assign all selected engineers to gather from this resource
-                            for (engineer_entity, _) in selected_engineers.iter() {
-                                // Remove any existing gathering target
-                                commands.entity(*engineer_entity).remove::<SelectedResource>();
+                            for (engineer_entity, _, _) in selected_engineers.iter() {
+                                // Remove any existing gathering or repair target
+                                commands.entity(*engineer_entity).remove::<(SelectedResource, RepairTarget)>();
                                 
                                 // Assign new gathering target
                                 commands.entity(*engineer_entity).insert(SelectedResource {
@@ -104,9 +143,9 @@ pub fn handle_engineer_selection(
                     }
                     
                     // If not clicked on a resource, set as build location (simplified for now)
-                    for (engineer_entity, _) in selected_engineers.iter() {
-                        // Remove any existing build location
-                        commands.entity(*engineer_entity).remove::<BuildLocation>();
+                    for (engineer_entity, _, _) in selected_engineers.iter() {
+                        // Remove any existing build location and stop gathering or repairing
+                        commands.entity(*engineer_entity).remove::<(BuildLocation, SelectedResource, RepairTarget)>();
                         
                         // Set new build location (for simplicity, always build a basic structure)
                         commands.entity(*engineer_entity).insert(BuildLocation {
@@ -123,113 +162,6 @@ pub fn handle_engineer_selection(
     }
 }
 
-// System to handle resource gathering by engineers
-pub fn handle_engineer_resource_gathering(
-    time: Res<Time>,
-    mut query_set: ParamSet<(
-        Query<(Entity, &mut Gatherer, &SelectedResource, &mut Transform), With<Engineer>>,
-        Query<(Entity, &mut ResourceNode, &Transform)>
-    )>,
-) {
-    // First get the engineers and store their data
-    let mut engineer_data = Vec::new();
-    for (entity, _gatherer, selected_resource, transform) in query_set.p0().iter() {
-        engineer_data.push((entity, selected_resource.resource_entity, transform.translation.truncate()));
-    }
-    
-    // Then process the engineers
-    for (engineer_entity, resource_entity, engineer_pos) in engineer_data.iter() {
-        // Try to get the resource node
-        if let Ok((_entity, _resource_node, resource_transform)) = query_set.p1().get(*resource_entity) {
-            // Get resource position from transform
-            let resource_pos = resource_transform.translation.truncate();
-            let distance = engineer_pos.distance(resource_pos);
-            
-            if distance > 30.0 {
-                // Engineer is too far, need to move closer
-                // Calculate direction to the resource
-                let direction = (resource_pos - *engineer_pos).normalize();
-                
-                // Move toward the resource
-                let new_pos = *engineer_pos + direction * 60.0 * time.delta_seconds();
-                
-                // Update engineer position in a separate step
-                let mut engineers = query_set.p0();
-                if let Ok((_entity, _gatherer, _selected_resource, mut transform)) = engineers.get_mut(*engineer_entity) {
-                    transform.translation.x = new_pos.x;
-                    transform.translation.y = new_pos.y;
-                }
-            } else {
-                // Engineer is close enough to gather
-                // We need to handle mutable borrows carefully to avoid borrowing query_set multiple times
-                
-                // Step 1: Check if we can gather (tick the timer)
-                let mut should_gather = false;
-                let mut gather_rate = 0.0;
-                let mut carry_capacity = 0;
-                let mut current_load = 0;
-                {
-                    let mut engineers = query_set.p0();
-                    if let Ok((_entity, mut gatherer, _selected_resource, _transform)) = engineers.get_mut(*engineer_entity) {
-                        gatherer.gather_timer.tick(time.delta());
-                        
-                        if gatherer.gather_timer.just_finished() {
-                            should_gather = true;
-                            gather_rate = gatherer.gather_rate;
-                            carry_capacity = gatherer.carry_capacity;
-                            current_load = gatherer.current_load;
-                        }
-                    }
-                }
-                
-                // Step 2: If we should gather, check the resource node
-                let mut resource_type = None;
-                let mut amount_gathered = 0;
-                let mut updated_load = current_load;
-                
-                if should_gather {
-                    let mut resource_nodes = query_set.p1();
-                    if let Ok((_entity, mut resource_node, _transform)) = resource_nodes.get_mut(*resource_entity) {
-                        // Gather resources if there are any left
-                        if resource_node.amount_remaining > 0 {
-                            let gather_amount = (gather_rate * time.delta_seconds()) as i32;
-                            let actual_amount = gather_amount.min(resource_node.amount_remaining);
-                            
-                            // Update resource node
-                            resource_node.amount_remaining -= actual_amount;
-                            updated_load += actual_amount;
-                            
-                            resource_type = Some(resource_node.resource_type.clone());
-                            amount_gathered = actual_amount;
-                        }
-                    }
-                }
-                
-                // Step 3: Update the engineer's current load if needed
-                if amount_gathered > 0 {
-                    let mut engineers = query_set.p0();
-                    if let Ok((_entity, mut gatherer, _selected_resource, _transform)) = engineers.get_mut(*engineer_entity) {
-                        gatherer.current_load = updated_load;
-                        
-                        // Check if engineer is full
-                        if gatherer.current_load >= carry_capacity {
-                            // Would need to find a base to deliver to
-                            // For now, just reset the load
-                            gatherer.current_load = 0;
-                            info!("Engineer delivered resources to base");
-                        }
-                    }
-                }
-                
-                // Log the gathering outside of the borrow scopes
-                if let Some(res_type) = resource_type {
-                    info!("Engineer gathered {} units of {:?}", amount_gathered, res_type);
-                }
-            }
-        }
-    }
-
-
 // System to handle building construction by engineers
 pub fn handle_engineer_building(
     time: Res<Time>,
@@ -284,7 +216,7 @@ pub fn handle_engineer_building(
                     },
                     Constructable {
                         construction_time: 10.0,
-                        resource_cost: vec![(ResourceType::Wood, 50), (ResourceType::Stone, 30)],
+                        resource_cost: ResourceWallet::from([(ResourceType::Wood, 50), (ResourceType::Stone, 30)]),
                     },
                     Name::new(format!("{}", build_location.building_type)),
                 )).id();
@@ -297,54 +229,58 @@ pub fn handle_engineer_building(
     }
 }
 
-// Spawn an engineer unit at the given position for the given team
-pub fn spawn_engineer(commands: &mut Commands, position: Vec2, team: Team) -> Entity {
-    let color = match team {
-        Team::Player => Color::srgb(0.2, 0.6, 0.8), // Blue for player
-        Team::Enemy => Color::srgb(0.8, 0.2, 0.2),  // Red for enemy
-        Team::Neutral => Color::srgb(0.5, 0.5, 0.5), // Gray for neutral
-    };
-    
-    let engineer = commands.spawn((
-        SpriteBundle {
-            sprite: Sprite {
-                color,
-                custom_size: Some(Vec2::new(24.0, 24.0)),
-                ..default()
-            },
-            transform: Transform::from_xyz(position.x, position.y, 10.0),
-            ..default()
-        },
-        Unit {
-            health: 50.0,
-            max_health: 50.0,
-            movement_speed: 60.0,
-            team,
-            attack_power: 5.0,
-            attack_range: 1.0,
-            state: UnitState::Idle,
-            attack_cooldown: Timer::from_seconds(1.0, TimerMode::Once),
-            attack_target: None,
-            movement_target: None,
-        },
-        UnitType::Engineer,
-        Engineer {
-            build_speed: 10.0,
-            build_timer: Timer::new(Duration::from_secs_f32(1.0), TimerMode::Repeating),
-            target_building: None,
-        },
-        Gatherer {
-            gather_rate: 5.0,
-            gather_timer: Timer::new(Duration::from_secs_f32(1.0), TimerMode::Repeating),
-            carry_capacity: 20,
-            current_load: 0,
-            target_resource: None,
-        },
-        UnitState::Idle,
-        Name::new(format!("{:?} Engineer", team)),
-    )).id();
-    
-    engineer
+/// System to let engineers next to their target base patch up its most damaged module
+///
+/// Engineers out of range walk over to the base first. Each engineer restores `build_speed`
+/// health a second, bringing disabled modules back online once they are above
+/// `MODULE_DISABLE_THRESHOLD`. Engineers go idle when there is nothing left to fix or the base is gone.
+pub fn repair_base_modules(
+    mut commands: Commands,
+    time: Res<Time>,
+    engineers: Query<(Entity, &Transform, &Engineer, &RepairTarget, Option<&MoveTarget>)>,
+    bases: Query<(&Transform, &Children), With<MechanicalBase>>,
+    mut modules: Query<&mut BaseModule>,
+) {
+    for (entity, transform, engineer, repair, move_target) in engineers.iter() {
+        let Ok((base_transform, children)) = bases.get(repair.base) else {
+            commands.entity(entity).remove::<RepairTarget>().insert(UnitState::Idle);
+            continue;
+        };
+
+        let damaged = children
+            .iter()
+            .filter_map(|&child| modules.get(child).ok().map(|module| (child, module.health / module.max_health.max(f32::EPSILON))))
+            .filter(|(_, fraction)| *fraction < 1.0)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+        let Some((module_entity, _)) = damaged else {
+            info!("Engineer finished repairing base modules");
+            commands.entity(entity).remove::<RepairTarget>().insert(UnitState::Idle);
+            continue;
+        };
+
+        // Walk over to the base, following it if it drives off, and stop once in range
+        let base_position = base_transform.translation.truncate();
+        if transform.translation.truncate().distance(base_position) > REPAIR_RANGE {
+            if move_target.is_none_or(|target| target.position.distance(base_position) > REPAIR_RANGE / 2.0) {
+                commands.entity(entity).insert(MoveTarget { position: base_position });
+            }
+            continue;
+        }
+        if move_target.is_some() {
+            commands.entity(entity).remove::<MoveTarget>();
+        }
+
+        if let Ok(mut module) = modules.get_mut(module_entity) {
+            let was_disabled = module.is_disabled();
+            module.health = (module.health + engineer.build_speed * time.delta_seconds()).min(module.max_health);
+            if was_disabled && !module.is_disabled() {
+                info!("{:?} base module {:?} is back online", module.team, module_entity);
+            }
+        }
+    }
 }
 
+// Spawn an engineer unit at the given position for the given team
+pub fn spawn_engineer(commands: &mut Commands, catalog: &UnitCatalog, position: Vec2, team: Team) -> Entity {
+    UnitType::Engineer.spawn_unit(commands, catalog, position, team)
 }
@@ -65,3 +65,33 @@ pub fn perlin_noise_2d(x: f32, y: f32, seed: u32) -> f32 {
     
     (a.sin() + b.sin()).abs() % 1.0
 }
+
+/// Calculate smooth 2D value noise in the range 0.0..1.0 (for map generation)
+///
+/// Unlike `perlin_noise_2d` this is continuous, and the same seed always gives the same field.
+pub fn value_noise_2d(x: f32, y: f32, seed: u64) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (tx, ty) = (x - x0, y - y0);
+    let (ix, iy) = (x0 as i64, y0 as i64);
+
+    // Smoothstep the blend so the field has no visible grid lines
+    let sx = tx * tx * (3.0 - 2.0 * tx);
+    let sy = ty * ty * (3.0 - 2.0 * ty);
+
+    let bottom = lerp(lattice_value(ix, iy, seed), lattice_value(ix + 1, iy, seed), sx);
+    let top = lerp(lattice_value(ix, iy + 1, seed), lattice_value(ix + 1, iy + 1, seed), sx);
+    lerp(bottom, top, sy)
+}
+
+// Pseudo-random value in 0.0..1.0 for a lattice point (splitmix64 hash)
+fn lattice_value(x: i64, y: i64, seed: u64) -> f32 {
+    let mut hash = seed
+        ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+    hash ^= hash >> 30;
+    hash = hash.wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    hash ^= hash >> 27;
+    hash = hash.wrapping_mul(0x94D0_49BB_1331_11EB);
+    hash ^= hash >> 31;
+    (hash >> 40) as f32 / (1u64 << 24) as f32
+}
@@ -3,6 +3,7 @@ pub mod font_plugin;
 pub mod math;
 pub mod pathfinding;
 pub mod config;
+pub mod ron_asset;
 
 // Temporarily commented out for debugging
 // pub use font_plugin::FontPlugin;
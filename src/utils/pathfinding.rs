@@ -1,7 +1,16 @@
 use bevy::prelude::*;
 use pathfinding::prelude::astar;
+use std::collections::VecDeque;
 use crate::resources::map_data::GameMap;
-use crate::components::terrain::Terrain;
+
+/// Cost of a straight step onto plains
+const STRAIGHT_COST: u32 = 10;
+
+/// Cost of a diagonal step onto plains (roughly 10 * sqrt(2))
+const DIAGONAL_COST: u32 = 14;
+
+/// Slowest speed multiplier applied to a unit, so one stranded on blocked terrain can still walk off
+const MIN_SPEED_MODIFIER: f32 = 0.1;
 
 /// A position on the grid map with its cost
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -14,86 +23,164 @@ impl GridPosition {
     pub fn new(x: i32, y: i32) -> Self {
         Self { x, y }
     }
-    
-    /// Calculate the manhattan distance to another position
+
+    /// Calculate the octile distance to another position, in path cost units
     pub fn distance(&self, other: &GridPosition) -> u32 {
-        ((self.x - other.x).abs() + (self.y - other.y).abs()) as u32
+        let dx = (self.x - other.x).unsigned_abs();
+        let dy = (self.y - other.y).unsigned_abs();
+        STRAIGHT_COST * dx.max(dy) + (DIAGONAL_COST - STRAIGHT_COST) * dx.min(dy)
     }
-    
-    /// Get neighboring positions (4-way, not diagonal)
+
+    /// Get passable neighboring positions (8-way) with the cost of stepping onto them
+    ///
+    /// A step costs more the slower the terrain it enters. Diagonal steps are only allowed when
+    /// both tiles they squeeze between are passable, so paths never cut the corner of a lake.
     pub fn neighbors(&self, game_map: &GameMap) -> Vec<(GridPosition, u32)> {
         let mut neighbors = Vec::new();
-        let directions = [(0, 1), (1, 0), (0, -1), (-1, 0)];
-        
+        let directions = [(0, 1), (1, 0), (0, -1), (-1, 0), (1, 1), (1, -1), (-1, 1), (-1, -1)];
+
         for (dx, dy) in directions.iter() {
             let nx = self.x + dx;
             let ny = self.y + dy;
-            
-            if game_map.is_in_bounds(nx, ny) {
-                neighbors.push((GridPosition::new(nx, ny), 1));
+
+            let modifier = game_map.movement_modifier_at(nx, ny);
+            if modifier <= 0.0 {
+                continue;
+            }
+
+            let diagonal = *dx != 0 && *dy != 0;
+            if diagonal && !(game_map.is_passable(nx, self.y) && game_map.is_passable(self.x, ny)) {
+                continue;
             }
+
+            let base_cost = if diagonal { DIAGONAL_COST } else { STRAIGHT_COST };
+            let cost = (base_cost as f32 / modifier).round() as u32;
+            neighbors.push((GridPosition::new(nx, ny), cost.max(1)));
         }
-        
+
         neighbors
     }
-    
-    /// Convert to world coordinates
+
+    /// Convert to world coordinates (centre of the tile)
     pub fn to_world(&self, game_map: &GameMap) -> Vec2 {
-        game_map.grid_to_world(self.x, self.y)
+        game_map.tile_center(self.x, self.y)
+    }
+}
+
+/// Waypoints a unit or base is following toward its move target
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct MovePath {
+    pub goal: Vec2,
+    pub waypoints: VecDeque<Vec2>,
+}
+
+impl MovePath {
+    /// Plan a route, going straight when there is no map or either end lies off it
+    ///
+    /// Returns `None` when the map has no way through to the goal.
+    pub fn plan(game_map: Option<&GameMap>, start: Vec2, goal: Vec2) -> Option<Self> {
+        let waypoints = match game_map {
+            Some(map) if is_on_map(map, start) && is_on_map(map, goal) => find_path(map, start, goal)?,
+            _ => vec![goal],
+        };
+
+        Some(Self { goal, waypoints: waypoints.into() })
+    }
+
+    /// Whether this path still leads to `goal`
+    ///
+    /// A goal that only shifted within the same tile (e.g. a unit chasing a slow target) just
+    /// moves the last waypoint instead of forcing a new search.
+    pub fn follows(&mut self, goal: Vec2, game_map: Option<&GameMap>) -> bool {
+        if self.goal == goal {
+            return true;
+        }
+
+        let same_tile = game_map.is_some_and(|map| {
+            is_on_map(map, goal) && map.world_to_grid(goal) == map.world_to_grid(self.goal)
+        });
+        if !same_tile {
+            return false;
+        }
+
+        self.goal = goal;
+        if let Some(last) = self.waypoints.back_mut() {
+            *last = goal;
+        }
+        true
+    }
+
+    /// Move from `position` up to `distance` along the path, dropping waypoints as they are passed
+    ///
+    /// Never overshoots the final waypoint.
+    pub fn advance(&mut self, mut position: Vec2, mut distance: f32) -> Vec2 {
+        while let Some(&waypoint) = self.waypoints.front() {
+            let to_waypoint = position.distance(waypoint);
+            if to_waypoint > distance || self.waypoints.len() == 1 {
+                return position + (waypoint - position).normalize_or_zero() * distance.min(to_waypoint);
+            }
+
+            position = waypoint;
+            distance -= to_waypoint;
+            self.waypoints.pop_front();
+        }
+
+        position
     }
 }
 
-/// Find a path from start to goal
+/// Find a path from start to goal over the map's terrain
+///
+/// The returned waypoints skip the start tile, only keep the tiles where the path turns and
+/// end exactly at `goal`. Returns `None` if the goal is off the map, impassable or cut off.
 pub fn find_path(
     game_map: &GameMap,
     start: Vec2,
     goal: Vec2,
-    terrain_query: &Query<(&Transform, &Terrain)>,
 ) -> Option<Vec<Vec2>> {
     let start_grid = game_map.world_to_grid(start);
     let goal_grid = game_map.world_to_grid(goal);
-    
+
     let start_pos = GridPosition::new(start_grid.0, start_grid.1);
     let goal_pos = GridPosition::new(goal_grid.0, goal_grid.1);
-    
+
+    if !game_map.is_passable(goal_pos.x, goal_pos.y) {
+        return None;
+    }
+
     // A* search
-    let result = astar(
+    let (positions, _) = astar(
         &start_pos,
-        |p| {
-            p.neighbors(game_map)
-                .into_iter()
-                .map(|(pos, cost)| {
-                    // Check terrain for movement cost
-                    let base_cost = cost;
-                    let movement_cost = get_terrain_movement_cost(game_map, &pos, terrain_query);
-                    let total_cost = (base_cost as f32 * movement_cost) as u32;
-                    (pos, total_cost.max(1)) // Ensure cost is at least 1
-                })
-                .collect::<Vec<_>>()
-        },
-        |p| p.distance(&goal_pos) / 3, // Heuristic - divide by 3 to not overestimate
+        |p| p.neighbors(game_map),
+        |p| p.distance(&goal_pos),
         |p| *p == goal_pos,
-    );
-    
-    // Convert the path to world coordinates
-    result.map(|(positions, _)| {
-        positions.into_iter().map(|pos| pos.to_world(game_map)).collect()
-    })
-}
+    )?;
 
-/// Get terrain movement cost at position
-fn get_terrain_movement_cost(
-    game_map: &GameMap,
-    pos: &GridPosition,
-    terrain_query: &Query<(&Transform, &Terrain)>,
-) -> f32 {
-    if let Some(_tile_entity) = game_map.get_tile_entity(pos.x, pos.y) {
-        // Find terrain component
-        for (_, terrain) in terrain_query.iter() {
-            return 1.0 / terrain.movement_modifier.max(0.1);
+    // Keep only the turning points, then swap the goal tile's centre for the exact goal
+    let mut waypoints = Vec::new();
+    for (index, pos) in positions.iter().enumerate().skip(1) {
+        let is_turn = positions.get(index + 1).is_none_or(|next| {
+            let previous = &positions[index - 1];
+            (pos.x - previous.x, pos.y - previous.y) != (next.x - pos.x, next.y - pos.y)
+        });
+        if is_turn {
+            waypoints.push(pos.to_world(game_map));
         }
     }
-    
-    // Default cost if terrain not found
-    1.0
+
+    waypoints.pop();
+    waypoints.push(goal);
+    Some(waypoints)
+}
+
+/// Speed multiplier for something moving over the terrain at a world position
+pub fn speed_modifier_at(game_map: Option<&GameMap>, position: Vec2) -> f32 {
+    let Some(terrain) = game_map.and_then(|map| map.terrain_stats_at(position)) else { return 1.0 };
+    terrain.movement_modifier.max(MIN_SPEED_MODIFIER)
+}
+
+// Check if a world position falls on a tile of the map
+fn is_on_map(game_map: &GameMap, position: Vec2) -> bool {
+    let (x, y) = game_map.world_to_grid(position);
+    game_map.is_in_bounds(x, y)
 }
@@ -0,0 +1,101 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{Asset, AssetLoader, AsyncReadExt, LoadContext};
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Generic asset loader for game data files written in RON
+///
+/// Each data asset registers its own loader instance with a distinct
+/// double extension (e.g. `damage.ron`) so Bevy can pick the right type.
+pub struct RonAssetLoader<A> {
+    extensions: &'static [&'static str],
+    _marker: PhantomData<fn() -> A>,
+}
+
+impl<A> RonAssetLoader<A> {
+    /// Create a loader for files ending in one of the given extensions
+    pub fn new(extensions: &'static [&'static str]) -> Self {
+        Self {
+            extensions,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Data assets loaded by `RonAssetLoader`
+pub trait RonAsset: Asset + DeserializeOwned {
+    /// Check the parsed data, returning a readable description of every problem found
+    fn validate(&self) -> Result<(), Vec<String>> {
+        Ok(())
+    }
+
+    /// Parse and validate an asset without going through the asset server
+    fn from_ron_bytes(bytes: &[u8]) -> Result<Self, RonAssetError> {
+        let asset = ron::de::from_bytes::<Self>(bytes)?;
+        asset.validate().map_err(RonAssetError::Invalid)?;
+        Ok(asset)
+    }
+}
+
+/// Errors that can occur while loading a RON data asset
+#[derive(Debug)]
+pub enum RonAssetError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Invalid(Vec<String>),
+}
+
+impl fmt::Display for RonAssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RonAssetError::Io(err) => write!(f, "could not read data file: {}", err),
+            RonAssetError::Parse(err) => write!(f, "malformed data file: {}", err),
+            RonAssetError::Invalid(problems) => {
+                write!(f, "invalid data file:")?;
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for RonAssetError {}
+
+impl From<std::io::Error> for RonAssetError {
+    fn from(err: std::io::Error) -> Self {
+        RonAssetError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for RonAssetError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        RonAssetError::Parse(err)
+    }
+}
+
+impl<A> AssetLoader for RonAssetLoader<A>
+where
+    A: RonAsset,
+{
+    type Asset = A;
+    type Settings = ();
+    type Error = RonAssetError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<A, RonAssetError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        A::from_ron_bytes(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+}
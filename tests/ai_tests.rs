@@ -0,0 +1,208 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use strategy_forge::{
+    components::ai::{AIBrain, AIControlled, AIDifficulty, AIPhase},
+    components::building::BuildingSpawner,
+    components::economy::ResourceType,
+    components::player::MechanicalBase,
+    components::resource::ResourceNode,
+    components::strategic::StrategicLocation,
+    components::unit::{Team, Unit, UnitState},
+    components::unit_types::UnitType,
+    states::game_state::GameState,
+    systems::ai::AIPlugin,
+    systems::base_movement::MoveTarget as BaseMoveTarget,
+    units::engineer::{Engineer, SelectedResource},
+};
+
+/// Helper function to build a minimal app running the AI systems
+fn create_ai_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .add_plugins(AIPlugin);
+    app
+}
+
+/// Helper function to spawn an AI-controlled base with a strategic location to race for
+fn spawn_ai_base(app: &mut App, difficulty: AIDifficulty) -> Entity {
+    app.world_mut().spawn(StrategicLocation {
+        position: Vec2::ZERO,
+        ..default()
+    });
+
+    app.world_mut()
+        .spawn((
+            Transform::from_xyz(500.0, 500.0, 0.0),
+            MechanicalBase {
+                team: Team::Enemy,
+                ..default()
+            },
+            AIControlled { difficulty },
+        ))
+        .id()
+}
+
+/// Helper function to spawn a unit of the given type
+fn spawn_unit(app: &mut App, position: Vec2, team: Team, unit_type: UnitType) -> Entity {
+    app.world_mut()
+        .spawn((
+            Transform::from_xyz(position.x, position.y, 0.0),
+            Unit {
+                health: 100.0,
+                max_health: 100.0,
+                attack_power: 15.0,
+                attack_range: 5.0,
+                movement_speed: 40.0,
+                team,
+                state: UnitState::Idle,
+                attack_cooldown: Timer::from_seconds(1.0, TimerMode::Once),
+                attack_target: None,
+                movement_target: None,
+            },
+            unit_type,
+        ))
+        .id()
+}
+
+/// Helper function to advance the app by a number of seconds
+fn run_for(app: &mut App, seconds: f32) {
+    for _ in 0..(seconds * 10.0).ceil() as usize {
+        app.update();
+    }
+}
+
+/// Helper function to read the current phase of an AI base
+fn phase(app: &App, base: Entity) -> AIPhase {
+    app.world().get::<AIBrain>(base).expect("AI base should have a brain").phase
+}
+
+#[test]
+fn test_easy_ai_advances_immediately() {
+    let mut app = create_ai_app();
+    let base = spawn_ai_base(&mut app, AIDifficulty::Easy);
+
+    run_for(&mut app, 3.5);
+
+    assert_eq!(phase(&app, base), AIPhase::Advance, "Easy AI should head out without an army");
+    let target = app.world().get::<BaseMoveTarget>(base).expect("Base should be moving");
+    assert_eq!(target.target_position, Vec2::ZERO, "Base should drive toward the strategic location");
+}
+
+#[test]
+fn test_medium_ai_waits_for_an_army() {
+    let mut app = create_ai_app();
+    let base = spawn_ai_base(&mut app, AIDifficulty::Medium);
+
+    run_for(&mut app, 3.5);
+    assert_eq!(phase(&app, base), AIPhase::Gather, "Medium AI should not leave home without an army");
+    assert!(app.world().get::<BaseMoveTarget>(base).is_none(), "Base should stay put");
+
+    spawn_unit(&mut app, Vec2::new(500.0, 450.0), Team::Enemy, UnitType::LandToLandTank);
+    spawn_unit(&mut app, Vec2::new(450.0, 500.0), Team::Enemy, UnitType::LandToLandTank);
+    run_for(&mut app, 2.0);
+
+    assert_eq!(phase(&app, base), AIPhase::Advance, "Medium AI should advance once it has an army");
+}
+
+#[test]
+fn test_hard_ai_retreats_when_outnumbered() {
+    let mut app = create_ai_app();
+    let hard_base = spawn_ai_base(&mut app, AIDifficulty::Hard);
+
+    // Put the base halfway to the objective
+    run_for(&mut app, 0.2);
+    app.world_mut().get_mut::<AIBrain>(hard_base).unwrap().phase = AIPhase::Advance;
+    app.world_mut().get_mut::<Transform>(hard_base).unwrap().translation = Vec3::new(250.0, 250.0, 0.0);
+
+    spawn_unit(&mut app, Vec2::new(500.0, 450.0), Team::Enemy, UnitType::LandToLandTank);
+    for i in 0..3 {
+        spawn_unit(&mut app, Vec2::new(i as f32 * 20.0, 0.0), Team::Player, UnitType::LandToLandTank);
+    }
+
+    run_for(&mut app, 1.0);
+
+    assert_eq!(phase(&app, hard_base), AIPhase::Retreat, "Hard AI should fall back when outnumbered");
+}
+
+#[test]
+fn test_easy_ai_never_retreats() {
+    let mut app = create_ai_app();
+    let easy_base = spawn_ai_base(&mut app, AIDifficulty::Easy);
+
+    for i in 0..3 {
+        spawn_unit(&mut app, Vec2::new(i as f32 * 20.0, 0.0), Team::Player, UnitType::LandToLandTank);
+    }
+
+    run_for(&mut app, 7.0);
+
+    assert_eq!(phase(&app, easy_base), AIPhase::Advance, "Easy AI should keep pushing regardless of odds");
+}
+
+#[test]
+fn test_ai_sends_engineers_to_resource_nodes() {
+    let mut app = create_ai_app();
+    spawn_ai_base(&mut app, AIDifficulty::Medium);
+
+    let node = app.world_mut()
+        .spawn((
+            Transform::from_xyz(400.0, 400.0, 0.0),
+            ResourceNode::new(ResourceType::Wood, 500, 3),
+        ))
+        .id();
+
+    let engineer = spawn_unit(&mut app, Vec2::new(500.0, 450.0), Team::Enemy, UnitType::Engineer);
+    app.world_mut().entity_mut(engineer).insert(Engineer {
+        build_speed: 10.0,
+        build_timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+        target_building: None,
+    });
+
+    run_for(&mut app, 0.3);
+
+    let selected = app.world().get::<SelectedResource>(engineer).expect("Engineer should be assigned a node");
+    assert_eq!(selected.resource_entity, node, "Engineer should gather from the only node");
+}
+
+#[test]
+fn test_ai_production_queue() {
+    let mut app = create_ai_app();
+    spawn_ai_base(&mut app, AIDifficulty::Hard);
+
+    let spawner = app.world_mut()
+        .spawn((
+            BuildingSpawner {
+                unit_type: None,
+                queue: Vec::new(),
+                produces: vec![UnitType::Engineer, UnitType::LandToLandTank, UnitType::LandToAirTank],
+                spawn_time: 10.0,
+                spawn_timer: Timer::from_seconds(10.0, TimerMode::Repeating),
+                in_production: None,
+            },
+            Team::Enemy,
+        ))
+        .id();
+
+    // Production is only reconsidered when the AI thinks, every half second on Hard
+    run_for(&mut app, 0.6);
+    assert_eq!(
+        app.world().get::<BuildingSpawner>(spawner).unwrap().unit_type,
+        Some(UnitType::Engineer),
+        "AI should build engineers before an army"
+    );
+
+    for i in 0..4 {
+        spawn_unit(&mut app, Vec2::new(500.0, 400.0 - i as f32 * 20.0), Team::Enemy, UnitType::Engineer);
+    }
+    spawn_unit(&mut app, Vec2::ZERO, Team::Player, UnitType::AirToAirFighter);
+    run_for(&mut app, 0.6);
+
+    assert_eq!(
+        app.world().get::<BuildingSpawner>(spawner).unwrap().unit_type,
+        Some(UnitType::LandToAirTank),
+        "Hard AI should counter enemy aircraft"
+    );
+}
@@ -0,0 +1,200 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use strategy_forge::{
+    components::base_modules::{BaseModule, DamageType, ModuleType},
+    components::player::MechanicalBase,
+    components::unit::{Team, Unit, UnitState},
+    states::game_state::GameState,
+    systems::combat::{BaseDamageEvent, CombatPlugin},
+    systems::damage::DamageRules,
+    systems::module_effects::{
+        apply_module_effects, manage_module_power, recharge_base_shields, BaseDefenses, SHIELD_RECHARGE_DELAY,
+    },
+    systems::save_load::{capture_save, restore_save},
+};
+
+/// Helper function to build a minimal app running combat and base defenses
+fn create_defense_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .init_resource::<DamageRules>()
+        .add_plugins(CombatPlugin)
+        .add_systems(Update, (manage_module_power, apply_module_effects, recharge_base_shields).chain());
+    app
+}
+
+/// Helper function to make a defense module that draws no power
+fn defense_module(armor_bonus: f32, shield_strength: f32, shield_recharge_rate: f32, damage_resistance: f32) -> BaseModule {
+    BaseModule {
+        module_type: ModuleType::Defense { armor_bonus, shield_strength, shield_recharge_rate, damage_resistance },
+        health: 100.0,
+        max_health: 100.0,
+        power_consumption: 0.0,
+        active: true,
+        team: Team::Enemy,
+    }
+}
+
+/// Helper function to spawn an enemy base with the given modules as children
+fn spawn_base(app: &mut App, position: Vec2, modules: Vec<BaseModule>) -> Entity {
+    let base = app
+        .world_mut()
+        .spawn((Transform::from_xyz(position.x, position.y, 0.0), MechanicalBase { team: Team::Enemy, ..default() }))
+        .id();
+    let modules: Vec<Entity> = modules
+        .into_iter()
+        .map(|module| app.world_mut().spawn((Transform::default(), Sprite::default(), module)).id())
+        .collect();
+    app.world_mut().entity_mut(base).push_children(&modules);
+    app.world_mut().get_mut::<MechanicalBase>(base).unwrap().modules = modules;
+    base
+}
+
+/// Helper function to spawn a unit that attacks once a second
+fn spawn_unit(app: &mut App, position: Vec2, team: Team) -> Entity {
+    app.world_mut()
+        .spawn((
+            Transform::from_xyz(position.x, position.y, 0.0),
+            Unit {
+                health: 100.0,
+                max_health: 100.0,
+                attack_power: 10.0,
+                attack_range: 3.0,
+                movement_speed: 40.0,
+                team,
+                state: UnitState::Idle,
+                attack_cooldown: Timer::from_seconds(1.0, TimerMode::Once),
+                attack_target: None,
+                movement_target: None,
+            },
+        ))
+        .id()
+}
+
+/// Helper function to hit a base and let the hit resolve
+fn hit(app: &mut App, base: Entity, amount: f32) {
+    app.world_mut().send_event(BaseDamageEvent {
+        base,
+        amount,
+        damage_type: DamageType::Kinetic,
+        attacker: None,
+        origin: Vec2::new(100.0, 0.0),
+    });
+    app.update();
+}
+
+/// Helper function to read a base's defenses
+fn defenses(app: &App, base: Entity) -> BaseDefenses {
+    app.world().get::<BaseDefenses>(base).cloned().expect("Bases with modules should track their defenses")
+}
+
+/// Helper function to read a base's health
+fn health(app: &App, base: Entity) -> f32 {
+    app.world().get::<MechanicalBase>(base).unwrap().health
+}
+
+#[test]
+fn test_defense_modules_are_kept_on_the_base() {
+    let mut app = create_defense_app();
+    let base = spawn_base(&mut app, Vec2::ZERO, vec![defense_module(10.0, 40.0, 20.0, 0.25), defense_module(5.0, 0.0, 0.0, 0.25)]);
+    app.update();
+
+    let stats = defenses(&app, base);
+    assert_eq!(stats.armor, 15.0);
+    assert_eq!(stats.max_shield, 40.0);
+    assert_eq!(stats.shield, 40.0, "Shields come online fully charged");
+    assert_eq!(stats.shield_recharge_rate, 20.0);
+    assert_eq!(stats.damage_resistance, 0.5);
+
+    // Resistance stays capped however many modules add to it
+    let extra = app.world_mut().spawn((Transform::default(), Sprite::default(), defense_module(0.0, 0.0, 0.0, 0.75))).id();
+    app.world_mut().entity_mut(base).add_child(extra);
+    app.update();
+    assert_eq!(defenses(&app, base).damage_resistance, 0.9);
+
+    // Losing the shield module takes its shields with it
+    let modules = app.world().get::<MechanicalBase>(base).unwrap().modules.clone();
+    app.world_mut().entity_mut(modules[0]).despawn_recursive();
+    app.update();
+    assert_eq!(defenses(&app, base).shield, 0.0);
+    assert_eq!(defenses(&app, base).armor, 5.0);
+}
+
+#[test]
+fn test_hits_go_through_resistance_shields_then_armor() {
+    let mut app = create_defense_app();
+    let base = spawn_base(&mut app, Vec2::ZERO, vec![defense_module(10.0, 40.0, 20.0, 0.5)]);
+    app.update();
+
+    // 200 kinetic: half resisted, 40 soaked by shields, 10 stopped by armor, then 0.6 against Fortified
+    hit(&mut app, base, 200.0);
+    assert_eq!(defenses(&app, base).shield, 0.0);
+    assert!((health(&app, base) - 970.0).abs() < 0.01, "Expected 30 hull damage, health is {}", health(&app, base));
+
+    // With the shields down the next hit goes straight to armor
+    hit(&mut app, base, 200.0);
+    assert!((health(&app, base) - 916.0).abs() < 0.01, "Expected 54 hull damage, health is {}", health(&app, base));
+}
+
+#[test]
+fn test_shields_recharge_after_a_delay() {
+    let mut app = create_defense_app();
+    let base = spawn_base(&mut app, Vec2::ZERO, vec![defense_module(0.0, 40.0, 20.0, 0.0)]);
+    app.update();
+
+    hit(&mut app, base, 30.0);
+    assert_eq!(defenses(&app, base).shield, 10.0);
+
+    // Nothing comes back while the base is still under fire
+    for _ in 0..(SHIELD_RECHARGE_DELAY * 10.0) as u32 - 2 {
+        app.update();
+    }
+    assert_eq!(defenses(&app, base).shield, 10.0);
+
+    // Then 20 shield points a second until full
+    for _ in 0..12 {
+        app.update();
+    }
+    let shield = defenses(&app, base).shield;
+    assert!(shield > 25.0 && shield < 40.0, "Shields should be part way back, got {}", shield);
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(defenses(&app, base).shield, 40.0);
+
+    // Shields and the recharge delay survive a save
+    hit(&mut app, base, 15.0);
+    let before = defenses(&app, base);
+    let save = capture_save(app.world_mut());
+    restore_save(app.world_mut(), &save);
+    let world = app.world_mut();
+    let restored = world.query::<&BaseDefenses>().iter(world).next().cloned().expect("Defenses should be restored");
+    assert_eq!(restored, before);
+}
+
+#[test]
+fn test_units_attack_enemy_bases_once_no_units_are_left() {
+    let mut app = create_defense_app();
+    let base = spawn_base(&mut app, Vec2::ZERO, Vec::new());
+    let attacker = spawn_unit(&mut app, Vec2::new(50.0, 0.0), Team::Player);
+    let guard = spawn_unit(&mut app, Vec2::new(90.0, 0.0), Team::Enemy);
+    app.world_mut().get_mut::<Unit>(guard).unwrap().attack_power = 0.0;
+
+    // Enemy units come first, even when the base is closer
+    app.update();
+    assert_eq!(app.world().get::<Unit>(attacker).unwrap().attack_target, Some(guard));
+
+    app.world_mut().entity_mut(guard).despawn_recursive();
+    app.update();
+    assert_eq!(app.world().get::<Unit>(attacker).unwrap().attack_target, Some(base));
+
+    // 10 kinetic against a fortified base lands 6 a hit
+    for _ in 0..25 {
+        app.update();
+    }
+    assert_eq!(health(&app, base), 988.0);
+}
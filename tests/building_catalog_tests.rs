@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use strategy_forge::{
+    components::building::{Building, BuildingSpawner, ResourceGenerator},
+    components::economy::ResourceType,
+    components::player::PlayerResources,
+    components::unit::{Team, Unit},
+    components::unit_types::UnitType,
+    entities::building_types::BuildingType,
+    states::game_state::GameState,
+    systems::building_catalog::BuildingCatalog,
+    systems::production::ProductionPlugin,
+    utils::ron_asset::RonAsset,
+};
+
+const ALL_BUILDINGS: [BuildingType; 10] = [
+    BuildingType::Barracks,
+    BuildingType::Workshop,
+    BuildingType::Airfield,
+    BuildingType::Sawmill,
+    BuildingType::StoneMine,
+    BuildingType::IronMine,
+    BuildingType::CommandCenter,
+    BuildingType::ResearchLab,
+    BuildingType::Turret,
+    BuildingType::AntiAirTurret,
+];
+
+/// Helper function to spawn a building from the built-in catalog and complete it
+fn spawn_completed(app: &mut App, building_type: BuildingType) -> Entity {
+    let catalog = BuildingCatalog::default();
+    let entity = building_type.spawn_building(&mut app.world_mut().commands(), &catalog, Vec2::ZERO, Team::Enemy);
+    app.world_mut().flush();
+    app.world_mut().get_mut::<Building>(entity).unwrap().is_completed = true;
+    entity
+}
+
+#[test]
+fn test_builtin_catalog_is_valid_and_complete() {
+    let catalog = BuildingCatalog::default();
+    assert_eq!(catalog.validate(), Ok(()), "Shipped building catalog should pass validation");
+
+    for building_type in ALL_BUILDINGS {
+        assert!(catalog.get(building_type).is_some(), "{:?} should be in the catalog", building_type);
+    }
+
+    let barracks = catalog.definition(BuildingType::Barracks);
+    assert!(barracks.production().contains(&UnitType::Engineer), "Barracks should train engineers");
+    assert!(catalog.definition(BuildingType::Turret).production().is_empty(), "Turrets should not produce units");
+}
+
+#[test]
+fn test_validation_reports_bad_entries() {
+    let catalog: BuildingCatalog = ron::from_str(
+        r#"(
+            version: 1,
+            buildings: {
+                Barracks: (
+                    health: 300.0,
+                    construction_time: 10.0,
+                    size: 32.0,
+                    cost: [(Wood, -5)],
+                    produces: ["Engineer", "Dragon"],
+                    spawn_time: 0.0,
+                ),
+            },
+        )"#,
+    )
+    .expect("Test catalog should parse");
+
+    let problems = catalog.validate().expect_err("Bad entries should fail validation");
+    assert_eq!(problems.len(), 3, "Each bad entry should be reported: {:?}", problems);
+    assert!(problems.iter().any(|problem| problem.contains("unknown unit \"Dragon\"")));
+    assert!(problems.iter().any(|problem| problem.contains("cost of Wood is negative")));
+    assert!(problems.iter().any(|problem| problem.contains("spawn_time must be positive")));
+}
+
+#[test]
+fn test_spawned_buildings_use_catalog_data() {
+    let mut app = App::new();
+    let barracks = spawn_completed(&mut app, BuildingType::Barracks);
+    let sawmill = spawn_completed(&mut app, BuildingType::Sawmill);
+
+    let catalog = BuildingCatalog::default();
+    let spawner = app.world().get::<BuildingSpawner>(barracks).expect("Barracks should get a spawner");
+    assert_eq!(spawner.produces, catalog.definition(BuildingType::Barracks).production());
+    assert_eq!(spawner.unit_type, Some(UnitType::Engineer), "Spawner should start on the first unit");
+
+    let generator = app.world().get::<ResourceGenerator>(sawmill).expect("Sawmill should get a generator");
+    assert_eq!(generator.resource_type, ResourceType::Wood);
+    assert!(app.world().get::<BuildingSpawner>(sawmill).is_none(), "Sawmill should not produce units");
+
+    let building = app.world().get::<Building>(barracks).unwrap();
+    assert_eq!(building.max_health, catalog.definition(BuildingType::Barracks).health);
+}
+
+#[test]
+fn test_spawned_buildings_take_colors_and_spawn_time_from_the_catalog() {
+    let catalog: BuildingCatalog = ron::from_str(
+        r#"(
+            version: 1,
+            buildings: {
+                Barracks: (
+                    health: 300.0,
+                    construction_time: 10.0,
+                    size: 32.0,
+                    produces: ["Engineer"],
+                    spawn_time: 4.0,
+                    colors: (enemy: (0.5, 0.0, 0.5)),
+                ),
+            },
+        )"#,
+    )
+    .expect("Test catalog should parse");
+    assert_eq!(catalog.validate(), Ok(()));
+
+    let mut app = App::new();
+    let enemy = BuildingType::Barracks.spawn_building(&mut app.world_mut().commands(), &catalog, Vec2::ZERO, Team::Enemy);
+    let player = BuildingType::Barracks.spawn_building(&mut app.world_mut().commands(), &catalog, Vec2::ZERO, Team::Player);
+    app.world_mut().flush();
+
+    let spawner = app.world().get::<BuildingSpawner>(enemy).unwrap();
+    assert_eq!(spawner.spawn_time, 4.0);
+    assert_eq!(spawner.spawn_timer.duration(), Duration::from_secs(4));
+    assert_eq!(app.world().get::<Sprite>(enemy).unwrap().color, Color::srgb(0.5, 0.0, 0.5));
+    // Teams the catalog leaves out keep the default colour
+    assert_eq!(app.world().get::<Sprite>(player).unwrap().color, Color::srgb(0.2, 0.6, 0.8));
+}
+
+#[test]
+fn test_production_refuses_units_not_on_the_list() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .insert_resource(PlayerResources::default())
+        .add_plugins(ProductionPlugin);
+
+    let barracks = spawn_completed(&mut app, BuildingType::Barracks);
+    {
+        let mut spawner = app.world_mut().get_mut::<BuildingSpawner>(barracks).unwrap();
+        spawner.unit_type = Some(UnitType::LargeBomber);
+        spawner.spawn_timer = Timer::from_seconds(1.0, TimerMode::Repeating);
+    }
+
+    for _ in 0..11 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    assert_eq!(world.query::<&Unit>().iter(world).count(), 0, "Barracks should not build bombers");
+}
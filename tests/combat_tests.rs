@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use strategy_forge::{
+    components::base_modules::DamageType,
+    components::unit::{Team, Unit, UnitState},
+    resources::map_data::{GameMap, TerrainType},
+    states::game_state::GameState,
+    systems::combat::CombatPlugin,
+    systems::damage::{ArmorClass, DamageRules},
+};
+
+/// Helper function to build a minimal app running the combat systems
+fn create_combat_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .init_resource::<DamageRules>()
+        .add_plugins(CombatPlugin);
+    app
+}
+
+/// Helper function to spawn a basic combat unit
+fn spawn_unit(app: &mut App, position: Vec2, team: Team, health: f32, attack_power: f32) -> Entity {
+    app.world_mut()
+        .spawn((
+            Transform::from_xyz(position.x, position.y, 0.0),
+            Unit {
+                health,
+                max_health: health,
+                attack_power,
+                attack_range: 5.0,
+                movement_speed: 40.0,
+                team,
+                state: UnitState::Idle,
+                attack_cooldown: Timer::from_seconds(1.0, TimerMode::Once),
+                attack_target: None,
+                movement_target: None,
+            },
+        ))
+        .id()
+}
+
+#[test]
+fn test_units_acquire_targets_in_range() {
+    let mut app = create_combat_app();
+    let attacker = spawn_unit(&mut app, Vec2::ZERO, Team::Player, 100.0, 10.0);
+    let target = spawn_unit(&mut app, Vec2::new(50.0, 0.0), Team::Enemy, 100.0, 10.0);
+    let far_away = spawn_unit(&mut app, Vec2::new(1000.0, 0.0), Team::Enemy, 100.0, 10.0);
+
+    app.update();
+
+    let unit = app.world().get::<Unit>(attacker).unwrap();
+    assert_eq!(unit.attack_target, Some(target), "Attacker should target the closest enemy");
+    assert!(matches!(unit.state, UnitState::Attacking), "Attacker should be attacking");
+
+    let far_unit = app.world().get::<Unit>(far_away).unwrap();
+    assert_eq!(far_unit.attack_target, None, "Units out of range should not acquire targets");
+}
+
+#[test]
+fn test_attacks_respect_cooldown() {
+    let mut app = create_combat_app();
+    spawn_unit(&mut app, Vec2::ZERO, Team::Player, 100.0, 10.0);
+    let target = spawn_unit(&mut app, Vec2::new(50.0, 0.0), Team::Enemy, 100.0, 0.0);
+
+    // Half a second in, the cooldown has not elapsed yet
+    for _ in 0..5 {
+        app.update();
+    }
+    assert_eq!(app.world().get::<Unit>(target).unwrap().health, 100.0, "No damage before cooldown");
+
+    // After a full second exactly one attack should have landed
+    for _ in 0..6 {
+        app.update();
+    }
+    assert_eq!(app.world().get::<Unit>(target).unwrap().health, 90.0, "One attack should land per cooldown");
+}
+
+#[test]
+fn test_dead_units_are_despawned() {
+    let mut app = create_combat_app();
+    let attacker = spawn_unit(&mut app, Vec2::ZERO, Team::Player, 100.0, 50.0);
+    let target = spawn_unit(&mut app, Vec2::new(50.0, 0.0), Team::Enemy, 40.0, 0.0);
+
+    for _ in 0..15 {
+        app.update();
+    }
+
+    assert!(app.world().get_entity(target).is_none(), "Target should be despawned at zero health");
+
+    let unit = app.world().get::<Unit>(attacker).unwrap();
+    assert_eq!(unit.attack_target, None, "Attacker should drop its dead target");
+    assert!(matches!(unit.state, UnitState::Idle), "Attacker should return to idle");
+}
+
+#[test]
+fn test_teams_do_not_attack_allies() {
+    let mut app = create_combat_app();
+    spawn_unit(&mut app, Vec2::ZERO, Team::Player, 100.0, 10.0);
+    let ally = spawn_unit(&mut app, Vec2::new(20.0, 0.0), Team::Player, 100.0, 10.0);
+    let neutral = spawn_unit(&mut app, Vec2::new(-20.0, 0.0), Team::Neutral, 100.0, 0.0);
+
+    for _ in 0..20 {
+        app.update();
+    }
+
+    assert_eq!(app.world().get::<Unit>(ally).unwrap().health, 100.0, "Allies should not be attacked");
+    assert_eq!(app.world().get::<Unit>(neutral).unwrap().health, 100.0, "Neutral units should not be attacked");
+}
+
+#[test]
+fn test_terrain_cover_reduces_damage_to_ground_units() {
+    let mut map = GameMap::default();
+    for tile in [(3, 2), (51, 50)] {
+        map.set_terrain(tile.0, tile.1, TerrainType::Forest);
+    }
+    let (forest, plains, aircraft) = (map.tile_center(3, 2), map.tile_center(31, 30), map.tile_center(51, 50));
+    let attackers = [map.tile_center(2, 2), map.tile_center(30, 30), map.tile_center(50, 50)];
+
+    let mut app = create_combat_app();
+    app.insert_resource(map);
+    for position in attackers {
+        spawn_unit(&mut app, position, Team::Player, 100.0, 10.0);
+    }
+    let in_forest = spawn_unit(&mut app, forest, Team::Enemy, 100.0, 0.0);
+    let in_open = spawn_unit(&mut app, plains, Team::Enemy, 100.0, 0.0);
+    let flying = spawn_unit(&mut app, aircraft, Team::Enemy, 100.0, 0.0);
+    app.world_mut().entity_mut(flying).insert(ArmorClass::Air);
+
+    for _ in 0..11 {
+        app.update();
+    }
+
+    let health = |entity| app.world().get::<Unit>(entity).unwrap().health;
+    assert_eq!(health(in_open), 90.0, "Plains give no cover");
+    let expected = 100.0 - 10.0 / TerrainType::Forest.defense_modifier();
+    assert!((health(in_forest) - expected).abs() < 0.01, "Forest should soak up damage, got {}", health(in_forest));
+    let air_damage = 10.0 * DamageRules::default().table.multiplier(DamageType::Kinetic, ArmorClass::Air);
+    assert!((health(flying) - (100.0 - air_damage)).abs() < 0.01, "Aircraft get no cover from the trees below");
+}
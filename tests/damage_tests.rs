@@ -0,0 +1,99 @@
+use strategy_forge::{
+    components::base_modules::DamageType,
+    components::unit_types::UnitType,
+    systems::damage::{ArmorClass, DamageTable, DefenseProfile},
+    systems::module_effects::Health,
+};
+
+/// Helper function to create a defensive profile for testing
+fn create_defense(armor: f32, shield: f32, resistance: f32) -> DefenseProfile {
+    DefenseProfile {
+        armor_class: ArmorClass::Medium,
+        armor,
+        shield,
+        resistance,
+    }
+}
+
+#[test]
+fn test_builtin_table_parses() {
+    // The shipped balance file must always parse, since it doubles as the fallback table
+    let table = DamageTable::default();
+
+    assert_eq!(table.version, 1, "Table version should be 1");
+    assert!(table.max_resistance <= 0.9, "Resistance cap should not exceed 90%");
+    assert!(table.multipliers.contains_key(&DamageType::EMP), "EMP row should exist");
+}
+
+#[test]
+fn test_unarmored_kinetic_damage() {
+    let table = DamageTable::default();
+    let result = table.resolve(20.0, DamageType::Kinetic, &DefenseProfile::unarmored(ArmorClass::Medium));
+
+    assert_eq!(result.shield_damage, 0.0, "No shield damage without shields");
+    assert_eq!(result.hull_damage, 20.0, "Kinetic vs Medium should deal full damage");
+}
+
+#[test]
+fn test_shields_absorb_before_armor() {
+    let table = DamageTable::default();
+    let result = table.resolve(30.0, DamageType::Kinetic, &create_defense(5.0, 20.0, 0.0));
+
+    assert_eq!(result.shield_damage, 20.0, "Shield should absorb up to its remaining strength");
+    assert_eq!(result.hull_damage, 5.0, "Armor should reduce the damage that gets through");
+}
+
+#[test]
+fn test_emp_strips_shields() {
+    let table = DamageTable::default();
+    let kinetic = table.resolve(10.0, DamageType::Kinetic, &create_defense(0.0, 100.0, 0.0));
+    let emp = table.resolve(10.0, DamageType::EMP, &create_defense(0.0, 100.0, 0.0));
+
+    assert!(emp.shield_damage > kinetic.shield_damage, "EMP should hit shields harder than kinetic");
+}
+
+#[test]
+fn test_explosive_ignores_part_of_armor() {
+    let table = DamageTable::default();
+    let kinetic = table.resolve(20.0, DamageType::Kinetic, &create_defense(10.0, 0.0, 0.0));
+    let explosive = table.resolve(20.0, DamageType::Explosive, &create_defense(10.0, 0.0, 0.0));
+
+    assert_eq!(kinetic.hull_damage, 10.0, "Kinetic should lose the full armor value");
+    assert_eq!(explosive.hull_damage, 15.0, "Explosive should ignore half the armor");
+}
+
+#[test]
+fn test_resistance_is_capped() {
+    let table = DamageTable::default();
+    let result = table.resolve(100.0, DamageType::Kinetic, &create_defense(0.0, 0.0, 2.0));
+
+    assert!((result.hull_damage - 10.0).abs() < 0.001, "Resistance should be capped at 90%");
+}
+
+#[test]
+fn test_apply_updates_health() {
+    let table = DamageTable::default();
+    let mut health = Health {
+        current: 100.0,
+        max: 100.0,
+        shield: 10.0,
+        max_shield: 10.0,
+        armor: 0.0,
+        damage_resistance: 0.0,
+    };
+
+    table.apply(&mut health, 25.0, DamageType::Kinetic, ArmorClass::Medium);
+
+    assert_eq!(health.shield, 0.0, "Shield should be depleted");
+    assert_eq!(health.current, 85.0, "Remaining damage should reach the hull");
+}
+
+#[test]
+fn test_anti_air_tanks_counter_aircraft() {
+    let table = DamageTable::default();
+    let flak = UnitType::LandToAirTank.damage_type();
+
+    assert!(table.multiplier(flak, ArmorClass::Air) > 1.0, "Flak should hit aircraft harder than anything else");
+    assert!(table.multiplier(flak, ArmorClass::Air) > table.multiplier(DamageType::Kinetic, ArmorClass::Air));
+    assert!(table.multiplier(flak, ArmorClass::Heavy) < 1.0, "Flak should do little against armor");
+}
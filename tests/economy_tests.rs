@@ -0,0 +1,273 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use strategy_forge::{
+    components::economy::{ResourceType, ResourceWallet},
+    components::player::{MechanicalBase, PlayerResources},
+    components::resource::{Gatherer, ResourceNode},
+    components::unit::{Team, UnitState},
+    components::unit_types::UnitType,
+    resources::map::plugin::MapInitialized,
+    states::game_state::GameState,
+    systems::base_movement::{BaseMovePlugin, MoveTarget as BaseMoveTarget},
+    systems::economy::{intercept_point, EconomyPlugin},
+    systems::movement::{MoveTarget, MovementPlugin},
+    systems::unit_catalog::UnitCatalog,
+    tech::{can_afford_technology, pay_research_cost, refund_research_cost, TechNode, TechStatus, TechTree},
+    units::engineer::SelectedResource,
+};
+
+/// Helper function to build a tech with a research cost and optional prerequisite
+fn tech(id: &str, cost: ResourceWallet, prerequisites: &[&str], unlocks: &[&str]) -> TechNode {
+    TechNode {
+        id: id.to_string(),
+        name: id.to_string(),
+        research_cost: cost,
+        research_time: 10.0,
+        status: if prerequisites.is_empty() { TechStatus::Available } else { TechStatus::Locked },
+        prerequisites: prerequisites.iter().map(|id| id.to_string()).collect(),
+        unlocks: unlocks.iter().map(|id| id.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+/// Helper function to build a minimal app running gathering, unit and base movement
+fn create_economy_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .init_resource::<ButtonInput<MouseButton>>()
+        .insert_resource(MapInitialized(true))
+        .insert_resource(PlayerResources::default())
+        .add_plugins((MovementPlugin, BaseMovePlugin, EconomyPlugin));
+    app
+}
+
+/// Helper function to spawn an empty base for a team
+fn spawn_base(app: &mut App, position: Vec2, team: Team) -> Entity {
+    app.world_mut()
+        .spawn((
+            Transform::from_xyz(position.x, position.y, 0.0),
+            MechanicalBase {
+                team,
+                resources: ResourceWallet::new(),
+                ..default()
+            },
+            UnitState::Idle,
+        ))
+        .id()
+}
+
+/// Helper function to spawn a gatherer assigned to a node
+fn spawn_gatherer(app: &mut App, position: Vec2, team: Team, node: Entity) -> Entity {
+    let catalog = UnitCatalog::default();
+    let unit = UnitType::Gatherer.spawn_unit(&mut app.world_mut().commands(), &catalog, position, team);
+    app.world_mut().flush();
+    app.world_mut().entity_mut(unit).insert(SelectedResource { resource_entity: node });
+    unit
+}
+
+/// Helper function to spawn a resource node
+fn spawn_node(app: &mut App, position: Vec2, amount: i32, max_gatherers: i32) -> Entity {
+    app.world_mut()
+        .spawn((
+            Transform::from_xyz(position.x, position.y, 0.0),
+            ResourceNode::new(ResourceType::Wood, amount, max_gatherers),
+        ))
+        .id()
+}
+
+/// Helper function to advance the app by a number of seconds
+fn run_for(app: &mut App, seconds: f32) {
+    for _ in 0..(seconds * 10.0).ceil() as usize {
+        app.update();
+    }
+}
+
+#[test]
+fn test_spend_is_all_or_nothing() {
+    let mut wallet = ResourceWallet::from([(ResourceType::Wood, 100), (ResourceType::Iron, 5)]);
+    let cost = ResourceWallet::from([(ResourceType::Wood, 40), (ResourceType::Iron, 10)]);
+
+    let err = wallet.spend(&cost).expect_err("Iron shortfall should block the purchase");
+    assert_eq!(err.missing, ResourceWallet::from([(ResourceType::Iron, 5)]), "Error should list what is missing");
+    assert_eq!(wallet.get(ResourceType::Wood), 100, "A failed spend should not touch the wallet");
+
+    wallet.add(ResourceType::Iron, 5);
+    assert!(wallet.can_afford(&cost));
+    wallet.spend(&cost).expect("Wallet should now cover the cost");
+    assert_eq!(wallet.get(ResourceType::Wood), 60);
+    assert_eq!(wallet.get(ResourceType::Iron), 0);
+
+    wallet.refund(&cost);
+    assert_eq!(wallet.get(ResourceType::Wood), 100, "Refund should return the full cost");
+    assert_eq!(wallet.get(ResourceType::Iron), 10);
+}
+
+#[test]
+fn test_wallet_reads_cost_lists_from_ron() {
+    let wallet: ResourceWallet = ron::from_str("[(Wood, 20), (Crystal, 5)]").expect("Cost list should parse");
+    assert_eq!(wallet.get(ResourceType::Wood), 20);
+    assert_eq!(wallet.get(ResourceType::Crystal), 5);
+    assert_eq!(wallet.get(ResourceType::Stone), 0, "Missing resources count as zero");
+
+    let written = ron::to_string(&wallet).unwrap();
+    assert_eq!(ron::from_str::<ResourceWallet>(&written).unwrap(), wallet, "Wallets should round-trip");
+}
+
+#[test]
+fn test_tech_costs_use_player_resources() {
+    let mut resources = PlayerResources::default();
+    let affordable = tech("steam_power", ResourceWallet::from([(ResourceType::Wood, 40), (ResourceType::Iron, 20)]), &[], &[]);
+    let too_expensive = tech("optics", ResourceWallet::from([(ResourceType::Crystal, 10)]), &[], &[]);
+
+    assert!(can_afford_technology(&affordable, &resources));
+    assert!(!can_afford_technology(&too_expensive, &resources), "Player starts without crystal");
+    assert!(pay_research_cost(&too_expensive, &mut resources).is_err());
+
+    pay_research_cost(&affordable, &mut resources).expect("Research should be paid for");
+    assert_eq!(resources.resources.get(ResourceType::Wood), 60);
+    assert_eq!(resources.resources.get(ResourceType::Iron), 5);
+
+    refund_research_cost(&affordable, &mut resources);
+    assert_eq!(resources.resources, PlayerResources::default().resources, "Refund should restore the stockpile");
+}
+
+#[test]
+fn test_finished_research_unlocks_dependents() {
+    let mut tree = TechTree::new("Test");
+    tree.add_technology(tech("basics", ResourceWallet::new(), &[], &["advanced"]));
+    tree.add_technology(tech("advanced", ResourceWallet::new(), &["basics"], &[]));
+
+    assert!(tree.start_research("basics"));
+    tree.update_research(5.0);
+    assert_eq!(tree.get_technology("advanced").unwrap().status, TechStatus::Locked);
+
+    tree.update_research(5.0);
+    assert!(tree.is_researched("basics"));
+    assert_eq!(tree.current_research, None);
+    assert_eq!(tree.get_technology("advanced").unwrap().status, TechStatus::Available, "Dependents should unlock");
+}
+
+#[test]
+fn test_gatherers_fill_up_and_deliver_to_their_base() {
+    let mut app = create_economy_app();
+    let base = spawn_base(&mut app, Vec2::ZERO, Team::Enemy);
+    let node = spawn_node(&mut app, Vec2::new(150.0, 0.0), 1000, 3);
+    let gatherer = spawn_gatherer(&mut app, Vec2::new(20.0, 0.0), Team::Enemy, node);
+
+    run_for(&mut app, 3.0);
+    let load = app.world().get::<Gatherer>(gatherer).unwrap();
+    assert!(load.current_load > 0, "Gatherer should be harvesting once it reaches the node");
+    assert_eq!(app.world().get::<ResourceNode>(node).unwrap().current_gatherers, 1);
+
+    run_for(&mut app, 10.0);
+    let delivered = app.world().get::<MechanicalBase>(base).unwrap().resources.get(ResourceType::Wood);
+    let capacity = app.world().get::<Gatherer>(gatherer).unwrap().carry_capacity;
+    assert!(delivered >= capacity, "A full load should reach the base (got {})", delivered);
+    assert_eq!(
+        app.world().get::<ResourceNode>(node).unwrap().amount_remaining,
+        1000 - delivered - app.world().get::<Gatherer>(gatherer).unwrap().current_load,
+        "Everything harvested should come out of the node"
+    );
+    assert_eq!(
+        app.world().resource::<PlayerResources>().resources,
+        PlayerResources::default().resources,
+        "Enemy deliveries should not pay the player"
+    );
+}
+
+#[test]
+fn test_nodes_respect_max_gatherers_and_deplete() {
+    let mut app = create_economy_app();
+    spawn_base(&mut app, Vec2::ZERO, Team::Player);
+    let node = spawn_node(&mut app, Vec2::new(60.0, 0.0), 30, 1);
+    let first = spawn_gatherer(&mut app, Vec2::new(55.0, 0.0), Team::Player, node);
+    let second = spawn_gatherer(&mut app, Vec2::new(65.0, 0.0), Team::Player, node);
+
+    run_for(&mut app, 1.5);
+    let harvesting = [first, second]
+        .iter()
+        .filter(|unit| app.world().get::<Gatherer>(**unit).unwrap().target_resource.is_some())
+        .count();
+    assert_eq!(harvesting, 1, "Only one gatherer fits on the node");
+    assert_eq!(app.world().get::<ResourceNode>(node).unwrap().current_gatherers, 1);
+
+    run_for(&mut app, 20.0);
+    assert!(app.world().get_entity(node).is_none(), "Mined out nodes should be removed");
+    let wood = app.world().resource::<PlayerResources>().resources.get(ResourceType::Wood);
+    assert_eq!(wood, 100 + 30, "The whole node should end up with the player");
+    for unit in [first, second] {
+        assert!(app.world().get::<SelectedResource>(unit).is_none(), "Gatherers should stand down");
+        assert_eq!(app.world().get::<Gatherer>(unit).unwrap().current_load, 0);
+    }
+}
+
+#[test]
+fn test_gatherers_catch_a_moving_base() {
+    let mut app = create_economy_app();
+    let base = spawn_base(&mut app, Vec2::ZERO, Team::Player);
+    let node = spawn_node(&mut app, Vec2::new(0.0, 200.0), 1000, 3);
+    let gatherer = spawn_gatherer(&mut app, Vec2::new(0.0, 190.0), Team::Player, node);
+
+    // Fill up, then have the base drive away along the x axis
+    run_for(&mut app, 4.5);
+    assert!(app.world().get::<Gatherer>(gatherer).unwrap().returning, "Gatherer should be heading home");
+    let wood_before = app.world().resource::<PlayerResources>().resources.get(ResourceType::Wood);
+    app.world_mut().entity_mut(base).insert((
+        BaseMoveTarget { target_position: Vec2::new(2000.0, 0.0) },
+        UnitState::Moving,
+    ));
+
+    run_for(&mut app, 8.0);
+    let base_x = app.world().get::<Transform>(base).unwrap().translation.x;
+    assert!(base_x > 200.0, "Base should have been driving the whole time");
+    assert!(
+        app.world().resource::<PlayerResources>().resources.get(ResourceType::Wood) > wood_before,
+        "Gatherer should catch up with the moving base and deliver"
+    );
+}
+
+#[test]
+fn test_intercept_leads_a_moving_base() {
+    let from = Vec2::new(0.0, 100.0);
+    let meeting = intercept_point(from, 70.0, Vec2::ZERO, 30.0, Some(Vec2::new(1000.0, 0.0)));
+    assert!(meeting.x > 0.0 && meeting.y.abs() < 0.01, "Should aim ahead of the base along its path");
+
+    let travel_time = from.distance(meeting) / 70.0;
+    assert!((meeting.x - 30.0 * travel_time).abs() < 0.5, "Unit and base should arrive together");
+
+    let parked = intercept_point(from, 70.0, Vec2::ZERO, 30.0, None);
+    assert_eq!(parked, Vec2::ZERO, "A parked base is met where it stands");
+
+    let too_fast = intercept_point(from, 10.0, Vec2::ZERO, 30.0, Some(Vec2::new(100.0, 0.0)));
+    assert_eq!(too_fast, Vec2::new(100.0, 0.0), "A base that can't be caught is met where it stops");
+}
+
+#[test]
+fn test_returning_gatherers_only_replan_when_the_base_moves_a_tile() {
+    let mut app = create_economy_app();
+    let base = spawn_base(&mut app, Vec2::new(1000.0, 0.0), Team::Player);
+    let catalog = UnitCatalog::default();
+    let gatherer = UnitType::Gatherer.spawn_unit(&mut app.world_mut().commands(), &catalog, Vec2::ZERO, Team::Player);
+    app.world_mut().flush();
+    let mut load = app.world_mut().get_mut::<Gatherer>(gatherer).unwrap();
+    load.current_load = 10;
+    load.carried_type = Some(ResourceType::Wood);
+    load.returning = true;
+
+    app.update();
+    let target = |app: &App| app.world().get::<MoveTarget>(gatherer).expect("The gatherer should head home").position;
+    assert_eq!(target(&app), Vec2::new(1000.0, 0.0));
+
+    // A base that has barely moved keeps the old path
+    app.world_mut().get_mut::<Transform>(base).unwrap().translation.x = 1010.0;
+    app.update();
+    assert_eq!(target(&app), Vec2::new(1000.0, 0.0));
+
+    app.world_mut().get_mut::<Transform>(base).unwrap().translation.x = 1100.0;
+    app.update();
+    assert_eq!(target(&app), Vec2::new(1100.0, 0.0), "Moving more than a tile should send the gatherer after it");
+}
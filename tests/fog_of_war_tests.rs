@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use strategy_forge::{
+    components::base_modules::BaseModule,
+    components::player::MechanicalBase,
+    components::terrain::MapTile,
+    components::unit::Team,
+    components::unit_types::UnitType,
+    resources::map::plugin::MapChanged,
+    resources::map_data::{GameMap, TerrainType},
+    states::game_state::GameState,
+    systems::fog_of_war::{FogOfWar, FogOfWarPlugin, FogOverlay, TileVisibility, BASE_SIGHT_RANGE, UNIT_SIGHT_RANGE},
+    systems::unit_catalog::UnitCatalog,
+};
+
+/// Helper function to build a minimal app with fog of war over a plains map
+fn create_fog_app(map: GameMap) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(map)
+        .add_plugins(FogOfWarPlugin);
+    app
+}
+
+/// Helper function to spawn a unit in the middle of a tile
+fn spawn_unit_at(app: &mut App, unit_type: UnitType, tile: (i32, i32), team: Team) -> Entity {
+    let position = app.world().resource::<GameMap>().tile_center(tile.0, tile.1);
+    let catalog = UnitCatalog::default();
+    let unit = unit_type.spawn_unit(&mut app.world_mut().commands(), &catalog, position, team);
+    app.world_mut().flush();
+    app.world_mut().entity_mut(unit).insert(VisibilityBundle::default());
+    unit
+}
+
+/// Helper function to move an entity to the middle of a tile
+fn move_to(app: &mut App, entity: Entity, tile: (i32, i32)) {
+    let position = app.world().resource::<GameMap>().tile_center(tile.0, tile.1);
+    let mut transform = app.world_mut().get_mut::<Transform>(entity).unwrap();
+    transform.translation.x = position.x;
+    transform.translation.y = position.y;
+}
+
+/// Helper function to read the player's view of a tile
+fn player_view(app: &App, x: i32, y: i32) -> TileVisibility {
+    app.world().resource::<FogOfWar>().tile_visibility(Team::Player, x, y)
+}
+
+#[test]
+fn test_units_reveal_and_explore_tiles() {
+    let mut app = create_fog_app(GameMap::default());
+    let scout = spawn_unit_at(&mut app, UnitType::Engineer, (10, 10), Team::Player);
+    app.update();
+
+    let edge = UNIT_SIGHT_RANGE as i32;
+    assert_eq!(player_view(&app, 10, 10), TileVisibility::Visible);
+    assert_eq!(player_view(&app, 10 + edge, 10), TileVisibility::Visible, "Tiles at the edge of sight are seen");
+    assert_eq!(player_view(&app, 10 + edge + 1, 10), TileVisibility::Unseen);
+    assert_eq!(player_view(&app, 40, 40), TileVisibility::Unseen);
+
+    move_to(&mut app, scout, (40, 40));
+    app.update();
+    assert_eq!(player_view(&app, 10, 10), TileVisibility::Explored, "Tiles left behind stay explored");
+    assert_eq!(player_view(&app, 40, 40), TileVisibility::Visible);
+
+    let fog = app.world().resource::<FogOfWar>();
+    assert!(!fog.is_explored(Team::Enemy, 10, 10), "Each team explores on its own");
+}
+
+#[test]
+fn test_mountains_extend_sight_and_forests_shorten_it() {
+    let mut map = GameMap::default();
+    map.set_terrain(10, 10, TerrainType::Mountains);
+    map.set_terrain(40, 10, TerrainType::Forest);
+    let mut app = create_fog_app(map);
+    spawn_unit_at(&mut app, UnitType::Engineer, (10, 10), Team::Player);
+    spawn_unit_at(&mut app, UnitType::Engineer, (40, 10), Team::Player);
+    app.update();
+
+    let edge = UNIT_SIGHT_RANGE as i32;
+    assert!(player_view(&app, 10 + edge + 2, 10) == TileVisibility::Visible, "High ground should see further");
+    assert!(player_view(&app, 40 + edge, 10) == TileVisibility::Unseen, "Forest cover should limit sight");
+    assert!(player_view(&app, 41, 10) == TileVisibility::Visible);
+}
+
+#[test]
+fn test_sensor_modules_extend_base_sight() {
+    let mut app = create_fog_app(GameMap::default());
+    let position = app.world().resource::<GameMap>().tile_center(20, 20);
+    let base = app
+        .world_mut()
+        .spawn((Transform::from_xyz(position.x, position.y, 0.0), MechanicalBase::default()))
+        .id();
+    app.update();
+
+    let beyond = 20 + BASE_SIGHT_RANGE as i32 + 3;
+    assert_eq!(player_view(&app, beyond, 20), TileVisibility::Unseen);
+
+    let sensor = app.world_mut().spawn(BaseModule::new_sensor_module(10.0, 0.0, 4.0, 5.0)).id();
+    app.world_mut().entity_mut(base).add_child(sensor);
+    app.update();
+    assert_eq!(player_view(&app, beyond, 20), TileVisibility::Visible, "Sensor should add its vision range");
+
+    app.world_mut().get_mut::<BaseModule>(sensor).unwrap().active = false;
+    app.update();
+    assert_eq!(player_view(&app, beyond, 20), TileVisibility::Explored, "Unpowered sensors see nothing");
+}
+
+#[test]
+fn test_enemies_outside_vision_are_hidden() {
+    let mut app = create_fog_app(GameMap::default());
+    spawn_unit_at(&mut app, UnitType::Engineer, (10, 10), Team::Player);
+    let hidden = spawn_unit_at(&mut app, UnitType::LandToLandTank, (40, 40), Team::Enemy);
+    let spotted = spawn_unit_at(&mut app, UnitType::LandToLandTank, (12, 10), Team::Enemy);
+    let tile = app.world_mut().spawn(MapTile { grid_x: 11, grid_y: 10, is_explored: false, is_visible: false }).id();
+    app.update();
+
+    assert_eq!(*app.world().get::<Visibility>(hidden).unwrap(), Visibility::Hidden);
+    assert_eq!(*app.world().get::<Visibility>(spotted).unwrap(), Visibility::Inherited);
+
+    let map_tile = app.world().get::<MapTile>(tile).unwrap();
+    assert!(map_tile.is_visible && map_tile.is_explored, "Map tiles should follow the player's view");
+
+    move_to(&mut app, hidden, (11, 11));
+    app.update();
+    assert_eq!(*app.world().get::<Visibility>(hidden).unwrap(), Visibility::Inherited, "Enemies walking into sight appear");
+}
+
+#[test]
+fn test_explored_tiles_survive_a_pause() {
+    let mut app = create_fog_app(GameMap::default());
+    let scout = spawn_unit_at(&mut app, UnitType::Engineer, (10, 10), Team::Player);
+    app.update();
+    move_to(&mut app, scout, (40, 40));
+
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Paused);
+    app.update();
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Gameplay);
+    app.update();
+    assert_eq!(player_view(&app, 10, 10), TileVisibility::Explored, "Resuming should not forget what was explored");
+
+    // A new match starts unexplored
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::MainMenu);
+    app.update();
+    app.world_mut().entity_mut(scout).despawn();
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Gameplay);
+    app.update();
+    assert_eq!(player_view(&app, 10, 10), TileVisibility::Unseen);
+}
+
+/// Fog overlay texture writes seen by the test app
+#[derive(Resource, Default)]
+struct OverlayWrites(usize);
+
+/// Helper system to count every time the fog texture is written to
+fn count_overlay_writes(mut writes: ResMut<OverlayWrites>, mut events: EventReader<AssetEvent<Image>>) {
+    writes.0 += events.read().filter(|event| matches!(event, AssetEvent::Modified { .. })).count();
+}
+
+/// Helper function to find the fog overlay and its texture
+fn fog_overlay(app: &mut App) -> (Entity, AssetId<Image>) {
+    let world = app.world_mut();
+    let (entity, handle) = world.query_filtered::<(Entity, &Handle<Image>), With<FogOverlay>>().single(world);
+    (entity, handle.id())
+}
+
+#[test]
+fn test_fog_overlay_is_only_redrawn_when_the_view_changes() {
+    let mut app = create_fog_app(GameMap::default());
+    app.add_plugins(AssetPlugin::default())
+        .init_asset::<Image>()
+        .init_resource::<OverlayWrites>()
+        .add_systems(Last, count_overlay_writes);
+    let scout = spawn_unit_at(&mut app, UnitType::Engineer, (10, 10), Team::Player);
+    app.update();
+    app.update();
+    let (overlay, texture) = fog_overlay(&mut app);
+
+    // Standing still leaves the texture alone
+    app.world_mut().resource_mut::<OverlayWrites>().0 = 0;
+    for _ in 0..5 {
+        app.update();
+    }
+    assert_eq!(app.world().resource::<OverlayWrites>().0, 0, "Nothing changed, so nothing should be redrawn");
+
+    move_to(&mut app, scout, (20, 10));
+    app.update();
+    app.update();
+    assert_eq!(app.world().resource::<OverlayWrites>().0, 1, "Moving should redraw the fog once");
+
+    // A new map gets a new texture
+    app.world_mut().send_event(MapChanged);
+    app.update();
+    let (new_overlay, new_texture) = fog_overlay(&mut app);
+    assert_ne!(new_overlay, overlay);
+    assert_ne!(new_texture, texture);
+    assert!(app.world().resource::<Assets<Image>>().get(texture).is_none(), "The old texture should be dropped");
+}
+
+#[test]
+fn test_new_map_forgets_explored_tiles() {
+    let mut app = create_fog_app(GameMap::default());
+    let scout = spawn_unit_at(&mut app, UnitType::Engineer, (10, 10), Team::Player);
+    app.update();
+    move_to(&mut app, scout, (40, 40));
+    app.update();
+    assert_eq!(player_view(&app, 10, 10), TileVisibility::Explored);
+
+    // Same size map, so only the event tells the fog it was replaced
+    app.world_mut().send_event(MapChanged);
+    app.update();
+    assert_eq!(player_view(&app, 10, 10), TileVisibility::Unseen, "Tiles seen on the old map should be forgotten");
+    assert_eq!(player_view(&app, 40, 40), TileVisibility::Visible);
+
+    // Tiles from a loaded save wait for its map to come in
+    let (width, height) = {
+        let game_map = app.world().resource::<GameMap>();
+        (game_map.width, game_map.height)
+    };
+    let mut explored = vec![false; width * height];
+    explored[10 * width + 10] = true;
+    app.world_mut().resource_mut::<FogOfWar>().restore_explored_on_new_map([(Team::Player, explored)].into());
+    app.world_mut().send_event(MapChanged);
+    app.update();
+    assert_eq!(player_view(&app, 10, 10), TileVisibility::Explored, "Saved tiles should be explored on the new map");
+}
@@ -0,0 +1,165 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use std::path::Path;
+use std::time::Duration;
+use strategy_forge::{
+    components::economy::ResourceType,
+    components::strategic::{StrategicLocation, StrategicLocationPlugin},
+    components::terrain::MapTile,
+    resources::map::plugin::{MapInitialized, MapPlugin, SelectedMap},
+    resources::map_data::{GameMap, TerrainType},
+    resources::map_file::{list_map_files, MapFile},
+    states::game_state::GameState,
+    utils::pathfinding::find_path,
+    utils::ron_asset::{RonAsset, RonAssetError},
+};
+
+/// A small valid map: water along the top row, forest in the bottom left corner
+const SMALL_MAP: &str = r#"(
+    version: 1,
+    name: "Pond",
+    width: 4,
+    height: 3,
+    terrain: [
+        "~~~~",
+        "..h.",
+        "T..*",
+    ],
+    start_positions: [(1, 1), (3, 1)],
+    strategic_locations: [(position: (2, 1), capture_value: 2)],
+    resources: [(resource_type: Iron, position: (3, 0))],
+)"#;
+
+/// Helper function to parse a map file from text
+fn parse(text: &str) -> Result<MapFile, RonAssetError> {
+    MapFile::from_ron_bytes(text.as_bytes())
+}
+
+/// Helper function to get the problems an invalid map file reports
+fn problems(text: &str) -> Vec<String> {
+    match parse(text) {
+        Err(RonAssetError::Invalid(problems)) => problems,
+        other => panic!("Expected validation problems, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_map_file_builds_game_map() {
+    let map_file = parse(SMALL_MAP).expect("Small map should be valid");
+    assert_eq!(map_file.tile_size, 32.0, "Tile size should default to 32");
+
+    let map = map_file.to_game_map();
+    assert_eq!((map.width, map.height), (4, 3));
+    assert!(map.initialized);
+    assert_eq!(map.terrain_at(0, 2), Some(TerrainType::Water), "The first row is the top of the map");
+    assert_eq!(map.terrain_at(0, 0), Some(TerrainType::Forest));
+    assert_eq!(map.terrain_at(2, 1), Some(TerrainType::Hills));
+    assert_eq!(map.terrain_at(3, 0), Some(TerrainType::MetalDeposit));
+
+    assert_eq!(map.start_positions, vec![(1, 1), (3, 1)]);
+    assert_eq!(map.strategic_locations[0].position, (2, 1));
+    assert_eq!(map.strategic_locations[0].capture_value, 2);
+    assert_eq!(map.resource_spawns[0].resource_type, ResourceType::Iron);
+}
+
+#[test]
+fn test_malformed_map_files_explain_the_problem() {
+    match parse("(version: 1, name: \"Broken\", width: 4") {
+        Err(RonAssetError::Parse(_)) => {}
+        other => panic!("Truncated file should be a parse error, got {:?}", other),
+    }
+
+    let narrow = problems(&SMALL_MAP.replace("\"..h.\"", "\"..h\""));
+    assert_eq!(narrow, vec!["terrain row 2 is 3 tiles wide, expected 4".to_string()]);
+
+    let unknown = problems(&SMALL_MAP.replace("\"T..*\"", "\"T.?*\""));
+    assert_eq!(unknown, vec!["terrain row 3, column 3: unknown terrain '?'".to_string()]);
+
+    let misplaced = problems(
+        &SMALL_MAP
+            .replace("[(1, 1), (3, 1)]", "[(1, 2), (9, 1)]")
+            .replace("capture_value: 2", "capture_value: 0"),
+    );
+    assert!(misplaced.contains(&"start position 1 at (1, 2) is on impassable terrain".to_string()), "{:?}", misplaced);
+    assert!(misplaced.contains(&"start position 2 at (9, 1) is off the map".to_string()), "{:?}", misplaced);
+    assert!(misplaced.iter().any(|problem| problem.contains("capture_value must be positive")), "{:?}", misplaced);
+
+    let no_radius = problems(&SMALL_MAP.replace("capture_value: 2", "capture_value: 2, capture_radius: Some(0.0)"));
+    assert_eq!(no_radius, vec!["strategic location at (2, 1): capture_radius must be positive".to_string()]);
+
+    let error = parse(&SMALL_MAP.replace("[(1, 1), (3, 1)]", "[(1, 1)]")).unwrap_err();
+    assert!(error.to_string().contains("needs at least 2 start positions"), "Errors should read well: {}", error);
+}
+
+#[test]
+fn test_shipped_maps_are_listed_and_playable() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/maps");
+    let listings = list_map_files(&directory);
+    assert!(listings.iter().any(|listing| listing.name == "Crossroads"), "Listed maps: {:?}", listings);
+
+    for listing in &listings {
+        assert!(listing.asset_path.starts_with("maps/") && listing.asset_path.ends_with(".map.ron"));
+        let map = MapFile::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("assets").join(&listing.asset_path))
+            .unwrap()
+            .to_game_map();
+
+        // Every player must be able to reach the objectives and their resources
+        for (x, y) in &map.start_positions {
+            let start = map.tile_center(*x, *y);
+            for location in &map.strategic_locations {
+                let target = map.tile_center(location.position.0, location.position.1);
+                assert!(find_path(&map, start, target).is_some(), "{}: {:?} can't reach {:?}", listing.name, (x, y), location.position);
+            }
+            for spawn in &map.resource_spawns {
+                let target = map.tile_center(spawn.position.0, spawn.position.1);
+                assert!(find_path(&map, start, target).is_some(), "{}: {:?} can't reach {:?}", listing.name, (x, y), spawn.position);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_map_plugin_loads_selected_map() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), StatesPlugin))
+        .insert_state(GameState::MainMenu)
+        .add_plugins((MapPlugin, StrategicLocationPlugin));
+    app.update();
+    assert!(app.world().resource::<GameMap>().seed.is_some(), "A generated map should be in place before one is picked");
+
+    app.world_mut().resource_mut::<SelectedMap>().path = Some("maps/crossroads.map.ron".to_string());
+
+    // Wait for the file to load from disk
+    let mut loaded = false;
+    for _ in 0..500 {
+        app.update();
+        if app.world().resource::<GameMap>().seed.is_none() {
+            loaded = true;
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert!(loaded, "Selected map should replace the generated one");
+
+    let map = app.world().resource::<GameMap>();
+    assert_eq!((map.width, map.height), (32, 24));
+    assert_eq!(map.start_positions, vec![(4, 4), (27, 19)]);
+    assert!(app.world().resource::<MapInitialized>().0);
+    let world = app.world_mut();
+    assert_eq!(world.query::<&MapTile>().iter(world).count(), 32 * 24, "Tiles should be rebuilt for the new map");
+
+    // Strategic locations keep the names and values the map gives them
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Gameplay);
+    app.update();
+    let world = app.world_mut();
+    let mut locations: Vec<(String, i32)> = world
+        .query::<&StrategicLocation>()
+        .iter(world)
+        .map(|location| (location.name.clone(), location.capture_value))
+        .collect();
+    locations.sort();
+    assert_eq!(
+        locations,
+        vec![("Central Hill".to_string(), 3), ("East Ford".to_string(), 1), ("West Ford".to_string(), 1)]
+    );
+}
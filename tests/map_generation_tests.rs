@@ -0,0 +1,166 @@
+use strategy_forge::{
+    components::economy::ResourceType,
+    resources::map_data::{GameMap, TerrainType},
+    resources::map_generator::{MapGenerationSettings, MapGenerator},
+    utils::config::GameConfig,
+    utils::pathfinding::find_path,
+};
+
+/// Helper function to generate a map from a seed and player count
+fn generate(seed: u64, players: usize) -> GameMap {
+    MapGenerator::new(MapGenerationSettings { players, ..MapGenerationSettings::with_seed(seed) }).generate()
+}
+
+/// Helper function to count how many tiles of each terrain a map has
+fn count_terrain(map: &GameMap, terrain_type: TerrainType) -> usize {
+    map.terrain.iter().flatten().filter(|terrain| **terrain == terrain_type).count()
+}
+
+#[test]
+fn test_same_seed_gives_same_map() {
+    let first = generate(42, 2);
+    let second = generate(42, 2);
+    assert_eq!(first.seed, Some(42), "Map should record its seed");
+    assert_eq!(first.terrain, second.terrain, "Same seed should give the same terrain");
+    assert_eq!(first.start_positions, second.start_positions);
+    assert_eq!(first.resource_spawns, second.resource_spawns);
+
+    let other = generate(43, 2);
+    assert_ne!(first.terrain, other.terrain, "A different seed should give a different map");
+}
+
+#[test]
+fn test_maps_have_every_kind_of_terrain() {
+    for seed in [1, 7, 99] {
+        let map = generate(seed, 2);
+        for terrain_type in [TerrainType::Plains, TerrainType::Forest, TerrainType::Mountains, TerrainType::Water, TerrainType::MetalDeposit] {
+            assert!(count_terrain(&map, terrain_type) > 0, "Seed {} has no {:?}", seed, terrain_type);
+        }
+        assert!(count_terrain(&map, TerrainType::Plains) > map.width * map.height / 3, "Seed {} is mostly impassable", seed);
+    }
+}
+
+#[test]
+fn test_two_player_maps_are_point_symmetric() {
+    for seed in [1, 7, 99] {
+        let map = generate(seed, 2);
+        let (w, h) = (map.width as i32, map.height as i32);
+
+        for y in 0..h {
+            for x in 0..w {
+                assert_eq!(
+                    map.terrain_at(x, y),
+                    map.terrain_at(w - 1 - x, h - 1 - y),
+                    "Seed {} is not symmetric at ({}, {})",
+                    seed, x, y
+                );
+            }
+        }
+
+        let (px, py) = map.start_positions[0];
+        assert_eq!(map.start_positions[1], (w - 1 - px, h - 1 - py), "Starts should mirror each other");
+        let centre = &map.strategic_locations[0];
+        assert_eq!(centre.position, (w / 2, h / 2), "The main objective should sit in the centre");
+        assert!(map.strategic_locations[1..].iter().all(|flank| flank.capture_value < centre.capture_value));
+        for pair in map.strategic_locations[1..].chunks(2) {
+            let (fx, fy) = pair[0].position;
+            assert_eq!(pair[1].position, (w - 1 - fx, h - 1 - fy), "Flank locations should mirror each other");
+        }
+    }
+}
+
+#[test]
+fn test_resources_sit_on_matching_terrain() {
+    for players in [2, 4] {
+        let map = generate(5, players);
+        assert_eq!(map.start_positions.len(), players);
+
+        for spawn in &map.resource_spawns {
+            let terrain = map.terrain_at(spawn.position.0, spawn.position.1);
+            let expected = match spawn.resource_type {
+                ResourceType::Wood => TerrainType::Forest,
+                ResourceType::Stone => TerrainType::Mountains,
+                ResourceType::Iron => TerrainType::MetalDeposit,
+                other => panic!("Unexpected resource {:?}", other),
+            };
+            assert_eq!(terrain, Some(expected), "{:?} node at {:?}", spawn.resource_type, spawn.position);
+        }
+
+        let wood = map.resource_spawns.iter().filter(|spawn| spawn.resource_type == ResourceType::Wood).count();
+        assert_eq!(wood % players, 0, "Every player should get the same resources");
+    }
+}
+
+#[test]
+fn test_starts_can_reach_objective_and_resources() {
+    for seed in [1, 7, 99, 1234] {
+        let map = generate(seed, 2);
+        for (x, y) in &map.start_positions {
+            let start = map.tile_center(*x, *y);
+            for location in &map.strategic_locations {
+                let objective = map.tile_center(location.position.0, location.position.1);
+                assert!(find_path(&map, start, objective).is_some(), "Seed {}: start {:?} is cut off from {:?}", seed, (x, y), location.position);
+            }
+
+            for spawn in &map.resource_spawns {
+                let node = map.tile_center(spawn.position.0, spawn.position.1);
+                assert!(find_path(&map, start, node).is_some(), "Seed {}: node at {:?} is unreachable", seed, spawn.position);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_strategic_point_count_follows_settings() {
+    for (players, requested, expected) in [(2, 3, 3), (2, 5, 5), (4, 5, 5), (2, 1, 1), (2, 4, 3), (2, 0, 0)] {
+        let settings = MapGenerationSettings { players, strategic_points: requested, ..MapGenerationSettings::with_seed(11) };
+        let map = MapGenerator::new(settings).generate();
+        assert_eq!(map.strategic_locations.len(), expected, "{} points for {} players", requested, players);
+
+        let mut positions: Vec<_> = map.strategic_locations.iter().map(|location| location.position).collect();
+        positions.sort();
+        positions.dedup();
+        assert_eq!(positions.len(), expected, "Locations should not overlap");
+        for location in &map.strategic_locations {
+            assert!(map.is_passable(location.position.0, location.position.1));
+        }
+    }
+}
+
+#[test]
+fn test_game_config_sets_the_strategic_point_count() {
+    let config = GameConfig { strategic_points: 5, ..GameConfig::default() };
+    let settings = MapGenerationSettings::from_config(&config);
+    assert_eq!(settings.strategic_points, 5);
+    assert_eq!(MapGenerator::new(settings).generate().strategic_locations.len(), 5);
+
+    let none = GameConfig { strategic_points: -1, ..GameConfig::default() };
+    assert_eq!(MapGenerationSettings::from_config(&none).strategic_points, 0);
+}
+
+#[test]
+fn test_command_line_picks_the_seed() {
+    let args = ["strategy_forge", "--seed", "1234", "--points", "5"].map(String::from);
+    let settings = MapGenerationSettings::with_seed(1).with_args(args.into_iter());
+    assert_eq!(settings.seed, 1234);
+    assert_eq!(settings.strategic_points, 5);
+    assert_eq!(MapGenerator::new(settings).generate().seed, Some(1234));
+
+    let args = ["strategy_forge", "--seed", "not-a-number"].map(String::from);
+    assert_eq!(MapGenerationSettings::with_seed(1).with_args(args.into_iter()).seed, 1, "Bad seeds are ignored");
+}
+
+#[test]
+fn test_maps_for_more_than_two_players_are_square() {
+    let settings = MapGenerationSettings { players: 4, width: 80, height: 48, ..MapGenerationSettings::with_seed(3) };
+    let map = MapGenerator::new(settings).generate();
+    assert_eq!((map.width, map.height), (48, 48), "Quarter turns only line up on a square map");
+    for &(x, y) in &map.start_positions {
+        assert!(x >= 0 && y >= 0 && (x as usize) < map.width && (y as usize) < map.height, "Start {:?} is off the map", (x, y));
+    }
+
+    // Two players only need a half turn, which fits any rectangle
+    let settings = MapGenerationSettings { players: 2, width: 80, height: 48, ..MapGenerationSettings::with_seed(3) };
+    let map = MapGenerator::new(settings).generate();
+    assert_eq!((map.width, map.height), (80, 48));
+}
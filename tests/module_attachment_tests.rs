@@ -0,0 +1,282 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use strategy_forge::{
+    components::ai::AIControlled,
+    components::base_modules::{
+        AttachModuleRequest, AttachableModule, AttachmentPoint, BaseModule, BaseModulePlugin, DetachModuleRequest,
+        InstalledModule, ModuleType,
+    },
+    components::economy::{ResourceType, ResourceWallet},
+    components::player::{MechanicalBase, PlayerResources},
+    components::unit::Team,
+    states::game_state::GameState,
+    systems::ai::AIPlugin,
+    systems::base_initialization::BaseInitializationPlugin,
+    systems::module_catalog::ModuleCatalog,
+    systems::save_load::{capture_save, restore_save},
+    utils::ron_asset::RonAsset,
+};
+
+/// Helper function to build a minimal app that runs module construction
+fn create_module_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .add_plugins(BaseModulePlugin);
+    app
+}
+
+/// Helper function to make a wallet from a list of amounts
+fn wallet(amounts: &[(ResourceType, i32)]) -> ResourceWallet {
+    let mut wallet = ResourceWallet::new();
+    for &(resource_type, amount) in amounts {
+        wallet.set(resource_type, amount);
+    }
+    wallet
+}
+
+/// Helper function to get the kind of module a catalog entry builds
+fn module_type(name: &str) -> ModuleType {
+    ModuleCatalog::default().get(name).expect("Module should be in the catalog").module_type.clone()
+}
+
+/// Helper function to spawn a base with one sensor and one weapon attachment point
+/// Returns the base and its two points
+fn spawn_base(app: &mut App, team: Team, resources: ResourceWallet) -> (Entity, Entity, Entity) {
+    let sensor_point = app
+        .world_mut()
+        .spawn(AttachmentPoint::new(Vec2::new(0.0, 48.0), 0.0, Vec2::splat(15.0), module_type("Radar")))
+        .id();
+    let weapon_point = app
+        .world_mut()
+        .spawn(AttachmentPoint::new(Vec2::new(48.0, 0.0), 0.0, Vec2::splat(15.0), module_type("Autocannon")))
+        .id();
+    let base = app
+        .world_mut()
+        .spawn((
+            Transform::default(),
+            MechanicalBase {
+                team,
+                resources,
+                attachment_points: vec![sensor_point, weapon_point],
+                ..default()
+            },
+        ))
+        .id();
+    (base, sensor_point, weapon_point)
+}
+
+/// Helper function to advance the app by a number of seconds
+fn run_seconds(app: &mut App, seconds: u32) {
+    for _ in 0..seconds * 10 {
+        app.update();
+    }
+}
+
+/// Helper function to list the modules still under construction on a base
+fn modules_under_construction(app: &mut App, base: Entity) -> Vec<Entity> {
+    let mut query = app.world_mut().query_filtered::<(Entity, &Parent), With<AttachableModule>>();
+    query
+        .iter(app.world())
+        .filter(|(_, parent)| parent.get() == base)
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+#[test]
+fn test_attached_module_is_paid_for_and_built_over_time() {
+    let mut app = create_module_app();
+    let (base, sensor_point, _) = spawn_base(&mut app, Team::Enemy, wallet(&[(ResourceType::Stone, 100), (ResourceType::Iron, 100)]));
+
+    app.world_mut().send_event(AttachModuleRequest { base, point: sensor_point, module: "Radar".to_string() });
+    app.update();
+
+    let resources = &app.world().get::<MechanicalBase>(base).unwrap().resources;
+    assert_eq!(resources.get(ResourceType::Stone), 80, "The base should pay for its own modules");
+    assert_eq!(resources.get(ResourceType::Iron), 70);
+
+    let building = modules_under_construction(&mut app, base);
+    assert_eq!(building.len(), 1, "The module should start building on the base");
+    let module = building[0];
+    let point = app.world().get::<AttachmentPoint>(sensor_point).unwrap();
+    assert!(point.occupied);
+    assert_eq!(point.attached_module, Some(module));
+    assert_eq!(app.world().get::<InstalledModule>(module).unwrap().name, "Radar");
+
+    // Radar takes 10 seconds to build and does nothing until then
+    run_seconds(&mut app, 5);
+    assert!(app.world().get::<BaseModule>(module).is_none(), "Half-built modules shouldn't work yet");
+    assert!(app.world().get::<MechanicalBase>(base).unwrap().modules.is_empty());
+
+    run_seconds(&mut app, 6);
+    assert!(app.world().get::<AttachableModule>(module).is_none());
+    let built = app.world().get::<BaseModule>(module).expect("The module should be finished");
+    assert!(matches!(built.module_type, ModuleType::Sensor { .. }));
+    assert_eq!(built.team, Team::Enemy);
+    assert_eq!(app.world().get::<MechanicalBase>(base).unwrap().modules, vec![module]);
+}
+
+#[test]
+fn test_invalid_attach_requests_are_rejected() {
+    let mut app = create_module_app();
+    let (base, sensor_point, weapon_point) = spawn_base(&mut app, Team::Enemy, wallet(&[(ResourceType::Stone, 30), (ResourceType::Iron, 40)]));
+
+    // Wrong kind of point, unknown module and a module the base can't afford
+    app.world_mut().send_event(AttachModuleRequest { base, point: weapon_point, module: "Radar".to_string() });
+    app.world_mut().send_event(AttachModuleRequest { base, point: sensor_point, module: "Death Ray".to_string() });
+    app.world_mut().send_event(AttachModuleRequest { base, point: weapon_point, module: "Autocannon".to_string() });
+    app.update();
+
+    assert!(modules_under_construction(&mut app, base).is_empty(), "None of the requests should build anything");
+    assert!(!app.world().get::<AttachmentPoint>(weapon_point).unwrap().occupied);
+    let resources = &app.world().get::<MechanicalBase>(base).unwrap().resources;
+    assert_eq!(resources.get(ResourceType::Stone), 30, "Rejected requests shouldn't cost anything");
+    assert_eq!(resources.get(ResourceType::Iron), 40);
+
+    // A point only takes one module at a time
+    app.world_mut().send_event(AttachModuleRequest { base, point: sensor_point, module: "Radar".to_string() });
+    app.world_mut().send_event(AttachModuleRequest { base, point: sensor_point, module: "Radar".to_string() });
+    app.update();
+
+    assert_eq!(modules_under_construction(&mut app, base).len(), 1);
+    assert_eq!(app.world().get::<MechanicalBase>(base).unwrap().resources.get(ResourceType::Stone), 10);
+}
+
+#[test]
+fn test_detaching_refunds_the_player() {
+    let mut app = create_module_app();
+    let mut player_resources = PlayerResources::default();
+    player_resources.resources = wallet(&[(ResourceType::Stone, 100), (ResourceType::Iron, 100)]);
+    app.insert_resource(player_resources);
+    let (base, sensor_point, _) = spawn_base(&mut app, Team::Player, ResourceWallet::new());
+
+    // Cancelling a module that is still being built gives everything back
+    app.world_mut().send_event(AttachModuleRequest { base, point: sensor_point, module: "Radar".to_string() });
+    app.update();
+    assert_eq!(app.world().resource::<PlayerResources>().resources.get(ResourceType::Stone), 80, "The player pays from their stockpile");
+
+    let module = modules_under_construction(&mut app, base)[0];
+    app.world_mut().send_event(DetachModuleRequest { module });
+    app.update();
+    assert!(app.world().get_entity(module).is_none());
+    assert!(!app.world().get::<AttachmentPoint>(sensor_point).unwrap().occupied, "The point should be free again");
+    let resources = &app.world().resource::<PlayerResources>().resources;
+    assert_eq!(resources.get(ResourceType::Stone), 100);
+    assert_eq!(resources.get(ResourceType::Iron), 100);
+
+    // Removing a finished module only gives part of the cost back
+    app.world_mut().send_event(AttachModuleRequest { base, point: sensor_point, module: "Radar".to_string() });
+    app.update();
+    let module = modules_under_construction(&mut app, base)[0];
+    run_seconds(&mut app, 11);
+    assert_eq!(app.world().get::<MechanicalBase>(base).unwrap().modules, vec![module]);
+
+    app.world_mut().send_event(DetachModuleRequest { module });
+    app.update();
+    assert!(app.world().get_entity(module).is_none());
+    assert!(app.world().get::<MechanicalBase>(base).unwrap().modules.is_empty());
+    let resources = &app.world().resource::<PlayerResources>().resources;
+    assert_eq!(resources.get(ResourceType::Stone), 90, "Half of the Radar's 20 stone comes back");
+    assert_eq!(resources.get(ResourceType::Iron), 85, "Half of the Radar's 30 iron comes back");
+}
+
+#[test]
+fn test_ai_builds_modules_on_its_base() {
+    let mut app = create_module_app();
+    app.add_plugins(AIPlugin);
+    let (base, _, weapon_point) = spawn_base(&mut app, Team::Enemy, wallet(&[(ResourceType::Wood, 20), (ResourceType::Iron, 50)]));
+    app.world_mut().entity_mut(base).insert(AIControlled::default());
+
+    run_seconds(&mut app, 2);
+
+    let point = app.world().get::<AttachmentPoint>(weapon_point).unwrap();
+    let module = point.attached_module.expect("The AI should start building the module it can afford");
+    assert_eq!(app.world().get::<InstalledModule>(module).unwrap().name, "Autocannon");
+    assert_eq!(app.world().get::<MechanicalBase>(base).unwrap().resources.get(ResourceType::Iron), 0);
+
+    // Nothing else is affordable, so nothing else is ordered
+    run_seconds(&mut app, 3);
+    assert_eq!(modules_under_construction(&mut app, base).len(), 1);
+}
+
+#[test]
+fn test_bases_get_their_attachment_points_once() {
+    let mut app = create_module_app();
+    app.add_plugins(BaseInitializationPlugin);
+    let first = app.world_mut().spawn((Transform::default(), MechanicalBase::default())).id();
+    app.update();
+
+    let points = app.world().get::<MechanicalBase>(first).unwrap().attachment_points.len();
+    assert!(points > 0, "A new base should be given attachment points");
+
+    // A base spawned later is picked up too, and the first base is left alone
+    let second = app.world_mut().spawn((Transform::default(), MechanicalBase::default())).id();
+    run_seconds(&mut app, 1);
+
+    assert_eq!(app.world().get::<MechanicalBase>(first).unwrap().attachment_points.len(), points);
+    assert_eq!(app.world().get::<MechanicalBase>(second).unwrap().attachment_points.len(), points);
+    let mut query = app.world_mut().query::<&AttachmentPoint>();
+    assert_eq!(query.iter(app.world()).count(), points * 2);
+}
+
+#[test]
+fn test_module_catalog_validation() {
+    let catalog = ModuleCatalog::default();
+    assert_eq!(catalog.validate(), Ok(()), "Shipped module catalog should pass validation");
+    assert!(catalog.names().contains(&"Radar"));
+
+    let catalog: ModuleCatalog = ron::from_str(
+        r#"(
+            version: 1,
+            modules: {
+                "Flimsy Radar": (
+                    module_type: Sensor(detection_radius: 100.0, stealth_detection: 0.0, vision_range: 1.0, scan_cooldown: 5.0),
+                    health: 0.0,
+                    cost: [(Iron, -10)],
+                    build_time: 5.0,
+                ),
+            },
+        )"#,
+    )
+    .expect("Test catalog should parse");
+
+    let problems = catalog.validate().expect_err("Bad entries should fail validation");
+    assert_eq!(problems.len(), 2, "Each bad entry should be reported: {:?}", problems);
+    assert!(problems.iter().any(|problem| problem.contains("health must be positive")));
+    assert!(problems.iter().any(|problem| problem.contains("cost of Iron is negative")));
+}
+
+#[test]
+fn test_modules_under_construction_survive_a_save() {
+    let mut app = create_module_app();
+    let (base, sensor_point, _) = spawn_base(&mut app, Team::Enemy, wallet(&[(ResourceType::Stone, 100), (ResourceType::Iron, 100)]));
+    app.world_mut().send_event(AttachModuleRequest { base, point: sensor_point, module: "Radar".to_string() });
+    run_seconds(&mut app, 5);
+
+    let save = capture_save(app.world_mut());
+    restore_save(app.world_mut(), &save);
+    app.update();
+
+    // The restored base keeps what it paid and the half-built radar
+    let world = app.world_mut();
+    let (base, restored) = world.query::<(Entity, &MechanicalBase)>().single(world);
+    assert_eq!(restored.resources.get(ResourceType::Stone), 80);
+    assert!(restored.modules.is_empty());
+    let building = modules_under_construction(&mut app, base);
+    assert_eq!(building.len(), 1, "The module under construction should be restored");
+    let module = building[0];
+    let progress = app.world().get::<AttachableModule>(module).unwrap().build_progress;
+    assert!((progress - 0.5).abs() < 0.02, "Build progress should be restored, got {}", progress);
+    let installed = app.world().get::<InstalledModule>(module).unwrap().clone();
+    assert_eq!(installed.name, "Radar");
+    let point = app.world().get::<AttachmentPoint>(installed.point).unwrap();
+    assert!(point.occupied);
+    assert_eq!(point.attached_module, Some(module));
+
+    run_seconds(&mut app, 6);
+    assert!(app.world().get::<BaseModule>(module).is_some(), "The module should finish building");
+    assert_eq!(app.world().get::<MechanicalBase>(base).unwrap().modules, vec![module]);
+}
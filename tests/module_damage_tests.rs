@@ -0,0 +1,257 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use strategy_forge::{
+    components::base_modules::{AttachmentPoint, BaseModule, BaseModulePlugin, DamageType, ModuleType},
+    components::economy::ResourceType,
+    components::player::MechanicalBase,
+    components::unit::{Team, UnitState},
+    components::unit_types::UnitType,
+    states::game_state::GameState,
+    systems::combat::{BaseDamageEvent, BaseDestroyedEvent, CombatPlugin},
+    systems::damage::DamageRules,
+    systems::module_effects::manage_module_power,
+    systems::movement::{MoveTarget, MovementPlugin},
+    systems::unit_catalog::UnitCatalog,
+    units::engineer::{repair_base_modules, repair_target_at, Engineer, RepairTarget, REPAIR_RANGE},
+};
+
+/// Helper function to build a minimal app running base damage, module power and repairs
+fn create_module_damage_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .init_resource::<DamageRules>()
+        .add_plugins((CombatPlugin, BaseModulePlugin))
+        .add_systems(Update, (manage_module_power, repair_base_modules).chain());
+    app
+}
+
+/// Helper function to make a storage module that draws no power
+fn storage_module() -> BaseModule {
+    BaseModule {
+        module_type: ModuleType::Storage { capacity: 100, resource_type: ResourceType::Wood, passive_generation: 0.0 },
+        health: 100.0,
+        max_health: 100.0,
+        power_consumption: 0.0,
+        active: true,
+        team: Team::Player,
+    }
+}
+
+/// Helper function to spawn a player base with one module on an attachment point at each offset
+/// Returns the base, its modules and their attachment points
+fn spawn_base(app: &mut App, offsets: &[Vec2]) -> (Entity, Vec<Entity>, Vec<Entity>) {
+    let base = app
+        .world_mut()
+        .spawn((Transform::default(), MechanicalBase { team: Team::Player, ..default() }))
+        .id();
+
+    let mut modules = Vec::new();
+    let mut points = Vec::new();
+    for &offset in offsets {
+        let module = app
+            .world_mut()
+            .spawn((Transform::from_xyz(offset.x, offset.y, 0.1), Sprite::default(), storage_module()))
+            .id();
+        let mut point = AttachmentPoint::new(offset, 0.0, Vec2::splat(20.0), storage_module().module_type);
+        point.occupied = true;
+        point.attached_module = Some(module);
+        let point = app.world_mut().spawn(point).id();
+        app.world_mut().entity_mut(base).add_child(module).add_child(point);
+        modules.push(module);
+        points.push(point);
+    }
+
+    let mut mechanical_base = app.world_mut().get_mut::<MechanicalBase>(base).unwrap();
+    mechanical_base.modules = modules.clone();
+    mechanical_base.attachment_points = points.clone();
+    (base, modules, points)
+}
+
+/// Helper function to hit a base from a position and let the hit resolve
+fn hit(app: &mut App, base: Entity, amount: f32, origin: Vec2) {
+    app.world_mut().send_event(BaseDamageEvent {
+        base,
+        amount,
+        damage_type: DamageType::Kinetic,
+        attacker: None,
+        origin,
+    });
+    app.update();
+}
+
+/// Helper function to read a module's health
+fn module_health(app: &App, module: Entity) -> f32 {
+    app.world().get::<BaseModule>(module).unwrap().health
+}
+
+#[test]
+fn test_hits_land_on_the_module_facing_the_attacker() {
+    let mut app = create_module_damage_app();
+    let (base, modules, _) = spawn_base(&mut app, &[Vec2::new(50.0, 0.0), Vec2::new(-50.0, 0.0), Vec2::new(0.0, 50.0)]);
+    app.update();
+
+    // 100 kinetic is 60 against a fortified base, half of it on the east module
+    hit(&mut app, base, 100.0, Vec2::new(300.0, 40.0));
+    assert_eq!(module_health(&app, modules[0]), 70.0);
+    assert_eq!(module_health(&app, modules[1]), 100.0);
+    assert_eq!(module_health(&app, modules[2]), 100.0);
+    assert_eq!(app.world().get::<MechanicalBase>(base).unwrap().health, 970.0);
+
+    // Turning the base around puts the other side in the line of fire
+    app.world_mut().get_mut::<Transform>(base).unwrap().rotation = Quat::from_rotation_z(std::f32::consts::PI);
+    hit(&mut app, base, 100.0, Vec2::new(300.0, 0.0));
+    assert_eq!(module_health(&app, modules[0]), 70.0);
+    assert_eq!(module_health(&app, modules[1]), 70.0);
+}
+
+#[test]
+fn test_damaged_modules_go_offline_then_fall_off() {
+    let mut app = create_module_damage_app();
+    let (base, modules, points) = spawn_base(&mut app, &[Vec2::new(50.0, 0.0)]);
+    let east = Vec2::new(300.0, 0.0);
+    app.update();
+
+    // Three hits of 30 leave it below a quarter of its health
+    for _ in 0..3 {
+        hit(&mut app, base, 100.0, east);
+    }
+    app.update();
+    assert!((module_health(&app, modules[0]) - 10.0).abs() < 0.01);
+    assert!(!app.world().get::<BaseModule>(modules[0]).unwrap().active, "Badly damaged modules should go offline");
+
+    // At zero it is knocked off, freeing its attachment point without a refund
+    hit(&mut app, base, 100.0, east);
+    app.update();
+    assert!(app.world().get_entity(modules[0]).is_none());
+    assert!(app.world().get::<MechanicalBase>(base).unwrap().modules.is_empty());
+    let point = app.world().get::<AttachmentPoint>(points[0]).unwrap();
+    assert!(!point.occupied);
+    assert_eq!(point.attached_module, None);
+
+    // With nothing left on that side the whole hit goes to the hull
+    let before = app.world().get::<MechanicalBase>(base).unwrap().health;
+    hit(&mut app, base, 100.0, east);
+    assert!((app.world().get::<MechanicalBase>(base).unwrap().health - (before - 60.0)).abs() < 0.01);
+}
+
+#[test]
+fn test_bases_worn_down_to_nothing_are_destroyed() {
+    let mut app = create_module_damage_app();
+    let (base, modules, points) = spawn_base(&mut app, &[Vec2::new(50.0, 0.0)]);
+    app.update();
+
+    // Two hits big enough to flatten it land in the same frame, only one counts
+    app.world_mut().send_event(BaseDamageEvent {
+        base,
+        amount: 5000.0,
+        damage_type: DamageType::Kinetic,
+        attacker: None,
+        origin: Vec2::new(300.0, 0.0),
+    });
+    hit(&mut app, base, 5000.0, Vec2::new(300.0, 0.0));
+
+    let events = app.world().resource::<Events<BaseDestroyedEvent>>();
+    let destroyed: Vec<_> = events.get_reader().read(events).copied().collect();
+    assert_eq!(destroyed.len(), 1, "A base is only destroyed once");
+    assert_eq!(destroyed[0].entity, base);
+    assert_eq!(destroyed[0].team, Team::Player);
+
+    // The base goes, and everything attached to it with it
+    assert!(app.world().get_entity(base).is_none());
+    assert!(app.world().get_entity(modules[0]).is_none());
+    assert!(app.world().get_entity(points[0]).is_none());
+}
+
+#[test]
+fn test_engineers_repair_modules_back_online() {
+    let mut app = create_module_damage_app();
+    let (base, modules, _) = spawn_base(&mut app, &[Vec2::new(50.0, 0.0), Vec2::new(-50.0, 0.0)]);
+    app.world_mut().get_mut::<BaseModule>(modules[0]).unwrap().health = 10.0;
+    app.world_mut().get_mut::<BaseModule>(modules[1]).unwrap().health = 90.0;
+
+    let engineer = app
+        .world_mut()
+        .spawn((
+            Transform::from_xyz(200.0, 0.0, 0.0),
+            Engineer {
+                build_speed: 10.0,
+                build_timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+                target_building: None,
+            },
+            RepairTarget { base },
+            UnitState::Building,
+        ))
+        .id();
+
+    // Nothing gets fixed from across the map
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(module_health(&app, modules[0]), 10.0);
+    assert!(!app.world().get::<BaseModule>(modules[0]).unwrap().active);
+
+    // Up close the worst module is patched first, at 10 health a second
+    app.world_mut().get_mut::<Transform>(engineer).unwrap().translation.x = 40.0;
+    for _ in 0..20 {
+        app.update();
+    }
+    let health = module_health(&app, modules[0]);
+    assert!(health > 28.0 && health <= 30.0, "About 2 seconds of repair, got {}", health);
+    assert_eq!(module_health(&app, modules[1]), 90.0);
+    assert!(app.world().get::<BaseModule>(modules[0]).unwrap().active, "Repaired modules come back online");
+
+    // Once everything is fixed the engineer stands down
+    for _ in 0..120 {
+        app.update();
+    }
+    assert_eq!(module_health(&app, modules[0]), 100.0);
+    assert_eq!(module_health(&app, modules[1]), 100.0);
+    assert!(app.world().get::<RepairTarget>(engineer).is_none());
+    assert_eq!(app.world().get::<UnitState>(engineer), Some(&UnitState::Idle));
+}
+
+#[test]
+fn test_engineers_walk_over_to_repair() {
+    let mut app = create_module_damage_app();
+    app.init_resource::<ButtonInput<MouseButton>>().add_plugins(MovementPlugin);
+    let (base, modules, _) = spawn_base(&mut app, &[Vec2::new(50.0, 0.0)]);
+    app.world_mut().get_mut::<BaseModule>(modules[0]).unwrap().health = 50.0;
+
+    let catalog = UnitCatalog::default();
+    let engineer = UnitType::Engineer.spawn_unit(&mut app.world_mut().commands(), &catalog, Vec2::new(400.0, 0.0), Team::Player);
+    app.world_mut().flush();
+    app.world_mut().entity_mut(engineer).insert(RepairTarget { base });
+
+    app.update();
+    assert_eq!(app.world().get::<MoveTarget>(engineer).map(|target| target.position), Some(Vec2::ZERO), "The engineer should head for the base");
+
+    // Walk until in range, then stop and get to work
+    for _ in 0..200 {
+        app.update();
+        if module_health(&app, modules[0]) > 50.0 {
+            break;
+        }
+    }
+    let distance = app.world().get::<Transform>(engineer).unwrap().translation.truncate().length();
+    assert!(distance <= REPAIR_RANGE, "The engineer should have walked into range, still {} away", distance);
+    assert!(module_health(&app, modules[0]) > 50.0, "The engineer should repair once it arrives");
+    assert!(app.world().get::<MoveTarget>(engineer).is_none(), "The engineer should stop next to the base");
+}
+
+#[test]
+fn test_right_clicks_near_a_friendly_base_are_repair_orders() {
+    let mut app = create_module_damage_app();
+    let (base, _, _) = spawn_base(&mut app, &[]);
+    app.world_mut().spawn((Transform::from_xyz(500.0, 0.0, 0.0), MechanicalBase { team: Team::Enemy, ..default() }));
+
+    let mut bases = app.world_mut().query::<(Entity, &Transform, &MechanicalBase)>();
+    let world = app.world();
+    assert_eq!(repair_target_at(bases.iter(world), Team::Player, Vec2::new(20.0, 10.0)), Some(base));
+    assert_eq!(repair_target_at(bases.iter(world), Team::Player, Vec2::new(REPAIR_RANGE + 1.0, 0.0)), None, "Too far from the base");
+    assert_eq!(repair_target_at(bases.iter(world), Team::Player, Vec2::new(500.0, 0.0)), None, "Enemy bases aren't repaired");
+    assert!(repair_target_at(bases.iter(world), Team::Enemy, Vec2::new(500.0, 0.0)).is_some());
+}
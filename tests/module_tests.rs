@@ -1,7 +1,8 @@
 use bevy::prelude::*;
 use strategy_forge::{
     components::{
-        base_modules::{BaseModule, ModuleType, DamageType, ResourceType},
+        base_modules::{BaseModule, ModuleType, DamageType},
+        economy::ResourceType,
         unit::Team,
     },
 };
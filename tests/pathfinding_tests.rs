@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use strategy_forge::{
+    components::player::MechanicalBase,
+    components::unit::{Team, UnitState},
+    components::unit_types::UnitType,
+    resources::map::plugin::MapInitialized,
+    resources::map_data::{GameMap, TerrainType},
+    states::game_state::GameState,
+    systems::base_movement::{BaseMovePlugin, MoveTarget as BaseMoveTarget},
+    systems::movement::{MoveTarget, MovementPlugin},
+    systems::unit_catalog::UnitCatalog,
+    utils::pathfinding::{find_path, MovePath},
+};
+
+/// Helper function to build a small plains map with the given tiles replaced
+fn create_map(tiles: &[((i32, i32), TerrainType)]) -> GameMap {
+    let mut map = GameMap {
+        width: 10,
+        height: 10,
+        terrain: vec![vec![TerrainType::Plains; 10]; 10],
+        tile_entities: vec![vec![None; 10]; 10],
+        initialized: true,
+        ..default()
+    };
+    for ((x, y), terrain_type) in tiles {
+        map.set_terrain(*x, *y, *terrain_type);
+    }
+    map
+}
+
+/// Helper function to build a map with a water wall along x = 5, open only at the top row
+fn create_walled_map() -> GameMap {
+    let wall: Vec<_> = (0..9).map(|y| ((5, y), TerrainType::Water)).collect();
+    create_map(&wall)
+}
+
+/// Helper function to list the tiles a sequence of waypoints passes over
+fn tiles_crossed(map: &GameMap, start: Vec2, waypoints: &[Vec2]) -> Vec<(i32, i32)> {
+    let mut tiles = Vec::new();
+    let mut from = start;
+    for waypoint in waypoints {
+        let steps = (from.distance(*waypoint) / 4.0).ceil().max(1.0) as usize;
+        for step in 0..=steps {
+            tiles.push(map.world_to_grid(from.lerp(*waypoint, step as f32 / steps as f32)));
+        }
+        from = *waypoint;
+    }
+    tiles
+}
+
+/// Helper function to build a minimal app with unit and base movement on a map
+fn create_movement_app(map: GameMap) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .init_resource::<ButtonInput<MouseButton>>()
+        .insert_resource(MapInitialized(true))
+        .insert_resource(map)
+        .add_plugins((MovementPlugin, BaseMovePlugin));
+    app
+}
+
+#[test]
+fn test_path_goes_around_water() {
+    let map = create_walled_map();
+    let start = map.tile_center(1, 1);
+    let goal = map.tile_center(8, 1);
+
+    let path = find_path(&map, start, goal).expect("There is a way around the wall");
+    assert_eq!(*path.last().unwrap(), goal, "Path should end exactly at the goal");
+    for (x, y) in tiles_crossed(&map, start, &path) {
+        assert!(map.is_passable(x, y), "Path crosses blocked tile ({}, {})", x, y);
+    }
+    assert!(path.iter().any(|point| map.world_to_grid(*point).1 == 9), "Path should use the gap at the top");
+}
+
+#[test]
+fn test_diagonals_do_not_cut_corners() {
+    let map = create_map(&[((1, 0), TerrainType::Water), ((0, 1), TerrainType::Water)]);
+
+    assert!(find_path(&map, map.tile_center(0, 0), map.tile_center(1, 1)).is_none(), "Squeezing between two lakes is not allowed");
+
+    let open = create_map(&[((1, 0), TerrainType::Water)]);
+    let path = find_path(&open, open.tile_center(0, 0), open.tile_center(1, 1)).unwrap();
+    assert_eq!(path.len(), 2, "Path should step around the single corner");
+    assert_eq!(open.world_to_grid(path[0]), (0, 1));
+
+    let clear = create_map(&[]);
+    let diagonal = find_path(&clear, clear.tile_center(0, 0), clear.tile_center(4, 4)).unwrap();
+    assert_eq!(diagonal, vec![clear.tile_center(4, 4)], "Open ground should allow a straight diagonal");
+}
+
+#[test]
+fn test_path_prefers_cheaper_terrain() {
+    // A band of mountains with a gap of plains just off the straight line
+    let band: Vec<_> = (0..10).filter(|y| *y != 5).map(|y| ((5, y), TerrainType::Mountains)).collect();
+    let map = create_map(&band);
+
+    let path = find_path(&map, map.tile_center(3, 4), map.tile_center(7, 4)).unwrap();
+    let crossing = tiles_crossed(&map, map.tile_center(3, 4), &path)
+        .into_iter()
+        .find(|(x, _)| *x == 5)
+        .unwrap();
+    assert_eq!(crossing, (5, 5), "Path should detour through the plains gap");
+}
+
+#[test]
+fn test_blocked_or_unreachable_goals_have_no_path() {
+    let map = create_walled_map();
+    assert!(find_path(&map, map.tile_center(1, 1), map.tile_center(5, 3)).is_none(), "Water is impassable");
+
+    let sealed = create_map(&(0..10).map(|y| ((5, y), TerrainType::Water)).collect::<Vec<_>>());
+    assert!(find_path(&sealed, sealed.tile_center(1, 1), sealed.tile_center(8, 1)).is_none());
+    assert!(MovePath::plan(Some(&sealed), sealed.tile_center(1, 1), sealed.tile_center(8, 1)).is_none());
+
+    let off_map = MovePath::plan(Some(&sealed), Vec2::new(-100.0, -100.0), sealed.tile_center(8, 1)).unwrap();
+    assert_eq!(off_map.waypoints, vec![sealed.tile_center(8, 1)], "Off-map moves fall back to a straight line");
+}
+
+#[test]
+fn test_units_and_bases_walk_around_water() {
+    let map = create_walled_map();
+    let start = map.tile_center(2, 1);
+    let goal = map.tile_center(8, 1);
+    let mut app = create_movement_app(map);
+    app.update();
+
+    let catalog = UnitCatalog::default();
+    let unit = UnitType::LandToLandTank.spawn_unit(&mut app.world_mut().commands(), &catalog, start, Team::Player);
+    let base = app
+        .world_mut()
+        .spawn((
+            Transform::from_xyz(start.x, start.y, 0.0),
+            MechanicalBase { effective_movement_speed: 80.0, ..default() },
+            UnitState::Moving,
+            BaseMoveTarget { target_position: goal },
+        ))
+        .id();
+    app.world_mut().flush();
+    app.world_mut().entity_mut(unit).insert((MoveTarget { position: goal }, UnitState::Moving));
+
+    let game_map = create_walled_map();
+    for _ in 0..400 {
+        app.update();
+        for entity in [unit, base] {
+            let position = app.world().get::<Transform>(entity).unwrap().translation.truncate();
+            let (x, y) = game_map.world_to_grid(position);
+            assert!(game_map.is_passable(x, y), "{:?} walked onto water at ({}, {})", entity, x, y);
+        }
+    }
+
+    for entity in [unit, base] {
+        let position = app.world().get::<Transform>(entity).unwrap().translation.truncate();
+        assert!(position.distance(goal) < 10.0, "{:?} should arrive, but is at {:?}", entity, position);
+        assert_eq!(*app.world().get::<UnitState>(entity).unwrap(), UnitState::Idle);
+        assert!(app.world().get::<MovePath>(entity).is_none(), "Finished paths should be cleared");
+    }
+}
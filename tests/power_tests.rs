@@ -0,0 +1,219 @@
+use bevy::prelude::*;
+use strategy_forge::{
+    components::base_modules::{BaseModule, DamageType, ModuleCategory, ModuleType},
+    components::economy::ResourceType,
+    components::player::MechanicalBase,
+    components::unit::Team,
+    systems::module_effects::{
+        apply_module_effects, manage_module_power, tint_modules_by_power, ModulePower, PowerPriority, PowerProfile,
+        BASE_POWER_OUTPUT,
+    },
+    systems::save_load::{capture_save, restore_save},
+};
+
+/// Helper function to build a minimal app that shares out base power
+fn create_power_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_systems(Update, (manage_module_power, tint_modules_by_power, apply_module_effects).chain());
+    app
+}
+
+/// Helper function to make a module that draws the given power
+fn module(module_type: ModuleType, power_consumption: f32) -> BaseModule {
+    BaseModule {
+        module_type,
+        health: 100.0,
+        max_health: 100.0,
+        power_consumption,
+        active: true,
+        team: Team::Player,
+    }
+}
+
+/// Helper function for a movement module
+fn engine(power_consumption: f32) -> BaseModule {
+    module(ModuleType::Movement { speed_modifier: 1.5, efficiency: 1.0, terrain_penalty_reduction: 0.0 }, power_consumption)
+}
+
+/// Helper function for a weapon module
+fn cannon(power_consumption: f32) -> BaseModule {
+    module(
+        ModuleType::Weapon {
+            damage: 10.0,
+            attack_speed: 1.0,
+            range: 100.0,
+            damage_type: DamageType::Kinetic,
+            splash_radius: 0.0,
+            tracking_speed: 1.0,
+        },
+        power_consumption,
+    )
+}
+
+/// Helper function for a storage module
+fn cargo(power_consumption: f32) -> BaseModule {
+    module(ModuleType::Storage { capacity: 100, resource_type: ResourceType::Wood, passive_generation: 0.0 }, power_consumption)
+}
+
+/// Helper function for an energy module
+fn reactor(power_output: f32) -> BaseModule {
+    module(ModuleType::Energy { power_output, power_capacity: 0.0, efficiency: 1.0, power_transfer_rate: 0.0 }, 0.0)
+}
+
+/// Helper function to spawn a base with the given modules as children
+fn spawn_base(app: &mut App, modules: Vec<BaseModule>) -> (Entity, Vec<Entity>) {
+    let base = app
+        .world_mut()
+        .spawn((Transform::default(), MechanicalBase { base_movement_speed: 30.0, ..default() }))
+        .id();
+    let modules: Vec<Entity> = modules
+        .into_iter()
+        .map(|module| app.world_mut().spawn((Transform::default(), Sprite::default(), module)).id())
+        .collect();
+    app.world_mut().entity_mut(base).push_children(&modules);
+    app.world_mut().get_mut::<MechanicalBase>(base).unwrap().modules = modules.clone();
+    (base, modules)
+}
+
+/// Helper function to read how much of its power a module is getting
+fn supply(app: &App, module: Entity) -> f32 {
+    app.world().get::<ModulePower>(module).expect("Modules should track their power").supply
+}
+
+#[test]
+fn test_profiles_decide_who_browns_out() {
+    let mut app = create_power_app();
+    let (base, modules) = spawn_base(&mut app, vec![engine(60.0), cannon(60.0)]);
+    let (engine, cannon) = (modules[0], modules[1]);
+
+    // Balanced puts movement ahead of weapons, so the cannon gets the remaining 40 of its 60
+    app.update();
+    app.update();
+    assert_eq!(supply(&app, engine), 1.0);
+    assert!((supply(&app, cannon) - 40.0 / 60.0).abs() < 0.001, "The cannon should brown out, got {}", supply(&app, cannon));
+    assert!(app.world().get::<BaseModule>(cannon).unwrap().active, "A browned-out module keeps working");
+    let power_drawn = app.world().get::<MechanicalBase>(base).unwrap().power_consumed;
+    assert!((power_drawn - BASE_POWER_OUTPUT).abs() < 0.001, "Every bit of power should be used, drew {}", power_drawn);
+
+    // Siege swaps them round
+    app.world_mut().entity_mut(base).insert(PowerPriority::from_profile(PowerProfile::Siege));
+    app.update();
+    assert_eq!(supply(&app, cannon), 1.0);
+    assert!((supply(&app, engine) - 40.0 / 60.0).abs() < 0.001);
+
+    // A half-powered engine gives half its speed bonus
+    let speed = app.world().get::<MechanicalBase>(base).unwrap().effective_movement_speed;
+    let expected = 30.0 * (1.0 + 0.5 * 40.0 / 60.0);
+    assert!((speed - expected).abs() < 0.01, "Expected speed {}, got {}", expected, speed);
+}
+
+#[test]
+fn test_starved_modules_stall_and_pass_power_down() {
+    let mut app = create_power_app();
+    let (_, modules) = spawn_base(&mut app, vec![engine(60.0), cannon(200.0), cargo(30.0)]);
+    let (cannon, cargo) = (modules[1], modules[2]);
+
+    app.update();
+    app.update();
+
+    // 40 left for a 200-power cannon is under the brownout cutoff, so it goes to storage instead
+    assert_eq!(supply(&app, cannon), 0.0);
+    assert!(!app.world().get::<BaseModule>(cannon).unwrap().active, "Starved modules should stall");
+    assert_eq!(supply(&app, cargo), 1.0);
+}
+
+#[test]
+fn test_switched_off_modules_stay_off_and_reactors_add_power() {
+    let mut app = create_power_app();
+    let (base, modules) = spawn_base(&mut app, vec![engine(60.0), cannon(90.0), reactor(50.0)]);
+    let (engine, cannon) = (modules[0], modules[1]);
+
+    app.update();
+    app.update();
+    assert_eq!(supply(&app, engine), 1.0);
+    assert_eq!(supply(&app, cannon), 1.0, "The reactor should cover the extra 50");
+    assert_eq!(app.world().get::<MechanicalBase>(base).unwrap().power_output, BASE_POWER_OUTPUT + 50.0);
+
+    app.world_mut().get_mut::<ModulePower>(engine).unwrap().switched_on = false;
+    for _ in 0..5 {
+        app.update();
+    }
+    let power = app.world().get::<ModulePower>(engine).unwrap();
+    assert!(!power.switched_on, "The player's switch should stick");
+    assert_eq!(power.supply, 0.0);
+    assert!(!app.world().get::<BaseModule>(engine).unwrap().active);
+    assert_eq!(app.world().get::<MechanicalBase>(base).unwrap().effective_movement_speed, 30.0);
+}
+
+#[test]
+fn test_modules_without_sprites_are_powered_and_tinted_on_change() {
+    let mut app = create_power_app();
+    let (base, modules) = spawn_base(&mut app, vec![engine(60.0), cannon(60.0)]);
+    let (engine, cannon) = (modules[0], modules[1]);
+    app.world_mut().entity_mut(engine).remove::<Sprite>();
+    app.update();
+    app.update();
+
+    assert_eq!(supply(&app, engine), 1.0, "Modules without a sprite should still get power");
+    assert!(app.world().get::<BaseModule>(engine).unwrap().active);
+
+    // The cannon is only repainted when its supply moves
+    app.world_mut().get_mut::<Sprite>(cannon).unwrap().color = Color::BLACK;
+    app.update();
+    assert_eq!(app.world().get::<Sprite>(cannon).unwrap().color, Color::BLACK, "Steady power shouldn't repaint the module");
+
+    app.world_mut().entity_mut(base).insert(PowerPriority::from_profile(PowerProfile::Siege));
+    app.update();
+    assert_eq!(app.world().get::<Sprite>(cannon).unwrap().color, Color::srgb(1.0, 1.0, 1.0), "Full power should paint the module white");
+}
+
+#[test]
+fn test_editing_priorities() {
+    let mut priority = PowerPriority::default();
+    assert_eq!(priority.profile, PowerProfile::Balanced);
+    assert_eq!(priority.order[0], ModuleCategory::Defense);
+    assert!(!priority.order.contains(&ModuleCategory::Energy), "Energy modules are always powered");
+
+    priority.raise(ModuleCategory::Utility);
+    assert_eq!(priority.profile, PowerProfile::Custom, "Editing the order makes a custom profile");
+    assert_eq!(priority.order[5], ModuleCategory::Utility);
+    assert_eq!(priority.order[6], ModuleCategory::Storage);
+
+    assert_eq!(PowerProfile::Balanced.next(), PowerProfile::Sprint);
+    assert_eq!(PowerProfile::Siege.next(), PowerProfile::Balanced);
+    assert_eq!(PowerProfile::Custom.next(), PowerProfile::Balanced);
+    assert_eq!(PowerPriority::from_profile(PowerProfile::Sprint).order[0], ModuleCategory::Movement);
+    assert_eq!(PowerPriority::from_profile(PowerProfile::Siege).order[0], ModuleCategory::Weapon);
+}
+
+#[test]
+fn test_power_settings_survive_save_and_load() {
+    let mut app = create_power_app();
+    let (base, modules) = spawn_base(&mut app, vec![engine(60.0), cannon(60.0)]);
+    let mut priority = PowerPriority::from_profile(PowerProfile::Siege);
+    priority.raise(ModuleCategory::Movement);
+    app.world_mut().entity_mut(base).insert(priority.clone());
+    app.update();
+    app.world_mut().get_mut::<ModulePower>(modules[1]).unwrap().switched_on = false;
+
+    let save = capture_save(app.world_mut());
+    restore_save(app.world_mut(), &save);
+
+    let world = app.world_mut();
+    let (restored_priority, restored_modules) = world
+        .query::<(&PowerPriority, &MechanicalBase)>()
+        .iter(world)
+        .map(|(priority, base)| (priority.clone(), base.modules.clone()))
+        .next()
+        .expect("The base should keep its power priorities");
+    assert_eq!(restored_priority, priority);
+
+    let switches: Vec<bool> = restored_modules
+        .iter()
+        .filter_map(|&module| world.get::<ModulePower>(module))
+        .map(|power| power.switched_on)
+        .collect();
+    assert_eq!(switches.len(), 2);
+    assert_eq!(switches.iter().filter(|on| !**on).count(), 1, "The switched-off cannon should stay off");
+}
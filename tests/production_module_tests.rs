@@ -0,0 +1,211 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use strategy_forge::{
+    components::base_modules::InstalledModule,
+    components::building::{Building, BuildingSpawner},
+    components::economy::ResourceType,
+    components::player::{MechanicalBase, PlayerResources},
+    components::unit::{Experience, Team, Unit},
+    components::unit_types::UnitType,
+    states::game_state::GameState,
+    systems::module_catalog::ModuleCatalog,
+    systems::module_effects::{apply_module_effects, manage_module_power, ProductionBonuses},
+    systems::production::{building_bonuses, ProductionPlugin, BASE_QUEUE_SLOTS},
+    systems::save_load::{capture_save, restore_save},
+    systems::unit_catalog::{UnitCatalog, UnitDefinitions},
+};
+
+/// Helper function to build a minimal app running module effects and unit production
+/// Every unit takes one second to build
+fn create_production_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .insert_resource(PlayerResources::default())
+        .add_plugins(ProductionPlugin)
+        .add_systems(Update, (manage_module_power, apply_module_effects).chain());
+
+    let mut catalog = UnitCatalog::default();
+    for definition in catalog.units.values_mut() {
+        definition.build_time = 1.0;
+    }
+    app.world_mut().resource_mut::<UnitDefinitions>().catalog = catalog;
+    app
+}
+
+/// Helper function to spawn a base carrying finished catalog modules
+fn spawn_base(app: &mut App, team: Team, position: Vec2, modules: &[&str]) -> Entity {
+    let catalog = ModuleCatalog::default();
+    let modules: Vec<Entity> = modules
+        .iter()
+        .map(|&name| {
+            let definition = catalog.get(name).expect("Module should be in the catalog");
+            app.world_mut()
+                .spawn((
+                    Transform::default(),
+                    Sprite::default(),
+                    definition.to_base_module(team),
+                    InstalledModule { name: name.to_string(), point: Entity::PLACEHOLDER },
+                ))
+                .id()
+        })
+        .collect();
+    let base = app
+        .world_mut()
+        .spawn((Transform::from_xyz(position.x, position.y, 0.0), MechanicalBase { team, modules: modules.clone(), ..default() }))
+        .id();
+    app.world_mut().entity_mut(base).push_children(&modules);
+    base
+}
+
+/// Helper function to spawn a completed building producing the given unit
+fn spawn_factory(app: &mut App, team: Team, position: Vec2, unit_type: UnitType, queue: Vec<UnitType>) -> Entity {
+    let in_production = queue.first().copied().or(Some(unit_type));
+    app.world_mut()
+        .spawn((
+            Transform::from_xyz(position.x, position.y, 0.0),
+            Building {
+                health: 300.0,
+                max_health: 300.0,
+                construction_progress: 1.0,
+                is_completed: true,
+            },
+            BuildingSpawner {
+                unit_type: Some(unit_type),
+                queue,
+                produces: vec![UnitType::Engineer, UnitType::LandToLandTank, UnitType::Artillery],
+                spawn_time: 1.0,
+                spawn_timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+                in_production,
+            },
+            team,
+        ))
+        .id()
+}
+
+/// Helper function to list the units that have been produced
+fn produced_units(app: &mut App) -> Vec<(UnitType, Option<Experience>)> {
+    let mut query = app.world_mut().query::<(&UnitType, Option<&Experience>, &Unit)>();
+    query.iter(app.world()).map(|(unit_type, experience, _)| (*unit_type, experience.copied())).collect()
+}
+
+/// Helper function to read the amount of one resource the player has
+fn player_amount(app: &App, resource_type: ResourceType) -> i32 {
+    app.world().resource::<PlayerResources>().resources.get(resource_type)
+}
+
+#[test]
+fn test_production_modules_add_up_on_their_base() {
+    let mut app = create_production_app();
+    let base = spawn_base(&mut app, Team::Player, Vec2::ZERO, &["Workshop", "Workshop", "Drill Yard"]);
+    spawn_base(&mut app, Team::Enemy, Vec2::new(100.0, 0.0), &[]);
+    app.update();
+
+    let bonuses = *app.world().get::<ProductionBonuses>(base).expect("Bases should track their production bonuses");
+    assert!((bonuses.build_speed - 1.44).abs() < 0.001, "Speed bonuses multiply, got {}", bonuses.build_speed);
+    assert_eq!(bonuses.queue_slots, 2);
+    assert!((bonuses.cost_reduction - 0.2).abs() < 0.001);
+    assert_eq!(bonuses.experience_gain, 25.0);
+    assert_eq!(bonuses.queue_capacity(), BASE_QUEUE_SLOTS + 2);
+
+    // Buildings take the bonuses of the nearest base on their own side
+    let mut query = app.world_mut().query::<(&Transform, &MechanicalBase, Option<&ProductionBonuses>)>();
+    let bases: Vec<_> = query.iter(app.world()).collect();
+    assert_eq!(building_bonuses(Team::Player, Vec2::new(120.0, 0.0), bases.iter().copied()), bonuses);
+    assert_eq!(building_bonuses(Team::Enemy, Vec2::ZERO, bases.iter().copied()), ProductionBonuses::default());
+    assert_eq!(building_bonuses(Team::Neutral, Vec2::ZERO, bases.iter().copied()), ProductionBonuses::default());
+}
+
+#[test]
+fn test_production_modules_speed_up_discount_and_train_units() {
+    let mut app = create_production_app();
+    spawn_base(&mut app, Team::Player, Vec2::ZERO, &["Workshop", "Drill Yard"]);
+    spawn_factory(&mut app, Team::Player, Vec2::new(60.0, 0.0), UnitType::LandToLandTank, Vec::new());
+    let wood_before = player_amount(&app, ResourceType::Wood);
+
+    // A one second build at 1.2 speed is done in under a second
+    for _ in 0..10 {
+        app.update();
+    }
+    let units = produced_units(&mut app);
+    assert_eq!(units.len(), 1, "The workshop should have sped the build up");
+    assert_eq!(units[0].1, Some(Experience { points: 25.0 }), "Units come out with the drill yard's experience");
+    assert_eq!(player_amount(&app, ResourceType::Wood), wood_before - 9, "10 wood less 10%");
+
+    // Without production modules the unit costs full price and comes out green
+    let mut plain = create_production_app();
+    spawn_base(&mut plain, Team::Player, Vec2::ZERO, &[]);
+    spawn_factory(&mut plain, Team::Player, Vec2::new(60.0, 0.0), UnitType::LandToLandTank, Vec::new());
+    for _ in 0..10 {
+        plain.update();
+    }
+    assert!(produced_units(&mut plain).is_empty());
+    plain.update();
+    assert_eq!(produced_units(&mut plain), vec![(UnitType::LandToLandTank, None)]);
+    assert_eq!(player_amount(&plain, ResourceType::Wood), wood_before - 10);
+}
+
+#[test]
+fn test_queued_units_come_before_the_standing_order() {
+    let mut app = create_production_app();
+    spawn_base(&mut app, Team::Enemy, Vec2::ZERO, &[]);
+    let factory = spawn_factory(
+        &mut app,
+        Team::Enemy,
+        Vec2::new(60.0, 0.0),
+        UnitType::LandToLandTank,
+        vec![UnitType::Artillery, UnitType::Engineer],
+    );
+
+    for _ in 0..11 {
+        app.update();
+    }
+    assert_eq!(app.world().get::<BuildingSpawner>(factory).unwrap().queue, vec![UnitType::Engineer]);
+
+    // The queue survives a save
+    let save = capture_save(app.world_mut());
+    restore_save(app.world_mut(), &save);
+    app.update();
+    let world = app.world_mut();
+    let restored = world.query::<&BuildingSpawner>().single(world);
+    assert_eq!(restored.queue, vec![UnitType::Engineer]);
+    assert_eq!(restored.unit_type, Some(UnitType::LandToLandTank));
+
+    for _ in 0..20 {
+        app.update();
+    }
+    let mut produced: Vec<UnitType> = produced_units(&mut app).into_iter().map(|(unit_type, _)| unit_type).collect();
+    produced.sort_by_key(|unit_type| unit_type.name());
+    assert_eq!(produced, vec![UnitType::Artillery, UnitType::Engineer, UnitType::LandToLandTank]);
+    let world = app.world_mut();
+    assert!(world.query::<&BuildingSpawner>().single(world).queue.is_empty());
+}
+
+#[test]
+fn test_new_orders_start_with_their_own_build_time() {
+    let mut app = create_production_app();
+    app.world_mut().resource_mut::<UnitDefinitions>().catalog.units.get_mut(&UnitType::Artillery).unwrap().build_time = 3.0;
+    spawn_base(&mut app, Team::Enemy, Vec2::ZERO, &[]);
+    let factory = spawn_factory(&mut app, Team::Enemy, Vec2::new(60.0, 0.0), UnitType::LandToLandTank, Vec::new());
+
+    // Halfway through a tank, artillery is ordered ahead of it
+    for _ in 0..6 {
+        app.update();
+    }
+    app.world_mut().get_mut::<BuildingSpawner>(factory).unwrap().queue.push(UnitType::Artillery);
+    for _ in 0..10 {
+        app.update();
+    }
+    assert!(produced_units(&mut app).is_empty(), "The artillery starts from scratch rather than finishing the tank's timer");
+    let spawner = app.world().get::<BuildingSpawner>(factory).unwrap();
+    assert_eq!(spawner.spawn_timer.duration().as_secs_f32(), 3.0);
+    assert_eq!(spawner.in_production, Some(UnitType::Artillery));
+
+    for _ in 0..21 {
+        app.update();
+    }
+    assert_eq!(produced_units(&mut app), vec![(UnitType::Artillery, None)]);
+}
@@ -0,0 +1,382 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::path::PathBuf;
+use std::time::Duration;
+use strategy_forge::{
+    components::ai::{AIBase, AIBrain, AIControlled, AIDifficulty, AIPhase},
+    components::base_modules::{AttachmentPoint, BaseModule, DamageType, ModuleType, UtilityEffect},
+    components::economy::ResourceType,
+    components::player::{MechanicalBase, PlayerResources, StrategicTarget},
+    components::resource::ResourceNode,
+    components::strategic::{StrategicLocation, StrategicScores, TeamScore},
+    components::unit::{Team, Unit, UnitState},
+    components::unit_types::UnitType,
+    resources::map::plugin::SelectedMap,
+    resources::map_data::GameMap,
+    resources::map_generator::{MapGenerationSettings, MapGenerator},
+    states::game_state::GameState,
+    systems::fog_of_war::FogOfWar,
+    systems::module_effects::Cooldown,
+    systems::save_load::{
+        capture_save, read_save_file, restore_save, write_save_file, SaveError, SaveGameEvent,
+        LoadGameEvent, SaveLoadPlugin, SavedMap, SAVE_VERSION,
+    },
+    systems::sensors::{EnemyIntel, IntelReport},
+    systems::status_effects::StatusEffects,
+    systems::victory::{MatchResult, VictoryProgress, VictoryReason},
+    tech::{FactionTechTrees, TechNode, TechStatus, TechTree},
+};
+
+/// Helper function to build a minimal app with the save/load systems
+fn create_save_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .init_resource::<ButtonInput<KeyCode>>()
+        .insert_resource(PlayerResources::default())
+        .add_plugins(SaveLoadPlugin);
+    app
+}
+
+/// Helper function to get a unique save path in the temp directory
+fn temp_save_path(name: &str) -> PathBuf {
+    std::env::temp_dir()
+        .join(format!("strategy_forge_{}", std::process::id()))
+        .join(format!("{}.ron", name))
+}
+
+/// Helper function to spawn a base with a single weapon module
+fn spawn_base_with_weapon(app: &mut App) -> Entity {
+    let weapon_type = ModuleType::Weapon {
+        damage: 20.0,
+        attack_speed: 2.0,
+        range: 150.0,
+        damage_type: DamageType::Kinetic,
+        splash_radius: 0.0,
+        tracking_speed: 1.0,
+    };
+
+    let module = app.world_mut()
+        .spawn((
+            Transform::from_xyz(10.0, -5.0, 0.1),
+            BaseModule {
+                module_type: weapon_type.clone(),
+                health: 80.0,
+                max_health: 100.0,
+                power_consumption: 10.0,
+                active: true,
+                team: Team::Enemy,
+            },
+            Cooldown { timer: Timer::from_seconds(0.5, TimerMode::Once) },
+        ))
+        .id();
+
+    let point = app.world_mut()
+        .spawn(AttachmentPoint {
+            position: Vec2::new(10.0, -5.0),
+            rotation: 0.0,
+            size: Vec2::new(15.0, 15.0),
+            module_type: weapon_type,
+            occupied: true,
+            attached_module: Some(module),
+        })
+        .id();
+
+    let base = app.world_mut()
+        .spawn((
+            Transform::from_xyz(300.0, 200.0, 1.0),
+            MechanicalBase {
+                health: 750.0,
+                team: Team::Enemy,
+                attachment_points: vec![point],
+                modules: vec![module],
+                ..default()
+            },
+            AIControlled { difficulty: AIDifficulty::Hard },
+            AIBase,
+            {
+                let mut brain = AIBrain::new(AIDifficulty::Hard, Vec2::new(300.0, 200.0));
+                brain.phase = AIPhase::Hold;
+                brain
+            },
+        ))
+        .id();
+    app.world_mut().entity_mut(base).push_children(&[module, point]);
+    base
+}
+
+/// Helper function to spawn a unit of the given type
+fn spawn_unit(app: &mut App, position: Vec2, team: Team, unit_type: UnitType) -> Entity {
+    app.world_mut()
+        .spawn((
+            Transform::from_xyz(position.x, position.y, 0.0),
+            Unit {
+                health: 100.0,
+                max_health: 100.0,
+                attack_power: 15.0,
+                attack_range: 5.0,
+                movement_speed: 40.0,
+                team,
+                state: UnitState::Idle,
+                attack_cooldown: Timer::from_seconds(1.0, TimerMode::Once),
+                attack_target: None,
+                movement_target: None,
+            },
+            unit_type,
+        ))
+        .id()
+}
+
+#[test]
+fn test_save_round_trip_restores_match() {
+    let mut app = create_save_app();
+    spawn_base_with_weapon(&mut app);
+
+    let tank = spawn_unit(&mut app, Vec2::new(50.0, 50.0), Team::Player, UnitType::LandToLandTank);
+    let target = spawn_unit(&mut app, Vec2::new(80.0, 50.0), Team::Enemy, UnitType::Artillery);
+    {
+        let mut unit = app.world_mut().get_mut::<Unit>(tank).unwrap();
+        unit.health = 42.0;
+        unit.state = UnitState::Attacking;
+        unit.attack_target = Some(target);
+    }
+
+    app.world_mut().spawn((
+        Transform::from_xyz(-100.0, 0.0, 0.0),
+        ResourceNode {
+            resource_type: ResourceType::Iron,
+            amount_remaining: 120,
+            max_amount: 500,
+            max_gatherers: 2,
+            current_gatherers: 0,
+        },
+    ));
+
+    app.world_mut().spawn(StrategicLocation {
+        name: "Ridge".to_string(),
+        control_points: 100.0,
+        controlling_team: Some(Team::Player),
+        position: Vec2::new(0.0, 0.0),
+        ..default()
+    });
+
+    app.world_mut().resource_mut::<PlayerResources>().score = 250;
+
+    let path = temp_save_path("round_trip");
+    let save = capture_save(app.world_mut());
+    write_save_file(&path, &save).expect("Save should be written");
+
+    // Change the world so the load has something to undo
+    app.world_mut().resource_mut::<PlayerResources>().score = 0;
+    app.world_mut().despawn(tank);
+
+    let loaded = read_save_file(&path).expect("Save should be readable");
+    restore_save(app.world_mut(), &loaded);
+
+    let world = app.world_mut();
+    assert_eq!(world.resource::<PlayerResources>().score, 250, "Player resources should be restored");
+
+    let bases: Vec<(Entity, f32, Vec<Entity>, Vec<Entity>)> = world
+        .query::<(Entity, &MechanicalBase)>()
+        .iter(world)
+        .map(|(entity, base)| (entity, base.health, base.modules.clone(), base.attachment_points.clone()))
+        .collect();
+    assert_eq!(bases.len(), 1, "Exactly one base should exist after loading");
+    let (base_entity, health, modules, points) = &bases[0];
+    assert_eq!(*health, 750.0, "Base health should be restored");
+    assert_eq!(modules.len(), 1, "Base should keep its module");
+    assert_eq!(points.len(), 1, "Base should keep its attachment point");
+
+    let module = world.get::<BaseModule>(modules[0]).expect("Module should be respawned");
+    assert_eq!(module.health, 80.0, "Module damage should be restored");
+    assert!(world.get::<Cooldown>(modules[0]).is_some(), "Weapon cooldown should be restored");
+    let point = world.get::<AttachmentPoint>(points[0]).unwrap();
+    assert_eq!(point.attached_module, Some(modules[0]), "Attachment point should reference the new module");
+
+    let brain = world.get::<AIBrain>(*base_entity).expect("AI state should be restored");
+    assert_eq!(brain.phase, AIPhase::Hold, "AI phase should be restored");
+
+    let units: Vec<(Entity, f32, Option<Entity>, UnitType)> = world
+        .query::<(Entity, &Unit, &UnitType)>()
+        .iter(world)
+        .map(|(entity, unit, unit_type)| (entity, unit.health, unit.attack_target, *unit_type))
+        .collect();
+    assert_eq!(units.len(), 2, "Despawned unit should come back");
+    let tank = units.iter().find(|unit| unit.3 == UnitType::LandToLandTank).unwrap();
+    let artillery = units.iter().find(|unit| unit.3 == UnitType::Artillery).unwrap();
+    assert_eq!(tank.1, 42.0, "Unit health should be restored");
+    assert_eq!(tank.2, Some(artillery.0), "Attack target should point at the respawned unit");
+
+    let node = world.query::<&ResourceNode>().single(world);
+    assert_eq!(node.amount_remaining, 120, "Resource node depletion should be restored");
+
+    let location = world.query::<&StrategicLocation>().single(world);
+    assert_eq!(location.controlling_team, Some(Team::Player), "Location control should be restored");
+    assert_eq!(location.control_points, 100.0, "Capture progress should be restored");
+}
+
+#[test]
+fn test_save_round_trip_restores_match_progress() {
+    let mut app = create_save_app();
+    let base = spawn_base_with_weapon(&mut app);
+    let module = app.world().get::<MechanicalBase>(base).unwrap().modules[0];
+    let tank = spawn_unit(&mut app, Vec2::new(50.0, 50.0), Team::Player, UnitType::LandToLandTank);
+    let mut effects = StatusEffects::default();
+    effects.apply(UtilityEffect::Slow, 0.3, 4.0, module);
+    app.world_mut().entity_mut(tank).insert(effects);
+
+    app.world_mut().spawn((
+        StrategicLocation { name: "Ridge".to_string(), controlling_team: Some(Team::Enemy), ..default() },
+        StrategicTarget { is_reached: true, time_held: 45.0, held_by: Some(Team::Enemy), ..default() },
+    ));
+
+    let mut scores = StrategicScores::default();
+    scores.teams.insert(Team::Enemy, TeamScore { score: 120, locations_held: 1, value_held: 3 });
+    let mut progress = VictoryProgress::default();
+    progress.majority_held.insert(Team::Enemy, 30.0);
+    progress.teams_with_bases.insert(Team::Enemy);
+    let mut fog = FogOfWar::default();
+    fog.reset(4, 4);
+    let mut explored = vec![false; 16];
+    explored[5] = true;
+    fog.restore_explored([(Team::Player, explored)].into());
+    app.insert_resource(scores)
+        .insert_resource(progress.clone())
+        .insert_resource(fog)
+        .init_resource::<EnemyIntel>();
+
+    let path = temp_save_path("match_progress");
+    write_save_file(&path, &capture_save(app.world_mut())).expect("Save should be written");
+    let save = read_save_file(&path).expect("Save should be read back");
+
+    // Play on a little, as if the old match carried on before the load
+    app.insert_resource(StrategicScores::default())
+        .insert_resource(VictoryProgress::default())
+        .insert_resource(MatchResult { winner: Team::Player, reason: VictoryReason::BasesDestroyed });
+    app.world_mut().resource_mut::<FogOfWar>().reset(4, 4);
+    app.world_mut().resource_mut::<EnemyIntel>().reports.insert(base, IntelReport {
+        team: Team::Enemy,
+        position: Vec2::ZERO,
+        modules: Vec::new(),
+        seen_at: 0.0,
+    });
+    restore_save(app.world_mut(), &save);
+
+    let world = app.world_mut();
+    assert_eq!(world.resource::<StrategicScores>().team(Team::Enemy).score, 120, "Scores should be restored");
+    assert_eq!(*world.resource::<VictoryProgress>(), progress, "Victory timers should be restored");
+    assert!(world.get_resource::<MatchResult>().is_none(), "The old match result should be dropped");
+    assert!(world.resource::<EnemyIntel>().reports.is_empty(), "Intel on the old match should be dropped");
+
+    let fog = world.resource::<FogOfWar>();
+    assert!(fog.is_explored(Team::Player, 1, 1), "Explored tiles should be restored");
+    assert!(!fog.is_explored(Team::Player, 2, 1));
+
+    let target = world.query::<&StrategicTarget>().single(world);
+    assert_eq!((target.held_by, target.time_held), (Some(Team::Enemy), 45.0), "Location hold time should be restored");
+
+    let new_module = world.query::<&MechanicalBase>().single(world).modules[0];
+    let effects = world.query::<&StatusEffects>().single(world);
+    assert_eq!(effects.strength(UtilityEffect::Slow), 0.3, "Status effects should be restored");
+    assert_eq!(effects.effects[0].source, new_module, "Effect sources should point at the restored module");
+}
+
+#[test]
+fn test_save_and_load_events() {
+    let mut app = create_save_app();
+    spawn_unit(&mut app, Vec2::ZERO, Team::Player, UnitType::LandToLandTank);
+
+    let path = temp_save_path("events");
+    app.world_mut().send_event(SaveGameEvent { path: path.clone() });
+    app.update();
+    assert!(path.exists(), "Save event should write a file");
+
+    spawn_unit(&mut app, Vec2::new(20.0, 0.0), Team::Player, UnitType::LandToLandTank);
+    app.world_mut().send_event(LoadGameEvent { path });
+    app.update();
+
+    let world = app.world_mut();
+    assert_eq!(world.query::<&Unit>().iter(world).count(), 1, "Loading should drop units added after the save");
+}
+
+#[test]
+fn test_newer_save_version_is_rejected() {
+    let mut app = create_save_app();
+    let mut save = capture_save(app.world_mut());
+    save.version = SAVE_VERSION + 1;
+
+    let path = temp_save_path("future_version");
+    write_save_file(&path, &save).expect("Save should be written");
+
+    match read_save_file(&path) {
+        Err(SaveError::UnsupportedVersion(version)) => assert_eq!(version, SAVE_VERSION + 1),
+        other => panic!("Expected a version error, got {:?}", other.map(|save| save.version)),
+    }
+}
+
+/// Helper function to make a faction with one technology available and one waiting on it
+fn tech_trees() -> FactionTechTrees {
+    let mut tree = TechTree::new("Mechanists");
+    tree.add_technology(TechNode {
+        id: "ballistics".to_string(),
+        status: TechStatus::Available,
+        research_time: 10.0,
+        unlocks: vec!["artillery".to_string()],
+        ..default()
+    });
+    tree.add_technology(TechNode {
+        id: "artillery".to_string(),
+        prerequisites: vec!["ballistics".to_string()],
+        ..default()
+    });
+
+    let mut trees = FactionTechTrees::default();
+    trees.trees.insert("mechanists".to_string(), tree);
+    trees
+}
+
+#[test]
+fn test_save_round_trip_restores_research_and_map() {
+    let mut app = create_save_app();
+    let settings = MapGenerationSettings { width: 40, height: 40, ..MapGenerationSettings::with_seed(7) };
+    app.insert_resource(MapGenerator::new(settings.clone()).generate())
+        .insert_resource(settings.clone())
+        .insert_resource(tech_trees());
+    let mut trees = app.world_mut().resource_mut::<FactionTechTrees>();
+    let tree = trees.trees.get_mut("mechanists").unwrap();
+    assert!(tree.start_research("ballistics"));
+    tree.update_research(5.0);
+    tree.research_points = 12.0;
+
+    let path = temp_save_path("research_and_map");
+    write_save_file(&path, &capture_save(app.world_mut())).expect("Save should be written");
+    let save = read_save_file(&path).expect("Save should be read back");
+    assert_eq!(save.map, Some(SavedMap::Generated(settings)));
+
+    // Lose the research and move to another map, then load
+    app.insert_resource(tech_trees())
+        .insert_resource(MapGenerator::new(MapGenerationSettings::with_seed(99)).generate());
+    restore_save(app.world_mut(), &save);
+
+    let tree = &app.world().resource::<FactionTechTrees>().trees["mechanists"];
+    assert_eq!(tree.current_research.as_deref(), Some("ballistics"));
+    assert_eq!(tree.research_points, 12.0);
+    let ballistics = tree.get_technology("ballistics").unwrap();
+    assert_eq!(ballistics.status, TechStatus::Researching);
+    assert_eq!(ballistics.research_progress, 0.5);
+    assert_eq!(tree.get_technology("artillery").unwrap().status, TechStatus::Locked);
+
+    let game_map = app.world().resource::<GameMap>();
+    assert_eq!(game_map.seed, Some(7), "Loading should bring back the map the save was played on");
+    assert_eq!((game_map.width, game_map.height), (40, 40));
+
+    // Matches on a map file remember the file
+    app.insert_resource(SelectedMap { path: Some("maps/crossroads.map.ron".to_string()) });
+    let save = capture_save(app.world_mut());
+    assert_eq!(save.map, Some(SavedMap::File("maps/crossroads.map.ron".to_string())));
+    app.insert_resource(SelectedMap::default());
+    restore_save(app.world_mut(), &save);
+    assert_eq!(app.world().resource::<SelectedMap>().path.as_deref(), Some("maps/crossroads.map.ron"));
+}
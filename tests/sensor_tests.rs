@@ -0,0 +1,230 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use bevy::transform::TransformPlugin;
+use std::time::Duration;
+use strategy_forge::{
+    components::ai::AIControlled,
+    components::base_modules::{InstalledModule, UtilityEffect},
+    components::player::MechanicalBase,
+    components::unit::{Team, Unit, UnitState},
+    resources::map_data::GameMap,
+    states::game_state::GameState,
+    systems::ai::AIPlugin,
+    systems::fog_of_war::{FogOfWar, FogOfWarPlugin},
+    systems::module_catalog::ModuleCatalog,
+    systems::sensors::{is_concealed_from, Detected, EnemyIntel, ScanRequest, SensorPlugin, SensorScan, SCAN_DURATION},
+    systems::status_effects::StatusEffects,
+};
+
+/// Helper function to build a minimal app with fog of war and sensors over a plains map
+fn create_sensor_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, TransformPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .insert_resource(GameMap::default())
+        .add_plugins((FogOfWarPlugin, SensorPlugin));
+    app
+}
+
+/// Helper function to spawn a base carrying finished catalog modules
+/// Returns the base and its modules
+fn spawn_base(app: &mut App, team: Team, position: Vec2, modules: &[&str]) -> (Entity, Vec<Entity>) {
+    let catalog = ModuleCatalog::default();
+    let modules: Vec<Entity> = modules
+        .iter()
+        .map(|&name| {
+            let definition = catalog.get(name).expect("Module should be in the catalog");
+            app.world_mut()
+                .spawn((
+                    TransformBundle::default(),
+                    definition.to_base_module(team),
+                    InstalledModule { name: name.to_string(), point: Entity::PLACEHOLDER },
+                ))
+                .id()
+        })
+        .collect();
+    let base = app
+        .world_mut()
+        .spawn((
+            TransformBundle::from_transform(Transform::from_xyz(position.x, position.y, 0.0)),
+            MechanicalBase { team, modules: modules.clone(), ..default() },
+        ))
+        .id();
+    app.world_mut().entity_mut(base).push_children(&modules);
+    (base, modules)
+}
+
+/// Helper function to spawn a cloaked unit
+fn spawn_cloaked_unit(app: &mut App, position: Vec2, team: Team) -> Entity {
+    let mut statuses = StatusEffects::default();
+    statuses.apply(UtilityEffect::Cloak, 1.0, 600.0, Entity::PLACEHOLDER);
+    app.world_mut()
+        .spawn((
+            Transform::from_xyz(position.x, position.y, 0.0),
+            Unit {
+                health: 100.0,
+                max_health: 100.0,
+                attack_power: 0.0,
+                attack_range: 1.0,
+                movement_speed: 40.0,
+                team,
+                state: UnitState::Idle,
+                attack_cooldown: Timer::from_seconds(1.0, TimerMode::Once),
+                attack_target: None,
+                movement_target: None,
+            },
+            statuses,
+        ))
+        .id()
+}
+
+/// Helper function to advance the app by a number of seconds
+fn run_seconds(app: &mut App, seconds: f32) {
+    for _ in 0..(seconds * 10.0).round() as u32 {
+        app.update();
+    }
+}
+
+/// Helper function to list the scans currently on the map
+fn scans(app: &mut App) -> Vec<SensorScan> {
+    let mut query = app.world_mut().query::<&SensorScan>();
+    query.iter(app.world()).copied().collect()
+}
+
+/// Helper function to check whether the player currently sees a world position
+fn player_sees(app: &App, position: Vec2) -> bool {
+    let map = app.world().resource::<GameMap>();
+    let (x, y) = map.world_to_grid(position);
+    app.world().resource::<FogOfWar>().is_visible(Team::Player, x, y)
+}
+
+#[test]
+fn test_scans_charge_reveal_and_fade() {
+    let mut app = create_sensor_app();
+    let (base, _) = spawn_base(&mut app, Team::Player, Vec2::new(200.0, 200.0), &["Radar"]);
+    let far_away = Vec2::new(2000.0, 200.0);
+
+    // The radar starts uncharged
+    app.update();
+    app.world_mut().send_event(ScanRequest { base, target: far_away });
+    app.update();
+    assert!(scans(&mut app).is_empty(), "An uncharged sensor can't scan");
+
+    // Once charged it scans, but only as far as three times its 300 detection radius
+    run_seconds(&mut app, 10.0);
+    app.world_mut().send_event(ScanRequest { base, target: far_away });
+    app.world_mut().send_event(ScanRequest { base, target: far_away });
+    app.update();
+    let fired = scans(&mut app);
+    assert_eq!(fired.len(), 1, "Scanning uses up the charge");
+    assert_eq!(fired[0].position, Vec2::new(1100.0, 200.0));
+    assert_eq!(fired[0].team, Team::Player);
+
+    app.update();
+    assert!(player_sees(&app, Vec2::new(1100.0, 200.0)), "The scanned area should be in sight");
+    assert!(player_sees(&app, Vec2::new(1100.0, 450.0)));
+    assert!(!player_sees(&app, Vec2::new(700.0, 200.0)), "Between the base and the scan stays fogged");
+
+    run_seconds(&mut app, SCAN_DURATION);
+    assert!(scans(&mut app).is_empty(), "Scans fade after a while");
+    assert!(!player_sees(&app, Vec2::new(1100.0, 200.0)));
+}
+
+#[test]
+fn test_sensors_extend_base_vision() {
+    let mut app = create_sensor_app();
+    spawn_base(&mut app, Team::Player, Vec2::new(200.0, 200.0), &[]);
+    spawn_base(&mut app, Team::Player, Vec2::new(1200.0, 1200.0), &["Radar"]);
+    app.update();
+
+    // 7 tiles of base sight, and 3 more from the radar
+    assert!(!player_sees(&app, Vec2::new(200.0 + 9.0 * 32.0, 200.0)));
+    assert!(player_sees(&app, Vec2::new(1200.0 + 9.0 * 32.0, 1200.0)));
+}
+
+#[test]
+fn test_cloaked_units_are_detected_by_sensors_and_scans() {
+    let mut app = create_sensor_app();
+    let (base, _) = spawn_base(&mut app, Team::Player, Vec2::new(200.0, 200.0), &["Radar"]);
+    // The radar's 300 radius at 0.3 stealth detection picks cloaks up within 90
+    let close = spawn_cloaked_unit(&mut app, Vec2::new(260.0, 200.0), Team::Enemy);
+    let distant = spawn_cloaked_unit(&mut app, Vec2::new(200.0, 500.0), Team::Enemy);
+    let own = spawn_cloaked_unit(&mut app, Vec2::new(220.0, 200.0), Team::Player);
+    app.update();
+    app.update();
+
+    let concealed = |app: &App, unit: Entity, team: Team| {
+        is_concealed_from(team, app.world().get::<StatusEffects>(unit), app.world().get::<Detected>(unit))
+    };
+    assert!(!concealed(&app, close, Team::Player), "Cloaks close to the radar should be picked up");
+    assert!(concealed(&app, distant, Team::Player));
+    assert!(concealed(&app, own, Team::Enemy), "Sensors don't give away their own side");
+    assert!(!app.world().get::<Detected>(own).unwrap().by(Team::Player));
+
+    // A scan over the distant unit exposes it too
+    run_seconds(&mut app, 10.0);
+    app.world_mut().send_event(ScanRequest { base, target: Vec2::new(200.0, 500.0) });
+    app.update();
+    app.update();
+    assert!(!concealed(&app, distant, Team::Player));
+
+    // Dropping the cloak drops the detection
+    app.world_mut().entity_mut(close).insert(StatusEffects::default());
+    app.update();
+    assert!(app.world().get::<Detected>(close).is_none());
+}
+
+#[test]
+fn test_scouting_records_enemy_loadouts() {
+    let mut app = create_sensor_app();
+    let (player_base, _) = spawn_base(&mut app, Team::Player, Vec2::new(200.0, 200.0), &["Radar"]);
+    let (enemy_base, _) = spawn_base(&mut app, Team::Enemy, Vec2::new(1000.0, 200.0), &["Autocannon", "Armor Plating"]);
+    app.update();
+    app.update();
+    assert!(app.world().resource::<EnemyIntel>().reports.is_empty(), "Nothing is known before the base is seen");
+
+    run_seconds(&mut app, 10.0);
+    app.world_mut().send_event(ScanRequest { base: player_base, target: Vec2::new(1000.0, 200.0) });
+    run_seconds(&mut app, 0.3);
+
+    let report = app.world().resource::<EnemyIntel>().reports.get(&enemy_base).cloned().expect("The scan should scout the base");
+    assert_eq!(report.team, Team::Enemy);
+    assert_eq!(report.modules, vec!["Autocannon".to_string(), "Armor Plating".to_string()]);
+
+    // The report outlives the scan, but stops being refreshed once the base is out of sight
+    run_seconds(&mut app, SCAN_DURATION);
+    let last_seen = app.world().resource::<EnemyIntel>().reports[&enemy_base].seen_at;
+    run_seconds(&mut app, 2.0);
+    let stale = app.world().resource::<EnemyIntel>().reports.get(&enemy_base).cloned().expect("Reports should be kept");
+    assert_eq!(stale.seen_at, last_seen);
+    assert!(stale.seen_at > report.seen_at && stale.seen_at < app.world().resource::<Time>().elapsed_seconds() - 1.0);
+
+    // Pausing keeps what has been scouted
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Paused);
+    app.update();
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Gameplay);
+    app.update();
+    assert!(app.world().resource::<EnemyIntel>().reports.contains_key(&enemy_base), "Resuming should keep the intel");
+
+    app.world_mut().entity_mut(enemy_base).despawn_recursive();
+    app.update();
+    assert!(app.world().resource::<EnemyIntel>().reports.is_empty(), "Destroyed bases are forgotten");
+}
+
+#[test]
+fn test_ai_scans_toward_the_nearest_hostile_base() {
+    let mut app = create_sensor_app();
+    app.add_plugins(AIPlugin);
+    let (ai_base, _) = spawn_base(&mut app, Team::Enemy, Vec2::new(1800.0, 200.0), &["Radar"]);
+    app.world_mut().entity_mut(ai_base).insert(AIControlled::default());
+    spawn_base(&mut app, Team::Player, Vec2::new(200.0, 200.0), &[]);
+
+    run_seconds(&mut app, 12.0);
+
+    let fired = scans(&mut app);
+    assert_eq!(fired.len(), 1, "The AI should scan once its radar is charged");
+    assert_eq!(fired[0].team, Team::Enemy);
+    assert_eq!(fired[0].position, Vec2::new(900.0, 200.0), "The scan lands at the edge of the radar's reach");
+}
@@ -0,0 +1,73 @@
+use strategy_forge::{
+    components::ai::AIDifficulty,
+    components::player::MechanicalBase,
+    components::strategic::StrategicLocation,
+    components::unit::Team,
+    simulation::{build_headless_app, run_simulation, SimulationConfig},
+    systems::victory::VictoryReason,
+};
+
+/// Helper function to create a short match config
+fn create_config(duration: f32) -> SimulationConfig {
+    SimulationConfig {
+        duration,
+        timestep: 0.1,
+        sample_interval: 1.0,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_headless_app_sets_up_match() {
+    let mut app = build_headless_app(create_config(1.0));
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    let bases: Vec<Team> = world.query::<&MechanicalBase>().iter(world).map(|base| base.team).collect();
+    assert_eq!(bases.len(), 2, "Match should have two bases");
+    assert!(bases.contains(&Team::Player) && bases.contains(&Team::Enemy), "Both teams should have a base");
+
+    let locations = world.query::<&StrategicLocation>().iter(world).count();
+    assert!(locations > 0, "Match should have at least one strategic location");
+}
+
+#[test]
+fn test_simulation_records_control_history() {
+    let report = run_simulation(create_config(5.0));
+
+    assert!((report.elapsed - 5.0).abs() < 0.2, "Match should run for the requested time");
+    assert!(report.control_history.len() >= 4, "Control should be sampled about once a second");
+    assert!(
+        report.control_history.windows(2).all(|pair| pair[0].time <= pair[1].time),
+        "Samples should be in chronological order"
+    );
+    assert!(
+        report.final_resources.contains_key(&Team::Player) && report.final_resources.contains_key(&Team::Enemy),
+        "Each team's base resources should be reported"
+    );
+    assert_eq!(report.winner, None, "Nobody should meet a victory rule in five seconds");
+}
+
+#[test]
+fn test_full_match_produces_a_winner() {
+    let mut config = create_config(300.0);
+    config.player_difficulty = AIDifficulty::Easy;
+    config.enemy_difficulty = AIDifficulty::Hard;
+
+    let report = run_simulation(config);
+
+    // Easy rushes the objective while Hard is still building up at home
+    assert_eq!(report.winner, Some(Team::Player), "The rushing AI should capture the location first");
+    assert!(
+        matches!(report.reason, Some(VictoryReason::HeldLocation { .. } | VictoryReason::HeldMajority { .. })),
+        "Holding the location should win the match, got {:?}",
+        report.reason
+    );
+    assert!(report.elapsed < 300.0, "Match should end early once a victory rule is met");
+
+    let last = report.control_history.last().expect("Control history should not be empty");
+    assert_eq!(last.controlling_team, Some(Team::Player), "History should end with the final owner");
+}
@@ -0,0 +1,244 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use bevy::transform::TransformPlugin;
+use std::time::Duration;
+use strategy_forge::{
+    components::base_modules::{BaseModule, ModuleType, UtilityEffect},
+    components::player::MechanicalBase,
+    components::terrain::TerrainType,
+    components::unit::{Team, Unit, UnitState},
+    resources::map_data::GameMap,
+    states::game_state::GameState,
+    systems::combat::CombatPlugin,
+    systems::damage::DamageRules,
+    systems::module_effects::handle_utility_modules,
+    systems::movement::MoveTarget,
+    systems::status_effects::{StatusEffects, StatusEffectsPlugin},
+};
+
+/// Helper function to build a minimal app running utility modules and status effects
+fn create_status_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, TransformPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .add_plugins(StatusEffectsPlugin)
+        .add_systems(Update, handle_utility_modules);
+    app
+}
+
+/// Helper function to spawn a player base carrying one utility module
+/// Returns the base and the module
+fn spawn_utility_base(app: &mut App, effect_type: UtilityEffect, effect_strength: f32, cooldown: f32) -> (Entity, Entity) {
+    let module = app
+        .world_mut()
+        .spawn((
+            TransformBundle::default(),
+            BaseModule {
+                module_type: ModuleType::Utility { effect_type, effect_strength, area_of_effect: 100.0, cooldown },
+                health: 100.0,
+                max_health: 100.0,
+                power_consumption: 0.0,
+                active: true,
+                team: Team::Player,
+            },
+        ))
+        .id();
+    let base = app
+        .world_mut()
+        .spawn((
+            TransformBundle::default(),
+            MechanicalBase {
+                team: Team::Player,
+                modules: vec![module],
+                ..default()
+            },
+        ))
+        .id();
+    app.world_mut().entity_mut(base).push_children(&[module]);
+    (base, module)
+}
+
+/// Helper function to spawn a unit with no attack
+fn spawn_unit(app: &mut App, position: Vec2, team: Team) -> Entity {
+    app.world_mut()
+        .spawn((
+            Transform::from_xyz(position.x, position.y, 0.0),
+            Unit {
+                health: 100.0,
+                max_health: 100.0,
+                attack_power: 0.0,
+                attack_range: 5.0,
+                movement_speed: 40.0,
+                team,
+                state: UnitState::Idle,
+                attack_cooldown: Timer::from_seconds(1.0, TimerMode::Once),
+                attack_target: None,
+                movement_target: None,
+            },
+        ))
+        .id()
+}
+
+/// Helper function to advance the app by a number of seconds
+fn run_seconds(app: &mut App, seconds: f32) {
+    for _ in 0..(seconds * 10.0).round() as u32 {
+        app.update();
+    }
+}
+
+/// Helper function to read the combined strength of an effect on an entity
+fn strength(app: &App, entity: Entity, effect: UtilityEffect) -> f32 {
+    app.world().get::<StatusEffects>(entity).map_or(0.0, |statuses| statuses.strength(effect))
+}
+
+#[test]
+fn test_stacking_refresh_and_expiry() {
+    let source_a = Entity::from_raw(1);
+    let source_b = Entity::from_raw(2);
+    let mut statuses = StatusEffects::default();
+
+    // Penalties don't stack: the strongest source wins
+    statuses.apply(UtilityEffect::Slow, 0.2, 3.0, source_a);
+    statuses.apply(UtilityEffect::Slow, 0.5, 1.0, source_b);
+    assert_eq!(statuses.strength(UtilityEffect::Slow), 0.5);
+    assert_eq!(statuses.speed_multiplier(), 0.5);
+
+    // Healing from several sources adds up, and reapplying from one source refreshes it
+    statuses.apply(UtilityEffect::Heal, 3.0, 1.0, source_a);
+    statuses.apply(UtilityEffect::Heal, 4.0, 1.0, source_b);
+    statuses.apply(UtilityEffect::Heal, 3.0, 2.0, source_a);
+    assert_eq!(statuses.strength(UtilityEffect::Heal), 7.0);
+    assert_eq!(statuses.effects.len(), 4);
+
+    statuses.tick(1.5);
+    assert_eq!(statuses.strength(UtilityEffect::Slow), 0.2, "The shorter, stronger slow should have worn off");
+    assert_eq!(statuses.strength(UtilityEffect::Heal), 3.0, "Only the refreshed heal should be left");
+
+    // Boosted shields soak up damage until they run out
+    statuses.apply(UtilityEffect::ShieldBoost, 30.0, 5.0, source_a);
+    assert_eq!(statuses.absorb(20.0), 0.0);
+    assert_eq!(statuses.absorb(20.0), 10.0);
+    assert!(!statuses.has(UtilityEffect::ShieldBoost), "A spent shield should be removed");
+
+    // Reveal cancels cloak, and stunned units can't move or attack
+    statuses.apply(UtilityEffect::Cloak, 1.0, 5.0, source_a);
+    assert!(statuses.is_cloaked());
+    statuses.apply(UtilityEffect::Reveal, 1.0, 5.0, source_b);
+    assert!(!statuses.is_cloaked());
+    statuses.apply(UtilityEffect::Stun, 1.0, 5.0, source_b);
+    assert_eq!(statuses.speed_multiplier(), 0.0);
+    assert_eq!(statuses.attack_rate_multiplier(), 0.0);
+}
+
+#[test]
+fn test_debuffs_hit_enemies_in_range_and_wear_off() {
+    let mut app = create_status_app();
+    spawn_utility_base(&mut app, UtilityEffect::Slow, 0.4, 2.0);
+    let enemy = spawn_unit(&mut app, Vec2::new(60.0, 0.0), Team::Enemy);
+    let far_enemy = spawn_unit(&mut app, Vec2::new(500.0, 0.0), Team::Enemy);
+    let ally = spawn_unit(&mut app, Vec2::new(-60.0, 0.0), Team::Player);
+
+    // The module charges for one cooldown before its first pulse
+    run_seconds(&mut app, 2.5);
+    assert_eq!(strength(&app, enemy, UtilityEffect::Slow), 0.4);
+    assert_eq!(strength(&app, far_enemy, UtilityEffect::Slow), 0.0, "Enemies out of range shouldn't be slowed");
+    assert_eq!(strength(&app, ally, UtilityEffect::Slow), 0.0, "Debuffs shouldn't hit allies");
+
+    // Staying in range keeps the effect going from pulse to pulse
+    run_seconds(&mut app, 5.0);
+    assert_eq!(strength(&app, enemy, UtilityEffect::Slow), 0.4);
+
+    // Once out of range it wears off shortly after the next pulse would have landed
+    app.world_mut().get_mut::<Transform>(enemy).unwrap().translation.x = 500.0;
+    run_seconds(&mut app, 3.0);
+    assert_eq!(strength(&app, enemy, UtilityEffect::Slow), 0.0);
+}
+
+#[test]
+fn test_heal_and_repair_restore_health_over_time() {
+    let mut app = create_status_app();
+    let (_, medic) = spawn_utility_base(&mut app, UtilityEffect::Heal, 5.0, 1.0);
+    let wounded = spawn_unit(&mut app, Vec2::new(30.0, 0.0), Team::Player);
+    app.world_mut().get_mut::<Unit>(wounded).unwrap().health = 50.0;
+
+    let (repaired_base, drone) = spawn_utility_base(&mut app, UtilityEffect::Repair, 10.0, 1.0);
+    app.world_mut().get_mut::<MechanicalBase>(repaired_base).unwrap().health = 500.0;
+    app.world_mut().get_mut::<BaseModule>(medic).unwrap().health = 40.0;
+    app.world_mut().get_mut::<BaseModule>(drone).unwrap().health = 40.0;
+
+    run_seconds(&mut app, 4.0);
+
+    let health = app.world().get::<Unit>(wounded).unwrap().health;
+    assert!(health > 60.0 && health < 70.0, "About 3 seconds of healing at 5 per second, got {}", health);
+
+    // Both bases sit at the same spot, so each gets repaired by the drone, modules included
+    let base_health = app.world().get::<MechanicalBase>(repaired_base).unwrap().health;
+    assert!(base_health > 520.0 && base_health < 540.0, "About 3 seconds of repair at 10 per second, got {}", base_health);
+    assert!(app.world().get::<BaseModule>(drone).unwrap().health > 60.0, "Modules on a repaired base get fixed too");
+    assert!(app.world().get::<BaseModule>(medic).unwrap().health > 60.0);
+}
+
+#[test]
+fn test_teleport_jumps_allies_toward_their_destination() {
+    let mut app = create_status_app();
+    spawn_utility_base(&mut app, UtilityEffect::Teleport, 50.0, 1.0);
+    let mover = spawn_unit(&mut app, Vec2::new(20.0, 0.0), Team::Player);
+    app.world_mut().entity_mut(mover).insert(MoveTarget { position: Vec2::new(500.0, 0.0) });
+    let idle = spawn_unit(&mut app, Vec2::new(0.0, 20.0), Team::Player);
+
+    run_seconds(&mut app, 1.5);
+
+    assert_eq!(app.world().get::<Transform>(mover).unwrap().translation.x, 70.0, "Units with a move order should jump ahead");
+    assert_eq!(app.world().get::<Transform>(idle).unwrap().translation.truncate(), Vec2::new(0.0, 20.0));
+}
+
+#[test]
+fn test_teleport_stops_short_of_impassable_terrain() {
+    let mut app = create_status_app();
+    let mut map = GameMap::default();
+    for y in 0..map.height as i32 {
+        map.set_terrain(2, y, TerrainType::Water);
+    }
+    app.insert_resource(map);
+    spawn_utility_base(&mut app, UtilityEffect::Teleport, 50.0, 1.0);
+    let mover = spawn_unit(&mut app, Vec2::new(20.0, 40.0), Team::Player);
+    app.world_mut().entity_mut(mover).insert(MoveTarget { position: Vec2::new(500.0, 40.0) });
+
+    run_seconds(&mut app, 1.5);
+
+    // A full jump would land at 70, in the river running through tiles 64 to 96
+    let landed = app.world().get::<Transform>(mover).unwrap().translation;
+    assert!(landed.x > 56.0 && landed.x < 64.0, "The unit should stop at the river bank, got {}", landed.x);
+    assert_eq!(landed.y, 40.0);
+}
+
+#[test]
+fn test_combat_respects_status_effects() {
+    let mut app = create_status_app();
+    app.init_resource::<DamageRules>().add_plugins(CombatPlugin);
+    let attacker = spawn_unit(&mut app, Vec2::ZERO, Team::Player);
+    app.world_mut().get_mut::<Unit>(attacker).unwrap().attack_power = 10.0;
+    let target = spawn_unit(&mut app, Vec2::new(50.0, 0.0), Team::Enemy);
+
+    let mut attacker_statuses = StatusEffects::default();
+    attacker_statuses.apply(UtilityEffect::DamageAmp, 0.5, 60.0, attacker);
+    app.world_mut().entity_mut(attacker).insert(attacker_statuses);
+    let mut target_statuses = StatusEffects::default();
+    target_statuses.apply(UtilityEffect::ShieldBoost, 10.0, 60.0, target);
+    app.world_mut().entity_mut(target).insert(target_statuses);
+
+    // One 15 damage hit, 10 of it soaked up by the boosted shield
+    run_seconds(&mut app, 1.1);
+    assert_eq!(app.world().get::<Unit>(target).unwrap().health, 95.0);
+
+    // Cloaked units can't be targeted
+    let mut cloak = StatusEffects::default();
+    cloak.apply(UtilityEffect::Cloak, 1.0, 60.0, target);
+    app.world_mut().entity_mut(target).insert(cloak);
+    app.update();
+    assert_eq!(app.world().get::<Unit>(attacker).unwrap().attack_target, None);
+    run_seconds(&mut app, 2.0);
+    assert_eq!(app.world().get::<Unit>(target).unwrap().health, 95.0);
+}
@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use strategy_forge::{
+    components::base_modules::{BaseModule, ModuleType},
+    components::economy::{ResourceType, ResourceWallet},
+    components::player::{MechanicalBase, PlayerResources},
+    components::resource::Gatherer,
+    components::unit::{Team, UnitState},
+    components::unit_types::UnitType,
+    states::game_state::GameState,
+    systems::economy::{DeliveryBackoff, EconomyPlugin, ResourceStorage, BASE_STORAGE_CAPACITY, FULL_BASE_RETRY_SECONDS},
+    systems::unit_catalog::UnitCatalog,
+};
+
+/// Helper function to build a minimal app running the economy
+fn create_storage_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .insert_resource(PlayerResources::default())
+        .add_plugins(EconomyPlugin);
+    app
+}
+
+/// Helper function to make a storage module that draws no power
+fn storage_module(capacity: i32, resource_type: ResourceType, passive_generation: f32) -> BaseModule {
+    BaseModule {
+        module_type: ModuleType::Storage { capacity, resource_type, passive_generation },
+        health: 100.0,
+        max_health: 100.0,
+        power_consumption: 0.0,
+        active: true,
+        team: Team::Player,
+    }
+}
+
+/// Helper function to spawn a base holding some resources with the given modules as children
+/// Returns the base and its modules
+fn spawn_base(app: &mut App, team: Team, resources: ResourceWallet, modules: Vec<BaseModule>) -> (Entity, Vec<Entity>) {
+    let modules: Vec<Entity> = modules.into_iter().map(|module| app.world_mut().spawn(module).id()).collect();
+    let base = app
+        .world_mut()
+        .spawn((
+            Transform::default(),
+            MechanicalBase { team, resources, modules: modules.clone(), ..default() },
+            UnitState::Idle,
+        ))
+        .id();
+    app.world_mut().entity_mut(base).push_children(&modules);
+    (base, modules)
+}
+
+/// Helper function to spawn a gatherer next to the base, on its way home with a load of wood
+fn spawn_loaded_gatherer(app: &mut App, team: Team, load: i32) -> Entity {
+    let catalog = UnitCatalog::default();
+    let unit = UnitType::Gatherer.spawn_unit(&mut app.world_mut().commands(), &catalog, Vec2::new(10.0, 0.0), team);
+    app.world_mut().flush();
+    let mut gatherer = app.world_mut().get_mut::<Gatherer>(unit).unwrap();
+    gatherer.current_load = load;
+    gatherer.carried_type = Some(ResourceType::Wood);
+    gatherer.returning = true;
+    unit
+}
+
+/// Helper function to read the amount of one resource a base holds
+fn base_amount(app: &App, base: Entity, resource_type: ResourceType) -> i32 {
+    app.world().get::<MechanicalBase>(base).unwrap().resources.get(resource_type)
+}
+
+/// Helper function to read the amount of one resource the player has
+fn player_amount(app: &App, resource_type: ResourceType) -> i32 {
+    app.world().resource::<PlayerResources>().resources.get(resource_type)
+}
+
+#[test]
+fn test_storage_modules_raise_the_cap_of_their_resource() {
+    let mut app = create_storage_app();
+    let (base, modules) = spawn_base(
+        &mut app,
+        Team::Enemy,
+        ResourceWallet::new(),
+        vec![storage_module(100, ResourceType::Wood, 0.0), storage_module(100, ResourceType::Wood, 0.0), storage_module(50, ResourceType::Iron, 0.5)],
+    );
+    app.update();
+
+    let storage = app.world().get::<ResourceStorage>(base).cloned().expect("Bases should track their storage");
+    assert_eq!(storage.capacity(ResourceType::Wood), BASE_STORAGE_CAPACITY + 200);
+    assert_eq!(storage.capacity(ResourceType::Iron), BASE_STORAGE_CAPACITY + 50);
+    assert_eq!(storage.capacity(ResourceType::Stone), BASE_STORAGE_CAPACITY, "Other resources keep the base cap");
+    assert_eq!(storage.generation.get(&ResourceType::Iron), Some(&0.5));
+
+    // Losing a module takes its capacity with it
+    app.world_mut().entity_mut(modules[0]).despawn_recursive();
+    app.update();
+    assert_eq!(app.world().get::<ResourceStorage>(base).unwrap().capacity(ResourceType::Wood), BASE_STORAGE_CAPACITY + 100);
+}
+
+#[test]
+fn test_full_bases_leave_the_overflow_with_the_gatherer() {
+    let mut app = create_storage_app();
+    let (base, _) = spawn_base(
+        &mut app,
+        Team::Enemy,
+        ResourceWallet::from([(ResourceType::Wood, BASE_STORAGE_CAPACITY + 95)]),
+        vec![storage_module(100, ResourceType::Wood, 0.0)],
+    );
+    let gatherer = spawn_loaded_gatherer(&mut app, Team::Enemy, 20);
+    app.update();
+
+    // Only 5 fit; the gatherer waits at the base with the other 15
+    assert_eq!(base_amount(&app, base, ResourceType::Wood), BASE_STORAGE_CAPACITY + 100);
+    let load = app.world().get::<Gatherer>(gatherer).unwrap();
+    assert_eq!(load.current_load, 15);
+    assert!(load.returning, "The gatherer should hold on to the rest");
+    assert_eq!(app.world().get::<UnitState>(gatherer), Some(&UnitState::Idle));
+    assert!(app.world().get::<DeliveryBackoff>(gatherer).is_some(), "The gatherer should wait before trying again");
+
+    // Once some is spent the rest goes in, after the gatherer's wait is up
+    app.world_mut().get_mut::<MechanicalBase>(base).unwrap().resources.add(ResourceType::Wood, -50);
+    app.update();
+    assert_eq!(base_amount(&app, base, ResourceType::Wood), BASE_STORAGE_CAPACITY + 50);
+    for _ in 0..(FULL_BASE_RETRY_SECONDS * 10.0) as usize {
+        app.update();
+    }
+    assert_eq!(base_amount(&app, base, ResourceType::Wood), BASE_STORAGE_CAPACITY + 65);
+    assert!(app.world().get::<DeliveryBackoff>(gatherer).is_none());
+    let load = app.world().get::<Gatherer>(gatherer).unwrap();
+    assert_eq!(load.current_load, 0);
+    assert!(!load.returning);
+}
+
+#[test]
+fn test_player_deliveries_are_capped_by_the_player_stockpile() {
+    let mut app = create_storage_app();
+    let (base, _) = spawn_base(&mut app, Team::Player, ResourceWallet::new(), Vec::new());
+    app.world_mut().resource_mut::<PlayerResources>().resources.set(ResourceType::Wood, BASE_STORAGE_CAPACITY - 8);
+    let gatherer = spawn_loaded_gatherer(&mut app, Team::Player, 20);
+    app.update();
+
+    assert_eq!(player_amount(&app, ResourceType::Wood), BASE_STORAGE_CAPACITY);
+    assert_eq!(base_amount(&app, base, ResourceType::Wood), 0, "The player's resources are only kept in one place");
+    assert_eq!(app.world().get::<Gatherer>(gatherer).unwrap().current_load, 12);
+}
+
+#[test]
+fn test_player_stockpile_holds_what_every_player_base_can() {
+    let mut app = create_storage_app();
+    spawn_base(&mut app, Team::Player, ResourceWallet::new(), Vec::new());
+    spawn_base(&mut app, Team::Player, ResourceWallet::new(), vec![storage_module(50, ResourceType::Wood, 0.0)]);
+    let far_away = spawn_base(&mut app, Team::Player, ResourceWallet::new(), Vec::new()).0;
+    app.world_mut().get_mut::<Transform>(far_away).unwrap().translation.x = 5000.0;
+    let total = 3 * BASE_STORAGE_CAPACITY + 50;
+    app.world_mut().resource_mut::<PlayerResources>().resources.set(ResourceType::Wood, total - 8);
+
+    // Whichever base takes the delivery, the cap is the total
+    let gatherer = spawn_loaded_gatherer(&mut app, Team::Player, 20);
+    app.update();
+
+    assert_eq!(player_amount(&app, ResourceType::Wood), total);
+    assert_eq!(app.world().get::<Gatherer>(gatherer).unwrap().current_load, 12);
+}
+
+#[test]
+fn test_storage_modules_trickle_in_income_up_to_the_cap() {
+    let mut app = create_storage_app();
+    let (base, modules) = spawn_base(&mut app, Team::Player, ResourceWallet::new(), vec![storage_module(50, ResourceType::Iron, 2.0)]);
+    let iron_before = player_amount(&app, ResourceType::Iron);
+
+    // 2 iron a second for three seconds
+    for _ in 0..31 {
+        app.update();
+    }
+    assert_eq!(player_amount(&app, ResourceType::Iron), iron_before + 6);
+    assert_eq!(base_amount(&app, base, ResourceType::Iron), 0);
+
+    // Offline modules generate nothing
+    app.world_mut().get_mut::<BaseModule>(modules[0]).unwrap().active = false;
+    for _ in 0..20 {
+        app.update();
+    }
+    assert_eq!(player_amount(&app, ResourceType::Iron), iron_before + 6);
+
+    // Income past the cap is lost
+    app.world_mut().get_mut::<BaseModule>(modules[0]).unwrap().active = true;
+    app.world_mut().resource_mut::<PlayerResources>().resources.set(ResourceType::Iron, BASE_STORAGE_CAPACITY + 49);
+    for _ in 0..20 {
+        app.update();
+    }
+    assert_eq!(player_amount(&app, ResourceType::Iron), BASE_STORAGE_CAPACITY + 50);
+}
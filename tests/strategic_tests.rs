@@ -0,0 +1,247 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use strategy_forge::{
+    components::player::{MechanicalBase, PlayerResources},
+    components::strategic::{
+        CaptureState, LocationCaptured, LocationLost, StrategicLocation, StrategicLocationPlugin, StrategicScores,
+    },
+    components::unit::{Team, Unit, UnitState},
+    components::unit_types::UnitType,
+    resources::map::plugin::MapInitialized,
+    resources::map_data::GameMap,
+    states::game_state::GameState,
+    systems::unit_catalog::UnitCatalog,
+};
+
+/// Helper function to build a minimal app scoring strategic locations
+fn create_strategic_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .insert_resource(GameMap::default())
+        .insert_resource(MapInitialized(true))
+        .insert_resource(PlayerResources::default())
+        .add_plugins(StrategicLocationPlugin);
+    app
+}
+
+/// Helper function to spawn a location already held by a team
+fn spawn_location(app: &mut App, name: &str, x: f32, capture_value: i32, controlling_team: Option<Team>) -> Entity {
+    app.world_mut()
+        .spawn(StrategicLocation {
+            name: name.to_string(),
+            control_points: if controlling_team.is_some() { 100.0 } else { 0.0 },
+            controlling_team,
+            position: Vec2::new(x, 0.0),
+            capture_value,
+            ..default()
+        })
+        .id()
+}
+
+#[test]
+fn test_held_locations_score_by_value() {
+    let mut app = create_strategic_app();
+    spawn_location(&mut app, "Centre", 0.0, 3, Some(Team::Player));
+    spawn_location(&mut app, "West", -1000.0, 1, Some(Team::Enemy));
+    spawn_location(&mut app, "East", 1000.0, 1, Some(Team::Enemy));
+    spawn_location(&mut app, "North", 2000.0, 5, None);
+
+    // One payout a second
+    for _ in 0..11 {
+        app.update();
+    }
+    let scores = app.world().resource::<StrategicScores>();
+    assert_eq!(scores.team(Team::Player).score, 3, "The centre is worth 3 a second");
+    assert_eq!(scores.team(Team::Enemy).score, 2, "Two flanks are worth 1 each");
+    assert_eq!(scores.team(Team::Enemy).locations_held, 2);
+    assert_eq!(scores.team(Team::Enemy).value_held, 2);
+
+    for _ in 0..20 {
+        app.update();
+    }
+    let resources = app.world().resource::<PlayerResources>();
+    assert_eq!(resources.score, 9, "The player's score should be added to their resources");
+    assert_eq!(resources.strategic_points_controlled, 1);
+    assert_eq!(app.world().resource::<StrategicScores>().team(Team::Enemy).score, 6);
+}
+
+#[test]
+fn test_lost_locations_stop_scoring() {
+    let mut app = create_strategic_app();
+    let location = spawn_location(&mut app, "Centre", 0.0, 2, Some(Team::Player));
+
+    for _ in 0..11 {
+        app.update();
+    }
+    assert_eq!(app.world().resource::<PlayerResources>().score, 2);
+
+    let mut held = app.world_mut().get_mut::<StrategicLocation>(location).unwrap();
+    held.controlling_team = None;
+    held.control_points = 0.0;
+    for _ in 0..20 {
+        app.update();
+    }
+
+    let resources = app.world().resource::<PlayerResources>();
+    assert_eq!(resources.score, 2, "Score already earned is kept");
+    assert_eq!(resources.strategic_points_controlled, 0);
+}
+
+/// Helper function to spawn a unit standing at a position
+fn spawn_unit(app: &mut App, x: f32, team: Team) -> Entity {
+    app.world_mut()
+        .spawn((
+            Transform::from_xyz(x, 0.0, 0.0),
+            Unit {
+                health: 100.0,
+                max_health: 100.0,
+                attack_power: 10.0,
+                attack_range: 5.0,
+                movement_speed: 40.0,
+                team,
+                state: UnitState::Idle,
+                attack_cooldown: Timer::from_seconds(1.0, TimerMode::Once),
+                attack_target: None,
+                movement_target: None,
+            },
+        ))
+        .id()
+}
+
+/// Capture events seen by the test app
+#[derive(Resource, Default)]
+struct CaptureLog {
+    captured: Vec<LocationCaptured>,
+    lost: Vec<LocationLost>,
+}
+
+/// Helper system to keep every capture event for the test to check
+fn record_capture_events(
+    mut log: ResMut<CaptureLog>,
+    mut captured: EventReader<LocationCaptured>,
+    mut lost: EventReader<LocationLost>,
+) {
+    log.captured.extend(captured.read().copied());
+    log.lost.extend(lost.read().copied());
+}
+
+/// Helper function to take the capture events recorded so far
+fn capture_events(app: &mut App) -> (Vec<LocationCaptured>, Vec<LocationLost>) {
+    let mut log = app.world_mut().resource_mut::<CaptureLog>();
+    (std::mem::take(&mut log.captured), std::mem::take(&mut log.lost))
+}
+
+#[test]
+fn test_capture_speed_follows_military_presence() {
+    let mut app = create_strategic_app();
+    let lone = spawn_location(&mut app, "Lone", 0.0, 1, None);
+    let crowded = spawn_location(&mut app, "Crowded", 1000.0, 1, None);
+    let guarded = spawn_location(&mut app, "Guarded", 2000.0, 1, None);
+    spawn_unit(&mut app, 0.0, Team::Player);
+    for _ in 0..3 {
+        spawn_unit(&mut app, 1000.0, Team::Player);
+    }
+    app.world_mut().spawn((
+        Transform::from_xyz(2000.0, 0.0, 0.0),
+        MechanicalBase { team: Team::Player, ..default() },
+    ));
+
+    for _ in 0..11 {
+        app.update();
+    }
+    let points = |app: &App, entity: Entity| app.world().get::<StrategicLocation>(entity).unwrap().control_points;
+    assert!((points(&app, lone) - 2.0).abs() < 0.01, "One unit captures at the base rate, got {}", points(&app, lone));
+    assert!((points(&app, crowded) - 6.0).abs() < 0.01, "Three units capture three times as fast");
+    assert!((points(&app, guarded) - 10.0).abs() < 0.01, "A base is worth five units");
+
+    let location = app.world().get::<StrategicLocation>(crowded).unwrap();
+    assert_eq!(location.state, CaptureState::Capturing(Team::Player));
+    assert_eq!(location.capturing_team, Some(Team::Player));
+    assert_eq!(location.controlling_team, None, "Not captured until the points are full");
+}
+
+#[test]
+fn test_capture_strength_follows_firepower() {
+    let mut app = create_strategic_app();
+    let workers = spawn_location(&mut app, "Workers", 0.0, 1, None);
+    let artillery = spawn_location(&mut app, "Artillery", 1000.0, 1, None);
+    let catalog = UnitCatalog::default();
+    let mut commands = app.world_mut().commands();
+    UnitType::Gatherer.spawn_unit(&mut commands, &catalog, Vec2::new(10.0, 0.0), Team::Player);
+    UnitType::Engineer.spawn_unit(&mut commands, &catalog, Vec2::new(-10.0, 0.0), Team::Player);
+    UnitType::Artillery.spawn_unit(&mut commands, &catalog, Vec2::new(1000.0, 0.0), Team::Player);
+    UnitType::Gatherer.spawn_unit(&mut commands, &catalog, Vec2::new(1010.0, 0.0), Team::Enemy);
+    app.world_mut().flush();
+
+    for _ in 0..11 {
+        app.update();
+    }
+    let workers = app.world().get::<StrategicLocation>(workers).unwrap();
+    assert_eq!(workers.state, CaptureState::Idle, "Non-combat units can't take ground");
+    assert_eq!(workers.control_points, 0.0);
+    let artillery = app.world().get::<StrategicLocation>(artillery).unwrap();
+    assert_eq!(artillery.state, CaptureState::Capturing(Team::Player), "Nor can they contest it");
+    assert!((artillery.control_points - 5.0).abs() < 0.01, "25 attack power captures 2.5 times as fast");
+}
+
+#[test]
+fn test_contested_locations_change_hands_and_send_events() {
+    let mut app = create_strategic_app();
+    app.init_resource::<CaptureLog>().add_systems(Update, record_capture_events);
+    let location = spawn_location(&mut app, "Centre", 0.0, 1, Some(Team::Enemy));
+    app.world_mut().get_mut::<StrategicLocation>(location).unwrap().capture_rate = 10.0;
+    spawn_unit(&mut app, 10.0, Team::Player);
+    spawn_unit(&mut app, -10.0, Team::Enemy);
+
+    // Evenly matched, so control wears down but nobody makes progress
+    for _ in 0..11 {
+        app.update();
+    }
+    let held = app.world().get::<StrategicLocation>(location).unwrap();
+    assert_eq!(held.state, CaptureState::Contested);
+    assert_eq!(held.controlling_team, Some(Team::Enemy));
+    assert!((held.control_points - 95.0).abs() < 0.01, "Contested locations lose 5 points a second");
+
+    // Reinforcements outnumber the defender, strip the enemy's control, then take it over
+    spawn_unit(&mut app, 20.0, Team::Player);
+    spawn_unit(&mut app, 30.0, Team::Player);
+    for _ in 0..50 {
+        app.update();
+    }
+    let (captured, lost) = capture_events(&mut app);
+    assert_eq!(lost, vec![LocationLost { location, team: Team::Enemy }]);
+    assert!(captured.is_empty(), "The player can't own it before building up their own control");
+    assert_eq!(app.world().get::<StrategicLocation>(location).unwrap().controlling_team, None);
+
+    for _ in 0..50 {
+        app.update();
+    }
+    let (captured, _) = capture_events(&mut app);
+    assert_eq!(captured, vec![LocationCaptured { location, team: Team::Player }]);
+    assert_eq!(app.world().get::<StrategicLocation>(location).unwrap().controlling_team, Some(Team::Player));
+}
+
+#[test]
+fn test_capture_radius_comes_from_the_location() {
+    let mut app = create_strategic_app();
+    let wide = spawn_location(&mut app, "Wide", 0.0, 1, None);
+    let narrow = spawn_location(&mut app, "Narrow", 1000.0, 1, None);
+    app.world_mut().get_mut::<StrategicLocation>(wide).unwrap().capture_radius = 300.0;
+    app.world_mut().get_mut::<StrategicLocation>(narrow).unwrap().capture_radius = 50.0;
+    spawn_unit(&mut app, 200.0, Team::Player);
+    spawn_unit(&mut app, 1080.0, Team::Player);
+
+    for _ in 0..11 {
+        app.update();
+    }
+    let wide = app.world().get::<StrategicLocation>(wide).unwrap();
+    let narrow = app.world().get::<StrategicLocation>(narrow).unwrap();
+    assert_eq!(wide.state, CaptureState::Capturing(Team::Player));
+    assert!(wide.control_points > 0.0);
+    assert_eq!(narrow.state, CaptureState::Idle, "The unit is outside the narrow radius");
+    assert_eq!(narrow.control_points, 0.0);
+}
@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use strategy_forge::{
+    components::terrain::{MapTile, Terrain},
+    resources::map::plugin::MapPlugin,
+    resources::map_data::{GameMap, TerrainType},
+    resources::map_generator::MapGenerationSettings,
+};
+
+#[test]
+fn test_map_plugin_spawns_a_tile_per_cell() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(MapGenerationSettings { width: 16, height: 12, ..MapGenerationSettings::with_seed(3) })
+        .add_plugins(MapPlugin);
+    app.update();
+
+    let world = app.world_mut();
+    let tiles: Vec<(Entity, (i32, i32), Terrain, Color)> = world
+        .query::<(Entity, &MapTile, &Terrain, &Sprite)>()
+        .iter(world)
+        .map(|(entity, tile, terrain, sprite)| (entity, (tile.grid_x, tile.grid_y), *terrain, sprite.color))
+        .collect();
+    assert_eq!(tiles.len(), 16 * 12, "Every grid cell should get a tile");
+
+    let game_map = app.world().resource::<GameMap>();
+    for (entity, (x, y), terrain, color) in tiles {
+        let terrain_type = game_map.terrain_at(x, y).unwrap();
+        assert_eq!(game_map.get_tile_entity(x, y), Some(entity), "Map should know its tile entities");
+        assert_eq!(terrain, Terrain::new(terrain_type));
+        assert_eq!(color, terrain_type.color(), "Tiles are drawn in their terrain's colour");
+    }
+}
+
+#[test]
+fn test_terrain_stats_lookup_by_world_position() {
+    let mut map = GameMap::default();
+    map.set_terrain(3, 4, TerrainType::Hills);
+    map.set_terrain(5, 5, TerrainType::Water);
+
+    let hills = map.terrain_stats_at(map.tile_center(3, 4) + Vec2::new(10.0, -10.0)).unwrap();
+    assert_eq!(hills.terrain_type, TerrainType::Hills);
+    assert_eq!(hills.movement_modifier, TerrainType::Hills.movement_modifier());
+    assert_eq!(hills.visibility_modifier, TerrainType::Hills.visibility_modifier());
+    assert!(hills.defense_modifier > 1.0, "High ground should help the defender");
+
+    assert_eq!(map.terrain_stats_at(map.tile_center(0, 0)).unwrap(), Terrain::new(TerrainType::Plains));
+    assert!(map.terrain_stats_at(Vec2::new(-1.0, 10.0)).is_none(), "Nothing to look up off the map");
+    assert!(!map.is_passable(5, 5), "Water stats should block ground units");
+
+    for terrain_type in TerrainType::ALL {
+        assert_eq!(terrain_type.stats().terrain_type, terrain_type);
+        assert!(terrain_type.defense_modifier() > 0.0, "{:?} cover must be usable as a divisor", terrain_type);
+    }
+}
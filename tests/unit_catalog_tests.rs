@@ -0,0 +1,226 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use strategy_forge::{
+    components::economy::{ResourceType, ResourceWallet},
+    components::building::{Building, BuildingSpawner},
+    components::player::PlayerResources,
+    components::resource::Gatherer,
+    components::unit::{Team, Unit},
+    components::unit_types::UnitType,
+    sprites::GameSprites,
+    states::game_state::GameState,
+    systems::production::ProductionPlugin,
+    systems::unit_catalog::{UnitCatalog, UnitCatalogPlugin, UnitDefinitions, UnitTag},
+    units::engineer::Engineer,
+};
+
+const ALL_UNIT_NAMES: [&str; 11] = [
+    "Engineer",
+    "Gatherer",
+    "LandToLandTank",
+    "LandToAirTank",
+    "Artillery",
+    "AirToAirFighter",
+    "AirToLandBomber",
+    "LargeTank",
+    "LargeHoveringAircraft",
+    "LargeBomber",
+    "LargeArtillery",
+];
+
+/// Helper function to build a minimal app running unit production
+fn create_production_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .insert_resource(PlayerResources::default())
+        .add_plugins(ProductionPlugin);
+    app
+}
+
+/// Helper function to spawn a completed building producing the given unit
+fn spawn_factory(app: &mut App, team: Team, unit_type: UnitType) -> Entity {
+    app.world_mut()
+        .spawn((
+            Transform::default(),
+            Building {
+                health: 300.0,
+                max_health: 300.0,
+                construction_progress: 1.0,
+                is_completed: true,
+            },
+            BuildingSpawner {
+                unit_type: Some(unit_type),
+                queue: Vec::new(),
+                produces: vec![unit_type],
+                spawn_time: 1.0,
+                spawn_timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+                in_production: Some(unit_type),
+            },
+            team,
+        ))
+        .id()
+}
+
+/// Helper function to read the amount of one resource the player has
+fn player_amount(app: &App, resource_type: ResourceType) -> i32 {
+    app.world().resource::<PlayerResources>().resources.get(resource_type)
+}
+
+#[test]
+fn test_builtin_catalog_defines_every_unit() {
+    let catalog = UnitCatalog::default();
+
+    for name in ALL_UNIT_NAMES {
+        let unit_type = UnitType::from_name(name).expect("Unit name should be known");
+        let definition = catalog.get(unit_type).unwrap_or_else(|| panic!("{} should be in the catalog", name));
+        assert!(definition.health > 0.0, "{} should have health", name);
+        assert!(definition.build_time > 0.0, "{} should take time to build", name);
+    }
+
+    let engineer = catalog.get(UnitType::Engineer).unwrap();
+    assert!(engineer.has_tag(UnitTag::Builder) && engineer.has_tag(UnitTag::Gatherer));
+    assert_eq!(engineer.cost, ResourceWallet::from([(ResourceType::Wood, 20), (ResourceType::Stone, 10)]));
+}
+
+#[test]
+fn test_spawned_units_use_catalog_stats() {
+    let mut app = create_production_app();
+    let mut catalog = UnitCatalog::default();
+    catalog.units.get_mut(&UnitType::Artillery).unwrap().health = 123.0;
+    catalog.units.get_mut(&UnitType::Artillery).unwrap().attack_range = 42.0;
+    app.world_mut().resource_mut::<UnitDefinitions>().catalog = catalog;
+
+    spawn_factory(&mut app, Team::Enemy, UnitType::Artillery);
+    for _ in 0..11 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    let unit = world.query::<&Unit>().single(world);
+    assert_eq!(unit.max_health, 123.0, "Health should come from the catalog");
+    assert_eq!(unit.attack_range, 42.0, "Range should come from the catalog");
+}
+
+#[test]
+fn test_engineers_get_builder_and_gatherer_abilities() {
+    let mut app = create_production_app();
+    spawn_factory(&mut app, Team::Enemy, UnitType::Engineer);
+
+    for _ in 0..11 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    let (_, engineer, gatherer) = world.query::<(&Unit, Option<&Engineer>, Option<&Gatherer>)>().single(world);
+    assert!(engineer.is_some(), "Builder tag should add the Engineer component");
+    assert!(gatherer.is_some(), "Gatherer tag should add the Gatherer component");
+
+    let definition = UnitCatalog::default().definition(UnitType::Engineer);
+    assert_eq!(engineer.unwrap().build_speed, definition.build_speed, "Build speed should come from the catalog");
+    let gatherer = gatherer.unwrap();
+    assert_eq!(gatherer.gather_rate, definition.gather_rate, "Gather rate should come from the catalog");
+    assert_eq!(gatherer.carry_capacity, definition.carry_capacity, "Carry capacity should come from the catalog");
+}
+
+#[test]
+fn test_production_charges_catalog_cost_and_build_time() {
+    let mut app = create_production_app();
+    let factory = spawn_factory(&mut app, Team::Player, UnitType::LandToLandTank);
+
+    let wood_before = player_amount(&app, ResourceType::Wood);
+    let iron_before = player_amount(&app, ResourceType::Iron);
+    for _ in 0..11 {
+        app.update();
+    }
+
+    let catalog = UnitCatalog::default();
+    let tank = catalog.get(UnitType::LandToLandTank).unwrap();
+    assert_eq!(player_amount(&app, ResourceType::Wood), wood_before - 10, "Tank should cost wood");
+    assert_eq!(player_amount(&app, ResourceType::Iron), iron_before - 5, "Tank should cost iron");
+
+    let spawner = app.world().get::<BuildingSpawner>(factory).unwrap();
+    assert_eq!(
+        spawner.spawn_timer.duration().as_secs_f32(),
+        tank.build_time,
+        "Next unit should take the catalog build time"
+    );
+}
+
+#[test]
+fn test_production_blocked_without_resources() {
+    let mut app = create_production_app();
+    app.world_mut().resource_mut::<PlayerResources>().resources = ResourceWallet::from([(ResourceType::Wood, 5)]);
+    spawn_factory(&mut app, Team::Player, UnitType::Engineer);
+
+    for _ in 0..11 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    assert_eq!(world.query::<&Unit>().iter(world).count(), 0, "Unaffordable units should not spawn");
+}
+
+#[test]
+fn test_catalog_reload_rebalances_existing_units() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), UnitCatalogPlugin));
+
+    // Wait for the shipped catalog to finish loading from disk
+    let mut loaded = false;
+    for _ in 0..500 {
+        app.update();
+        let handle = app.world().resource::<UnitDefinitions>().handle.clone().unwrap();
+        if app.world().resource::<AssetServer>().is_loaded_with_dependencies(&handle) {
+            loaded = true;
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert!(loaded, "Shipped unit catalog should load and parse");
+
+    let catalog = app.world().resource::<UnitDefinitions>().catalog.clone();
+    let tank = UnitType::LandToLandTank.spawn_unit(&mut app.world_mut().commands(), &catalog, Vec2::ZERO, Team::Player);
+    app.world_mut().flush();
+    app.world_mut().get_mut::<Unit>(tank).unwrap().health = 50.0;
+
+    // Simulate a designer editing the file
+    let mut edited = catalog.clone();
+    edited.units.get_mut(&UnitType::LandToLandTank).unwrap().health = 200.0;
+    edited.units.get_mut(&UnitType::LandToLandTank).unwrap().attack_power = 99.0;
+    edited.units.get_mut(&UnitType::LandToLandTank).unwrap().attack_cooldown = 2.5;
+    let handle = app.world().resource::<UnitDefinitions>().handle.clone().unwrap();
+    app.world_mut().resource_mut::<Assets<UnitCatalog>>().insert(&handle, edited);
+
+    app.update();
+    app.update();
+
+    let unit = app.world().get::<Unit>(tank).unwrap();
+    assert_eq!(unit.attack_power, 99.0, "Existing units should pick up new stats");
+    assert_eq!(unit.max_health, 200.0, "Existing units should pick up new max health");
+    assert_eq!(unit.health, 100.0, "Health percentage should be preserved");
+    assert_eq!(unit.attack_cooldown.duration(), Duration::from_secs_f32(2.5), "Existing units should pick up the new attack rate");
+}
+
+#[test]
+fn test_units_get_their_catalog_sprite_however_they_spawn() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), UnitCatalogPlugin));
+
+    // One handle per direction, so the facing can be checked too
+    let tank_sprites: Vec<Handle<Image>> = (0..8).map(Handle::weak_from_u128).collect();
+    let mut game_sprites = GameSprites { is_loaded: true, ..default() };
+    game_sprites.unit_sprites.insert("tank_enemy".to_string(), tank_sprites.clone());
+    app.insert_resource(game_sprites);
+
+    let catalog = UnitCatalog::default();
+    let tank = UnitType::LandToLandTank.spawn_unit(&mut app.world_mut().commands(), &catalog, Vec2::ZERO, Team::Enemy);
+    let gatherer = UnitType::Gatherer.spawn_unit(&mut app.world_mut().commands(), &catalog, Vec2::ZERO, Team::Enemy);
+    app.update();
+
+    assert_eq!(app.world().get::<Handle<Image>>(tank), Some(&tank_sprites[4]), "Enemy tanks should get the tank sprite facing left");
+    assert_eq!(app.world().get::<Handle<Image>>(gatherer), Some(&Handle::default()), "Units without artwork keep the plain square");
+}
@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use strategy_forge::{
+    components::player::{MechanicalBase, PlayerResources, StrategicTarget},
+    components::strategic::{StrategicLocation, StrategicLocationPlugin, StrategicScores},
+    components::unit::Team,
+    resources::map::plugin::MapInitialized,
+    resources::map_data::GameMap,
+    states::game_state::GameState,
+    systems::victory::{MatchResult, VictoryPlugin, VictoryReason, VictoryRules},
+};
+
+/// Helper function to build a minimal app that checks only the given victory rules
+fn create_victory_app(rules: VictoryRules) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Gameplay)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .insert_resource(GameMap::default())
+        .insert_resource(MapInitialized(true))
+        .insert_resource(PlayerResources::default())
+        .add_plugins((StrategicLocationPlugin, VictoryPlugin))
+        .insert_resource(rules);
+    app
+}
+
+/// Helper function for a rule set with every rule switched off
+fn no_rules() -> VictoryRules {
+    VictoryRules {
+        hold_location_seconds: None,
+        hold_majority_seconds: None,
+        destroy_enemy_bases: false,
+        score_limit: None,
+    }
+}
+
+/// Helper function to spawn a location already held by a team, far from any base
+fn spawn_location(app: &mut App, name: &str, x: f32, capture_value: i32, controlling_team: Option<Team>) -> Entity {
+    app.world_mut()
+        .spawn(StrategicLocation {
+            name: name.to_string(),
+            control_points: if controlling_team.is_some() { 100.0 } else { 0.0 },
+            controlling_team,
+            position: Vec2::new(x, 5000.0),
+            capture_value,
+            ..default()
+        })
+        .id()
+}
+
+/// Helper function to spawn a team's base
+fn spawn_base(app: &mut App, team: Team) -> Entity {
+    app.world_mut()
+        .spawn((Transform::default(), MechanicalBase { team, ..default() }))
+        .id()
+}
+
+/// Helper function to advance the app by a number of seconds
+fn run_seconds(app: &mut App, seconds: u32) {
+    for _ in 0..seconds * 10 {
+        app.update();
+    }
+}
+
+/// Helper function to get the current game state
+fn state(app: &App) -> GameState {
+    *app.world().resource::<State<GameState>>().get()
+}
+
+#[test]
+fn test_holding_the_key_location_wins() {
+    let mut app = create_victory_app(VictoryRules { hold_location_seconds: Some(10.0), ..no_rules() });
+    app.update();
+    let centre = spawn_location(&mut app, "Central Hill", 0.0, 3, Some(Team::Player));
+    spawn_location(&mut app, "West Ford", -1000.0, 1, Some(Team::Enemy));
+
+    run_seconds(&mut app, 5);
+    let target = app.world().get::<StrategicTarget>(centre).expect("Locations should be tracked");
+    assert_eq!(target.held_by, Some(Team::Player));
+    assert!(target.is_reached);
+    assert!((target.time_held - 4.9).abs() < 0.15, "Held for about 5 seconds, got {}", target.time_held);
+
+    // Losing the location for a moment restarts the clock
+    app.world_mut().get_mut::<StrategicLocation>(centre).unwrap().controlling_team = None;
+    app.update();
+    app.world_mut().get_mut::<StrategicLocation>(centre).unwrap().controlling_team = Some(Team::Player);
+    run_seconds(&mut app, 8);
+    assert_eq!(state(&app), GameState::Gameplay, "The hold was broken, so 8 more seconds isn't enough");
+
+    // The enemy's flank has been held all along but isn't the key location
+    run_seconds(&mut app, 3);
+    assert_eq!(state(&app), GameState::Victory);
+    let result = app.world().resource::<MatchResult>();
+    assert_eq!(result.winner, Team::Player);
+    assert_eq!(result.reason, VictoryReason::HeldLocation { name: "Central Hill".to_string(), seconds: 10.0 });
+    assert_eq!(result.describe(), "You held Central Hill for 10 seconds");
+}
+
+#[test]
+fn test_enemy_majority_is_a_defeat() {
+    let mut app = create_victory_app(VictoryRules { hold_majority_seconds: Some(5.0), ..no_rules() });
+    app.update();
+    spawn_location(&mut app, "Central Hill", 0.0, 3, Some(Team::Player));
+    spawn_location(&mut app, "West Ford", -1000.0, 1, Some(Team::Enemy));
+    let east = spawn_location(&mut app, "East Ford", 1000.0, 1, None);
+
+    run_seconds(&mut app, 6);
+    assert_eq!(state(&app), GameState::Gameplay, "Nobody holds a majority yet");
+
+    let mut captured = app.world_mut().get_mut::<StrategicLocation>(east).unwrap();
+    captured.controlling_team = Some(Team::Enemy);
+    captured.control_points = 100.0;
+    run_seconds(&mut app, 6);
+    assert_eq!(state(&app), GameState::GameOver, "Losing the match should show the defeat screen");
+    let result = app.world().resource::<MatchResult>();
+    assert!(!result.player_won());
+    assert_eq!(result.describe(), "The enemy held 2 of 3 strategic locations for 5 seconds");
+}
+
+#[test]
+fn test_destroying_enemy_bases_wins() {
+    let mut app = create_victory_app(VictoryRules { destroy_enemy_bases: true, ..no_rules() });
+    spawn_base(&mut app, Team::Player);
+    let enemy = spawn_base(&mut app, Team::Enemy);
+    run_seconds(&mut app, 1);
+    assert_eq!(state(&app), GameState::Gameplay);
+
+    app.world_mut().get_mut::<MechanicalBase>(enemy).unwrap().health = 0.0;
+    run_seconds(&mut app, 1);
+    assert_eq!(state(&app), GameState::Victory);
+    assert_eq!(app.world().resource::<MatchResult>().reason, VictoryReason::BasesDestroyed);
+}
+
+#[test]
+fn test_score_limit_ends_the_match_and_new_match_starts_fresh() {
+    let mut app = create_victory_app(VictoryRules { score_limit: Some(6), ..no_rules() });
+    app.update();
+    spawn_location(&mut app, "Central Hill", 0.0, 3, Some(Team::Enemy));
+
+    run_seconds(&mut app, 1);
+    assert_eq!(state(&app), GameState::Gameplay);
+    run_seconds(&mut app, 2);
+    assert_eq!(state(&app), GameState::GameOver);
+    let result = app.world().resource::<MatchResult>();
+    assert_eq!(result.reason, VictoryReason::ScoreLimit { score: 6 });
+    assert_eq!(result.describe(), "The enemy reached 6 points");
+
+    // Starting another match clears the result and the scores
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Gameplay);
+    app.update();
+    assert!(app.world().get_resource::<MatchResult>().is_none());
+    assert_eq!(app.world().resource::<StrategicScores>().team(Team::Enemy).score, 0);
+}
+
+#[test]
+fn test_pausing_keeps_the_majority_hold() {
+    let mut app = create_victory_app(VictoryRules { hold_majority_seconds: Some(5.0), ..no_rules() });
+    app.update();
+    spawn_location(&mut app, "Central Hill", 0.0, 3, Some(Team::Player));
+    spawn_location(&mut app, "West Ford", -1000.0, 1, Some(Team::Player));
+    spawn_location(&mut app, "East Ford", 1000.0, 1, None);
+
+    run_seconds(&mut app, 3);
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Paused);
+    run_seconds(&mut app, 1);
+    assert_eq!(state(&app), GameState::Paused);
+
+    // Resuming picks the hold up where it left off
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Gameplay);
+    app.update();
+    assert_eq!(state(&app), GameState::Gameplay);
+    run_seconds(&mut app, 3);
+    assert_eq!(state(&app), GameState::Victory, "Three seconds before and after the pause should add up to a win");
+    assert!(app.world().resource::<MatchResult>().player_won());
+
+    // Coming back from the main menu is a new match
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::MainMenu);
+    app.update();
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Gameplay);
+    app.update();
+    assert!(app.world().get_resource::<MatchResult>().is_none());
+}